@@ -0,0 +1,110 @@
+//! # Batch
+//!
+//! Reductions over slices of vectors for mesh and point cloud preprocessing:
+//! centroid, per-component minimum and maximum, bounds, and the length of a
+//! polyline. The centroid and bounds also come in a `_par` form reduced on
+//! the rayon thread pool.
+//!
+//! # Example
+//!
+//! ```
+//! use math3d::batch;
+//! use math3d::vectors::Vector3;
+//!
+//! let points = [Vector3::new(0.0, 0.0, 0.0), Vector3::new(2.0, 0.0, 0.0), Vector3::new(2.0, 4.0, 0.0)];
+//!
+//! assert!(batch::centroid(&points) == Some(Vector3::new(4.0 / 3.0, 4.0 / 3.0, 0.0)));
+//! assert_eq!(batch::polyline_length(&points), 6.0);
+//! ```
+
+use num::Float;
+use crate::vectors::Vector3;
+use rayon::prelude::*;
+
+/// Number of elements handled by one task in the parallel `_par` functions
+/// throughout the crate.
+
+pub(crate) const PAR_CHUNK_SIZE: usize = 16 * 1024;
+
+/// Mean of the vectors, or `None` for an empty slice.
+
+pub fn centroid<F: Float>(vectors: &[Vector3<F>]) -> Option<Vector3<F>> {
+	weighted_mean(vectors.iter().map(|v| (*v, F::one())))
+}
+
+/// Weighted mean `Σ wᵢ vᵢ / Σ wᵢ` of `(vector, weight)` pairs, or `None` if
+/// the weights sum to zero, which includes no pairs at all. Shared by
+/// `centroid` and `points::weighted_centroid`.
+
+pub(crate) fn weighted_mean<F: Float>(pairs: impl Iterator<Item = (Vector3<F>, F)>) -> Option<Vector3<F>> {
+	let (sum, total) = pairs.fold((Vector3::zero(), F::zero()), |(sum, total), (v, w)| (sum + v * w, total + w));
+	if total == F::zero() {
+		return None;
+	}
+	Some(sum / total)
+}
+
+/// Component-wise minimum of the vectors, or `None` for an empty slice.
+
+pub fn min<F: Float>(vectors: &[Vector3<F>]) -> Option<Vector3<F>> {
+	vectors.iter().copied().reduce(|a, b| a.min(b))
+}
+
+/// Component-wise maximum of the vectors, or `None` for an empty slice.
+
+pub fn max<F: Float>(vectors: &[Vector3<F>]) -> Option<Vector3<F>> {
+	vectors.iter().copied().reduce(|a, b| a.max(b))
+}
+
+/// Corners `(min, max)` of the axis-aligned box around the vectors, or `None`
+/// for an empty slice. Computed in a single pass.
+///
+/// # Example
+///
+/// ```
+/// use math3d::batch;
+/// use math3d::vectors::Vector3;
+///
+/// let points = [Vector3::new(1.0, -2.0, 3.0), Vector3::new(-1.0, 5.0, 0.0)];
+/// let (min, max) = batch::bounds(&points).unwrap();
+///
+/// assert!(min == Vector3::new(-1.0, -2.0, 0.0));
+/// assert!(max == Vector3::new(1.0, 5.0, 3.0));
+/// ```
+
+pub fn bounds<F: Float>(vectors: &[Vector3<F>]) -> Option<(Vector3<F>, Vector3<F>)> {
+	let first = *vectors.first()?;
+	Some(vectors[1..].iter().fold((first, first), |(min, max), v| (min.min(*v), max.max(*v))))
+}
+
+/// Total length of the polyline through the points in order, zero for fewer
+/// than two points.
+
+pub fn polyline_length<F: Float>(points: &[Vector3<F>]) -> F {
+	points
+		.windows(2)
+		.fold(F::zero(), |length, pair| length + (pair[1] - pair[0]).magnitude())
+}
+
+/// Parallel version of `centroid`. The sum is reduced from chunk sums, so
+/// the result may differ from `centroid` in the last bits.
+
+pub fn centroid_par<F: Float + Send + Sync>(vectors: &[Vector3<F>]) -> Option<Vector3<F>> {
+	if vectors.is_empty() {
+		return None;
+	}
+	let sum = vectors
+		.par_chunks(PAR_CHUNK_SIZE)
+		.map(|chunk| chunk.iter().fold(Vector3::zero(), |sum, v| sum + *v))
+		.reduce(Vector3::zero, |a, b| a + b);
+	Some(sum / F::from(vectors.len()).unwrap())
+}
+
+/// Parallel version of `bounds`, with an identical result.
+
+pub fn bounds_par<F: Float + Send + Sync>(vectors: &[Vector3<F>]) -> Option<(Vector3<F>, Vector3<F>)> {
+	vectors
+		.par_chunks(PAR_CHUNK_SIZE)
+		.filter_map(bounds)
+		.reduce_with(|(min_a, max_a), (min_b, max_b)| (min_a.min(min_b), max_a.max(max_b)))
+}
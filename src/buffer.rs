@@ -0,0 +1,246 @@
+//! # Buffer
+//!
+//! Structure-of-arrays storage for large numbers of vectors. `Vec3Buffer`
+//! keeps the x, y and z components in three separate contiguous arrays, so
+//! bulk operations run as tight loops over plain slices that the compiler
+//! can vectorize, instead of striding over interleaved `Vector3` values.
+//!
+//! # Example
+//!
+//! ```
+//! use math3d::buffer::Vec3Buffer;
+//! use math3d::quaternion::Quaternion;
+//! use math3d::vectors::Vector3;
+//!
+//! let mut positions: Vec3Buffer<f64> = (0..4).map(|i| Vector3::new(i as f64, 0.0, 0.0)).collect();
+//! positions.rotate(Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 90.0));
+//!
+//! assert!((positions.get(3) - Vector3::new(0.0, 3.0, 0.0)).magnitude() < 1e-12);
+//! ```
+
+use num::Float;
+use crate::matrices::Matrix3;
+use crate::quaternion::Quaternion;
+use crate::vectors::Vector3;
+
+/// Growable list of 3D vectors stored as separate x, y and z arrays.
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Vec3Buffer<F: Float> {
+	x: Vec<F>,
+	y: Vec<F>,
+	z: Vec<F>,
+}
+
+impl<F: Float> Vec3Buffer<F> {
+
+	/// Creates an empty buffer.
+	pub fn new() -> Vec3Buffer<F> {
+		Vec3Buffer::with_capacity(0)
+	}
+
+	/// Creates an empty buffer with room for `capacity` vectors.
+	pub fn with_capacity(capacity: usize) -> Vec3Buffer<F> {
+		Vec3Buffer {
+			x: Vec::with_capacity(capacity),
+			y: Vec::with_capacity(capacity),
+			z: Vec::with_capacity(capacity),
+		}
+	}
+
+	/// Buffer of `len` copies of `v`.
+	pub fn splat(v: Vector3<F>, len: usize) -> Vec3Buffer<F> {
+		Vec3Buffer {
+			x: vec![v.x(); len],
+			y: vec![v.y(); len],
+			z: vec![v.z(); len],
+		}
+	}
+
+	/// Number of vectors.
+	pub fn len(&self) -> usize {
+		self.x.len()
+	}
+
+	/// Whether the buffer holds no vectors.
+	pub fn is_empty(&self) -> bool {
+		self.x.is_empty()
+	}
+
+	/// Appends a vector.
+	pub fn push(&mut self, v: Vector3<F>) {
+		self.x.push(v.x());
+		self.y.push(v.y());
+		self.z.push(v.z());
+	}
+
+	/// Vector at `index`.
+	///
+	/// # Panics
+	///
+	/// Panics if `index` is out of bounds.
+
+	pub fn get(&self, index: usize) -> Vector3<F> {
+		Vector3::new(self.x[index], self.y[index], self.z[index])
+	}
+
+	/// Replaces the vector at `index`.
+	///
+	/// # Panics
+	///
+	/// Panics if `index` is out of bounds.
+
+	pub fn set(&mut self, index: usize, v: Vector3<F>) {
+		self.x[index] = v.x();
+		self.y[index] = v.y();
+		self.z[index] = v.z();
+	}
+
+	/// The x components.
+	pub fn x(&self) -> &[F] {
+		&self.x
+	}
+
+	/// The y components.
+	pub fn y(&self) -> &[F] {
+		&self.y
+	}
+
+	/// The z components.
+	pub fn z(&self) -> &[F] {
+		&self.z
+	}
+
+	/// Mutable slices of the x, y and z components, for custom bulk loops.
+	pub fn components_mut(&mut self) -> (&mut [F], &mut [F], &mut [F]) {
+		(&mut self.x, &mut self.y, &mut self.z)
+	}
+
+	/// Iterates over the vectors in order.
+	pub fn iter(&self) -> impl Iterator<Item = Vector3<F>> + '_ {
+		(0..self.len()).map(|i| self.get(i))
+	}
+
+	/// Copies the vectors out into the array-of-structures layout.
+	pub fn to_vectors(&self) -> Vec<Vector3<F>> {
+		self.iter().collect()
+	}
+
+	/// Adds the vectors of `other` element-wise.
+	///
+	/// # Panics
+	///
+	/// Panics if the buffers have different lengths.
+
+	pub fn add(&mut self, other: &Vec3Buffer<F>) {
+		assert_eq!(self.len(), other.len(), "buffers differ in length");
+		add_assign(&mut self.x, &other.x);
+		add_assign(&mut self.y, &other.y);
+		add_assign(&mut self.z, &other.z);
+	}
+
+	/// Adds `other` scaled by `s` element-wise, the `a += b * s` update of
+	/// particle integrators.
+	///
+	/// # Panics
+	///
+	/// Panics if the buffers have different lengths.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::buffer::Vec3Buffer;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let mut positions = Vec3Buffer::splat(Vector3::new(0.0, 10.0, 0.0), 100);
+	/// let velocities = Vec3Buffer::splat(Vector3::new(1.0, 0.0, 0.0), 100);
+	///
+	/// positions.add_scaled(&velocities, 0.5);
+	///
+	/// assert!(positions.get(99) == Vector3::new(0.5, 10.0, 0.0));
+	/// ```
+
+	pub fn add_scaled(&mut self, other: &Vec3Buffer<F>, s: F) {
+		assert_eq!(self.len(), other.len(), "buffers differ in length");
+		for (a, b) in [(&mut self.x, &other.x), (&mut self.y, &other.y), (&mut self.z, &other.z)] {
+			for (a, b) in a.iter_mut().zip(b) {
+				*a = *a + *b * s;
+			}
+		}
+	}
+
+	/// Multiplies every vector by `s`.
+	pub fn scale(&mut self, s: F) {
+		for a in self.x.iter_mut().chain(self.y.iter_mut()).chain(self.z.iter_mut()) {
+			*a = *a * s;
+		}
+	}
+
+	/// Dot products of the vectors with the matching vectors of `other`.
+	///
+	/// # Panics
+	///
+	/// Panics if the buffers have different lengths.
+
+	pub fn dot(&self, other: &Vec3Buffer<F>) -> Vec<F> {
+		assert_eq!(self.len(), other.len(), "buffers differ in length");
+		let mut out: Vec<F> = self.x.iter().zip(&other.x).map(|(a, b)| *a * *b).collect();
+		for (out, (a, b)) in out.iter_mut().zip(self.y.iter().zip(&other.y)) {
+			*out = *out + *a * *b;
+		}
+		for (out, (a, b)) in out.iter_mut().zip(self.z.iter().zip(&other.z)) {
+			*out = *out + *a * *b;
+		}
+		out
+	}
+
+	/// Multiplies every vector by the matrix, `v * m` as in `Vector3 * Matrix3`.
+	pub fn transform(&mut self, m: &Matrix3<F>) {
+		for i in 0..self.len() {
+			let (x, y, z) = (self.x[i], self.y[i], self.z[i]);
+			self.x[i] = x * m[0][0] + y * m[1][0] + z * m[2][0];
+			self.y[i] = x * m[0][1] + y * m[1][1] + z * m[2][1];
+			self.z[i] = x * m[0][2] + y * m[1][2] + z * m[2][2];
+		}
+	}
+
+	/// Rotates every vector by the unit quaternion. The quaternion is turned
+	/// into a rotation matrix once, so this costs the same as `transform`.
+	pub fn rotate(&mut self, q: Quaternion<F>) {
+		self.transform(&q.rotation_matrix_row_major());
+	}
+}
+
+impl<F: Float> Default for Vec3Buffer<F> {
+	fn default() -> Vec3Buffer<F> {
+		Vec3Buffer::new()
+	}
+}
+
+impl<F: Float> From<&[Vector3<F>]> for Vec3Buffer<F> {
+	fn from(vectors: &[Vector3<F>]) -> Vec3Buffer<F> {
+		vectors.iter().copied().collect()
+	}
+}
+
+impl<F: Float> FromIterator<Vector3<F>> for Vec3Buffer<F> {
+	fn from_iter<I: IntoIterator<Item = Vector3<F>>>(iter: I) -> Vec3Buffer<F> {
+		let mut buffer = Vec3Buffer::new();
+		buffer.extend(iter);
+		buffer
+	}
+}
+
+impl<F: Float> Extend<Vector3<F>> for Vec3Buffer<F> {
+	fn extend<I: IntoIterator<Item = Vector3<F>>>(&mut self, iter: I) {
+		for v in iter {
+			self.push(v);
+		}
+	}
+}
+
+fn add_assign<F: Float>(a: &mut [F], b: &[F]) {
+	for (a, b) in a.iter_mut().zip(b) {
+		*a = *a + *b;
+	}
+}
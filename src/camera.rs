@@ -1,31 +1,106 @@
-use crate::points::Point3;
-use crate::vectors::Vector4;
-use crate::matrices::Matrix4;
+use crate::geometry::Plane;
+use crate::points::{Point2, Point3};
+use crate::vectors::{Vector3, Vector4};
+use crate::matrices::{Matrix3, Matrix4};
 use crate::quaternion::Quaternion;
+use crate::transform::Transform;
 use num::Float;
 
-pub fn translation<F: Float>(x: F, y: F, z: F) -> Matrix4<F> {
-	let zero = F::zero();
-	let one = F::one();
+/// Translation matrix for row vectors; see `Matrix4::from_translation`.
 
-	Matrix4::from_vectors(
-		Vector4::new(one, zero, zero, x),
-		Vector4::new(zero, one, zero, y),
-		Vector4::new(zero, zero, one, z),
-		Vector4::new(zero, zero, zero, one),
-	).transpose()
+pub fn translation<F: Float>(x: F, y: F, z: F) -> Matrix4<F> {
+	Matrix4::from_translation(Vector3::new(x, y, z))
 }
 
+/// Scale matrix; see `Matrix4::from_scale`.
+
 pub fn scale<F: Float>(x: F, y: F, z: F) -> Matrix4<F> {
-	let zero = F::zero();
-	let one = F::one();
+	Matrix4::from_scale(Vector3::new(x, y, z))
+}
+
+/// Vertical field of view matching a horizontal one at the given aspect
+/// ratio (width over height), both in radians: `2 * atan(tan(hfov / 2) / aspect)`.
+///
+/// # Example
+///
+/// ```
+/// use math3d::camera::vertical_fov_from_horizontal;
+///
+/// let hfov = std::f64::consts::FRAC_PI_2;
+///
+/// assert!((vertical_fov_from_horizontal(hfov, 1.0) - hfov).abs() < 1e-12);
+/// assert!(vertical_fov_from_horizontal(hfov, 16.0 / 9.0) < hfov);
+/// ```
+
+pub fn vertical_fov_from_horizontal<F: Float>(hfov: F, aspect: F) -> F {
+	let two = F::from(2.0).unwrap();
+	((hfov / two).tan() / aspect).atan() * two
+}
+
+/// Horizontal field of view matching a vertical one at the given aspect
+/// ratio, both in radians. The inverse of `vertical_fov_from_horizontal`.
 
-	Matrix4::from_vectors(
-		Vector4::new(x, zero, zero, zero),
-		Vector4::new(zero, y, zero, zero),
-		Vector4::new(zero, zero, z, zero),
-		Vector4::new(zero, zero, zero, one),
-	)
+pub fn horizontal_fov_from_vertical<F: Float>(vfov: F, aspect: F) -> F {
+	let two = F::from(2.0).unwrap();
+	((vfov / two).tan() * aspect).atan() * two
+}
+
+/// Object motion matrix for velocity buffers: takes a world-space point on
+/// an object at its `current` transform to where the same point was at its
+/// `previous` transform, for row vectors. Multiplied by the previous frame's
+/// view-projection matrix it gives the previous clip-space position of each
+/// vertex.
+///
+/// # Example
+///
+/// ```
+/// use math3d::camera::motion_matrix;
+/// use math3d::quaternion::Quaternion;
+/// use math3d::transform::Transform;
+/// use math3d::vectors::{Vector3, Vector4};
+///
+/// let one = Vector3::new(1.0, 1.0, 1.0);
+/// let previous = Transform::new(Vector3::new(0.0, 0.0, 0.0), Quaternion::identity(), one);
+/// let current = Transform::new(Vector3::new(2.0, 0.0, 0.0), Quaternion::identity(), one);
+///
+/// let p = Vector4::new(3.0, 1.0, 0.0, 1.0) * motion_matrix(&previous, &current);
+///
+/// assert!((p - Vector4::new(1.0, 1.0, 0.0, 1.0)).magnitude() < 1e-12);
+/// ```
+
+pub fn motion_matrix<F: Float>(previous: &Transform<F>, current: &Transform<F>) -> Matrix4<F> {
+	current.inverse_matrix() * previous.matrix()
+}
+
+/// World-space point on `ground` under the cursor, for placing and dragging
+/// objects in editors: unprojects the cursor position in pixels into a ray
+/// with `Camera::screen_ray` and intersects it with the plane. Returns `None`
+/// when the ray misses the plane, such as above the horizon.
+///
+/// # Example
+///
+/// ```
+/// use math3d::camera::{cursor_to_ground_point, Camera, Viewport};
+/// use math3d::geometry::Plane;
+/// use math3d::points::{Point2, Point3};
+/// use math3d::quaternion::Quaternion;
+/// use math3d::vectors::Vector3;
+///
+/// // Looking straight down from 10 units above the ground.
+/// let rotation = Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), -90.0);
+/// let camera = Camera::new(Point3::new(0.0, 10.0, 0.0), rotation, 1.0, 1.0, 0.1, 100.0);
+/// let viewport = Viewport::new(0.0, 0.0, 800.0, 800.0);
+/// let ground = Plane::new(Vector3::new(0.0, 1.0, 0.0), 0.0);
+///
+/// let point = cursor_to_ground_point(&camera, Point2::new(400.0, 400.0), &viewport, &ground).unwrap();
+///
+/// assert!(point.distance_to(Point3::new(0.0, 0.0, 0.0)) < 1e-9);
+/// ```
+
+pub fn cursor_to_ground_point<F: Float>(camera: &Camera<F>, cursor: Point2<F>, viewport: &Viewport<F>, ground: &Plane<F>) -> Option<Point3<F>> {
+	let (origin, direction) = camera.screen_ray(cursor, viewport);
+	let t = ground.ray_parameter(origin, direction)?;
+	Some(Point3::from_vector(origin.to_vector() + direction * t))
 }
 
 fn projection_<F: Float>(fov: F, aspect: F, near: F, far: F) -> Matrix4<F> {
@@ -49,6 +124,7 @@ pub struct Camera<F: Float> {
 	aspect: F,
 	near: F,
 	far: F,
+	clip_plane: Option<Plane<F>>,
 }
 
 impl<F: Float> Camera<F> {
@@ -62,6 +138,7 @@ impl<F: Float> Camera<F> {
 			aspect,
 			near,
 			far,
+			clip_plane: None,
 		}
 	}
 
@@ -95,6 +172,34 @@ impl<F: Float> Camera<F> {
 		self.fov = fov;
 	}
 
+	/// Horizontal field of view in radians, derived from the vertical field of
+	/// view and the aspect ratio.
+	pub fn horizontal_fov(&self) -> F {
+		horizontal_fov_from_vertical(self.fov, self.aspect)
+	}
+
+	/// Sets the vertical field of view from a horizontal one in radians at the
+	/// current aspect ratio, for engines that specify the horizontal field of
+	/// view. Changing the aspect ratio afterwards keeps the vertical one.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::camera::Camera;
+	/// use math3d::points::Point3;
+	/// use math3d::quaternion::Quaternion;
+	///
+	/// let mut camera = Camera::new(Point3::new(0.0, 0.0, 0.0), Quaternion::identity(), 1.0, 2.0, 0.1, 100.0);
+	/// camera.set_horizontal_fov(1.5);
+	///
+	/// assert!((camera.horizontal_fov() - 1.5_f64).abs() < 1e-12);
+	/// assert!(*camera.fov() < 1.5);
+	/// ```
+
+	pub fn set_horizontal_fov(&mut self, hfov: F) {
+		self.fov = vertical_fov_from_horizontal(hfov, self.aspect);
+	}
+
 	// Returns the camera's aspect ratio.
 	pub fn aspect(&self) -> &F {
 		&self.aspect
@@ -125,24 +230,498 @@ impl<F: Float> Camera<F> {
 		self.far = far;
 	}
 
-	// Returns the camera's view matrix.
+	/// Returns the camera's view matrix, taking world space to view space for
+	/// row vectors: `point.to_vector4() * camera.view()` agrees with
+	/// `to_view_space`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::camera::Camera;
+	/// use math3d::points::Point3;
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector4;
+	///
+	/// let camera = Camera::new(Point3::new(0.0, 0.0, 5.0), Quaternion::identity(), 1.0, 1.0, 0.1, 100.0);
+	///
+	/// let p = Point3::new(0.0, 0.0, 0.0).to_vector4() * camera.view();
+	///
+	/// assert!(p == Vector4::new(0.0, 0.0, -5.0, 1.0));
+	/// ```
+
 	pub fn view(&self) -> Matrix4<F> {
-		let zero = F::zero();
-		let one = F::one();
+		let translation = translation(-self.position[0], -self.position[1], -self.position[2]);
+		translation * self.rotation.conjugate().rotation_matrix4()
+	}
 
-		let translation = translation(self.position[0], self.position[1], self.position[2]);
-		let rotation = self.rotation.rotation_matrix();
-		let rot4x4 = Matrix4::from_vectors(
-			Vector4::new(rotation[0][0], rotation[0][1], rotation[0][2], zero),
-			Vector4::new(rotation[1][0], rotation[1][1], rotation[1][2], zero),
-			Vector4::new(rotation[2][0], rotation[2][1], rotation[2][2], zero),
-			Vector4::new(zero, zero, zero, one),
+	/// Creates a camera from an existing view matrix, recovering its position
+	/// and rotation. The inverse of `view`.
+	///
+	/// The matrix must be rigid, a rotation followed by a translation, in the
+	/// row-vector convention of `view`; transpose view matrices from
+	/// column-vector engines such as OpenGL first. Both conventions look down
+	/// the negative z axis with y up.
+	///
+	/// # Arguments
+	///
+	/// * `view` - The view matrix.
+	/// * `fov` - Vertical field of view in radians.
+	/// * `aspect` - Width divided by height.
+	/// * `near` - Distance to the near plane.
+	/// * `far` - Distance to the far plane.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::camera::Camera;
+	/// use math3d::points::Point3;
+	/// use math3d::quaternion::Quaternion;
+	///
+	/// let camera = Camera::new(Point3::new(1.0, 2.0, 3.0), Quaternion::identity(), 1.0, 1.0, 0.1, 100.0);
+	/// let copy = Camera::from_view_matrix(camera.view(), 1.0, 1.0, 0.1, 100.0);
+	///
+	/// assert!(copy.position().distance_to(Point3::new(1.0, 2.0, 3.0)) < 1e-12);
+	/// ```
+
+	pub fn from_view_matrix(view: Matrix4<F>, fov: F, aspect: F, near: F, far: F) -> Camera<F> {
+		let inverse_rotation = Matrix3::from_vectors(
+			Vector3::new(view[0][0], view[0][1], view[0][2]),
+			Vector3::new(view[1][0], view[1][1], view[1][2]),
+			Vector3::new(view[2][0], view[2][1], view[2][2]),
 		);
-		translation * rot4x4
+		let translation = Vector3::new(view[3][0], view[3][1], view[3][2]);
+		let position = -(translation * inverse_rotation.transpose());
+		let rotation = Quaternion::from_rotation_matrix(inverse_rotation).conjugate();
+
+		Camera::new(Point3::from_vector(position), rotation, fov, aspect, near, far)
 	}
 
-	// Returns the camera's projection matrix.
+	/// World-space plane that replaces the near plane of the projection, if
+	/// any. Points on its negative side are clipped.
+	pub fn clip_plane(&self) -> Option<&Plane<F>> {
+		self.clip_plane.as_ref()
+	}
+
+	/// Sets or removes the clip plane.
+	pub fn update_clip_plane(&mut self, clip_plane: Option<Plane<F>>) {
+		self.clip_plane = clip_plane;
+	}
+
+	/// Returns the camera's projection matrix. With a clip plane, the near
+	/// plane is replaced by it as in `Matrix4::with_oblique_near_plane`.
 	pub fn projection(&self) -> Matrix4<F> {
-		projection_(self.fov, self.aspect, self.near, self.far)
+		let projection = projection_(self.fov, self.aspect, self.near, self.far);
+		match self.clip_plane {
+			Some(plane) => {
+				let normal = self.rotation.conjugate().rotate_vector(plane.normal());
+				let view_plane = Vector4::new_from_vector3(normal, plane.signed_distance(self.position));
+				projection.with_oblique_near_plane(view_plane)
+			},
+			None => projection,
+		}
+	}
+
+	/// Returns the camera's combined view and projection matrix, taking world
+	/// space to clip space for row vectors.
+	pub fn view_projection(&self) -> Matrix4<F> {
+		self.view() * self.projection()
+	}
+
+	/// Inverse of `view`, taking view space back to world space. Computed
+	/// directly from the position and rotation.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::camera::Camera;
+	/// use math3d::points::Point3;
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let rotation = Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 30.0);
+	/// let camera = Camera::new(Point3::new(1.0, 2.0, 3.0), rotation, 1.0, 1.5, 0.1, 100.0);
+	///
+	/// assert!(camera.inverse_view().approx_eq(camera.view().inverse(), 1e-12));
+	/// ```
+
+	pub fn inverse_view(&self) -> Matrix4<F> {
+		self.rotation.rotation_matrix4() * translation(self.position[0], self.position[1], self.position[2])
+	}
+
+	/// Inverse of `projection`, taking clip space back to view space.
+	/// Computed in closed form from the field of view, aspect ratio and clip
+	/// distances rather than by inverting the matrix, unless there is a clip
+	/// plane.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::camera::Camera;
+	/// use math3d::matrices::Matrix4;
+	/// use math3d::points::Point3;
+	/// use math3d::quaternion::Quaternion;
+	///
+	/// let camera = Camera::new(Point3::new(1.0, 2.0, 3.0), Quaternion::identity(), 1.0, 1.5, 0.1, 100.0);
+	///
+	/// assert!((camera.projection() * camera.inverse_projection()).approx_eq(Matrix4::<f64>::identity(), 1e-9));
+	/// ```
+
+	pub fn inverse_projection(&self) -> Matrix4<F> {
+		if self.clip_plane.is_some() {
+			return self.projection().inverse();
+		}
+		let zero = F::zero();
+		let one = F::one();
+		let two = F::from(2.0).unwrap();
+		let f = self.focal_length();
+		let a = (self.far + self.near) / (self.near - self.far);
+		let b = (two * self.far * self.near) / (self.near - self.far);
+
+		Matrix4::new(
+			self.aspect / f, zero, zero, zero,
+			zero, one / f, zero, zero,
+			zero, zero, zero, -one,
+			zero, zero, one / b, a / b,
+		).transpose()
+	}
+
+	/// Inverse of `view_projection`, taking clip space back to world space,
+	/// as `inverse_projection() * inverse_view()`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::camera::Camera;
+	/// use math3d::matrices::Matrix4;
+	/// use math3d::points::Point3;
+	/// use math3d::quaternion::Quaternion;
+	///
+	/// let camera = Camera::new(Point3::new(1.0, 2.0, 3.0), Quaternion::identity(), 1.0, 1.5, 0.1, 100.0);
+	/// let m = camera.view_projection() * camera.inverse_view_projection();
+	///
+	/// for i in 0..4 {
+	///     for j in 0..4 {
+	///         assert!((m[i][j] - Matrix4::<f64>::identity()[i][j]).abs() < 1e-9);
+	///     }
+	/// }
+	/// ```
+
+	pub fn inverse_view_projection(&self) -> Matrix4<F> {
+		self.inverse_projection() * self.inverse_view()
+	}
+
+	/// Reprojection matrix for temporal effects such as TAA and motion blur:
+	/// takes a clip-space position of this frame to the clip-space position the
+	/// same world-space point had in the previous frame, given that frame's
+	/// `view_projection`. Only camera motion is accounted for; combine with
+	/// `motion_matrix` for moving objects.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::camera::Camera;
+	/// use math3d::points::Point3;
+	/// use math3d::quaternion::Quaternion;
+	///
+	/// let previous = Camera::new(Point3::new(0.0, 0.0, 5.0), Quaternion::identity(), 1.0, 1.0, 0.1, 100.0);
+	/// let current = Camera::new(Point3::new(0.5, 0.0, 5.0), Quaternion::identity(), 1.0, 1.0, 0.1, 100.0);
+	///
+	/// let world = Point3::new(0.0, 1.0, 0.0).to_vector4();
+	/// let reprojected = world * current.view_projection() * current.reproject_matrix(previous.view_projection());
+	///
+	/// assert!((reprojected - world * previous.view_projection()).magnitude() < 1e-9);
+	/// ```
+
+	pub fn reproject_matrix(&self, prev_view_proj: Matrix4<F>) -> Matrix4<F> {
+		self.inverse_view_projection() * prev_view_proj
+	}
+
+	/// Transforms a world-space point into view space, where the camera sits
+	/// at the origin looking down the negative z axis with y up.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::camera::Camera;
+	/// use math3d::points::Point3;
+	/// use math3d::quaternion::Quaternion;
+	///
+	/// let camera = Camera::new(Point3::new(0.0, 0.0, 5.0), Quaternion::identity(), 1.0, 1.0, 0.1, 100.0);
+	///
+	/// let p = camera.to_view_space(Point3::new(0.0, 0.0, 0.0));
+	///
+	/// assert_eq!(p.z(), -5.0);
+	/// ```
+
+	pub fn to_view_space(&self, point: Point3<F>) -> Vector3<F> {
+		let relative = point.to_vector() - self.position.to_vector();
+		self.rotation.conjugate().rotate_vector(relative)
+	}
+
+	/// World-space corners of the view frustum: the near plane corners
+	/// followed by the far plane corners, each in bottom left, bottom right,
+	/// top right, top left order as seen by the camera.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::camera::Camera;
+	/// use math3d::points::Point3;
+	/// use math3d::quaternion::Quaternion;
+	///
+	/// let fov = std::f64::consts::FRAC_PI_2;
+	/// let camera = Camera::new(Point3::new(0.0, 0.0, 0.0), Quaternion::identity(), fov, 1.0, 1.0, 10.0);
+	/// let corners = camera.frustum_corners();
+	///
+	/// assert!(corners[0].distance_to(Point3::new(-1.0, -1.0, -1.0)) < 1e-12);
+	/// assert!(corners[6].distance_to(Point3::new(10.0, 10.0, -10.0)) < 1e-12);
+	/// ```
+
+	pub fn frustum_corners(&self) -> [Point3<F>; 8] {
+		let mut corners = [self.position; 8];
+		for (plane, depth) in [self.near, self.far].iter().enumerate() {
+			let half_height = *depth / self.focal_length();
+			let half_width = half_height * self.aspect;
+			let quad = [(-F::one(), -F::one()), (F::one(), -F::one()), (F::one(), F::one()), (-F::one(), F::one())];
+			for (i, (sx, sy)) in quad.iter().enumerate() {
+				let local = Vector3::new(*sx * half_width, *sy * half_height, -*depth);
+				let world = self.position.to_vector() + self.rotation.rotate_vector(local);
+				corners[plane * 4 + i] = Point3::from_vector(world);
+			}
+		}
+		corners
+	}
+
+	/// World-space ray from the camera through a pixel of the viewport, as an
+	/// origin at the camera position and a unit direction.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::camera::{Camera, Viewport};
+	/// use math3d::points::{Point2, Point3};
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let camera = Camera::new(Point3::new(0.0, 0.0, 0.0), Quaternion::identity(), 1.0, 1.0, 0.1, 100.0);
+	/// let viewport = Viewport::new(0.0, 0.0, 800.0, 600.0);
+	///
+	/// let (_, direction) = camera.screen_ray(Point2::new(400.0, 300.0), &viewport);
+	///
+	/// assert!((direction - Vector3::new(0.0, 0.0, -1.0)).magnitude() < 1e-12);
+	/// ```
+
+	pub fn screen_ray(&self, pixel: Point2<F>, viewport: &Viewport<F>) -> (Point3<F>, Vector3<F>) {
+		let (x, y) = viewport.to_ndc(pixel.x(), pixel.y());
+		let f = self.focal_length();
+		let local = Vector3::new(x * self.aspect / f, y / f, -F::one());
+		(self.position, self.rotation.rotate_vector(local).normalized())
+	}
+
+	/// Blends this camera towards `other` for camera transitions. `t` is
+	/// clamped to `[0, 1]`.
+	///
+	/// The position, aspect ratio and clip planes are interpolated linearly and
+	/// the rotation with `Quaternion::slerp`. The field of view is interpolated
+	/// logarithmically through `tan(fov / 2)`, so a zoom between two lenses
+	/// magnifies by the same factor in every step. A clip plane shared by
+	/// both cameras, as between two poses of one reflection camera, is kept;
+	/// otherwise the result has none.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::camera::Camera;
+	/// use math3d::points::Point3;
+	/// use math3d::quaternion::Quaternion;
+	///
+	/// let a = Camera::new(Point3::new(0.0, 0.0, 0.0), Quaternion::identity(), 1.0, 1.0, 0.1, 100.0);
+	/// let b = Camera::new(Point3::new(2.0, 0.0, 0.0), Quaternion::identity(), 1.0, 1.0, 0.1, 100.0);
+	///
+	/// let c = a.interpolate(&b, 0.5);
+	///
+	/// assert!(c.position().distance_to(Point3::new(1.0, 0.0, 0.0)) < 1e-12);
+	/// ```
+
+	pub fn interpolate(&self, other: &Camera<F>, t: F) -> Camera<F> {
+		let t = t.max(F::zero()).min(F::one());
+		let two = F::from(2.0).unwrap();
+		let lerp = |a: F, b: F| a + (b - a) * t;
+		let (from, to) = ((self.fov / two).tan().ln(), (other.fov / two).tan().ln());
+		Camera {
+			position: Point3::from_vector(self.position.to_vector().lerp(other.position.to_vector(), t)),
+			rotation: self.rotation.slerp(other.rotation, t),
+			fov: lerp(from, to).exp().atan() * two,
+			aspect: lerp(self.aspect, other.aspect),
+			near: lerp(self.near, other.near),
+			far: lerp(self.far, other.far),
+			clip_plane: match (self.clip_plane, other.clip_plane) {
+				(Some(a), Some(b)) if a.normal() == b.normal() && a.d() == b.d() => Some(a),
+				_ => None,
+			},
+		}
+	}
+
+	/// Camera mirrored across a plane, for rendering planar reflections such
+	/// as water and mirrors. The position and the viewing and up directions
+	/// are reflected, and the plane becomes the clip plane so that nothing
+	/// between the mirrored camera and the plane is drawn.
+	///
+	/// The rotation stays a proper rotation, so the image comes out flipped
+	/// left to right compared to a true mirror image and triangles keep
+	/// their winding order. Sampling the rendered reflection by projecting
+	/// surface points with this camera's `view_projection` undoes the flip.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::camera::Camera;
+	/// use math3d::geometry::Plane;
+	/// use math3d::points::Point3;
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let down = Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), -30.0);
+	/// let camera = Camera::new(Point3::new(0.0, 5.0, 10.0), down, 1.0, 1.0, 0.1, 100.0);
+	/// let water = Plane::new(Vector3::new(0.0, 1.0, 0.0), 0.0);
+	///
+	/// let reflection = camera.reflected_across(&water);
+	/// assert!(reflection.position().distance_to(Point3::new(0.0, -5.0, 10.0)) < 1e-12);
+	///
+	/// // Points above the water are drawn, points below it clipped.
+	/// let above = Point3::new(1.0, 2.0, 0.0).to_vector4() * reflection.view_projection();
+	/// let below = Point3::new(1.0, -2.0, 0.0).to_vector4() * reflection.view_projection();
+	/// assert!(above[2] > -above[3] && above[2] < above[3]);
+	/// assert!(below[2] < -below[3]);
+	/// ```
+
+	pub fn reflected_across(&self, plane: &Plane<F>) -> Camera<F> {
+		let n = plane.normal();
+		let two = F::from(2.0).unwrap();
+		let reflect = |v: Vector3<F>| v - n * (two * n.dot(v));
+		let distance = plane.signed_distance(self.position);
+
+		let up = reflect(self.rotation.rotate_vector(Vector3::new(F::zero(), F::one(), F::zero())));
+		let back = reflect(self.rotation.rotate_vector(Vector3::new(F::zero(), F::zero(), F::one())));
+		let rotation = Quaternion::from_rotation_matrix(Matrix3::from_vectors(up.cross(back), up, back));
+		// Keep the side of the plane the original camera looks from.
+		let clip_plane = if distance < F::zero() { Plane::new(-n, -plane.d()) } else { *plane };
+
+		Camera {
+			position: Point3::from_vector(self.position.to_vector() - n * (two * distance)),
+			rotation,
+			fov: self.fov,
+			aspect: self.aspect,
+			near: self.near,
+			far: self.far,
+			clip_plane: Some(clip_plane),
+		}
+	}
+
+	/// Focal length in normalized device coordinates, `1 / tan(fov / 2)`.
+
+	pub(crate) fn focal_length(&self) -> F {
+		let two = F::from(2.0).unwrap();
+		F::one() / (self.fov / two).tan()
+	}
+}
+
+// //////////////////////////////////////////////////////////////////////////////////////
+//
+// Viewport
+//
+// //////////////////////////////////////////////////////////////////////////////////////
+
+/// Rectangle of the render target a camera draws into, in pixels with the
+/// origin at the top left corner and y growing downwards.
+
+#[derive(Copy, Clone, Debug)]
+pub struct Viewport<F: Float> {
+	x: F,
+	y: F,
+	width: F,
+	height: F,
+}
+
+impl<F: Float> Viewport<F> {
+
+	/// Creates a new viewport.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::camera::Viewport;
+	///
+	/// let viewport = Viewport::new(0.0, 0.0, 1920.0, 1080.0);
+	/// ```
+
+	pub fn new(x: F, y: F, width: F, height: F) -> Viewport<F> {
+		Viewport {
+			x,
+			y,
+			width,
+			height,
+		}
+	}
+
+	/// Returns the left edge.
+	pub fn x(&self) -> F {
+		self.x
 	}
-}
\ No newline at end of file
+
+	/// Returns the top edge.
+	pub fn y(&self) -> F {
+		self.y
+	}
+
+	/// Returns the width.
+	pub fn width(&self) -> F {
+		self.width
+	}
+
+	/// Returns the height.
+	pub fn height(&self) -> F {
+		self.height
+	}
+
+	/// Maps normalized device coordinates to pixel coordinates.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::camera::Viewport;
+	///
+	/// let viewport = Viewport::new(0.0, 0.0, 200.0, 100.0);
+	///
+	/// assert_eq!(viewport.from_ndc(1.0, 1.0), (200.0, 0.0));
+	/// ```
+
+	pub fn from_ndc(&self, x: F, y: F) -> (F, F) {
+		let two = F::from(2.0).unwrap();
+		(
+			self.x + (x + F::one()) / two * self.width,
+			self.y + (F::one() - y) / two * self.height,
+		)
+	}
+
+	/// Maps pixel coordinates to normalized device coordinates. The inverse
+	/// of `from_ndc`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::camera::Viewport;
+	///
+	/// let viewport = Viewport::new(0.0, 0.0, 200.0, 100.0);
+	///
+	/// assert_eq!(viewport.to_ndc(200.0, 0.0), (1.0, 1.0));
+	/// ```
+
+	pub fn to_ndc(&self, x: F, y: F) -> (F, F) {
+		let two = F::from(2.0).unwrap();
+		(
+			(x - self.x) / self.width * two - F::one(),
+			F::one() - (y - self.y) / self.height * two,
+		)
+	}
+}
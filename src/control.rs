@@ -0,0 +1,261 @@
+//! # Control
+//!
+//! Feedback controllers for driving bodies towards targets: a spring-damper
+//! and a PID controller over vectors, and a PD attitude controller over
+//! quaternions.
+//!
+//! # Example
+//!
+//! ```
+//! use math3d::control::SpringDamper;
+//! use math3d::vectors::Vector3;
+//!
+//! let spring = SpringDamper::critically_damped(40.0, 1.0);
+//! let force = spring.force(Vector3::new(1.0, 0.0, 0.0), Vector3::zero());
+//!
+//! assert!(force == Vector3::new(-40.0, 0.0, 0.0));
+//! ```
+
+use num::Float;
+use crate::quaternion::Quaternion;
+use crate::vectors::Vector3;
+
+// //////////////////////////////////////////////////////////////////////////////////////
+//
+// SpringDamper
+//
+// //////////////////////////////////////////////////////////////////////////////////////
+
+/// Linear spring with viscous damping, pulling a body towards its rest
+/// position with the force
+///
+/// $$\vec{f} = -k \vec{x} - c \vec{v}$$
+///
+/// where `x` is the displacement from the rest position and `v` the velocity
+/// relative to it.
+
+#[derive(Copy, Clone, Debug)]
+pub struct SpringDamper<F: Float> {
+	stiffness: F,
+	damping: F,
+}
+
+impl<F: Float> SpringDamper<F> {
+
+	/// Creates a new spring-damper.
+	///
+	/// # Arguments
+	///
+	/// * `stiffness` - Spring constant `k`, force per unit of displacement.
+	/// * `damping` - Damping coefficient `c`, force per unit of velocity.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::control::SpringDamper;
+	///
+	/// let spring = SpringDamper::new(10.0, 2.0);
+	/// ```
+
+	pub fn new(stiffness: F, damping: F) -> SpringDamper<F> {
+		SpringDamper {
+			stiffness,
+			damping,
+		}
+	}
+
+	/// Spring-damper with the damping that returns a body of the given mass to
+	/// rest fastest without overshooting, `c = 2 sqrt(k m)`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::control::SpringDamper;
+	///
+	/// let spring = SpringDamper::critically_damped(4.0, 1.0);
+	///
+	/// assert_eq!(spring.damping(), 4.0);
+	/// ```
+
+	pub fn critically_damped(stiffness: F, mass: F) -> SpringDamper<F> {
+		let damping = F::from(2.0).unwrap() * (stiffness * mass).sqrt();
+		SpringDamper::new(stiffness, damping)
+	}
+
+	/// Returns the stiffness.
+	pub fn stiffness(&self) -> F {
+		self.stiffness
+	}
+
+	/// Returns the damping.
+	pub fn damping(&self) -> F {
+		self.damping
+	}
+
+	/// Force on the body.
+	///
+	/// # Arguments
+	///
+	/// * `displacement` - Position of the body minus the rest position.
+	/// * `velocity` - Velocity of the body relative to the rest position.
+
+	pub fn force(&self, displacement: Vector3<F>, velocity: Vector3<F>) -> Vector3<F> {
+		-(displacement * self.stiffness + velocity * self.damping)
+	}
+}
+
+// //////////////////////////////////////////////////////////////////////////////////////
+//
+// Pid
+//
+// //////////////////////////////////////////////////////////////////////////////////////
+
+/// Proportional-integral-derivative controller over vector errors.
+///
+/// Each call to `update` takes the current error, target minus measured
+/// value, and returns the control output
+///
+/// $$\vec{u} = k_p \vec{e} + k_i \int \vec{e} \, dt + k_d \frac{d\vec{e}}{dt}$$
+///
+/// The derivative term is zero on the first update after creation or
+/// `reset`, so a large initial error does not produce a kick.
+///
+/// # Example
+///
+/// ```
+/// use math3d::control::Pid;
+/// use math3d::vectors::Vector3;
+///
+/// let mut pid = Pid::new(2.0, 0.5, 0.0);
+/// let output = pid.update(Vector3::new(1.0, 0.0, 0.0), 0.1);
+///
+/// assert!(output == Vector3::new(2.05, 0.0, 0.0));
+/// ```
+
+#[derive(Copy, Clone, Debug)]
+pub struct Pid<F: Float> {
+	proportional: F,
+	integral: F,
+	derivative: F,
+	accumulated: Vector3<F>,
+	previous_error: Option<Vector3<F>>,
+}
+
+impl<F: Float> Pid<F> {
+
+	/// Creates a new PID controller with the given gains.
+
+	pub fn new(proportional: F, integral: F, derivative: F) -> Pid<F> {
+		Pid {
+			proportional,
+			integral,
+			derivative,
+			accumulated: Vector3::zero(),
+			previous_error: None,
+		}
+	}
+
+	/// Feeds the error over the last `dt` seconds and returns the control
+	/// output.
+
+	pub fn update(&mut self, error: Vector3<F>, dt: F) -> Vector3<F> {
+		self.accumulated += error * dt;
+		let rate = match self.previous_error {
+			Some(previous) if dt > F::zero() => (error - previous) / dt,
+			_ => Vector3::zero(),
+		};
+		self.previous_error = Some(error);
+		error * self.proportional + self.accumulated * self.integral + rate * self.derivative
+	}
+
+	/// Clears the integral and derivative history.
+
+	pub fn reset(&mut self) {
+		self.accumulated = Vector3::zero();
+		self.previous_error = None;
+	}
+}
+
+// //////////////////////////////////////////////////////////////////////////////////////
+//
+// AttitudeController
+//
+// //////////////////////////////////////////////////////////////////////////////////////
+
+/// Proportional-derivative controller turning a body towards a target
+/// orientation. The orientation error is the rotation vector of
+/// `target * orientation^-1`, taken along the shorter arc, giving the torque
+///
+/// $$\vec{\tau} = k_p \log(q_t q^{-1}) - k_d \vec{\omega}$$
+///
+/// Orientations rotate body space to world space; the angular velocity and
+/// the torque are in world space.
+///
+/// # Example
+///
+/// ```
+/// use math3d::control::AttitudeController;
+/// use math3d::quaternion::Quaternion;
+/// use math3d::vectors::Vector3;
+///
+/// let controller = AttitudeController::new(10.0, 2.0);
+/// let target = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 90.0);
+/// let torque = controller.torque(Quaternion::identity(), target, Vector3::zero());
+///
+/// assert!(torque.z() > 0.0);
+/// ```
+
+#[derive(Copy, Clone, Debug)]
+pub struct AttitudeController<F: Float> {
+	proportional: F,
+	derivative: F,
+}
+
+impl<F: Float> AttitudeController<F> {
+
+	/// Creates a new attitude controller.
+	///
+	/// # Arguments
+	///
+	/// * `proportional` - Torque per radian of orientation error.
+	/// * `derivative` - Torque per radian per second of angular velocity.
+
+	pub fn new(proportional: F, derivative: F) -> AttitudeController<F> {
+		AttitudeController {
+			proportional,
+			derivative,
+		}
+	}
+
+	/// Attitude controller that settles without overshoot for a body with the
+	/// given moment of inertia about the rotation axis, `k_d = 2 sqrt(k_p I)`.
+
+	pub fn critically_damped(proportional: F, inertia: F) -> AttitudeController<F> {
+		let derivative = F::from(2.0).unwrap() * (proportional * inertia).sqrt();
+		AttitudeController::new(proportional, derivative)
+	}
+
+	/// Returns the proportional gain.
+	pub fn proportional(&self) -> F {
+		self.proportional
+	}
+
+	/// Returns the derivative gain.
+	pub fn derivative(&self) -> F {
+		self.derivative
+	}
+
+	/// Rotation vector taking `orientation` to `target` in world space, along
+	/// the shorter arc.
+
+	pub fn error(&self, orientation: Quaternion<F>, target: Quaternion<F>) -> Vector3<F> {
+		(target * orientation.conjugate()).versor().to_rotation_vector()
+	}
+
+	/// Torque turning a body with the given orientation and angular velocity
+	/// towards `target`.
+
+	pub fn torque(&self, orientation: Quaternion<F>, target: Quaternion<F>, angular_velocity: Vector3<F>) -> Vector3<F> {
+		self.error(orientation, target) * self.proportional - angular_velocity * self.derivative
+	}
+}
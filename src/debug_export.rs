@@ -0,0 +1,261 @@
+//! # Debug export
+//!
+//! Dumps of points, triangles, boxes, frusta and coordinate frames as
+//! Wavefront OBJ or ASCII PLY text, for inspecting spatial algorithms in an
+//! external viewer such as MeshLab or Blender.
+//!
+//! Geometry is collected into a `DebugMesh` and written out in one go.
+//! Vertices carry a color: the current color set with `set_color`, or red,
+//! green and blue for the x, y and z axes of frames. PLY stores colors as
+//! standard vertex properties; OBJ uses the common `v x y z r g b` extension.
+//!
+//! # Example
+//!
+//! ```
+//! use math3d::debug_export::DebugMesh;
+//! use math3d::geometry::Aabb3;
+//! use math3d::points::Point3;
+//!
+//! let points = [Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 2.0, 3.0)];
+//!
+//! let mut mesh = DebugMesh::new();
+//! mesh.add_points(&points);
+//! mesh.add_aabb(&Aabb3::from_points(&points).unwrap());
+//!
+//! let mut obj = Vec::new();
+//! mesh.write_obj(&mut obj).unwrap();
+//! ```
+
+use std::io::{self, Write};
+use num::Float;
+use crate::camera::Camera;
+use crate::geometry::Aabb3;
+use crate::gizmo::{self, Segment, HEXAHEDRON_EDGES};
+use crate::points::Point3;
+use crate::quaternion::Quaternion;
+use crate::transform::Transform;
+use crate::vectors::Vector3;
+
+/// Color of geometry added before any call to `set_color`.
+
+const DEFAULT_COLOR: [u8; 3] = [200, 200, 200];
+
+/// Collection of colored points, line segments and triangles to export.
+
+#[derive(Clone, Debug)]
+pub struct DebugMesh<F: Float> {
+	vertices: Vec<(Point3<F>, [u8; 3])>,
+	points: Vec<usize>,
+	edges: Vec<[usize; 2]>,
+	faces: Vec<[usize; 3]>,
+	color: [u8; 3],
+}
+
+impl<F: Float> DebugMesh<F> {
+
+	/// Creates an empty mesh.
+	pub fn new() -> DebugMesh<F> {
+		DebugMesh {
+			vertices: Vec::new(),
+			points: Vec::new(),
+			edges: Vec::new(),
+			faces: Vec::new(),
+			color: DEFAULT_COLOR,
+		}
+	}
+
+	/// Sets the RGB color of the geometry added from now on.
+	pub fn set_color(&mut self, color: [u8; 3]) {
+		self.color = color;
+	}
+
+	/// Adds a point cloud.
+	pub fn add_points(&mut self, points: &[Point3<F>]) {
+		for p in points {
+			let v = self.vertex(*p, self.color);
+			self.points.push(v);
+		}
+	}
+
+	/// Adds a line segment.
+	pub fn add_line(&mut self, a: Point3<F>, b: Point3<F>) {
+		let color = self.color;
+		self.add_colored_line(a, b, color);
+	}
+
+	/// Adds line segments, such as those produced by the `gizmo` generators.
+	pub fn add_segments(&mut self, segments: &[Segment<F>]) {
+		for (a, b) in segments {
+			self.add_line(*a, *b);
+		}
+	}
+
+	/// Adds triangles, each given by its corners in counter-clockwise order.
+	pub fn add_triangles(&mut self, triangles: &[[Point3<F>; 3]]) {
+		for [a, b, c] in triangles {
+			let face = [self.vertex(*a, self.color), self.vertex(*b, self.color), self.vertex(*c, self.color)];
+			self.faces.push(face);
+		}
+	}
+
+	/// Adds the twelve edges of an axis-aligned box.
+	pub fn add_aabb(&mut self, aabb: &Aabb3<F>) {
+		self.add_hexahedron(gizmo::aabb_corners(aabb));
+	}
+
+	/// Adds the twelve edges of the view frustum of a camera.
+	pub fn add_frustum(&mut self, camera: &Camera<F>) {
+		self.add_hexahedron(camera.frustum_corners());
+	}
+
+	/// Adds a coordinate frame as three axis segments of length `size`,
+	/// colored red, green and blue for x, y and z.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::debug_export::DebugMesh;
+	/// use math3d::points::Point3;
+	/// use math3d::quaternion::Quaternion;
+	///
+	/// let mut mesh = DebugMesh::new();
+	/// mesh.add_frame(Point3::new(0.0, 1.0, 0.0), Quaternion::identity(), 0.5);
+	/// ```
+
+	pub fn add_frame(&mut self, origin: Point3<F>, rotation: Quaternion<F>, size: F) {
+		let ends = frame_axes(size).map(|(axis, color)| (Point3::from_vector(origin.to_vector() + rotation.rotate_vector(axis)), color));
+		for (end, color) in ends {
+			self.add_colored_line(origin, end, color);
+		}
+	}
+
+	/// Adds the frame of a transform: its axes scaled by `size` and then
+	/// mapped by the transform, so non-uniform scale is visible as well.
+	pub fn add_transform(&mut self, transform: &Transform<F>, size: F) {
+		let origin = transform.transform_point(Point3::new(F::zero(), F::zero(), F::zero()));
+		for (axis, color) in frame_axes(size) {
+			let end = transform.transform_point(Point3::from_vector(axis));
+			self.add_colored_line(origin, end, color);
+		}
+	}
+
+	/// Writes the mesh as Wavefront OBJ, with `p`, `l` and `f` elements for
+	/// points, segments and triangles.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::debug_export::DebugMesh;
+	/// use math3d::points::Point3;
+	///
+	/// let mut mesh = DebugMesh::new();
+	/// mesh.add_line(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 0.0, 0.0));
+	///
+	/// let mut obj = Vec::new();
+	/// mesh.write_obj(&mut obj).unwrap();
+	///
+	/// assert!(String::from_utf8(obj).unwrap().ends_with("l 1 2\n"));
+	/// ```
+
+	pub fn write_obj<W: Write>(&self, mut out: W) -> io::Result<()> {
+		writeln!(out, "# m3d debug export")?;
+		for (p, [r, g, b]) in &self.vertices {
+			writeln!(
+				out,
+				"v {} {} {} {:.4} {:.4} {:.4}",
+				coordinate(p[0]),
+				coordinate(p[1]),
+				coordinate(p[2]),
+				*r as f64 / 255.0,
+				*g as f64 / 255.0,
+				*b as f64 / 255.0,
+			)?;
+		}
+		for i in &self.points {
+			writeln!(out, "p {}", i + 1)?;
+		}
+		for [a, b] in &self.edges {
+			writeln!(out, "l {} {}", a + 1, b + 1)?;
+		}
+		for [a, b, c] in &self.faces {
+			writeln!(out, "f {} {} {}", a + 1, b + 1, c + 1)?;
+		}
+		Ok(())
+	}
+
+	/// Writes the mesh as ASCII PLY with colored vertices and `face` and
+	/// `edge` elements. Points are vertices not used by any face or edge.
+
+	pub fn write_ply<W: Write>(&self, mut out: W) -> io::Result<()> {
+		writeln!(out, "ply")?;
+		writeln!(out, "format ascii 1.0")?;
+		writeln!(out, "comment m3d debug export")?;
+		writeln!(out, "element vertex {}", self.vertices.len())?;
+		writeln!(out, "property float x")?;
+		writeln!(out, "property float y")?;
+		writeln!(out, "property float z")?;
+		writeln!(out, "property uchar red")?;
+		writeln!(out, "property uchar green")?;
+		writeln!(out, "property uchar blue")?;
+		writeln!(out, "element face {}", self.faces.len())?;
+		writeln!(out, "property list uchar int vertex_indices")?;
+		writeln!(out, "element edge {}", self.edges.len())?;
+		writeln!(out, "property int vertex1")?;
+		writeln!(out, "property int vertex2")?;
+		writeln!(out, "end_header")?;
+		for (p, [r, g, b]) in &self.vertices {
+			writeln!(out, "{} {} {} {} {} {}", coordinate(p[0]), coordinate(p[1]), coordinate(p[2]), r, g, b)?;
+		}
+		for [a, b, c] in &self.faces {
+			writeln!(out, "3 {} {} {}", a, b, c)?;
+		}
+		for [a, b] in &self.edges {
+			writeln!(out, "{} {}", a, b)?;
+		}
+		Ok(())
+	}
+
+	fn vertex(&mut self, p: Point3<F>, color: [u8; 3]) -> usize {
+		self.vertices.push((p, color));
+		self.vertices.len() - 1
+	}
+
+	fn add_colored_line(&mut self, a: Point3<F>, b: Point3<F>, color: [u8; 3]) {
+		let edge = [self.vertex(a, color), self.vertex(b, color)];
+		self.edges.push(edge);
+	}
+
+	fn add_hexahedron(&mut self, corners: [Point3<F>; 8]) {
+		let first = self.vertices.len();
+		for c in &corners {
+			self.vertex(*c, self.color);
+		}
+		for [a, b] in &HEXAHEDRON_EDGES {
+			self.edges.push([first + a, first + b]);
+		}
+	}
+
+}
+
+impl<F: Float> Default for DebugMesh<F> {
+	fn default() -> DebugMesh<F> {
+		DebugMesh::new()
+	}
+}
+
+/// The x, y and z axes scaled by `size`, with their colors.
+
+fn frame_axes<F: Float>(size: F) -> [(Vector3<F>, [u8; 3]); 3] {
+	[
+		(Vector3::unit_x() * size, [255, 0, 0]),
+		(Vector3::unit_y() * size, [0, 255, 0]),
+		(Vector3::unit_z() * size, [0, 0, 255]),
+	]
+}
+
+/// Coordinate as an `f64`, whose `Display` prints the shortest text that
+/// reads back to the same value.
+
+fn coordinate<F: Float>(value: F) -> f64 {
+	value.to_f64().unwrap()
+}
@@ -0,0 +1,433 @@
+//! # Filter
+//!
+//! Temporal smoothing filters for noisy inputs such as tracked controllers
+//! or motion capture, and `Mahony` for fusing inertial sensors into an
+//! orientation. Every update takes the time elapsed since the previous
+//! sample, so the amount of smoothing does not depend on the sample rate.
+//!
+//! # Example
+//!
+//! ```
+//! use math3d::filter::OneEuro;
+//! use math3d::vectors::Vector3;
+//!
+//! let mut filter = OneEuro::new(1.0, 0.5, 1.0);
+//!
+//! let a = filter.update(Vector3::new(0.0, 1.0, 0.0), 1.0 / 90.0);
+//! let b = filter.update(Vector3::new(0.01, 1.0, 0.0), 1.0 / 90.0);
+//! ```
+
+use num::Float;
+use crate::quaternion::Quaternion;
+use crate::vectors::Vector3;
+
+/// Blend factor of a first-order low-pass filter with the given cutoff
+/// frequency in hertz for a sample taken `dt` seconds after the previous one.
+
+fn smoothing_factor<F: Float>(cutoff: F, dt: F) -> F {
+	let two_pi = F::from(2.0 * std::f64::consts::PI).unwrap();
+	let tau = F::one() / (two_pi * cutoff);
+	F::one() / (F::one() + tau / dt)
+}
+
+// //////////////////////////////////////////////////////////////////////////////////////
+//
+// Ema
+//
+// //////////////////////////////////////////////////////////////////////////////////////
+
+/// Exponential moving average of a vector signal.
+///
+/// Each update moves the output towards the sample by
+/// `1 - exp(-dt / time_constant)`, so splitting a step into smaller steps
+/// with the same sample gives the same result.
+///
+/// # Example
+///
+/// ```
+/// use math3d::filter::Ema;
+/// use math3d::vectors::Vector3;
+///
+/// let mut ema = Ema::new(0.1);
+///
+/// ema.update(Vector3::new(0.0, 0.0, 0.0), 0.01);
+/// let smoothed = ema.update(Vector3::new(1.0, 0.0, 0.0), 0.01);
+///
+/// assert!(smoothed.x() > 0.0 && smoothed.x() < 0.1);
+/// ```
+
+#[derive(Copy, Clone, Debug)]
+pub struct Ema<F: Float> {
+	time_constant: F,
+	value: Option<Vector3<F>>,
+}
+
+impl<F: Float> Ema<F> {
+
+	/// Creates a new filter.
+	///
+	/// # Arguments
+	///
+	/// * `time_constant` - Time in seconds for the output to cover 63% of a
+	///   step in the input.
+
+	pub fn new(time_constant: F) -> Ema<F> {
+		Ema {
+			time_constant,
+			value: None,
+		}
+	}
+
+	/// Feeds a sample taken `dt` seconds after the previous one and returns
+	/// the filtered value. The first sample is returned unchanged.
+
+	pub fn update(&mut self, sample: Vector3<F>, dt: F) -> Vector3<F> {
+		let value = match self.value {
+			Some(previous) if self.time_constant > F::zero() => {
+				let alpha = F::one() - (-dt / self.time_constant).exp();
+				previous + (sample - previous) * alpha
+			}
+			_ => sample,
+		};
+		self.value = Some(value);
+		value
+	}
+
+	/// Returns the last filtered value, if any.
+	pub fn value(&self) -> Option<Vector3<F>> {
+		self.value
+	}
+
+	/// Forgets the filter state; the next sample is returned unchanged.
+	pub fn reset(&mut self) {
+		self.value = None;
+	}
+}
+
+// //////////////////////////////////////////////////////////////////////////////////////
+//
+// OneEuro
+//
+// //////////////////////////////////////////////////////////////////////////////////////
+
+/// One euro filter for vectors, after Casiez et al., "1€ Filter: A Simple
+/// Speed-based Low-pass Filter for Noisy Input in Interactive Systems".
+///
+/// A low-pass filter whose cutoff frequency rises with the speed of the
+/// signal: jitter is smoothed heavily while the input is still and lag stays
+/// small while it moves fast. Tune by setting `beta` to zero and lowering
+/// `min_cutoff` until jitter is acceptable, then raising `beta` until lag
+/// during fast motion is acceptable.
+///
+/// # Example
+///
+/// ```
+/// use math3d::filter::OneEuro;
+/// use math3d::vectors::Vector3;
+///
+/// let mut filter = OneEuro::new(1.0, 0.0, 1.0);
+///
+/// filter.update(Vector3::new(0.0, 0.0, 0.0), 0.01);
+/// let smoothed = filter.update(Vector3::new(1.0, 0.0, 0.0), 0.01);
+///
+/// assert!(smoothed.x() < 0.1);
+/// ```
+
+#[derive(Copy, Clone, Debug)]
+pub struct OneEuro<F: Float> {
+	min_cutoff: F,
+	beta: F,
+	derivative_cutoff: F,
+	value: Option<Vector3<F>>,
+	derivative: Vector3<F>,
+}
+
+impl<F: Float> OneEuro<F> {
+
+	/// Creates a new filter.
+	///
+	/// # Arguments
+	///
+	/// * `min_cutoff` - Cutoff frequency in hertz while the signal is still.
+	/// * `beta` - Increase of the cutoff frequency per unit of speed.
+	/// * `derivative_cutoff` - Cutoff frequency in hertz for the speed
+	///   estimate, usually 1.
+
+	pub fn new(min_cutoff: F, beta: F, derivative_cutoff: F) -> OneEuro<F> {
+		OneEuro {
+			min_cutoff,
+			beta,
+			derivative_cutoff,
+			value: None,
+			derivative: Vector3::zero(),
+		}
+	}
+
+	/// Feeds a sample taken `dt` seconds after the previous one and returns
+	/// the filtered value. The first sample is returned unchanged.
+
+	pub fn update(&mut self, sample: Vector3<F>, dt: F) -> Vector3<F> {
+		let previous = match self.value {
+			Some(previous) if dt > F::zero() => previous,
+			_ => {
+				self.value = Some(sample);
+				return sample;
+			}
+		};
+
+		let rate = (sample - previous) / dt;
+		let alpha = smoothing_factor(self.derivative_cutoff, dt);
+		self.derivative = self.derivative + (rate - self.derivative) * alpha;
+
+		let cutoff = self.min_cutoff + self.beta * self.derivative.magnitude();
+		let alpha = smoothing_factor(cutoff, dt);
+		let value = previous + (sample - previous) * alpha;
+		self.value = Some(value);
+		value
+	}
+
+	/// Returns the last filtered value, if any.
+	pub fn value(&self) -> Option<Vector3<F>> {
+		self.value
+	}
+
+	/// Forgets the filter state; the next sample is returned unchanged.
+	pub fn reset(&mut self) {
+		self.value = None;
+		self.derivative = Vector3::zero();
+	}
+}
+
+// //////////////////////////////////////////////////////////////////////////////////////
+//
+// OneEuroQuaternion
+//
+// //////////////////////////////////////////////////////////////////////////////////////
+
+/// One euro filter for unit quaternions. Works like `OneEuro`, with the
+/// speed measured as angular speed in radians per second and the smoothing
+/// done with `slerp`, so `q` and `-q` samples are treated as the same
+/// orientation.
+///
+/// # Example
+///
+/// ```
+/// use math3d::filter::OneEuroQuaternion;
+/// use math3d::quaternion::Quaternion;
+/// use math3d::vectors::Vector3;
+///
+/// let mut filter = OneEuroQuaternion::new(1.0, 0.5, 1.0);
+///
+/// filter.update(Quaternion::identity(), 0.01);
+/// let smoothed = filter.update(Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 10.0), 0.01);
+/// ```
+
+#[derive(Copy, Clone, Debug)]
+pub struct OneEuroQuaternion<F: Float> {
+	min_cutoff: F,
+	beta: F,
+	derivative_cutoff: F,
+	value: Option<Quaternion<F>>,
+	angular_velocity: Vector3<F>,
+}
+
+impl<F: Float> OneEuroQuaternion<F> {
+
+	/// Creates a new filter.
+	///
+	/// # Arguments
+	///
+	/// * `min_cutoff` - Cutoff frequency in hertz while the signal is still.
+	/// * `beta` - Increase of the cutoff frequency per radian per second of
+	///   angular speed.
+	/// * `derivative_cutoff` - Cutoff frequency in hertz for the angular
+	///   speed estimate, usually 1.
+
+	pub fn new(min_cutoff: F, beta: F, derivative_cutoff: F) -> OneEuroQuaternion<F> {
+		OneEuroQuaternion {
+			min_cutoff,
+			beta,
+			derivative_cutoff,
+			value: None,
+			angular_velocity: Vector3::zero(),
+		}
+	}
+
+	/// Feeds a sample taken `dt` seconds after the previous one and returns
+	/// the filtered orientation. The first sample is returned unchanged.
+
+	pub fn update(&mut self, sample: Quaternion<F>, dt: F) -> Quaternion<F> {
+		let previous = match self.value {
+			Some(previous) if dt > F::zero() => previous,
+			_ => {
+				self.value = Some(sample);
+				return sample;
+			}
+		};
+
+		let rate = (sample * previous.conjugate()).versor().to_rotation_vector() / dt;
+		let alpha = smoothing_factor(self.derivative_cutoff, dt);
+		self.angular_velocity = self.angular_velocity + (rate - self.angular_velocity) * alpha;
+
+		let cutoff = self.min_cutoff + self.beta * self.angular_velocity.magnitude();
+		let alpha = smoothing_factor(cutoff, dt);
+		let value = previous.slerp(sample, alpha);
+		self.value = Some(value);
+		value
+	}
+
+	/// Returns the last filtered orientation, if any.
+	pub fn value(&self) -> Option<Quaternion<F>> {
+		self.value
+	}
+
+	/// Forgets the filter state; the next sample is returned unchanged.
+	pub fn reset(&mut self) {
+		self.value = None;
+		self.angular_velocity = Vector3::zero();
+	}
+}
+
+// //////////////////////////////////////////////////////////////////////////////////////
+//
+// Mahony
+//
+// //////////////////////////////////////////////////////////////////////////////////////
+
+/// Mahony complementary filter, after Mahony et al., "Nonlinear
+/// Complementary Filters on the Special Orthogonal Group", fusing a
+/// gyroscope with an accelerometer and optionally a magnetometer into an
+/// orientation.
+///
+/// The gyroscope is integrated for the short term, while the directions of
+/// up and east measured by the other sensors pull the estimate back with a
+/// proportional-integral correction; the integral term learns the gyroscope
+/// bias. The orientation uses the frame of `Quaternion::from_accel_mag`: it
+/// rotates sensor-frame vectors into a world frame with y up and magnetic
+/// north along -z. Without magnetometer readings the heading drifts with the
+/// gyroscope.
+///
+/// # Example
+///
+/// ```
+/// use math3d::filter::Mahony;
+/// use math3d::vectors::Vector3;
+///
+/// let mut filter = Mahony::new(2.0, 0.1);
+/// let gyro = Vector3::new(0.0, 0.0, 0.0);
+/// let accel = Vector3::new(0.0, 9.81, 0.0);
+/// let mag = Vector3::new(0.0, -30.0, -20.0);
+///
+/// let q = filter.update(gyro, accel, Some(mag), 0.01);
+///
+/// assert!(q.approx_eq(math3d::quaternion::Quaternion::identity(), 1e-12));
+/// ```
+
+#[derive(Copy, Clone, Debug)]
+pub struct Mahony<F: Float> {
+	kp: F,
+	ki: F,
+	orientation: Option<Quaternion<F>>,
+	integral: Vector3<F>,
+}
+
+impl<F: Float> Mahony<F> {
+
+	/// Creates a new filter.
+	///
+	/// # Arguments
+	///
+	/// * `kp` - Proportional gain in radians per second per unit of error,
+	///   how fast the estimate follows the accelerometer and magnetometer.
+	///   Around 1 to 2 is typical.
+	/// * `ki` - Integral gain for the gyroscope bias estimate, zero to
+	///   disable it.
+
+	pub fn new(kp: F, ki: F) -> Mahony<F> {
+		Mahony {
+			kp,
+			ki,
+			orientation: None,
+			integral: Vector3::zero(),
+		}
+	}
+
+	/// Feeds one set of readings taken `dt` seconds after the previous one
+	/// and returns the estimated orientation.
+	///
+	/// The first update starts from `Quaternion::from_accel_mag` when it has
+	/// a usable magnetometer reading. Otherwise it starts from the shortest
+	/// rotation taking the measured up direction to world up, which gets the
+	/// tilt right and leaves the heading arbitrary, or from the identity if
+	/// the accelerometer reads zero. A zero accelerometer reading, as in free
+	/// fall, skips the correction and only integrates the gyroscope.
+	///
+	/// # Arguments
+	///
+	/// * `gyro` - Angular velocity in the sensor frame, in radians per second.
+	/// * `accel` - Accelerometer reading in the sensor frame, in any unit.
+	/// * `mag` - Magnetometer reading in the sensor frame, in any unit.
+	/// * `dt` - Seconds since the previous update.
+
+	pub fn update(&mut self, gyro: Vector3<F>, accel: Vector3<F>, mag: Option<Vector3<F>>, dt: F) -> Quaternion<F> {
+		let q = match self.orientation {
+			Some(q) => q,
+			None => {
+				let q = mag
+					.and_then(|mag| Quaternion::from_accel_mag(accel, mag))
+					.or_else(|| tilt_from_accel(accel))
+					.unwrap_or_else(Quaternion::identity);
+				self.orientation = Some(q);
+				return q;
+			}
+		};
+
+		// Body-frame error rotating the predicted directions onto the measured.
+		let to_sensor = q.conjugate();
+		let mut error = Vector3::zero();
+		if let Some(up) = accel.try_normalize() {
+			let predicted = to_sensor.rotate_vector(Vector3::new(F::zero(), F::one(), F::zero()));
+			error += up.cross(predicted);
+			if let Some(east) = mag.and_then(|mag| mag.cross(up).try_normalize()) {
+				let predicted = to_sensor.rotate_vector(Vector3::new(F::one(), F::zero(), F::zero()));
+				error += east.cross(predicted);
+			}
+		}
+
+		self.integral += error * (self.ki * dt);
+		let rate = gyro + error * self.kp + self.integral;
+		let q = (q * Quaternion::from_rotation_vector(rate * dt)).versor();
+		self.orientation = Some(q);
+		q
+	}
+
+	/// Returns the current orientation estimate, if any.
+	pub fn orientation(&self) -> Option<Quaternion<F>> {
+		self.orientation
+	}
+
+	/// Gyroscope bias learned by the integral term, in radians per second.
+	pub fn gyro_bias(&self) -> Vector3<F> {
+		-self.integral
+	}
+
+	/// Forgets the orientation and the bias estimate.
+	pub fn reset(&mut self) {
+		self.orientation = None;
+		self.integral = Vector3::zero();
+	}
+}
+
+/// Shortest rotation taking the accelerometer's up direction onto world up,
+/// or `None` for a zero reading.
+
+fn tilt_from_accel<F: Float>(accel: Vector3<F>) -> Option<Quaternion<F>> {
+	let up = accel.try_normalize()?;
+	let w = F::one() + up.y();
+	if w <= F::epsilon() {
+		// Upside down: half a turn about x, one of many shortest rotations.
+		return Some(Quaternion::new(F::zero(), [F::one(), F::zero(), F::zero()]));
+	}
+	let axis = up.cross(Vector3::new(F::zero(), F::one(), F::zero()));
+	Some(Quaternion::new(w, [axis.x(), axis.y(), axis.z()]).versor())
+}
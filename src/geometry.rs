@@ -0,0 +1,1652 @@
+//! # Geometry
+//!
+//! Geometric primitives built on top of the vector, point and matrix types.
+//!
+//! # Example
+//!
+//! ```
+//! use math3d::geometry::Obb;
+//! use math3d::points::Point3;
+//!
+//! let points = [
+//! 	Point3::new(0.0, 0.0, 0.0),
+//! 	Point3::new(1.0, 0.0, 0.0),
+//! 	Point3::new(0.0, 2.0, 0.0),
+//! 	Point3::new(0.0, 0.0, 3.0),
+//! ];
+//!
+//! let obb = Obb::fit(&points).unwrap();
+//!
+//! println!("{}", obb.volume());
+//! ```
+
+use num::Float;
+use crate::batch::PAR_CHUNK_SIZE;
+use crate::camera::{self, Camera, Viewport};
+use crate::gizmo;
+use crate::matrices::{Matrix3, Matrix4};
+use crate::points::{self, Point2, Point3};
+use crate::quaternion::Quaternion;
+use crate::vectors::{UnitVector3, Vector3};
+use rayon::prelude::*;
+
+// //////////////////////////////////////////////////////////////////////////////////////
+//
+// Obb
+//
+// //////////////////////////////////////////////////////////////////////////////////////
+
+/// Oriented bounding box described by a center, three orthonormal axes and the
+/// half extents of the box along each axis.
+
+#[derive(Copy, Clone, Debug)]
+pub struct Obb<F: Float> {
+	center: Point3<F>,
+	axes: [Vector3<F>; 3],
+	half_extents: Vector3<F>,
+}
+
+impl<F: Float> Obb<F> {
+
+	/// Creates a new oriented bounding box.
+	///
+	/// # Arguments
+	///
+	/// * `center` - Center of the box.
+	/// * `axes` - Orthonormal axes of the box.
+	/// * `half_extents` - Half of the box size along each of the axes.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Obb;
+	/// use math3d::points::Point3;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let obb = Obb::new(
+	/// 	Point3::new(0.0, 0.0, 0.0),
+	/// 	[Vector3::new(1.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0), Vector3::new(0.0, 0.0, 1.0)],
+	/// 	Vector3::new(1.0, 1.0, 1.0),
+	/// );
+	/// ```
+
+	pub fn new(center: Point3<F>, axes: [Vector3<F>; 3], half_extents: Vector3<F>) -> Obb<F> {
+		Obb {
+			center,
+			axes,
+			half_extents,
+		}
+	}
+
+	/// Returns the center of the box.
+	pub fn center(&self) -> Point3<F> {
+		self.center
+	}
+
+	/// Returns the orthonormal axes of the box.
+	pub fn axes(&self) -> [Vector3<F>; 3] {
+		self.axes
+	}
+
+	/// Returns the half extents of the box along its axes.
+	pub fn half_extents(&self) -> Vector3<F> {
+		self.half_extents
+	}
+
+	/// Volume of the box.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Obb;
+	/// use math3d::points::Point3;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let obb = Obb::new(
+	/// 	Point3::new(0.0, 0.0, 0.0),
+	/// 	[Vector3::new(1.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0), Vector3::new(0.0, 0.0, 1.0)],
+	/// 	Vector3::new(1.0, 2.0, 3.0),
+	/// );
+	///
+	/// assert_eq!(obb.volume(), 48.0);
+	/// ```
+
+	pub fn volume(&self) -> F {
+		let eight = F::from(8.0).unwrap();
+		eight * self.half_extents.element_product()
+	}
+
+	/// Tests whether the point lies inside the box, allowing for `epsilon` of
+	/// slack on every face.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Obb;
+	/// use math3d::points::Point3;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let obb = Obb::new(
+	/// 	Point3::new(0.0, 0.0, 0.0),
+	/// 	[Vector3::new(1.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0), Vector3::new(0.0, 0.0, 1.0)],
+	/// 	Vector3::new(1.0, 1.0, 1.0),
+	/// );
+	///
+	/// assert!(obb.contains(Point3::new(0.5, -0.5, 1.0), 0.0));
+	/// assert!(!obb.contains(Point3::new(1.5, 0.0, 0.0), 0.0));
+	/// ```
+
+	pub fn contains(&self, point: Point3<F>, epsilon: F) -> bool {
+		let d = point.to_vector() - self.center.to_vector();
+		(0..3).all(|i| d.dot(self.axes[i]).abs() <= self.half_extents[i] + epsilon)
+	}
+
+	/// Conservative axis-aligned box around the space the box sweeps over a
+	/// timestep, for inserting fast moving rotating bodies into a broad phase.
+	///
+	/// The center moves along `linear_velocity * dt` and the box spins about
+	/// it by the rotation vector `angular_velocity * dt`, in radians. Over a
+	/// rotation by at most `θ` every box axis moves by a chord of at most `θ`,
+	/// so each half extent of the box's own AABB grows by at most `θ` times
+	/// the sum of its half extents, and never beyond its bounding sphere.
+	/// Only the rotation angle matters, not its axis.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Obb;
+	/// use math3d::points::Point3;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let obb = Obb::new(
+	/// 	Point3::new(0.0, 0.0, 0.0),
+	/// 	[Vector3::new(1.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0), Vector3::new(0.0, 0.0, 1.0)],
+	/// 	Vector3::new(1.0, 1.0, 1.0),
+	/// );
+	///
+	/// let swept = obb.swept_aabb(Vector3::new(4.0, 0.0, 0.0), Vector3::zero(), 0.5);
+	///
+	/// assert!(swept.min() == Point3::new(-1.0, -1.0, -1.0));
+	/// assert!(swept.max() == Point3::new(3.0, 1.0, 1.0));
+	/// ```
+
+	pub fn swept_aabb(&self, linear_velocity: Vector3<F>, angular_velocity: Vector3<F>, dt: F) -> Aabb3<F> {
+		let two = F::from(2.0).unwrap();
+		let angle = (angular_velocity.magnitude() * dt.abs()).min(two);
+		let spread = self.half_extents.element_sum() * angle;
+		let radius = self.half_extents.magnitude();
+		let mut extent = Vector3::zero();
+		for k in 0..3 {
+			let own = (0..3).fold(F::zero(), |acc, i| acc + self.half_extents[i] * self.axes[i][k].abs());
+			extent[k] = (own + spread).min(radius);
+		}
+
+		let start = self.center.to_vector();
+		let end = start + linear_velocity * dt;
+		Aabb3::new(
+			Point3::from_vector(start.min(end) - extent),
+			Point3::from_vector(start.max(end) + extent),
+		)
+	}
+
+	/// Fits an oriented bounding box to a point cloud using principal component
+	/// analysis.
+	///
+	/// The axes of the box are the eigenvectors of the covariance matrix of the
+	/// points. The result is always a valid bounding box, but it is not
+	/// necessarily the one of minimum volume; see `fit_refined`.
+	///
+	/// Returns `None` when `points` is empty.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Obb;
+	/// use math3d::points::Point3;
+	///
+	/// let points = [Point3::new(0.0, 0.0, 0.0), Point3::new(2.0, 2.0, 0.0)];
+	/// let obb = Obb::fit(&points).unwrap();
+	///
+	/// assert!(points.iter().all(|p| obb.contains(*p, 1e-9)));
+	/// ```
+
+	pub fn fit(points: &[Point3<F>]) -> Option<Obb<F>> {
+		if points.is_empty() {
+			return None;
+		}
+		let (_, vectors) = covariance(points).symmetric_eigen();
+		let axes = [vectors[0], vectors[1], vectors[0].cross(vectors[1])];
+		Some(Obb::enclosing(points, axes))
+	}
+
+	/// Fits an oriented bounding box to a point cloud, refining the principal
+	/// component fit with rotating calipers.
+	///
+	/// For each of the three PCA axes the points are projected onto the
+	/// perpendicular plane and the minimum-area enclosing rectangle of their
+	/// convex hull is found; one side of that rectangle is always collinear
+	/// with a hull edge. The box of smallest volume among the PCA box and the
+	/// three refined candidates is returned.
+	///
+	/// Returns `None` when `points` is empty.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Obb;
+	/// use math3d::points::Point3;
+	///
+	/// let points = [
+	/// 	Point3::new(0.0, 0.0, 0.0),
+	/// 	Point3::new(1.0, 0.0, 0.0),
+	/// 	Point3::new(0.0, 1.0, 0.0),
+	/// 	Point3::new(1.0, 1.0, 1.0),
+	/// ];
+	///
+	/// let pca = Obb::fit(&points).unwrap();
+	/// let refined = Obb::fit_refined(&points).unwrap();
+	///
+	/// assert!(refined.volume() <= pca.volume());
+	/// ```
+
+	pub fn fit_refined(points: &[Point3<F>]) -> Option<Obb<F>> {
+		let mut best = Obb::fit(points)?;
+		let pca_axes = best.axes;
+
+		for k in 0..3 {
+			let normal = pca_axes[k];
+			let u = pca_axes[(k + 1) % 3];
+			let v = pca_axes[(k + 2) % 3];
+
+			let projected: Vec<(F, F)> = points
+				.iter()
+				.map(|p| (p.to_vector().dot(u), p.to_vector().dot(v)))
+				.collect();
+			let (cos, sin) = match min_area_rectangle(convex_hull_2d(projected)) {
+				Some(direction) => direction,
+				None => continue,
+			};
+
+			let a = u * cos + v * sin;
+			let b = normal.cross(a);
+			let candidate = Obb::enclosing(points, [a, b, normal]);
+			if candidate.volume() < best.volume() {
+				best = candidate;
+			}
+		}
+		Some(best)
+	}
+
+	/// Smallest box with the given axes that encloses all points.
+
+	fn enclosing(points: &[Point3<F>], axes: [Vector3<F>; 3]) -> Obb<F> {
+		let mut min = [F::infinity(); 3];
+		let mut max = [F::neg_infinity(); 3];
+
+		for p in points {
+			for i in 0..3 {
+				let d = p.to_vector().dot(axes[i]);
+				min[i] = min[i].min(d);
+				max[i] = max[i].max(d);
+			}
+		}
+
+		let two = F::from(2.0).unwrap();
+		let mut center = Vector3::zero();
+		let mut half_extents = Vector3::zero();
+		for i in 0..3 {
+			center += axes[i] * ((min[i] + max[i]) / two);
+			half_extents[i] = (max[i] - min[i]) / two;
+		}
+		Obb::new(Point3::from_vector(center), axes, half_extents)
+	}
+}
+
+// //////////////////////////////////////////////////////////////////////////////////////
+//
+// Plane
+//
+// //////////////////////////////////////////////////////////////////////////////////////
+
+/// Plane in Hessian normal form: the points `p` for which `normal · p + d = 0`.
+
+#[derive(Copy, Clone, Debug)]
+pub struct Plane<F: Float> {
+	normal: UnitVector3<F>,
+	d: F,
+}
+
+impl<F: Float> Plane<F> {
+
+	/// Creates a plane from a normal and a distance term. The normal does not
+	/// need to be unit length; the plane equation is scaled to normalize it.
+	///
+	/// # Arguments
+	///
+	/// * `normal` - Normal of the plane.
+	/// * `d` - Distance term of the plane equation `normal · p + d = 0`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Plane;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let plane = Plane::new(Vector3::new(0.0, 2.0, 0.0), -2.0);
+	///
+	/// assert_eq!(plane.d(), -1.0);
+	/// ```
+
+	pub fn new(normal: Vector3<F>, d: F) -> Plane<F> {
+		let magnitude = normal.magnitude();
+		Plane {
+			normal: UnitVector3::new_unchecked(normal / magnitude),
+			d: d / magnitude,
+		}
+	}
+
+	/// Creates a plane from a unit normal and a distance term, without
+	/// rescaling.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Plane;
+	/// use math3d::vectors::UnitVector3;
+	///
+	/// let plane = Plane::from_unit_normal(UnitVector3::y_axis(), -2.0);
+	///
+	/// assert_eq!(plane.d(), -2.0);
+	/// ```
+
+	pub fn from_unit_normal(normal: UnitVector3<F>, d: F) -> Plane<F> {
+		Plane {
+			normal,
+			d,
+		}
+	}
+
+	/// Creates a plane passing through `point` with the given normal.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Plane;
+	/// use math3d::points::Point3;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let plane = Plane::from_point_normal(Point3::new(0.0, 1.0, 0.0), Vector3::new(0.0, 1.0, 0.0));
+	///
+	/// assert_eq!(plane.d(), -1.0);
+	/// ```
+
+	pub fn from_point_normal(point: Point3<F>, normal: Vector3<F>) -> Plane<F> {
+		let normal = UnitVector3::new_unchecked(normal.normalized());
+		Plane {
+			normal,
+			d: -normal.dot(point.to_vector()),
+		}
+	}
+
+	/// Returns the unit normal of the plane.
+	pub fn normal(&self) -> Vector3<F> {
+		self.normal.to_vector()
+	}
+
+	/// Returns the unit normal of the plane as a `UnitVector3`.
+	pub fn unit_normal(&self) -> UnitVector3<F> {
+		self.normal
+	}
+
+	/// Returns the distance term of the plane equation.
+	pub fn d(&self) -> F {
+		self.d
+	}
+
+	/// Signed distance from the plane to the point, positive on the side the
+	/// normal points to.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Plane;
+	/// use math3d::points::Point3;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let plane = Plane::new(Vector3::new(0.0, 1.0, 0.0), -1.0);
+	///
+	/// assert_eq!(plane.signed_distance(Point3::new(5.0, 3.0, 0.0)), 2.0);
+	/// ```
+
+	pub fn signed_distance(&self, point: Point3<F>) -> F {
+		self.normal.dot(point.to_vector()) + self.d
+	}
+
+	/// Intersects the ray `origin + direction * t` with the plane, returning
+	/// the ray parameter `t >= 0` of the hit like `Heightfield::raycast`. Rays
+	/// parallel to the plane or pointing away from it miss. The plane is hit
+	/// from both sides.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Plane;
+	/// use math3d::points::Point3;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let ground = Plane::new(Vector3::new(0.0, 1.0, 0.0), 0.0);
+	///
+	/// assert_eq!(ground.ray_parameter(Point3::new(0.0, 4.0, 0.0), Vector3::new(1.0, -2.0, 0.0)), Some(2.0));
+	/// assert_eq!(ground.ray_parameter(Point3::new(0.0, 4.0, 0.0), Vector3::new(1.0, 0.0, 0.0)), None);
+	/// ```
+
+	pub fn ray_parameter(&self, origin: Point3<F>, direction: Vector3<F>) -> Option<F> {
+		let denominator = self.normal.dot(direction);
+		if denominator == F::zero() {
+			return None;
+		}
+		let t = -self.signed_distance(origin) / denominator;
+		if t >= F::zero() && t.is_finite() {
+			Some(t)
+		} else {
+			None
+		}
+	}
+}
+
+// //////////////////////////////////////////////////////////////////////////////////////
+//
+// Aabb3
+//
+// //////////////////////////////////////////////////////////////////////////////////////
+
+/// Axis-aligned bounding box described by its minimum and maximum corners.
+
+#[derive(Copy, Clone, Debug)]
+pub struct Aabb3<F: Float> {
+	min: Point3<F>,
+	max: Point3<F>,
+}
+
+impl<F: Float> Aabb3<F> {
+
+	/// Creates a new axis-aligned bounding box.
+	///
+	/// # Arguments
+	///
+	/// * `min` - Corner with the smallest coordinates.
+	/// * `max` - Corner with the largest coordinates.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Aabb3;
+	/// use math3d::points::Point3;
+	///
+	/// let aabb = Aabb3::new(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 2.0, 3.0));
+	/// ```
+
+	pub fn new(min: Point3<F>, max: Point3<F>) -> Aabb3<F> {
+		Aabb3 {
+			min,
+			max,
+		}
+	}
+
+	/// Returns the minimum corner.
+	pub fn min(&self) -> Point3<F> {
+		self.min
+	}
+
+	/// Returns the maximum corner.
+	pub fn max(&self) -> Point3<F> {
+		self.max
+	}
+
+	/// Center of the box.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Aabb3;
+	/// use math3d::points::Point3;
+	///
+	/// let aabb = Aabb3::new(Point3::new(0.0, 0.0, 0.0), Point3::new(2.0, 4.0, 6.0));
+	///
+	/// assert!(aabb.center() == Point3::new(1.0, 2.0, 3.0));
+	/// ```
+
+	pub fn center(&self) -> Point3<F> {
+		let two = F::from(2.0).unwrap();
+		Point3::from_vector((self.min.to_vector() + self.max.to_vector()) / two)
+	}
+
+	/// Tests whether the point lies inside the box or on its boundary.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Aabb3;
+	/// use math3d::points::Point3;
+	///
+	/// let aabb = Aabb3::new(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 1.0, 1.0));
+	///
+	/// assert!(aabb.contains(Point3::new(0.5, 1.0, 0.0)));
+	/// assert!(!aabb.contains(Point3::new(0.5, 1.5, 0.0)));
+	/// ```
+
+	pub fn contains(&self, point: Point3<F>) -> bool {
+		(0..3).all(|i| self.min[i] <= point[i] && point[i] <= self.max[i])
+	}
+
+	/// Smallest box enclosing both boxes.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Aabb3;
+	/// use math3d::points::Point3;
+	///
+	/// let a = Aabb3::new(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 1.0, 1.0));
+	/// let b = Aabb3::new(Point3::new(-1.0, 0.5, 0.5), Point3::new(0.5, 0.5, 2.0));
+	/// let merged = a.merge(&b);
+	///
+	/// assert!(merged.min() == Point3::new(-1.0, 0.0, 0.0));
+	/// assert!(merged.max() == Point3::new(1.0, 1.0, 2.0));
+	/// ```
+
+	pub fn merge(&self, other: &Aabb3<F>) -> Aabb3<F> {
+		Aabb3::new(
+			Point3::from_vector(self.min.to_vector().min(other.min.to_vector())),
+			Point3::from_vector(self.max.to_vector().max(other.max.to_vector())),
+		)
+	}
+
+	/// Smallest box enclosing all the points.
+	///
+	/// Returns `None` when `points` is empty.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Aabb3;
+	/// use math3d::points::Point3;
+	///
+	/// let points = [Point3::new(1.0, -1.0, 0.0), Point3::new(-2.0, 3.0, 1.0)];
+	/// let aabb = Aabb3::from_points(&points).unwrap();
+	///
+	/// assert!(aabb.min() == Point3::new(-2.0, -1.0, 0.0));
+	/// assert!(aabb.max() == Point3::new(1.0, 3.0, 1.0));
+	/// ```
+
+	pub fn from_points(points: &[Point3<F>]) -> Option<Aabb3<F>> {
+		let (first, rest) = points.split_first()?;
+		let (mut min, mut max) = (first.to_vector(), first.to_vector());
+		for p in rest {
+			min = min.min(p.to_vector());
+			max = max.max(p.to_vector());
+		}
+		Some(Aabb3::new(Point3::from_vector(min), Point3::from_vector(max)))
+	}
+
+	/// Tight box around this box transformed by an affine matrix, in the
+	/// row-vector convention of `Vector4 * Matrix4`.
+	///
+	/// Uses the center and half extent form: the center is transformed as a
+	/// point, and each new half extent is the sum of the old half extents
+	/// weighted by the absolute values of the matching matrix column. This
+	/// gives the same box as transforming all eight corners, at a fraction of
+	/// the cost. The last column of the matrix must be `(0, 0, 0, 1)`; use
+	/// the corners for projective matrices.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Aabb3;
+	/// use math3d::points::Point3;
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let aabb = Aabb3::new(Point3::new(-1.0, -1.0, -1.0), Point3::new(1.0, 1.0, 1.0));
+	/// let m = Quaternion::<f64>::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 45.0).rotation_matrix4();
+	/// let rotated = aabb.transformed(&m);
+	///
+	/// assert!((rotated.max()[0] - 2.0f64.sqrt()).abs() < 1e-12);
+	/// assert!((rotated.max()[2] - 1.0).abs() < 1e-12);
+	/// ```
+
+	pub fn transformed(&self, m: &Matrix4<F>) -> Aabb3<F> {
+		let half = F::from(0.5).unwrap();
+		let center = (self.min.to_vector() + self.max.to_vector()) * half;
+		let extent = (self.max.to_vector() - self.min.to_vector()) * half;
+
+		let center = (center.extend(F::one()) * *m).truncate();
+		let mut new_extent = Vector3::zero();
+		for j in 0..3 {
+			new_extent[j] = (0..3).fold(F::zero(), |sum, i| sum + m[i][j].abs() * extent[i]);
+		}
+		Aabb3::new(Point3::from_vector(center - new_extent), Point3::from_vector(center + new_extent))
+	}
+
+	/// Estimates the screen-space rectangle covered by the box, as the
+	/// minimum and maximum pixel coordinates of its projected corners clipped
+	/// to the viewport. Useful for culling objects that are too small to see.
+	///
+	/// Returns `None` when the box is entirely behind the near plane or
+	/// projects outside of the viewport. A box that crosses the near plane
+	/// covers the whole viewport, which is conservative.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::camera::{Camera, Viewport};
+	/// use math3d::geometry::Aabb3;
+	/// use math3d::points::Point3;
+	/// use math3d::quaternion::Quaternion;
+	///
+	/// let camera = Camera::new(Point3::new(0.0, 0.0, 10.0), Quaternion::identity(), 1.0, 1.0, 0.1, 100.0);
+	/// let viewport = Viewport::new(0.0, 0.0, 800.0, 800.0);
+	/// let aabb = Aabb3::new(Point3::new(-1.0, -1.0, -1.0), Point3::new(1.0, 1.0, 1.0));
+	///
+	/// let (min, max) = aabb.screen_rect(&camera, &viewport).unwrap();
+	///
+	/// assert!(max.x() - min.x() < 200.0);
+	/// ```
+
+	pub fn screen_rect(&self, camera: &Camera<F>, viewport: &Viewport<F>) -> Option<(Point2<F>, Point2<F>)> {
+		let near = -*camera.near();
+		let corners = gizmo::aabb_corners(self).map(|c| camera.to_view_space(c));
+		if corners.iter().all(|c| c.z() > near) {
+			return None;
+		}
+
+		let (left, top) = (viewport.x(), viewport.y());
+		let (right, bottom) = (left + viewport.width(), top + viewport.height());
+		if corners.iter().any(|c| c.z() > near) {
+			return Some((Point2::new(left, top), Point2::new(right, bottom)));
+		}
+
+		let f = camera.focal_length();
+		let (mut min_x, mut min_y) = (F::infinity(), F::infinity());
+		let (mut max_x, mut max_y) = (F::neg_infinity(), F::neg_infinity());
+		for c in &corners {
+			let depth = -c.z();
+			let (x, y) = viewport.from_ndc(f / *camera.aspect() * c.x() / depth, f * c.y() / depth);
+			min_x = min_x.min(x);
+			min_y = min_y.min(y);
+			max_x = max_x.max(x);
+			max_y = max_y.max(y);
+		}
+
+		if max_x < left || min_x > right || max_y < top || min_y > bottom {
+			return None;
+		}
+		Some((
+			Point2::new(min_x.max(left), min_y.max(top)),
+			Point2::new(max_x.min(right), max_y.min(bottom)),
+		))
+	}
+}
+
+impl<F: Float + Send + Sync> Aabb3<F> {
+
+	/// Smallest box enclosing all the points, computed in parallel.
+	///
+	/// The points are split into chunks that are bounded independently on the
+	/// rayon thread pool, and the chunk-local boxes are merged at the end. The
+	/// result is identical to `from_points`.
+	///
+	/// Returns `None` when `points` is empty.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Aabb3;
+	/// use math3d::points::Point3;
+	///
+	/// let points: Vec<_> = (0..1000).map(|i| Point3::new(i as f64, 0.0, -(i as f64))).collect();
+	/// let aabb = Aabb3::from_points_par(&points).unwrap();
+	///
+	/// assert!(aabb.max() == Point3::new(999.0, 0.0, 0.0));
+	/// ```
+
+	pub fn from_points_par(points: &[Point3<F>]) -> Option<Aabb3<F>> {
+		points
+			.par_chunks(PAR_CHUNK_SIZE)
+			.filter_map(Aabb3::from_points)
+			.reduce_with(|a, b| a.merge(&b))
+	}
+}
+
+// //////////////////////////////////////////////////////////////////////////////////////
+//
+// Sphere
+//
+// //////////////////////////////////////////////////////////////////////////////////////
+
+/// Sphere described by a center and a radius.
+
+#[derive(Copy, Clone, Debug)]
+pub struct Sphere<F: Float> {
+	center: Point3<F>,
+	radius: F,
+}
+
+impl<F: Float> Sphere<F> {
+
+	/// Creates a new sphere.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Sphere;
+	/// use math3d::points::Point3;
+	///
+	/// let sphere = Sphere::new(Point3::new(0.0, 0.0, 0.0), 1.0);
+	/// ```
+
+	pub fn new(center: Point3<F>, radius: F) -> Sphere<F> {
+		Sphere {
+			center,
+			radius,
+		}
+	}
+
+	/// Returns the center of the sphere.
+	pub fn center(&self) -> Point3<F> {
+		self.center
+	}
+
+	/// Returns the radius of the sphere.
+	pub fn radius(&self) -> F {
+		self.radius
+	}
+
+	/// Tests whether the point lies inside the sphere or on its surface.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Sphere;
+	/// use math3d::points::Point3;
+	///
+	/// let sphere = Sphere::new(Point3::new(0.0, 0.0, 0.0), 1.0);
+	///
+	/// assert!(sphere.contains(Point3::new(0.0, 1.0, 0.0)));
+	/// ```
+
+	pub fn contains(&self, point: Point3<F>) -> bool {
+		self.center.distance_to(point) <= self.radius
+	}
+
+	/// Estimates the radius of the sphere on screen in normalized device
+	/// coordinates, where the height of the viewport spans two units. Multiply
+	/// by half the viewport height for pixels. Useful for selecting levels of
+	/// detail.
+	///
+	/// The estimate is exact for spheres centered on the view axis and grows
+	/// slightly too small towards the edges of wide fields of view. Returns
+	/// infinity when the camera is inside the sphere.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::camera::Camera;
+	/// use math3d::geometry::Sphere;
+	/// use math3d::points::Point3;
+	/// use math3d::quaternion::Quaternion;
+	///
+	/// let fov = std::f64::consts::FRAC_PI_2;
+	/// let camera = Camera::new(Point3::new(0.0, 0.0, 0.0), Quaternion::identity(), fov, 1.0, 0.1, 100.0);
+	/// let sphere = Sphere::new(Point3::new(0.0, 0.0, -10.0), 1.0);
+	///
+	/// let radius = sphere.projected_radius(&camera);
+	///
+	/// assert!((radius - 1.0 / 99.0f64.sqrt()).abs() < 1e-12);
+	/// ```
+
+	pub fn projected_radius(&self, camera: &Camera<F>) -> F {
+		let distance = camera.position().distance_to(self.center);
+		if distance <= self.radius {
+			return F::infinity();
+		}
+		let tangent = self.radius / (distance * distance - self.radius * self.radius).sqrt();
+		tangent * camera.focal_length()
+	}
+
+	/// Bounding sphere of the points, centered on the center of their
+	/// axis-aligned bounding box. The sphere is not the minimal one, but it is
+	/// cheap to compute and its radius is at most `sqrt(3)` times the minimal
+	/// radius.
+	///
+	/// Returns `None` when `points` is empty.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Sphere;
+	/// use math3d::points::Point3;
+	///
+	/// let points = [Point3::new(-1.0, 0.0, 0.0), Point3::new(1.0, 0.0, 0.0)];
+	/// let sphere = Sphere::from_points(&points).unwrap();
+	///
+	/// assert_eq!(sphere.radius(), 1.0);
+	/// ```
+
+	pub fn from_points(points: &[Point3<F>]) -> Option<Sphere<F>> {
+		let center = Aabb3::from_points(points)?.center();
+		let radius = points
+			.iter()
+			.fold(F::zero(), |r, p| r.max(center.distance_to(*p)));
+		Some(Sphere::new(center, radius))
+	}
+}
+
+impl<F: Float + Send + Sync> Sphere<F> {
+
+	/// Bounding sphere of the points, computed in parallel. Both the bounding
+	/// box used for the center and the radius are reduced from chunk-local
+	/// results; the result is identical to `from_points`.
+	///
+	/// Returns `None` when `points` is empty.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Sphere;
+	/// use math3d::points::Point3;
+	///
+	/// let points: Vec<_> = (0..1000).map(|i| Point3::new(i as f64, 0.0, 0.0)).collect();
+	/// let sphere = Sphere::from_points_par(&points).unwrap();
+	///
+	/// assert_eq!(sphere.radius(), 499.5);
+	/// ```
+
+	pub fn from_points_par(points: &[Point3<F>]) -> Option<Sphere<F>> {
+		let center = Aabb3::from_points_par(points)?.center();
+		let radius = points
+			.par_chunks(PAR_CHUNK_SIZE)
+			.map(|chunk| chunk.iter().fold(F::zero(), |r, p| r.max(center.distance_to(*p))))
+			.reduce(F::zero, F::max);
+		Some(Sphere::new(center, radius))
+	}
+}
+
+// //////////////////////////////////////////////////////////////////////////////////////
+//
+// Ellipsoid
+//
+// //////////////////////////////////////////////////////////////////////////////////////
+
+/// Ellipsoid described by a center, its radii along its local x, y and z
+/// axes, and a rotation from local to world space.
+
+#[derive(Copy, Clone, Debug)]
+pub struct Ellipsoid<F: Float> {
+	center: Point3<F>,
+	radii: Vector3<F>,
+	rotation: Quaternion<F>,
+}
+
+impl<F: Float> Ellipsoid<F> {
+
+	/// Creates a new ellipsoid. The radii must be positive and the rotation
+	/// a unit quaternion.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Ellipsoid;
+	/// use math3d::points::Point3;
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let ellipsoid = Ellipsoid::new(Point3::new(0.0, 0.0, 0.0), Vector3::new(3.0, 2.0, 1.0), Quaternion::identity());
+	/// ```
+
+	pub fn new(center: Point3<F>, radii: Vector3<F>, rotation: Quaternion<F>) -> Ellipsoid<F> {
+		Ellipsoid {
+			center,
+			radii,
+			rotation,
+		}
+	}
+
+	/// Ellipsoid covering the same points as the sphere.
+	pub fn from_sphere(sphere: &Sphere<F>) -> Ellipsoid<F> {
+		let r = sphere.radius();
+		Ellipsoid::new(sphere.center(), Vector3::new(r, r, r), Quaternion::identity())
+	}
+
+	/// Returns the center of the ellipsoid.
+	pub fn center(&self) -> Point3<F> {
+		self.center
+	}
+
+	/// Returns the radii along the local axes.
+	pub fn radii(&self) -> Vector3<F> {
+		self.radii
+	}
+
+	/// Returns the rotation from local to world space.
+	pub fn rotation(&self) -> Quaternion<F> {
+		self.rotation
+	}
+
+	/// Matrix taking world space to the space where the ellipsoid is the
+	/// unit sphere at the origin, for row vectors `v * M`. Lengths along a
+	/// ray are scaled, but ray parameters are the same in both spaces.
+
+	pub fn to_unit_sphere(&self) -> Matrix4<F> {
+		let (c, r) = (self.center, self.radii);
+		let one = F::one();
+		camera::translation(-c.x(), -c.y(), -c.z())
+			* self.rotation.conjugate().rotation_matrix4()
+			* camera::scale(one / r.x(), one / r.y(), one / r.z())
+	}
+
+	/// Tests whether the point lies inside the ellipsoid or on its surface.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Ellipsoid;
+	/// use math3d::points::Point3;
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let rotation = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 90.0);
+	/// let ellipsoid = Ellipsoid::new(Point3::new(0.0, 0.0, 0.0), Vector3::new(3.0, 1.0, 1.0), rotation);
+	///
+	/// assert!(ellipsoid.contains(Point3::new(0.0, 2.5, 0.0)));
+	/// assert!(!ellipsoid.contains(Point3::new(2.5, 0.0, 0.0)));
+	/// ```
+
+	pub fn contains(&self, point: Point3<F>) -> bool {
+		(point.to_vector4() * self.to_unit_sphere()).truncate().magnitude_squared() <= F::one()
+	}
+
+	/// First intersection of a ray with the surface of the ellipsoid, found
+	/// by intersecting the unit sphere in the space of `to_unit_sphere`.
+	///
+	/// Returns the ray parameter `t >= 0` of the hit, so the hit point is
+	/// `origin + direction * t`. A ray starting inside hits the surface on
+	/// its way out.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Ellipsoid;
+	/// use math3d::points::Point3;
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let ellipsoid = Ellipsoid::<f64>::new(Point3::new(0.0, 0.0, 0.0), Vector3::new(4.0, 2.0, 1.0), Quaternion::identity());
+	/// let t = ellipsoid.raycast(Point3::new(-10.0, 0.0, 0.0), Vector3::new(2.0, 0.0, 0.0)).unwrap();
+	///
+	/// assert!((t - 3.0).abs() < 1e-12);
+	/// ```
+
+	pub fn raycast(&self, origin: Point3<F>, direction: Vector3<F>) -> Option<F> {
+		let m = self.to_unit_sphere();
+		let o = (origin.to_vector4() * m).truncate();
+		let d = (direction.extend(F::zero()) * m).truncate();
+
+		let a = d.dot(d);
+		let b = o.dot(d);
+		let discriminant = b * b - a * (o.dot(o) - F::one());
+		if a == F::zero() || discriminant < F::zero() {
+			return None;
+		}
+		let root = discriminant.sqrt();
+		let (near, far) = ((-b - root) / a, (-b + root) / a);
+		if far < F::zero() {
+			None
+		} else if near >= F::zero() {
+			Some(near)
+		} else {
+			Some(far)
+		}
+	}
+
+	/// Tight axis-aligned bounding box. Along each world axis the half
+	/// extent is the length of that row of the rotated and scaled axes,
+	/// `sqrt(sum_j (R_ij r_j)^2)`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Ellipsoid;
+	/// use math3d::points::Point3;
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let rotation = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 90.0);
+	/// let ellipsoid = Ellipsoid::new(Point3::new(1.0, 0.0, 0.0), Vector3::new(3.0, 1.0, 2.0), rotation);
+	/// let aabb = ellipsoid.aabb();
+	///
+	/// assert!(aabb.min().distance_to(Point3::new(0.0, -3.0, -2.0)) < 1e-12);
+	/// assert!(aabb.max().distance_to(Point3::new(2.0, 3.0, 2.0)) < 1e-12);
+	/// ```
+
+	pub fn aabb(&self) -> Aabb3<F> {
+		let axes = [Vector3::unit_x(), Vector3::unit_y(), Vector3::unit_z()]
+			.map(|axis| self.rotation.rotate_vector(axis));
+		let extent = |i: usize| {
+			(0..3)
+				.map(|j| axes[j][i] * self.radii[j])
+				.fold(F::zero(), |sum, a| sum + a * a)
+				.sqrt()
+		};
+		let half = Vector3::new(extent(0), extent(1), extent(2));
+		let center = self.center.to_vector();
+		Aabb3::new(Point3::from_vector(center - half), Point3::from_vector(center + half))
+	}
+}
+
+// //////////////////////////////////////////////////////////////////////////////////////
+//
+// Heightfield
+//
+// //////////////////////////////////////////////////////////////////////////////////////
+
+/// Terrain described by a regular grid of heights over the xz plane.
+///
+/// Sample `(column, row)` lies at `origin + (column * spacing, height,
+/// row * spacing)`, so columns run along x and rows along z. Between samples
+/// the surface is interpolated bilinearly by `height_at` and `normal_at`;
+/// `raycast` splits each cell into two triangles along the diagonal from
+/// `(column, row)` to `(column + 1, row + 1)`. Both agree at the samples and
+/// along the cell edges.
+
+#[derive(Clone, Debug)]
+pub struct Heightfield<F: Float> {
+	origin: Point3<F>,
+	spacing: F,
+	columns: usize,
+	rows: usize,
+	heights: Vec<F>,
+}
+
+impl<F: Float> Heightfield<F> {
+
+	/// Creates a new heightfield.
+	///
+	/// # Arguments
+	///
+	/// * `origin` - Position of sample `(0, 0)` at height zero.
+	/// * `spacing` - Distance between neighboring samples.
+	/// * `columns` - Number of samples along x, at least 2.
+	/// * `rows` - Number of samples along z, at least 2.
+	/// * `heights` - `columns * rows` heights, row by row.
+	///
+	/// # Panics
+	///
+	/// Panics if the grid is smaller than 2 by 2 or `heights` has the wrong
+	/// length.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Heightfield;
+	/// use math3d::points::Point3;
+	///
+	/// let terrain = Heightfield::new(Point3::new(0.0, 0.0, 0.0), 1.0, 2, 2, vec![0.0, 1.0, 2.0, 3.0]);
+	///
+	/// assert_eq!(terrain.height_at(0.5, 0.5), Some(1.5));
+	/// ```
+
+	pub fn new(origin: Point3<F>, spacing: F, columns: usize, rows: usize, heights: Vec<F>) -> Heightfield<F> {
+		assert!(columns >= 2 && rows >= 2, "heightfield needs at least 2 by 2 samples");
+		assert_eq!(heights.len(), columns * rows, "heights must have columns * rows samples");
+		Heightfield {
+			origin,
+			spacing,
+			columns,
+			rows,
+			heights,
+		}
+	}
+
+	/// Returns the position of sample `(0, 0)` at height zero.
+	pub fn origin(&self) -> Point3<F> {
+		self.origin
+	}
+
+	/// Returns the distance between neighboring samples.
+	pub fn spacing(&self) -> F {
+		self.spacing
+	}
+
+	/// Returns the number of samples along x.
+	pub fn columns(&self) -> usize {
+		self.columns
+	}
+
+	/// Returns the number of samples along z.
+	pub fn rows(&self) -> usize {
+		self.rows
+	}
+
+	/// World-space height of a sample.
+	pub fn sample(&self, column: usize, row: usize) -> F {
+		self.origin[1] + self.heights[row * self.columns + column]
+	}
+
+	/// World-space position of a sample.
+	pub fn sample_point(&self, column: usize, row: usize) -> Point3<F> {
+		Point3::new(
+			self.origin[0] + F::from(column).unwrap() * self.spacing,
+			self.sample(column, row),
+			self.origin[2] + F::from(row).unwrap() * self.spacing,
+		)
+	}
+
+	/// Replaces the height of a sample, given above the origin like the
+	/// heights passed to `new`.
+	///
+	/// # Panics
+	///
+	/// Panics if the sample is outside the grid.
+
+	pub fn set_height(&mut self, column: usize, row: usize, height: F) {
+		assert!(column < self.columns && row < self.rows, "sample outside the heightfield");
+		self.heights[row * self.columns + column] = height;
+	}
+
+	/// Bilinearly interpolated height at world position `(x, z)`, or `None`
+	/// outside the grid.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Heightfield;
+	/// use math3d::points::Point3;
+	///
+	/// let terrain = Heightfield::new(Point3::new(0.0, 0.0, 0.0), 2.0, 2, 2, vec![0.0, 2.0, 0.0, 2.0]);
+	///
+	/// assert_eq!(terrain.height_at(1.0, 1.0), Some(1.0));
+	/// assert_eq!(terrain.height_at(3.0, 1.0), None);
+	/// ```
+
+	pub fn height_at(&self, x: F, z: F) -> Option<F> {
+		let (column, row, u, v) = self.locate(x, z)?;
+		let h00 = self.sample(column, row);
+		let h10 = self.sample(column + 1, row);
+		let h01 = self.sample(column, row + 1);
+		let h11 = self.sample(column + 1, row + 1);
+		let near = h00 + (h10 - h00) * u;
+		let far = h01 + (h11 - h01) * u;
+		Some(near + (far - near) * v)
+	}
+
+	/// Unit normal of the bilinear surface at world position `(x, z)`,
+	/// pointing up, or `None` outside the grid.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Heightfield;
+	/// use math3d::points::Point3;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let flat = Heightfield::new(Point3::new(0.0, 5.0, 0.0), 1.0, 3, 3, vec![0.0; 9]);
+	///
+	/// assert!(flat.normal_at(1.5, 0.5) == Some(Vector3::new(0.0, 1.0, 0.0)));
+	/// ```
+
+	pub fn normal_at(&self, x: F, z: F) -> Option<Vector3<F>> {
+		let (column, row, u, v) = self.locate(x, z)?;
+		let h00 = self.sample(column, row);
+		let h10 = self.sample(column + 1, row);
+		let h01 = self.sample(column, row + 1);
+		let h11 = self.sample(column + 1, row + 1);
+		let dx = ((h10 - h00) * (F::one() - v) + (h11 - h01) * v) / self.spacing;
+		let dz = ((h01 - h00) * (F::one() - u) + (h11 - h10) * u) / self.spacing;
+		Some(Vector3::new(-dx, F::one(), -dz).normalized())
+	}
+
+	/// First intersection of a ray with the triangulated surface.
+	///
+	/// Returns the ray parameter `t >= 0` of the hit, so the hit point is
+	/// `origin + direction * t`. The cells under the ray are visited in order
+	/// with a 2D DDA walk and only their two triangles are tested, so the cost
+	/// grows with the length of the ray across the grid rather than with the
+	/// size of the grid. Triangles are hit from both sides.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Heightfield;
+	/// use math3d::points::Point3;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let flat = Heightfield::new(Point3::new(0.0, 0.0, 0.0), 1.0, 4, 4, vec![1.0; 16]);
+	/// let t = flat.raycast(Point3::new(-1.0, 3.0, 1.5), Vector3::new(1.0, -1.0, 0.0));
+	///
+	/// assert_eq!(t, Some(2.0));
+	/// ```
+
+	pub fn raycast(&self, origin: Point3<F>, direction: Vector3<F>) -> Option<F> {
+		let cells = [F::from(self.columns - 1).unwrap(), F::from(self.rows - 1).unwrap()];
+		let start = [
+			(origin[0] - self.origin[0]) / self.spacing,
+			(origin[2] - self.origin[2]) / self.spacing,
+		];
+		let step = [direction[0] / self.spacing, direction[2] / self.spacing];
+
+		// Clip the ray to the grid rectangle.
+		let (mut enter, mut exit) = (F::zero(), F::infinity());
+		for axis in 0..2 {
+			if step[axis] == F::zero() {
+				if start[axis] < F::zero() || start[axis] > cells[axis] {
+					return None;
+				}
+				continue;
+			}
+			let t0 = (F::zero() - start[axis]) / step[axis];
+			let t1 = (cells[axis] - start[axis]) / step[axis];
+			enter = enter.max(t0.min(t1));
+			exit = exit.min(t0.max(t1));
+		}
+		if enter > exit {
+			return None;
+		}
+
+		let mut cell = [0usize; 2];
+		let mut next = [F::infinity(); 2];
+		let mut delta = [F::infinity(); 2];
+		for axis in 0..2 {
+			let position = start[axis] + step[axis] * enter;
+			let last = if axis == 0 { self.columns - 2 } else { self.rows - 2 };
+			cell[axis] = position.floor().max(F::zero()).to_usize().unwrap_or(0).min(last);
+			if step[axis] != F::zero() {
+				let boundary = F::from(cell[axis]).unwrap() + if step[axis] > F::zero() { F::one() } else { F::zero() };
+				next[axis] = (boundary - start[axis]) / step[axis];
+				delta[axis] = F::one() / step[axis].abs();
+			}
+		}
+
+		loop {
+			let hit = self.raycast_cell(cell[0], cell[1], origin, direction);
+			if hit.is_some() {
+				return hit;
+			}
+
+			// A vertical ray never leaves its first cell, and both crossings
+			// are then infinite, as is `exit`.
+			let axis = if next[0] < next[1] { 0 } else { 1 };
+			if next[axis] > exit || next[axis].is_infinite() {
+				return None;
+			}
+			let last = if axis == 0 { self.columns - 2 } else { self.rows - 2 };
+			if step[axis] > F::zero() {
+				if cell[axis] == last {
+					return None;
+				}
+				cell[axis] += 1;
+			} else {
+				if cell[axis] == 0 {
+					return None;
+				}
+				cell[axis] -= 1;
+			}
+			next[axis] = next[axis] + delta[axis];
+		}
+	}
+
+	/// First intersection of a ray with the two triangles of a cell.
+
+	fn raycast_cell(&self, column: usize, row: usize, origin: Point3<F>, direction: Vector3<F>) -> Option<F> {
+		let a = self.sample_point(column, row);
+		let b = self.sample_point(column + 1, row);
+		let c = self.sample_point(column + 1, row + 1);
+		let d = self.sample_point(column, row + 1);
+		[ray_triangle(origin, direction, a, b, c), ray_triangle(origin, direction, a, c, d)]
+			.iter()
+			.flatten()
+			.fold(None, |best: Option<F>, &t| Some(best.map_or(t, |b| b.min(t))))
+	}
+
+	/// Cell containing world position `(x, z)` and the position within it in
+	/// `[0, 1]`.
+
+	fn locate(&self, x: F, z: F) -> Option<(usize, usize, F, F)> {
+		let gx = (x - self.origin[0]) / self.spacing;
+		let gz = (z - self.origin[2]) / self.spacing;
+		let (max_x, max_z) = (F::from(self.columns - 1).unwrap(), F::from(self.rows - 1).unwrap());
+		if !(gx >= F::zero() && gx <= max_x && gz >= F::zero() && gz <= max_z) {
+			return None;
+		}
+		let column = gx.floor().to_usize()?.min(self.columns - 2);
+		let row = gz.floor().to_usize()?.min(self.rows - 2);
+		Some((column, row, gx - F::from(column).unwrap(), gz - F::from(row).unwrap()))
+	}
+}
+
+/// Heightfield with a quadtree of the minimum and maximum heights over its
+/// cells, for ray queries over large terrains.
+///
+/// Each leaf bounds one cell and each node the up to four nodes below it, so
+/// a ray only descends into the boxes it passes through and stops once the
+/// remaining boxes all start behind the closest hit. Rays that pass well
+/// above the terrain are rejected near the root, where `Heightfield::raycast`
+/// walks every cell they cross. A ray that grazes flat terrain still enters
+/// the box of every cell along its path, so in the worst case the cost is
+/// linear in the number of cells crossed, like `Heightfield::raycast`.
+/// Changing a sample with `set_height` only updates the nodes above it.
+///
+/// # Example
+///
+/// ```
+/// use math3d::geometry::{Heightfield, HeightfieldQuadtree};
+/// use math3d::points::Point3;
+/// use math3d::vectors::Vector3;
+///
+/// let terrain = Heightfield::new(Point3::new(0.0, 0.0, 0.0), 1.0, 256, 256, vec![0.0; 256 * 256]);
+/// let mut tree = HeightfieldQuadtree::new(terrain);
+/// let (origin, direction) = (Point3::new(-1.0, 1.0, 100.5), Vector3::new(1.0, -0.01, 0.0));
+///
+/// assert_eq!(tree.raycast(origin, direction), Some(100.0));
+///
+/// tree.set_height(50, 100, 2.0);
+///
+/// assert!(tree.raycast(origin, direction).unwrap() < 51.0);
+/// assert_eq!(tree.raycast(origin, direction), tree.heightfield().raycast(origin, direction));
+/// ```
+
+#[derive(Clone, Debug)]
+pub struct HeightfieldQuadtree<F: Float> {
+	heightfield: Heightfield<F>,
+	levels: Vec<Vec<(F, F)>>,
+}
+
+impl<F: Float> HeightfieldQuadtree<F> {
+
+	/// Builds the quadtree over a heightfield.
+	pub fn new(heightfield: Heightfield<F>) -> HeightfieldQuadtree<F> {
+		let mut tree = HeightfieldQuadtree {
+			heightfield,
+			levels: Vec::new(),
+		};
+		let (mut columns, mut rows) = tree.cells();
+		let mut leaves = Vec::with_capacity(columns * rows);
+		for row in 0..rows {
+			for column in 0..columns {
+				leaves.push(tree.cell_range(column, row));
+			}
+		}
+		tree.levels.push(leaves);
+		while columns > 1 || rows > 1 {
+			columns = columns.div_ceil(2);
+			rows = rows.div_ceil(2);
+			let level = tree.levels.len();
+			let nodes = (0..columns * rows).map(|i| tree.children_range(level, i % columns, i / columns)).collect();
+			tree.levels.push(nodes);
+		}
+		tree
+	}
+
+	/// Returns the heightfield.
+	pub fn heightfield(&self) -> &Heightfield<F> {
+		&self.heightfield
+	}
+
+	/// Returns the heightfield, dropping the quadtree.
+	pub fn into_heightfield(self) -> Heightfield<F> {
+		self.heightfield
+	}
+
+	/// Lowest and highest world-space height of the terrain.
+	pub fn height_range(&self) -> (F, F) {
+		self.levels[self.levels.len() - 1][0]
+	}
+
+	/// Replaces the height of a sample like `Heightfield::set_height` and
+	/// updates the bounds of the cells around it and of the nodes above
+	/// those.
+	///
+	/// # Panics
+	///
+	/// Panics if the sample is outside the grid.
+
+	pub fn set_height(&mut self, column: usize, row: usize, height: F) {
+		self.heightfield.set_height(column, row, height);
+
+		let (columns, rows) = self.cells();
+		let (c0, c1) = (column.saturating_sub(1), column.min(columns - 1));
+		let (r0, r1) = (row.saturating_sub(1), row.min(rows - 1));
+		for r in r0..=r1 {
+			for c in c0..=c1 {
+				self.levels[0][r * columns + c] = self.cell_range(c, r);
+			}
+		}
+		for level in 1..self.levels.len() {
+			let width = self.level_size(level).0;
+			for r in (r0 >> level)..=(r1 >> level) {
+				for c in (c0 >> level)..=(c1 >> level) {
+					self.levels[level][r * width + c] = self.children_range(level, c, r);
+				}
+			}
+		}
+	}
+
+	/// First intersection of a ray with the triangulated surface, the same
+	/// as `Heightfield::raycast`.
+	///
+	/// Returns the ray parameter `t >= 0` of the hit, so the hit point is
+	/// `origin + direction * t`.
+
+	pub fn raycast(&self, origin: Point3<F>, direction: Vector3<F>) -> Option<F> {
+		let top = self.levels.len() - 1;
+		let mut best = None;
+		if self.node_interval(top, 0, 0, origin, direction).is_some() {
+			self.raycast_node(top, 0, 0, origin, direction, &mut best);
+		}
+		best
+	}
+
+	/// Descends into the children of a node that the ray enters, nearest
+	/// first, keeping the closest hit in `best`.
+
+	fn raycast_node(&self, level: usize, x: usize, y: usize, origin: Point3<F>, direction: Vector3<F>, best: &mut Option<F>) {
+		if level == 0 {
+			if let Some(t) = self.heightfield.raycast_cell(x, y, origin, direction) {
+				*best = Some(best.map_or(t, |b: F| b.min(t)));
+			}
+			return;
+		}
+
+		let (columns, rows) = self.level_size(level - 1);
+		let mut children = [(F::zero(), 0, 0); 4];
+		let mut count = 0;
+		for cy in 2 * y..(2 * y + 2).min(rows) {
+			for cx in 2 * x..(2 * x + 2).min(columns) {
+				if let Some(enter) = self.node_interval(level - 1, cx, cy, origin, direction) {
+					children[count] = (enter, cx, cy);
+					count += 1;
+				}
+			}
+		}
+		let children = &mut children[..count];
+		children.sort_unstable_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+		for &(enter, cx, cy) in children.iter() {
+			if best.is_some_and(|b| b < enter) {
+				return;
+			}
+			self.raycast_node(level - 1, cx, cy, origin, direction, best);
+		}
+	}
+
+	/// Ray parameter at which the ray enters the box of a node, or `None` if
+	/// it misses the box.
+
+	fn node_interval(&self, level: usize, x: usize, y: usize, origin: Point3<F>, direction: Vector3<F>) -> Option<F> {
+		let (low, high) = self.levels[level][y * self.level_size(level).0 + x];
+		let (columns, rows) = self.cells();
+		let size = 1 << level;
+		let corner = |column: usize, row: usize| (
+			self.heightfield.origin[0] + F::from(column).unwrap() * self.heightfield.spacing,
+			self.heightfield.origin[2] + F::from(row).unwrap() * self.heightfield.spacing,
+		);
+		let (x0, z0) = corner(x * size, y * size);
+		let (x1, z1) = corner((x * size + size).min(columns), (y * size + size).min(rows));
+		ray_box(origin, direction, [x0, low, z0], [x1, high, z1])
+	}
+
+	/// Lowest and highest world-space height of a cell.
+
+	fn cell_range(&self, column: usize, row: usize) -> (F, F) {
+		let heights = [
+			self.heightfield.sample(column, row),
+			self.heightfield.sample(column + 1, row),
+			self.heightfield.sample(column, row + 1),
+			self.heightfield.sample(column + 1, row + 1),
+		];
+		heights.iter().fold((F::infinity(), F::neg_infinity()), |(low, high), &h| (low.min(h), high.max(h)))
+	}
+
+	/// Lowest and highest height of the children of a node, from the level
+	/// below.
+
+	fn children_range(&self, level: usize, x: usize, y: usize) -> (F, F) {
+		let (columns, rows) = self.level_size(level - 1);
+		let below = &self.levels[level - 1];
+		let mut range = (F::infinity(), F::neg_infinity());
+		for cy in 2 * y..(2 * y + 2).min(rows) {
+			for cx in 2 * x..(2 * x + 2).min(columns) {
+				let (low, high) = below[cy * columns + cx];
+				range = (range.0.min(low), range.1.max(high));
+			}
+		}
+		range
+	}
+
+	/// Number of cells along x and z.
+
+	fn cells(&self) -> (usize, usize) {
+		(self.heightfield.columns - 1, self.heightfield.rows - 1)
+	}
+
+	/// Number of nodes along x and z on a level, where level 0 holds the
+	/// cells.
+
+	fn level_size(&self, level: usize) -> (usize, usize) {
+		let (columns, rows) = self.cells();
+		(columns.div_ceil(1 << level), rows.div_ceil(1 << level))
+	}
+}
+
+/// Ray parameter `t >= 0` at which the ray enters the axis-aligned box from
+/// `min` to `max`, zero if it starts inside, using the slab method.
+
+fn ray_box<F: Float>(origin: Point3<F>, direction: Vector3<F>, min: [F; 3], max: [F; 3]) -> Option<F> {
+	let (mut enter, mut exit) = (F::zero(), F::infinity());
+	for axis in 0..3 {
+		if direction[axis] == F::zero() {
+			if origin[axis] < min[axis] || origin[axis] > max[axis] {
+				return None;
+			}
+			continue;
+		}
+		let t0 = (min[axis] - origin[axis]) / direction[axis];
+		let t1 = (max[axis] - origin[axis]) / direction[axis];
+		enter = enter.max(t0.min(t1));
+		exit = exit.min(t0.max(t1));
+	}
+	if enter <= exit { Some(enter) } else { None }
+}
+
+/// Ray parameter `t >= 0` where the ray hits triangle `abc`, from either side,
+/// using the Möller–Trumbore algorithm.
+
+fn ray_triangle<F: Float>(origin: Point3<F>, direction: Vector3<F>, a: Point3<F>, b: Point3<F>, c: Point3<F>) -> Option<F> {
+	let e1 = b.to_vector() - a.to_vector();
+	let e2 = c.to_vector() - a.to_vector();
+	let p = direction.cross(e2);
+	let det = e1.dot(p);
+	if det == F::zero() {
+		return None;
+	}
+	let s = origin.to_vector() - a.to_vector();
+	let u = s.dot(p) / det;
+	if u < F::zero() || u > F::one() {
+		return None;
+	}
+	let q = s.cross(e1);
+	let v = direction.dot(q) / det;
+	if v < F::zero() || u + v > F::one() {
+		return None;
+	}
+	let t = e2.dot(q) / det;
+	if t < F::zero() {
+		return None;
+	}
+	Some(t)
+}
+
+/// Covariance matrix of a non-empty point cloud.
+
+fn covariance<F: Float>(points: &[Point3<F>]) -> Matrix3<F> {
+	let weights = vec![F::one(); points.len()];
+	let mean = points::weighted_centroid(points, &weights).unwrap();
+	points::second_moment(points, &weights, mean) / F::from(points.len()).unwrap()
+}
+
+/// Convex hull of a 2D point set using the monotone chain algorithm. The hull
+/// is returned in counter-clockwise order without repeating the first point.
+
+fn convex_hull_2d<F: Float>(mut points: Vec<(F, F)>) -> Vec<(F, F)> {
+	points.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+	points.dedup();
+	if points.len() < 3 {
+		return points;
+	}
+
+	let cross = |o: (F, F), a: (F, F), b: (F, F)| (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0);
+	let mut hull: Vec<(F, F)> = Vec::with_capacity(2 * points.len());
+
+	for &p in points.iter().chain(points.iter().rev().skip(1)) {
+		while hull.len() >= 2 && cross(hull[hull.len() - 2], hull[hull.len() - 1], p) <= F::zero() {
+			hull.pop();
+		}
+		hull.push(p);
+	}
+	hull.pop();
+	hull
+}
+
+/// Direction `(cos, sin)` of one side of the minimum-area rectangle enclosing
+/// a convex polygon, or `None` for polygons without a proper edge.
+
+fn min_area_rectangle<F: Float>(hull: Vec<(F, F)>) -> Option<(F, F)> {
+	let mut best: Option<(F, (F, F))> = None;
+
+	for i in 0..hull.len() {
+		let (x0, y0) = hull[i];
+		let (x1, y1) = hull[(i + 1) % hull.len()];
+		let length = ((x1 - x0) * (x1 - x0) + (y1 - y0) * (y1 - y0)).sqrt();
+		if length == F::zero() {
+			continue;
+		}
+		let (cos, sin) = ((x1 - x0) / length, (y1 - y0) / length);
+
+		let (mut min_a, mut max_a) = (F::infinity(), F::neg_infinity());
+		let (mut min_b, mut max_b) = (F::infinity(), F::neg_infinity());
+		for &(x, y) in &hull {
+			let a = x * cos + y * sin;
+			let b = y * cos - x * sin;
+			min_a = min_a.min(a);
+			max_a = max_a.max(a);
+			min_b = min_b.min(b);
+			max_b = max_b.max(b);
+		}
+
+		let area = (max_a - min_a) * (max_b - min_b);
+		match best {
+			Some((best_area, _)) if best_area <= area => {}
+			_ => best = Some((area, (cos, sin))),
+		}
+	}
+	best.map(|(_, direction)| direction)
+}
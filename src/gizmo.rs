@@ -0,0 +1,189 @@
+//! # Gizmo
+//!
+//! Line segment geometry for debug drawing: arrows, axis triads, circles,
+//! rotation rings, box and sphere wireframes and camera frustum outlines.
+//!
+//! Every generator returns a list of segments as point pairs, ready to be
+//! handed to a line renderer or to `DebugMesh::add_segments`.
+//!
+//! # Example
+//!
+//! ```
+//! use math3d::gizmo;
+//! use math3d::geometry::Aabb3;
+//! use math3d::points::Point3;
+//!
+//! let aabb = Aabb3::<f64>::new(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 2.0, 3.0));
+//!
+//! assert_eq!(gizmo::aabb(&aabb).len(), 12);
+//! ```
+
+use num::Float;
+use crate::camera::Camera;
+use crate::geometry::{Aabb3, Obb, Sphere};
+use crate::points::Point3;
+use crate::quaternion::Quaternion;
+use crate::vectors::Vector3;
+
+/// A line segment between two points.
+
+pub type Segment<F> = (Point3<F>, Point3<F>);
+
+/// Edges of a box or frustum given as eight corners, the first four around
+/// one face and the last four around the opposite face in the same order.
+
+pub(crate) const HEXAHEDRON_EDGES: [[usize; 2]; 12] = [
+	[0, 1], [1, 2], [2, 3], [3, 0],
+	[4, 5], [5, 6], [6, 7], [7, 4],
+	[0, 4], [1, 5], [2, 6], [3, 7],
+];
+
+/// Eight corners of an axis-aligned box in the order `HEXAHEDRON_EDGES`
+/// expects: the four corners of the `min` z face counter-clockwise from
+/// `min`, then the `max` z face in the same order.
+
+pub(crate) fn aabb_corners<F: Float>(aabb: &Aabb3<F>) -> [Point3<F>; 8] {
+	let (min, max) = (aabb.min(), aabb.max());
+	let corner = |x: Point3<F>, y: Point3<F>, z: Point3<F>| Point3::new(x[0], y[1], z[2]);
+	[
+		corner(min, min, min),
+		corner(max, min, min),
+		corner(max, max, min),
+		corner(min, max, min),
+		corner(min, min, max),
+		corner(max, min, max),
+		corner(max, max, max),
+		corner(min, max, max),
+	]
+}
+
+/// Arrow from `from` to `to`: the shaft and four head lines of length
+/// `head_size` spreading back from the tip. A zero length arrow is just the
+/// degenerate shaft.
+///
+/// # Example
+///
+/// ```
+/// use math3d::gizmo;
+/// use math3d::points::Point3;
+///
+/// let segments = gizmo::arrow(Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 2.0, 0.0), 0.25);
+///
+/// assert_eq!(segments.len(), 5);
+/// assert!(segments.iter().all(|s| s.0 == Point3::new(0.0, 2.0, 0.0) || s.1 == Point3::new(0.0, 2.0, 0.0)));
+/// ```
+
+pub fn arrow<F: Float>(from: Point3<F>, to: Point3<F>, head_size: F) -> Vec<Segment<F>> {
+	let mut segments = vec![(from, to)];
+	let direction = match (to.to_vector() - from.to_vector()).try_normalize() {
+		Some(direction) => direction,
+		None => return segments,
+	};
+	let side = direction.any_perpendicular();
+	let up = direction.cross(side);
+	let back = direction * -head_size;
+	let spread = head_size / F::from(2.0).unwrap();
+	for offset in [side, -side, up, -up] {
+		segments.push((to, translated(to, back + offset * spread)));
+	}
+	segments
+}
+
+/// Axis triad of a coordinate frame: arrows of length `size` along the x, y
+/// and z axes of `rotation`, in that order, five segments each.
+
+pub fn axes<F: Float>(origin: Point3<F>, rotation: Quaternion<F>, size: F) -> Vec<Segment<F>> {
+	let head_size = size / F::from(5.0).unwrap();
+	unit_axes()
+		.iter()
+		.flat_map(|axis| arrow(origin, translated(origin, rotation.rotate_vector(*axis * size)), head_size))
+		.collect()
+}
+
+/// Circle of `segments` segments around `center` in the plane with the
+/// given non-zero normal.
+///
+/// # Example
+///
+/// ```
+/// use math3d::gizmo;
+/// use math3d::points::Point3;
+/// use math3d::vectors::Vector3;
+///
+/// let center = Point3::<f64>::new(1.0, 0.0, 0.0);
+/// let segments = gizmo::circle(center, Vector3::new(0.0, 0.0, 1.0), 2.0, 32);
+///
+/// assert_eq!(segments.len(), 32);
+/// assert!(segments.iter().all(|s| (s.0.distance_to(center) - 2.0).abs() < 1e-12));
+/// ```
+
+pub fn circle<F: Float>(center: Point3<F>, normal: Vector3<F>, radius: F, segments: usize) -> Vec<Segment<F>> {
+	let normal = normal.normalized();
+	let u = normal.any_perpendicular() * radius;
+	let v = normal.cross(u);
+	let step = F::from(2.0 * std::f64::consts::PI / segments as f64).unwrap();
+	let point = |i: usize| {
+		let angle = step * F::from(i % segments).unwrap();
+		translated(center, u * angle.cos() + v * angle.sin())
+	};
+	(0..segments).map(|i| (point(i), point(i + 1))).collect()
+}
+
+/// Rotation rings: circles of the given radius around the x, y and z axes of
+/// `rotation`, in that order.
+
+pub fn rotation_rings<F: Float>(center: Point3<F>, rotation: Quaternion<F>, radius: F, segments: usize) -> Vec<Segment<F>> {
+	unit_axes()
+		.iter()
+		.flat_map(|axis| circle(center, rotation.rotate_vector(*axis), radius, segments))
+		.collect()
+}
+
+/// Twelve edges of an axis-aligned box.
+
+pub fn aabb<F: Float>(aabb: &Aabb3<F>) -> Vec<Segment<F>> {
+	hexahedron(aabb_corners(aabb))
+}
+
+/// Twelve edges of an oriented box.
+
+pub fn obb<F: Float>(obb: &Obb<F>) -> Vec<Segment<F>> {
+	let [a, b, c] = obb.axes();
+	let half = obb.half_extents();
+	let (a, b, c) = (a * half[0], b * half[1], c * half[2]);
+	let center = obb.center();
+	hexahedron([
+		translated(center, -a - b - c),
+		translated(center, a - b - c),
+		translated(center, a + b - c),
+		translated(center, -a + b - c),
+		translated(center, -a - b + c),
+		translated(center, a - b + c),
+		translated(center, a + b + c),
+		translated(center, -a + b + c),
+	])
+}
+
+/// Twelve edges of the view frustum of a camera.
+
+pub fn frustum<F: Float>(camera: &Camera<F>) -> Vec<Segment<F>> {
+	hexahedron(camera.frustum_corners())
+}
+
+/// Sphere wireframe as three great circles around the x, y and z axes.
+
+pub fn sphere<F: Float>(sphere: &Sphere<F>, segments: usize) -> Vec<Segment<F>> {
+	rotation_rings(sphere.center(), Quaternion::identity(), sphere.radius(), segments)
+}
+
+fn hexahedron<F: Float>(corners: [Point3<F>; 8]) -> Vec<Segment<F>> {
+	HEXAHEDRON_EDGES.iter().map(|[a, b]| (corners[*a], corners[*b])).collect()
+}
+
+fn translated<F: Float>(point: Point3<F>, offset: Vector3<F>) -> Point3<F> {
+	Point3::from_vector(point.to_vector() + offset)
+}
+
+fn unit_axes<F: Float>() -> [Vector3<F>; 3] {
+	[Vector3::unit_x(), Vector3::unit_y(), Vector3::unit_z()]
+}
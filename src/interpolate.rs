@@ -0,0 +1,123 @@
+//! # Interpolate
+//!
+//! Frame-rate independent smoothing of scalars, vectors, points and
+//! rotations towards a moving target.
+//!
+//! The common pattern `current = current.lerp(target, k * dt)` depends on
+//! the frame rate: after one second at `n` frames per second the remaining
+//! distance is `(1 - k / n)^n` of the start, which is not the same for 30
+//! and 144 frames per second and overshoots once `k * dt > 1`. `damp`
+//! instead decays the distance exponentially in time, leaving `exp(-k t)`
+//! of it after `t` seconds however the time is split into frames.
+//!
+//! # Example
+//!
+//! ```
+//! use math3d::interpolate::damp;
+//! use math3d::vectors::Vector3;
+//!
+//! let target = Vector3::new(10.0, 0.0, 0.0);
+//! let mut slow = Vector3::zero();
+//! let mut fast = Vector3::zero();
+//!
+//! for _ in 0..30 {
+//! 	slow = damp(slow, target, 4.0, 1.0 / 30.0);
+//! }
+//! for _ in 0..144 {
+//! 	fast = damp(fast, target, 4.0, 1.0 / 144.0);
+//! }
+//!
+//! assert!((slow - fast).magnitude() < 1e-9);
+//! ```
+
+use num::Float;
+use crate::points::Point3;
+use crate::quaternion::Quaternion;
+use crate::vectors::{Vector2, Vector3, Vector4};
+
+/// Values that can be interpolated towards a target, linearly for scalars,
+/// vectors and points and along the shorter arc for unit quaternions.
+
+pub trait Interpolate<F: Float>: Copy {
+	/// Value a fraction `t` in `[0, 1]` of the way from `self` to `target`.
+	fn interpolate(&self, target: Self, t: F) -> Self;
+}
+
+impl<F: Float> Interpolate<F> for F {
+	fn interpolate(&self, target: F, t: F) -> F {
+		*self + (target - *self) * t
+	}
+}
+
+impl<F: Float> Interpolate<F> for Vector2<F> {
+	fn interpolate(&self, target: Vector2<F>, t: F) -> Vector2<F> {
+		self.lerp_unclamped(target, t)
+	}
+}
+
+impl<F: Float> Interpolate<F> for Vector3<F> {
+	fn interpolate(&self, target: Vector3<F>, t: F) -> Vector3<F> {
+		self.lerp_unclamped(target, t)
+	}
+}
+
+impl<F: Float> Interpolate<F> for Vector4<F> {
+	fn interpolate(&self, target: Vector4<F>, t: F) -> Vector4<F> {
+		self.lerp_unclamped(target, t)
+	}
+}
+
+impl<F: Float> Interpolate<F> for Point3<F> {
+	fn interpolate(&self, target: Point3<F>, t: F) -> Point3<F> {
+		Point3::from_vector(self.to_vector().lerp_unclamped(target.to_vector(), t))
+	}
+}
+
+impl<F: Float> Interpolate<F> for Quaternion<F> {
+	fn interpolate(&self, target: Quaternion<F>, t: F) -> Quaternion<F> {
+		self.slerp_unclamped(target, t)
+	}
+}
+
+/// Moves `current` towards `target` over `dt` seconds so that the remaining
+/// distance decays as `exp(-smoothing * t)`:
+///
+/// $$x_{n+1} = x_n + (x_{target} - x_n) (1 - e^{-\lambda \Delta t})$$
+///
+/// `smoothing` is the decay rate `λ` per second; larger values follow the
+/// target more tightly, zero never moves and infinity snaps to the target.
+/// Use `smoothing_from_half_life` to give it as the time it takes to cover
+/// half of the distance. Quaternions are interpolated along the shorter
+/// arc, so the distance is the rotation angle.
+///
+/// # Example
+///
+/// ```
+/// use math3d::interpolate::damp;
+///
+/// let x = damp(0.0, 1.0, 2.0_f64.ln(), 1.0);
+///
+/// assert!((x - 0.5).abs() < 1e-12);
+/// ```
+
+pub fn damp<F: Float, T: Interpolate<F>>(current: T, target: T, smoothing: F, dt: F) -> T {
+	current.interpolate(target, F::one() - (-smoothing * dt).exp())
+}
+
+/// Decay rate for `damp` that halves the distance to a fixed target every
+/// `half_life` seconds.
+///
+/// # Example
+///
+/// ```
+/// use math3d::interpolate::{damp, smoothing_from_half_life};
+///
+/// let smoothing = smoothing_from_half_life(0.25_f64);
+/// let x = damp(0.0, 8.0, smoothing, 0.5);
+///
+/// assert!((x - 6.0).abs() < 1e-12);
+/// ```
+
+pub fn smoothing_from_half_life<F: Float>(half_life: F) -> F {
+	F::from(std::f64::consts::LN_2).unwrap() / half_life
+}
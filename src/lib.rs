@@ -1,9 +1,25 @@
 //!
+pub mod scalar;
 pub mod vectors;
 pub mod quaternion;
 pub mod matrices;
 pub mod points;
+pub mod batch;
+pub mod buffer;
 pub mod camera;
+pub mod geometry;
+pub mod transform;
+pub mod pose;
+pub mod dynamics;
+pub mod control;
+pub mod filter;
+pub mod interpolate;
+pub mod gizmo;
+pub mod shapes;
+pub mod debug_export;
+pub mod predicates;
+pub mod triangulate;
+pub mod trajectory;
 
 #[cfg(test)]
 mod tests {
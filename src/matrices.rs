@@ -13,7 +13,354 @@
 //! ```
 
 use num::Float;
+use rayon::prelude::*;
 use serde_derive::{Deserialize, Serialize};
+use crate::batch::PAR_CHUNK_SIZE;
+use crate::scalar::{cast, impl_scalar_lhs_mul, try_to_array_f32, F32ConversionError};
+
+// //////////////////////////////////////////////////////////////////////////////////////
+//
+// Row and Col
+//
+// //////////////////////////////////////////////////////////////////////////////////////
+
+/// Row index of a matrix, used with `at` and `set` to spell out which index
+/// is the row.
+///
+/// ```
+/// use math3d::matrices::{Col, Matrix3, Row};
+///
+/// let m = Matrix3::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+///
+/// assert_eq!(m.at(Row(0), Col(1)), 2.0);
+/// ```
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Row(pub usize);
+
+/// Column index of a matrix, used with `at` and `set` to spell out which
+/// index is the column.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Col(pub usize);
+
+// //////////////////////////////////////////////////////////////////////////////////////
+//
+// Matrix2
+//
+// //////////////////////////////////////////////////////////////////////////////////////
+
+use crate::vectors::Vector2;
+
+/// 2x2 matrix for 2D rotation, scaling and covariance math. Like `Matrix3`,
+/// it transforms row vectors: `v * m`.
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[repr(C)]
+pub struct Matrix2<F: Float> {
+	m: [Vector2<F>; 2],
+}
+
+impl<F: Float> Matrix2<F> {
+
+	/// Create a new matrix from 4 values, row by row.
+	///
+	/// ```
+	/// use math3d::matrices::Matrix2;
+	///
+	/// let m = Matrix2::new(1.0, 2.0, 3.0, 4.0);
+	///
+	/// assert_eq!(m[0][1], 2.0);
+	/// assert_eq!(m[1][0], 3.0);
+	/// ```
+
+	pub fn new(m00: F, m01: F, m10: F, m11: F) -> Matrix2<F> {
+		Matrix2 {
+			m: [Vector2::new(m00, m01), Vector2::new(m10, m11)],
+		}
+	}
+
+	/// Identity matrix.
+	pub fn identity() -> Matrix2<F> {
+		Matrix2::new(F::one(), F::zero(), F::zero(), F::one())
+	}
+
+	/// Create a matrix from 2 row vectors.
+	pub fn from_vectors(v0: Vector2<F>, v1: Vector2<F>) -> Matrix2<F> {
+		Matrix2 { m: [v0, v1] }
+	}
+
+	/// Create a matrix from 4 values in row-major order.
+	pub fn from_array(arr: [F; 4]) -> Matrix2<F> {
+		Matrix2::new(arr[0], arr[1], arr[2], arr[3])
+	}
+
+	/// Convert a matrix to [F; 4] array in row-major order.
+	pub fn to_array(&self) -> [F; 4] {
+		[self.m[0].x(), self.m[0].y(), self.m[1].x(), self.m[1].y()]
+	}
+
+	/// Create a matrix from [[F; 2]; 2] array of rows.
+	pub fn from_array_2d(arr: [[F; 2]; 2]) -> Matrix2<F> {
+		Matrix2::new(arr[0][0], arr[0][1], arr[1][0], arr[1][1])
+	}
+
+	/// Convert a matrix to [[F; 2]; 2] array of rows.
+	pub fn to_array_2d(&self) -> [[F; 2]; 2] {
+		[[self.m[0].x(), self.m[0].y()], [self.m[1].x(), self.m[1].y()]]
+	}
+
+	/// Rotation by `angle` radians, counter-clockwise for row vectors like
+	/// `Vector2::rotate`.
+	///
+	/// ```
+	/// use math3d::matrices::Matrix2;
+	/// use math3d::vectors::Vector2;
+	///
+	/// let m = Matrix2::from_angle(std::f64::consts::FRAC_PI_2);
+	///
+	/// assert!((Vector2::new(1.0, 0.0) * m).approx_eq(Vector2::new(0.0, 1.0), 1e-12));
+	/// ```
+
+	pub fn from_angle(angle: F) -> Matrix2<F> {
+		let (sin, cos) = angle.sin_cos();
+		Matrix2::new(cos, sin, -sin, cos)
+	}
+
+	/// Scaling by `scale.x()` along x and `scale.y()` along y.
+	pub fn from_scale(scale: Vector2<F>) -> Matrix2<F> {
+		Matrix2::new(scale.x(), F::zero(), F::zero(), scale.y())
+	}
+
+	/// Row `i`, the same as `m[i]`.
+	///
+	/// # Panics
+	///
+	/// Panics if `i` is out of bounds.
+
+	pub fn row(&self, i: usize) -> Vector2<F> {
+		self.m[i]
+	}
+
+	/// Column `j`.
+	///
+	/// # Panics
+	///
+	/// Panics if `j` is out of bounds.
+
+	pub fn column(&self, j: usize) -> Vector2<F> {
+		Vector2::new(self.m[0][j], self.m[1][j])
+	}
+
+	/// Multiply every element by a scalar.
+	pub fn mul_scalar(self, scalar: F) -> Matrix2<F> {
+		Matrix2::from_vectors(self.m[0] * scalar, self.m[1] * scalar)
+	}
+
+	/// Column-vector product `M * v`. Rotation and scale matrices are built
+	/// for row vectors, `v * M`, so this applies their inverse rotation.
+	///
+	/// ```
+	/// use math3d::matrices::Matrix2;
+	/// use math3d::vectors::Vector2;
+	///
+	/// let m = Matrix2::new(1.0, 2.0, 3.0, 4.0);
+	///
+	/// assert!(m * Vector2::new(1.0, 1.0) == Vector2::new(3.0, 7.0));
+	/// assert!(Vector2::new(1.0, 1.0) * m == Vector2::new(4.0, 6.0));
+	/// ```
+
+	pub fn product_vector(self, v: Vector2<F>) -> Vector2<F> {
+		Vector2::new(self.m[0].dot(v), self.m[1].dot(v))
+	}
+
+	/// Transposed matrix.
+	pub fn transpose(self) -> Matrix2<F> {
+		Matrix2::from_vectors(self.column(0), self.column(1))
+	}
+
+	/// Sum of the diagonal elements.
+	pub fn trace(&self) -> F {
+		self.m[0][0] + self.m[1][1]
+	}
+
+	/// Determinant of the matrix, the signed area scale of the transform.
+	///
+	/// ```
+	/// use math3d::matrices::Matrix2;
+	///
+	/// assert_eq!(Matrix2::new(1.0, 2.0, 3.0, 4.0).determinant(), -2.0);
+	/// ```
+
+	pub fn determinant(&self) -> F {
+		self.m[0][0] * self.m[1][1] - self.m[0][1] * self.m[1][0]
+	}
+
+	/// Inverse of the matrix, the adjugate divided by the determinant.
+	///
+	/// Singular matrices give non-finite elements; use `try_inverse` to
+	/// detect them.
+	///
+	/// ```
+	/// use math3d::matrices::Matrix2;
+	///
+	/// let m = Matrix2::new(4.0, 7.0, 2.0, 6.0);
+	///
+	/// assert!((m * m.inverse()).approx_eq(Matrix2::identity(), 1e-12));
+	/// ```
+
+	pub fn inverse(&self) -> Matrix2<F> {
+		let adjugate = Matrix2::new(self.m[1][1], -self.m[0][1], -self.m[1][0], self.m[0][0]);
+		adjugate.mul_scalar(F::one() / self.determinant())
+	}
+
+	/// Inverse of the matrix, or `None` if it is singular.
+	///
+	/// ```
+	/// use math3d::matrices::Matrix2;
+	///
+	/// assert!(Matrix2::new(1.0, 2.0, 2.0, 4.0).try_inverse().is_none());
+	/// ```
+
+	pub fn try_inverse(&self) -> Option<Matrix2<F>> {
+		let det = self.determinant();
+		if det == F::zero() || !det.is_finite() {
+			return None;
+		}
+		Some(self.inverse())
+	}
+
+	/// Eigen decomposition of a symmetric matrix, such as a 2D covariance.
+	///
+	/// Returns the eigenvalues sorted in descending order and a matrix whose
+	/// rows are the corresponding unit eigenvectors, the axes of the
+	/// covariance ellipse. Only the upper triangle is assumed to mirror the
+	/// lower one.
+	///
+	/// ```
+	/// use math3d::matrices::Matrix2;
+	///
+	/// let (values, vectors) = Matrix2::<f64>::new(2.0, 1.0, 1.0, 2.0).symmetric_eigen();
+	///
+	/// assert!((values[0] - 3.0).abs() < 1e-12 && (values[1] - 1.0).abs() < 1e-12);
+	/// assert!((vectors[0][0].abs() - 0.5_f64.sqrt()).abs() < 1e-12);
+	/// ```
+
+	pub fn symmetric_eigen(&self) -> (Vector2<F>, Matrix2<F>) {
+		let (values, vectors) = jacobi_eigen(self.to_array_2d());
+		let order = if values[0] >= values[1] { [0, 1] } else { [1, 0] };
+		let column = |j: usize| Vector2::new(vectors[0][j], vectors[1][j]);
+		(
+			Vector2::new(values[order[0]], values[order[1]]),
+			Matrix2::from_vectors(column(order[0]), column(order[1])),
+		)
+	}
+
+	/// Whether every element is within `epsilon` of the other matrix's.
+	pub fn approx_eq(&self, other: Matrix2<F>, epsilon: F) -> bool {
+		(0..2).all(|i| self.m[i].approx_eq(other.m[i], epsilon))
+	}
+
+	/// Element-wise `FloatExt::relative_eq`.
+	pub fn relative_eq(&self, other: Matrix2<F>, epsilon: F, max_relative: F) -> bool {
+		(0..2).all(|i| self.m[i].relative_eq(other.m[i], epsilon, max_relative))
+	}
+}
+
+impl<F: Float> core::fmt::Display for Matrix2<F> {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		let a = self.to_array().map(|x| x.to_f64().unwrap());
+		write!(f, "[ {:.2} {:.2} ]\n\n[ {:.2} {:.2} ]", a[0], a[1], a[2], a[3])
+	}
+}
+
+impl<F: Float> std::cmp::PartialEq for Matrix2<F> {
+	fn eq(&self, other: &Matrix2<F>) -> bool {
+		self.m[0] == other.m[0] && self.m[1] == other.m[1]
+	}
+}
+
+/// The zero matrix; see `identity` for the identity.
+
+impl<F: Float> Default for Matrix2<F> {
+	fn default() -> Matrix2<F> {
+		Matrix2::from_array([F::zero(); 4])
+	}
+}
+
+impl<F: Float> num::One for Matrix2<F> {
+	fn one() -> Matrix2<F> {
+		Matrix2::identity()
+	}
+}
+
+/// Matrix product, applying the left matrix first to row vectors.
+
+impl<F: Float> std::ops::Mul for Matrix2<F> {
+	type Output = Matrix2<F>;
+
+	fn mul(self, rhs: Matrix2<F>) -> Matrix2<F> {
+		let (c0, c1) = (rhs.column(0), rhs.column(1));
+		Matrix2::new(
+			self.m[0].dot(c0), self.m[0].dot(c1),
+			self.m[1].dot(c0), self.m[1].dot(c1),
+		)
+	}
+}
+
+impl<F: Float> std::ops::Mul<F> for Matrix2<F> {
+	type Output = Matrix2<F>;
+
+	fn mul(self, rhs: F) -> Matrix2<F> {
+		self.mul_scalar(rhs)
+	}
+}
+
+/// Column-vector product `M * v`, see `Matrix2::product_vector`.
+
+impl<F: Float> std::ops::Mul<Vector2<F>> for Matrix2<F> {
+	type Output = Vector2<F>;
+
+	fn mul(self, rhs: Vector2<F>) -> Vector2<F> {
+		self.product_vector(rhs)
+	}
+}
+
+impl<F: Float> std::ops::MulAssign for Matrix2<F> {
+	fn mul_assign(&mut self, rhs: Matrix2<F>) {
+		*self = *self * rhs;
+	}
+}
+
+impl<F: Float> std::ops::Index<usize> for Matrix2<F> {
+	type Output = Vector2<F>;
+
+	fn index(&self, index: usize) -> &Self::Output {
+		&self.m[index]
+	}
+}
+
+impl<F: Float> std::ops::IndexMut<usize> for Matrix2<F> {
+	fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+		&mut self.m[index]
+	}
+}
+
+/// Element at `(row, column)`, the same as `m[row][column]`.
+
+impl<F: Float> std::ops::Index<(usize, usize)> for Matrix2<F> {
+	type Output = F;
+
+	fn index(&self, (row, col): (usize, usize)) -> &F {
+		&self.m[row][col]
+	}
+}
+
+impl<F: Float> std::ops::IndexMut<(usize, usize)> for Matrix2<F> {
+	fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut F {
+		&mut self.m[row][col]
+	}
+}
 
 // //////////////////////////////////////////////////////////////////////////////////////
 //
@@ -24,6 +371,7 @@ use serde_derive::{Deserialize, Serialize};
 use crate::vectors::Vector3;
 
 #[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[repr(C)]
 pub struct Matrix3<F: Float> {
     m: [Vector3<F>; 3],
 }
@@ -78,6 +426,7 @@ impl<F: Float> Matrix3<F> {
     /// Create a matrix from 3 vectors.
     /// ```
     /// use math3d::matrices::Matrix3;
+    /// use math3d::vectors::Vector3;
     ///
     /// let m = Matrix3::from_vectors(
     /// 	Vector3::new(1.0, 2.0, 3.0),
@@ -85,21 +434,136 @@ impl<F: Float> Matrix3<F> {
     /// 	Vector3::new(7.0, 8.0, 9.0),
     /// );
     ///
-    /// assert_eq!(m.m[0].x(), 1.0);
-    /// assert_eq!(m.m[0].y(), 2.0);
-    /// assert_eq!(m.m[0].z(), 3.0);
-    /// assert_eq!(m.m[1].x(), 4.0);
-    /// assert_eq!(m.m[1].y(), 5.0);
-    /// assert_eq!(m.m[1].z(), 6.0);
-    /// assert_eq!(m.m[2].x(), 7.0);
-    /// assert_eq!(m.m[2].y(), 8.0);
-    /// assert_eq!(m.m[2].z(), 9.0);
+    /// assert_eq!(m[0].x(), 1.0);
+    /// assert_eq!(m[0].y(), 2.0);
+    /// assert_eq!(m[0].z(), 3.0);
+    /// assert_eq!(m[1].x(), 4.0);
+    /// assert_eq!(m[1].y(), 5.0);
+    /// assert_eq!(m[1].z(), 6.0);
+    /// assert_eq!(m[2].x(), 7.0);
+    /// assert_eq!(m[2].y(), 8.0);
+    /// assert_eq!(m[2].z(), 9.0);
     /// ```
 
     pub fn from_vectors(v0: Vector3<F>, v1: Vector3<F>, v2: Vector3<F>) -> Matrix3<F> {
         Matrix3 { m: [v0, v1, v2] }
     }
 
+    /// Cross-product matrix of `v`, so that the column-vector product
+    /// `m * w == v.cross(w)` for any vector `w`. The row-vector product has
+    /// the opposite sign, `w * m == w.cross(v)`, since `m` is antisymmetric.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    /// use math3d::vectors::Vector3;
+    ///
+    /// let v = Vector3::new(1.0, 2.0, 3.0);
+    /// let w = Vector3::new(-4.0, 0.5, 2.0);
+    /// let m = Matrix3::skew_symmetric(v);
+    ///
+    /// assert!(m * w == v.cross(w));
+    /// assert!(w * m == w.cross(v));
+    /// assert!(m.transpose() == m * -1.0);
+    /// ```
+
+    pub fn skew_symmetric(v: Vector3<F>) -> Matrix3<F> {
+        let zero = F::zero();
+        Matrix3::new(
+            zero, -v.z(), v.y(),
+            v.z(), zero, -v.x(),
+            -v.y(), v.x(), zero,
+        )
+    }
+
+    /// Rotation by `angle` degrees about a unit `axis`, for row vectors
+    /// (`v * m`), the same matrix as
+    /// `Quaternion::from_axis_angle(axis, angle).rotation_matrix()`.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    /// use math3d::vectors::Vector3;
+    ///
+    /// let m = Matrix3::<f64>::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 90.0);
+    ///
+    /// assert!((Vector3::new(1.0, 0.0, 0.0) * m - Vector3::new(0.0, 1.0, 0.0)).magnitude() < 1e-12);
+    /// ```
+
+    pub fn from_axis_angle(axis: Vector3<F>, angle: F) -> Matrix3<F> {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let k = Matrix3::skew_symmetric(axis);
+        let mut m = [[F::zero(); 3]; 3];
+        for i in 0..3 {
+            for j in 0..3 {
+                let diagonal = if i == j { cos } else { F::zero() };
+                m[i][j] = diagonal + (F::one() - cos) * axis[i] * axis[j] + sin * k[j][i];
+            }
+        }
+        Matrix3::from_array_2d(m)
+    }
+
+    /// Rotation by `angle` degrees about the x axis, for row vectors.
+    pub fn from_rotation_x(angle: F) -> Matrix3<F> {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let (zero, one) = (F::zero(), F::one());
+        Matrix3::new(
+            one, zero, zero,
+            zero, cos, sin,
+            zero, -sin, cos,
+        )
+    }
+
+    /// Rotation by `angle` degrees about the y axis, for row vectors.
+    pub fn from_rotation_y(angle: F) -> Matrix3<F> {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let (zero, one) = (F::zero(), F::one());
+        Matrix3::new(
+            cos, zero, -sin,
+            zero, one, zero,
+            sin, zero, cos,
+        )
+    }
+
+    /// Rotation by `angle` degrees about the z axis, for row vectors.
+    pub fn from_rotation_z(angle: F) -> Matrix3<F> {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let (zero, one) = (F::zero(), F::one());
+        Matrix3::new(
+            cos, sin, zero,
+            -sin, cos, zero,
+            zero, zero, one,
+        )
+    }
+
+    /// Rotation from euler angles in degrees, the same matrix as
+    /// `Quaternion::from_euler_angles(x, y, z).rotation_matrix()`: intrinsic
+    /// rotations about x, then the new y, then the new z. With row vectors
+    /// the single axis matrices multiply in reverse, `rz * ry * rx`.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    ///
+    /// let m = Matrix3::<f64>::from_euler_angles(90.0, 0.0, 0.0);
+    ///
+    /// assert!(m.approx_eq(Matrix3::from_rotation_x(90.0), 1e-15));
+    /// ```
+
+    pub fn from_euler_angles(x: F, y: F, z: F) -> Matrix3<F> {
+        Matrix3::from_rotation_z(z) * Matrix3::from_rotation_y(y) * Matrix3::from_rotation_x(x)
+    }
+
+    /// Unit quaternion of a rotation matrix for row vectors, the inverse of
+    /// `Quaternion::rotation_matrix`; see `Quaternion::from_rotation_matrix`.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    /// use math3d::quaternion::Quaternion;
+    /// use math3d::vectors::Vector3;
+    ///
+    /// let q = Matrix3::<f64>::from_rotation_y(180.0).to_quaternion();
+    ///
+    /// assert!((q.dot(Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 180.0)).abs() - 1.0).abs() < 1e-12);
+    /// ```
+
+    pub fn to_quaternion(&self) -> Quaternion<F> {
+        Quaternion::from_rotation_matrix(*self)
+    }
+
     /// Create a matrix from [F; 9] array.
     /// ```
     /// use math3d::matrices::Matrix3;
@@ -127,6 +591,20 @@ impl<F: Float> Matrix3<F> {
         }
     }
 
+    /// Create a matrix from 9 values in column-major order, the inverse of
+    /// `to_cols_array`.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    ///
+    /// let m = Matrix3::from_cols_array([1.0, 4.0, 7.0, 2.0, 5.0, 8.0, 3.0, 6.0, 9.0]);
+    ///
+    /// assert!(m == Matrix3::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0));
+    /// ```
+
+    pub fn from_cols_array(arr: [F; 9]) -> Matrix3<F> {
+        Matrix3::from_array(arr).transpose()
+    }
+
     /// Convert a matrix to [F; 9] array.
     ///
     /// ```
@@ -141,61 +619,248 @@ impl<F: Float> Matrix3<F> {
 
     pub fn to_array(&self) -> [F; 9] {
         [
-            *self.m[0].x(),
-            *self.m[0].y(),
-            *self.m[0].z(),
-            *self.m[1].x(),
-            *self.m[1].y(),
-            *self.m[1].z(),
-            *self.m[2].x(),
-            *self.m[2].y(),
-            *self.m[2].z(),
+            self.m[0].x(),
+            self.m[0].y(),
+            self.m[0].z(),
+            self.m[1].x(),
+            self.m[1].y(),
+            self.m[1].z(),
+            self.m[2].x(),
+            self.m[2].y(),
+            self.m[2].z(),
         ]
     }
 
-    /// Slice at index.
+    /// View a slice of exactly 9 row-major elements as a matrix without
+    /// copying, or `None` for any other length.
     ///
     /// ```
     /// use math3d::matrices::Matrix3;
     ///
-    /// let m = Matrix3::from_array([1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
-    ///
-    /// let m0 = m.index(0);
+    /// let data = [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0];
     ///
-    /// assert_eq!(m0[0], 1.0);
+    /// assert!(Matrix3::from_slice(&data) == Some(&Matrix3::identity()));
+    /// assert!(Matrix3::from_slice(&data[1..]).is_none());
     /// ```
 
-    pub fn index(&self, i: usize) -> &F {
-        let v = &self.m[i / 3];
-        &v[i % 3]
+    pub fn from_slice(slice: &[F]) -> Option<&Matrix3<F>> {
+        Matrix3::cast_slice(slice).filter(|m| m.len() == 1).map(|m| &m[0])
     }
 
-    /// Create a matrix from [[F; 3]; 3] array.
+    /// Mutable version of `from_slice`.
+    pub fn from_slice_mut(slice: &mut [F]) -> Option<&mut Matrix3<F>> {
+        Matrix3::cast_slice_mut(slice).filter(|m| m.len() == 1).map(|m| &mut m[0])
+    }
+
+    /// View a flat buffer of row-major matrices as matrices without copying,
+    /// or `None` if its length is not a multiple of 9.
+    ///
     /// ```
     /// use math3d::matrices::Matrix3;
     ///
-    /// let m = Matrix3::from_array_2d([
-    /// 	[1.0, 2.0, 3.0],
-    /// 	[4.0, 5.0, 6.0],
-    /// 	[7.0, 8.0, 9.0],]);
+    /// let mut data = vec![0.0; 18];
+    /// data[9..].copy_from_slice(&Matrix3::<f64>::identity().to_array());
+    ///
+    /// let matrices = Matrix3::cast_slice(&data).unwrap();
     ///
+    /// assert_eq!(matrices.len(), 2);
+    /// assert!(matrices[1] == Matrix3::identity());
+    /// ```
 
-    pub fn from_array_2d(arr: [[F; 3]; 3]) -> Matrix3<F> {
-        Matrix3 {
-            m: [
-                Vector3::new(arr[0][0], arr[0][1], arr[0][2]),
-                Vector3::new(arr[1][0], arr[1][1], arr[1][2]),
-                Vector3::new(arr[2][0], arr[2][1], arr[2][2]),
-            ],
+    pub fn cast_slice(slice: &[F]) -> Option<&[Matrix3<F>]> {
+        if !slice.len().is_multiple_of(9) {
+            return None;
         }
+        // SAFETY: `Matrix3` is `repr(C)` around three `repr(C)` vectors of
+        // three `F` each, so it has the size of `[F; 9]` and the alignment of
+        // `F`, and every group of 9 elements is a valid matrix.
+        Some(unsafe { std::slice::from_raw_parts(slice.as_ptr() as *const Matrix3<F>, slice.len() / 9) })
     }
 
-    /// Decompose tha matrix into a [[F; 3]; 3] array.
+    /// Mutable version of `cast_slice`.
+    pub fn cast_slice_mut(slice: &mut [F]) -> Option<&mut [Matrix3<F>]> {
+        if !slice.len().is_multiple_of(9) {
+            return None;
+        }
+        // SAFETY: as in `cast_slice`.
+        Some(unsafe { std::slice::from_raw_parts_mut(slice.as_mut_ptr() as *mut Matrix3<F>, slice.len() / 9) })
+    }
+
+    /// Slice at index.
+    ///
     /// ```
     /// use math3d::matrices::Matrix3;
     ///
-    /// let m = Matrix3::from_array_2d([
-    /// 	[1.0, 2.0, 3.0],
+    /// let m = Matrix3::from_array([1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+    ///
+    /// let m0 = m.index(0);
+    ///
+    /// assert_eq!(m0[0], 1.0);
+    /// ```
+
+    pub fn index(&self, i: usize) -> &F {
+        let v = &self.m[i / 3];
+        &v[i % 3]
+    }
+
+    /// Element at the given row and column, the same as `m[(row, col)]`
+    /// with the `Row` and `Col` types guarding against swapped indices.
+    ///
+    /// ```
+    /// use math3d::matrices::{Col, Matrix3, Row};
+    ///
+    /// let m = Matrix3::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+    ///
+    /// assert_eq!(m.at(Row(1), Col(2)), 6.0);
+    /// assert_eq!(m.at(Row(1), Col(2)), m[1][2]);
+    /// assert_eq!(m.at(Row(1), Col(2)), m[(1, 2)]);
+    /// ```
+
+    pub fn at(&self, row: Row, col: Col) -> F {
+        self[(row.0, col.0)]
+    }
+
+    /// Set the element at the given row and column.
+    ///
+    /// ```
+    /// use math3d::matrices::{Col, Matrix3, Row};
+    ///
+    /// let mut m = Matrix3::identity();
+    /// m.set(Row(2), Col(0), 5.0);
+    ///
+    /// assert_eq!(m[2][0], 5.0);
+    /// ```
+
+    pub fn set(&mut self, row: Row, col: Col, value: F) {
+        self[(row.0, col.0)] = value;
+    }
+
+    /// Iterator over the elements in row-major order.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    ///
+    /// let m = Matrix3::<f64>::new(1.0, -2.0, 3.0, 4.0, 5.0, -6.0, 7.0, 8.0, 9.0);
+    ///
+    /// assert_eq!(m.iter().fold(0.0, |acc: f64, a| acc.max(a.abs())), 9.0);
+    /// assert!(m.iter().copied().eq(m.to_array()));
+    /// ```
+
+    pub fn iter(&self) -> impl Iterator<Item = &F> + '_ {
+        self.m.iter().flat_map(|row| row.iter())
+    }
+
+    /// Mutable iterator over the elements in row-major order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut F> + '_ {
+        self.m.iter_mut().flat_map(|row| row.iter_mut())
+    }
+
+    /// Iterator over the rows, as returned by `row`.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    /// use math3d::vectors::Vector3;
+    ///
+    /// let m = Matrix3::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+    ///
+    /// assert!(m.rows().nth(1).unwrap() == Vector3::new(4.0, 5.0, 6.0));
+    /// assert!(m.cols().nth(1).unwrap() == Vector3::new(2.0, 5.0, 8.0));
+    /// ```
+
+    pub fn rows(&self) -> std::array::IntoIter<Vector3<F>, 3> {
+        [self.row(0), self.row(1), self.row(2)].into_iter()
+    }
+
+    /// Iterator over the columns, as returned by `column`.
+    pub fn cols(&self) -> std::array::IntoIter<Vector3<F>, 3> {
+        [self.column(0), self.column(1), self.column(2)].into_iter()
+    }
+
+    /// Row `i`, the same as `m[i]`. `rows` iterates over these; single
+    /// elements are read with `at` or `m[(row, col)]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is out of bounds.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    /// use math3d::vectors::Vector3;
+    ///
+    /// let m = Matrix3::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+    ///
+    /// assert!(m.row(1) == Vector3::new(4.0, 5.0, 6.0));
+    /// assert!(m.column(1) == Vector3::new(2.0, 5.0, 8.0));
+    /// ```
+
+    pub fn row(&self, i: usize) -> Vector3<F> {
+        self.m[i]
+    }
+
+    /// Column `j`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `j` is out of bounds.
+
+    pub fn column(&self, j: usize) -> Vector3<F> {
+        Vector3::new(self[(0, j)], self[(1, j)], self[(2, j)])
+    }
+
+    /// Replace row `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is out of bounds.
+
+    pub fn set_row(&mut self, i: usize, row: Vector3<F>) {
+        self.m[i] = row;
+    }
+
+    /// Replace column `j`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `j` is out of bounds.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    /// use math3d::vectors::Vector3;
+    ///
+    /// let mut m = Matrix3::identity();
+    /// m.set_column(2, Vector3::new(4.0, 5.0, 6.0));
+    ///
+    /// assert!(m == Matrix3::new(1.0, 0.0, 4.0, 0.0, 1.0, 5.0, 0.0, 0.0, 6.0));
+    /// ```
+
+    pub fn set_column(&mut self, j: usize, column: Vector3<F>) {
+        for (i, value) in column.into_iter().enumerate() {
+            self[(i, j)] = value;
+        }
+    }
+
+    /// Create a matrix from [[F; 3]; 3] array.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    ///
+    /// let m = Matrix3::from_array_2d([
+    /// 	[1.0, 2.0, 3.0],
+    /// 	[4.0, 5.0, 6.0],
+    /// 	[7.0, 8.0, 9.0],]);
+    ///
+
+    pub fn from_array_2d(arr: [[F; 3]; 3]) -> Matrix3<F> {
+        Matrix3 {
+            m: [
+                Vector3::new(arr[0][0], arr[0][1], arr[0][2]),
+                Vector3::new(arr[1][0], arr[1][1], arr[1][2]),
+                Vector3::new(arr[2][0], arr[2][1], arr[2][2]),
+            ],
+        }
+    }
+
+    /// Decompose tha matrix into a [[F; 3]; 3] array.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    ///
+    /// let m = Matrix3::from_array_2d([
+    /// 	[1.0, 2.0, 3.0],
     /// 	[4.0, 5.0, 6.0],
     /// 	[7.0, 8.0, 9.0],]);
     ///
@@ -214,12 +879,60 @@ impl<F: Float> Matrix3<F> {
 
     pub fn to_array_2d(&self) -> [[F; 3]; 3] {
         [
-            [*self.m[0].x(), *self.m[0].y(), *self.m[0].z()],
-            [*self.m[1].x(), *self.m[1].y(), *self.m[1].z()],
-            [*self.m[2].x(), *self.m[2].y(), *self.m[2].z()],
+            [self.m[0].x(), self.m[0].y(), self.m[0].z()],
+            [self.m[1].x(), self.m[1].y(), self.m[1].z()],
+            [self.m[2].x(), self.m[2].y(), self.m[2].z()],
         ]
     }
 
+    /// Convert a matrix to [F; 9] array in column-major order, the order in
+    /// which GLSL, WGSL and HLSL by default store matrices. A shader matrix
+    /// filled from it has the same rows and columns as this one, so it is
+    /// applied to row vectors as `v * m`.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    ///
+    /// let m = Matrix3::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+    ///
+    /// assert_eq!(m.to_cols_array(), [1.0, 4.0, 7.0, 2.0, 5.0, 8.0, 3.0, 6.0, 9.0]);
+    /// assert!(Matrix3::from_cols_array(m.to_cols_array()) == m);
+    /// ```
+
+    pub fn to_cols_array(&self) -> [F; 9] {
+        self.transpose().to_array()
+    }
+
+    /// Convert a matrix to an array of its columns.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    ///
+    /// let m = Matrix3::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+    ///
+    /// assert_eq!(m.to_cols_array_2d()[0], [1.0, 4.0, 7.0]);
+    /// ```
+
+    pub fn to_cols_array_2d(&self) -> [[F; 3]; 3] {
+        self.transpose().to_array_2d()
+    }
+
+    /// Columns padded to four elements, the std140 layout of a `mat3` in
+    /// GLSL uniform blocks and of a `mat3x3` in WGSL uniform buffers. The
+    /// padding is zero. Cast to `f32` first for uploading.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    ///
+    /// let m = Matrix3::<f64>::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+    ///
+    /// assert_eq!(m.cast::<f32>().to_std140(), [
+    /// 	[1.0, 4.0, 7.0, 0.0],
+    /// 	[2.0, 5.0, 8.0, 0.0],
+    /// 	[3.0, 6.0, 9.0, 0.0],]);
+    /// ```
+
+    pub fn to_std140(&self) -> [[F; 4]; 3] {
+        self.to_cols_array_2d().map(|[x, y, z]| [x, y, z, F::zero()])
+    }
+
     /// To [f63; 9] array.
     ///
     /// ```
@@ -233,17 +946,7 @@ impl<F: Float> Matrix3<F> {
     /// ```
 
     pub fn to_array_f64(&self) -> [f64; 9] {
-        [
-            self.m[0][0].to_f64().unwrap(),
-            self.m[0][1].to_f64().unwrap(),
-            self.m[0][2].to_f64().unwrap(),
-            self.m[1][0].to_f64().unwrap(),
-            self.m[1][1].to_f64().unwrap(),
-            self.m[1][2].to_f64().unwrap(),
-            self.m[2][0].to_f64().unwrap(),
-            self.m[2][1].to_f64().unwrap(),
-            self.m[2][2].to_f64().unwrap(),
-        ]
+        self.cast().to_array()
     }
 
     /// To [f32; 9] array.
@@ -259,17 +962,26 @@ impl<F: Float> Matrix3<F> {
     /// ```
 
     pub fn to_array_f32(&self) -> [f32; 9] {
-        [
-            self.m[0][0].to_f32().unwrap(),
-            self.m[0][1].to_f32().unwrap(),
-            self.m[0][2].to_f32().unwrap(),
-            self.m[1][0].to_f32().unwrap(),
-            self.m[1][1].to_f32().unwrap(),
-            self.m[1][2].to_f32().unwrap(),
-            self.m[2][0].to_f32().unwrap(),
-            self.m[2][1].to_f32().unwrap(),
-            self.m[2][2].to_f32().unwrap(),
-        ]
+        self.cast().to_array()
+    }
+
+    /// To [f32; 9] array, or an error if an element is out of `f32` range or
+    /// would be rounded by more than `max_error`. Use this instead of
+    /// `to_array_f32` where silent precision loss is a bug, e.g. for large
+    /// world coordinates.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    /// use math3d::scalar::F32ConversionError;
+    ///
+    /// let mut m = Matrix3::<f64>::identity();
+    /// assert_eq!(m.try_to_array_f32(1e-9).unwrap(), m.to_array_f32());
+    ///
+    /// m[1][2] = 6378137.123;
+    /// assert_eq!(m.try_to_array_f32(1e-3), Err(F32ConversionError::PrecisionLoss { index: 5 }));
+    /// ```
+
+    pub fn try_to_array_f32(&self, max_error: F) -> Result<[f32; 9], F32ConversionError> {
+        try_to_array_f32(self.to_array(), max_error)
     }
 
     /// Multiply two matrices.
@@ -328,47 +1040,74 @@ impl<F: Float> Matrix3<F> {
     /// ```
 
     pub fn mul_scalar(self, scalar: F) -> Matrix3<F> {
-        Matrix3 {
-            m: [self.m[0] * scalar, self.m[1] * scalar, self.m[2] * scalar],
-        }
+        self.map(|a| a * scalar)
     }
 
-    /// Divide matrix by a matrix.
+    /// Multiply matrix by a column vector, `M * v`. This is the transpose of
+    /// the row-vector product `v * M` used for transforms elsewhere in the
+    /// crate: `m * v == v * m.transpose()`.
     /// ```
     /// use math3d::matrices::Matrix3;
+    /// use math3d::vectors::Vector3;
     ///
-    /// let m1 = Matrix3::from_array_2d([
+    /// let m = Matrix3::from_array_2d([
     /// 	[1.0, 2.0, 3.0],
     /// 	[4.0, 5.0, 6.0],
     /// 	[7.0, 8.0, 9.0],]);
     ///
-    /// let m2 = Matrix3::from_array_2d([
+    /// let v = Vector3::new(1.0, 0.0, -1.0);
+    ///
+    /// assert!(m * v == Vector3::new(-2.0, -2.0, -2.0));
+    /// assert!(v * m == Vector3::new(-6.0, -6.0, -6.0));
+    /// ```
+
+    pub fn product_vector(self, v: Vector3<F>) -> Vector3<F> {
+        Vector3::new(self.m[0].dot(v), self.m[1].dot(v), self.m[2].dot(v))
+    }
+
+    /// Right division by a matrix, `a / b == a * b^-1`, so that `(a / b) * b == a`.
+    /// A singular divisor gives non-finite elements; see `checked_div`.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    ///
+    /// let m1 = Matrix3::from_array_2d([
     /// 	[1.0, 2.0, 3.0],
     /// 	[4.0, 5.0, 6.0],
     /// 	[7.0, 8.0, 9.0],]);
     ///
+    /// let m2 = Matrix3::from_array_2d([
+    /// 	[2.0, 0.0, 0.0],
+    /// 	[0.0, 4.0, 0.0],
+    /// 	[1.0, 0.0, 1.0],]);
+    ///
     /// let expected = Matrix3::from_array_2d([
-    /// 	[1.0, 1.0, 1.0],
-    /// 	[1.0, 1.0, 1.0],
-    /// 	[1.0, 1.0, 1.0],]);
+    /// 	[-1.0, 0.5, 3.0],
+    /// 	[-1.0, 1.25, 6.0],
+    /// 	[-1.0, 2.0, 9.0],]);
     ///
     /// assert!(m1 / m2 == expected);
+    /// assert!((m1 / m2) * m2 == m1);
     /// ```
 
     pub fn div(self, other: Matrix3<F>) -> Matrix3<F> {
-        let lhs = self.to_array_2d();
-        let rhs = other.to_array_2d();
+        self.mul(other.inverse())
+    }
 
-        let mut res = [[F::zero(); 3]; 3];
+    /// Right division `self * other^-1`, or `None` if `other` is singular.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    ///
+    /// let a = Matrix3::<f64>::from_array_2d([
+    /// 	[1.0, 2.0, 0.0],
+    /// 	[0.0, 1.0, 0.0],
+    /// 	[0.0, 0.0, 1.0],]);
+    ///
+    /// assert!(a.checked_div(a) == Some(Matrix3::identity()));
+    /// assert!(a.checked_div(Matrix3::default()).is_none());
+    /// ```
 
-        for i in 0..3 {
-            for j in 0..3 {
-                for k in 0..3 {
-                    res[i][j] = res[i][j] + lhs[i][k] * rhs[k][j];
-                }
-            }
-        }
-        Matrix3::from_array_2d(res)
+    pub fn checked_div(&self, other: Matrix3<F>) -> Option<Matrix3<F>> {
+        other.try_inverse().map(|inverse| self.mul(inverse))
     }
 
     /// Divide matrix by scalar.
@@ -389,11 +1128,115 @@ impl<F: Float> Matrix3<F> {
     /// ```
 
     pub fn div_scalar(self, scalar: F) -> Matrix3<F> {
+        self.map(|a| a / scalar)
+    }
+
+    /// Apply a function to each element.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    ///
+    /// let m = Matrix3::<f64>::from_array_2d([
+    /// 	[1.0, -2.0, 3.0],
+    /// 	[-4.0, 5.0, -6.0],
+    /// 	[7.0, -8.0, 9.0],]);
+    ///
+    /// assert!(m.map(|a| a.abs()) == Matrix3::from_array_2d([
+    /// 	[1.0, 2.0, 3.0],
+    /// 	[4.0, 5.0, 6.0],
+    /// 	[7.0, 8.0, 9.0],]));
+    /// ```
+
+    pub fn map<M: FnMut(F) -> F>(self, mut f: M) -> Matrix3<F> {
+        Matrix3 {
+            m: self.m.map(|row| row.map(&mut f)),
+        }
+    }
+
+    /// Converts the elements to another float type.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    ///
+    /// let m: Matrix3<f32> = Matrix3::<f64>::identity().cast();
+    ///
+    /// assert!(m == Matrix3::identity());
+    /// ```
+
+    pub fn cast<T: Float>(&self) -> Matrix3<T> {
         Matrix3 {
-            m: [self.m[0] / scalar, self.m[1] / scalar, self.m[2] / scalar],
+            m: self.m.map(|row| row.cast()),
         }
     }
 
+    /// Whether all elements are finite, neither infinite nor NaN.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    ///
+    /// let mut m = Matrix3::<f64>::identity();
+    /// assert!(m.is_finite());
+    ///
+    /// m[1][2] = f64::INFINITY;
+    /// assert!(!m.is_finite());
+    /// ```
+
+    pub fn is_finite(&self) -> bool {
+        self.m.iter().all(|row| row.is_finite())
+    }
+
+    /// Whether any element is NaN.
+    pub fn is_nan(&self) -> bool {
+        self.m.iter().any(|row| row.is_nan())
+    }
+
+    /// Whether every element is within `epsilon` of the other matrix's.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    /// use math3d::quaternion::Quaternion;
+    /// use math3d::vectors::Vector3;
+    ///
+    /// let r = Quaternion::<f64>::from_axis_angle(Vector3::new(1.0, 2.0, 3.0).normalized(), 40.0).rotation_matrix();
+    ///
+    /// assert!((r * r.transpose()).approx_eq(Matrix3::identity(), 1e-12));
+    /// ```
+
+    pub fn approx_eq(&self, other: Matrix3<F>, epsilon: F) -> bool {
+        (0..3).all(|i| self.m[i].approx_eq(other.m[i], epsilon))
+    }
+
+    /// Element-wise `FloatExt::relative_eq`.
+    pub fn relative_eq(&self, other: Matrix3<F>, epsilon: F, max_relative: F) -> bool {
+        (0..3).all(|i| self.m[i].relative_eq(other.m[i], epsilon, max_relative))
+    }
+
+    /// Elements as aligned rows with `precision` decimals, one row per line,
+    /// for readable test failures and logs.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    ///
+    /// let m = Matrix3::<f64>::new(1.0, -20.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+    ///
+    /// assert_eq!(m.to_table_string(1), "[ 1.0  -20.0  3.0 ]\n[ 4.0    5.0  6.0 ]\n[ 7.0    8.0  9.0 ]");
+    /// ```
+
+    pub fn to_table_string(&self, precision: usize) -> String {
+        table_rows(&self.to_array_f64(), 3, precision, &[false; 9]).join("\n")
+    }
+
+    /// Both matrices side by side with the elements differing by more than
+    /// `tolerance` marked with `*`, followed by a summary of the largest
+    /// difference. Meant as the message of a failed assertion:
+    /// ```should_panic
+    /// use math3d::matrices::Matrix3;
+    ///
+    /// let a = Matrix3::<f64>::identity();
+    /// let b = Matrix3::new(1.0, 0.0, 0.0, 0.0, 1.0, 0.5, 0.0, 0.0, 1.0);
+    ///
+    /// assert!(a.approx_eq(b, 1e-9), "{}", a.diff_string(b, 1e-9));
+    /// ```
+
+    pub fn diff_string(&self, other: Matrix3<F>, tolerance: F) -> String {
+        diff_string(&self.to_array_f64(), &other.to_array_f64(), 3, tolerance.to_f64().unwrap())
+    }
+
     /// Transpose matrix.
     /// ```
     /// use math3d::matrices::Matrix3;
@@ -426,29 +1269,37 @@ impl<F: Float> Matrix3<F> {
         Matrix3::from_array_2d(m)
     }
 
-    /// Get determinant of matrix.
+    /// Sum of the diagonal elements, which is also the sum of the
+    /// eigenvalues. For a rotation matrix it is `1 + 2 cos(angle)`.
     /// ```
     /// use math3d::matrices::Matrix3;
     ///
-    /// let m = Matrix3::from_array_2d([
-    /// 	[1.0, 2.0, 3.0],
-    /// 	[4.0, 5.0, 6.0],
-    /// 	[7.0, 8.0, 9.0],]);
+    /// let m = Matrix3::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
     ///
-    /// assert!(m.determinant() == 0.0);
+    /// assert_eq!(m.trace(), 15.0);
     /// ```
 
-    pub fn determinant(self) -> F {
-        let m = self.to_array_2d();
-        let mut res = F::zero();
-        for i in 0..3 {
-            res = res + m[0][i] * m[1][(i + 1) % 3] * m[2][(i + 2) % 3];
-            res = res - m[0][i] * m[1][(i + 2) % 3] * m[2][(i + 1) % 3];
-        }
-        res
+    pub fn trace(&self) -> F {
+        self[0][0] + self[1][1] + self[2][2]
+    }
+
+    /// Frobenius norm, the square root of the sum of the squared elements.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    ///
+    /// let m = Matrix3::from_array_2d([
+    /// 	[1.0, 2.0, 0.0],
+    /// 	[0.0, 2.0, 0.0],
+    /// 	[0.0, 0.0, 4.0],]);
+    ///
+    /// assert_eq!(m.frobenius_norm(), 5.0);
+    /// ```
+
+    pub fn frobenius_norm(&self) -> F {
+        self.iter().fold(F::zero(), |acc, x| acc + *x * *x).sqrt()
     }
 
-    /// Get inverse of matrix.
+    /// Get determinant of matrix.
     /// ```
     /// use math3d::matrices::Matrix3;
     ///
@@ -457,44 +1308,603 @@ impl<F: Float> Matrix3<F> {
     /// 	[4.0, 5.0, 6.0],
     /// 	[7.0, 8.0, 9.0],]);
     ///
+    /// assert!(m.determinant() == 0.0);
+    /// ```
+
+    pub fn determinant(self) -> F {
+        let m = self.to_array_2d();
+        let mut res = F::zero();
+        for i in 0..3 {
+            res = res + m[0][i] * m[1][(i + 1) % 3] * m[2][(i + 2) % 3];
+            res = res - m[0][i] * m[1][(i + 2) % 3] * m[2][(i + 1) % 3];
+        }
+        res
+    }
+
+    /// Get inverse of matrix, the adjugate divided by the determinant.
+    ///
+    /// Singular matrices give non-finite elements; use `try_inverse` to
+    /// detect them.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    ///
+    /// let m = Matrix3::from_array_2d([
+    /// 	[2.0, 0.0, 0.0],
+    /// 	[0.0, 4.0, 0.0],
+    /// 	[1.0, 0.0, 1.0],]);
+    ///
     /// let expected = Matrix3::from_array_2d([
-    /// 	[-2.0, 4.0, -2.0],
-    /// 	[1.0, -5.0, 1.0],
-    /// 	[2.0, -1.0, 2.0],]);
+    /// 	[0.5, 0.0, 0.0],
+    /// 	[0.0, 0.25, 0.0],
+    /// 	[-0.5, 0.0, 1.0],]);
     ///
     /// assert!(m.inverse() == expected);
     /// ```
 
     pub fn inverse(self) -> Matrix3<F> {
-        let mut m = self.to_array_2d();
-        let mut res = Matrix3::<F>::identity().to_array_2d();
-        let det = self.determinant();
+        self.inverse_and_det().0
+    }
+
+    /// Inverse of the matrix, or `None` if it is singular.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    ///
+    /// let m = Matrix3::from_array_2d([
+    /// 	[1.0, 2.0, 3.0],
+    /// 	[4.0, 5.0, 6.0],
+    /// 	[7.0, 8.0, 9.0],]);
+    ///
+    /// assert!(m.try_inverse().is_none());
+    /// assert!(Matrix3::<f64>::identity().try_inverse() == Some(Matrix3::identity()));
+    /// ```
 
-        if det == F::zero() {
-            return Matrix3::<F>::identity();
+    pub fn try_inverse(&self) -> Option<Matrix3<F>> {
+        let (inverse, det) = self.inverse_and_det();
+        if det == F::zero() || !det.is_finite() {
+            return None;
         }
+        Some(inverse)
+    }
 
-        for i in 0..3 {
-            for j in 0..3 {
-                if i > j {
-                    let tmp = m[i][j];
-                    m[i][j] = m[j][i];
-                    m[j][i] = tmp;
-                    let tmp = res[i][j];
-                    res[i][j] = res[j][i];
-                    res[j][i] = tmp;
+    /// Eigen decomposition of a symmetric matrix using cyclic Jacobi rotations.
+    ///
+    /// Returns the eigenvalues sorted in descending order and a matrix whose
+    /// rows are the corresponding unit eigenvectors. Only the upper triangle is
+    /// assumed to mirror the lower one; non-symmetric input gives meaningless
+    /// results.
+    ///
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    ///
+    /// let m = Matrix3::<f64>::from_array_2d([
+    /// 	[2.0, 0.0, 0.0],
+    /// 	[0.0, 3.0, 0.0],
+    /// 	[0.0, 0.0, 1.0],]);
+    ///
+    /// let (values, vectors) = m.symmetric_eigen();
+    ///
+    /// assert_eq!(values[0], 3.0);
+    /// assert_eq!(vectors[0][1].abs(), 1.0);
+    /// ```
+
+    pub fn symmetric_eigen(&self) -> (Vector3<F>, Matrix3<F>) {
+        let (values, vectors) = jacobi_eigen(self.to_array_2d());
+
+        let mut order = [0, 1, 2];
+        order.sort_by(|&a, &b| values[b].partial_cmp(&values[a]).unwrap_or(std::cmp::Ordering::Equal));
+
+        let column = |j: usize| Vector3::new(vectors[0][j], vectors[1][j], vectors[2][j]);
+        (
+            Vector3::new(values[order[0]], values[order[1]], values[order[2]]),
+            Matrix3::from_vectors(column(order[0]), column(order[1]), column(order[2])),
+        )
+    }
+
+    /// Moore-Penrose pseudo-inverse computed from the singular value
+    /// decomposition.
+    ///
+    /// Singular values below `max(σ) * 3 * ε` are treated as zero, so singular
+    /// and rank-deficient matrices produce the least-squares minimum-norm
+    /// solution instead of failing. For invertible matrices the result equals
+    /// the inverse.
+    ///
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    ///
+    /// let m = Matrix3::from_array_2d([
+    /// 	[1.0, 0.0, 0.0],
+    /// 	[0.0, 2.0, 0.0],
+    /// 	[0.0, 0.0, 0.0],]);
+    ///
+    /// let expected = Matrix3::from_array_2d([
+    /// 	[1.0, 0.0, 0.0],
+    /// 	[0.0, 0.5, 0.0],
+    /// 	[0.0, 0.0, 0.0],]);
+    ///
+    /// assert!(m.pinv() == expected);
+    /// ```
+
+    pub fn pinv(&self) -> Matrix3<F> {
+        Matrix3::from_array_2d(pseudo_inverse(self.to_array_2d(), None))
+    }
+
+    /// Moore-Penrose pseudo-inverse treating singular values at or below
+    /// `tolerance` as zero.
+    ///
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    ///
+    /// let m = Matrix3::from_array_2d([
+    /// 	[1.0, 0.0, 0.0],
+    /// 	[0.0, 1e-9, 0.0],
+    /// 	[0.0, 0.0, 1.0],]);
+    ///
+    /// let p = m.pinv_with_tolerance(1e-6);
+    ///
+    /// assert_eq!(p[1][1], 0.0);
+    /// ```
+
+    pub fn pinv_with_tolerance(&self, tolerance: F) -> Matrix3<F> {
+        Matrix3::from_array_2d(pseudo_inverse(self.to_array_2d(), Some(tolerance)))
+    }
+
+    /// Cofactor matrix, the transpose of the adjugate.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    ///
+    /// let m = Matrix3::from_array_2d([
+    /// 	[2.0, 0.0, 0.0],
+    /// 	[0.0, 3.0, 0.0],
+    /// 	[0.0, 0.0, 4.0],]);
+    ///
+    /// assert!(m.cofactor() == Matrix3::from_array_2d([
+    /// 	[12.0, 0.0, 0.0],
+    /// 	[0.0, 8.0, 0.0],
+    /// 	[0.0, 0.0, 6.0],]));
+    /// ```
+
+    pub fn cofactor(&self) -> Matrix3<F> {
+        let m = self.to_array_2d();
+        let mut res = [[F::zero(); 3]; 3];
+        for (i, row) in res.iter_mut().enumerate() {
+            for (j, c) in row.iter_mut().enumerate() {
+                let (i1, i2) = ((i + 1) % 3, (i + 2) % 3);
+                let (j1, j2) = ((j + 1) % 3, (j + 2) % 3);
+                *c = m[i1][j1] * m[i2][j2] - m[i1][j2] * m[i2][j1];
+            }
+        }
+        Matrix3::from_array_2d(res)
+    }
+
+    /// Adjugate matrix, the transpose of the cofactor matrix. Equals the
+    /// inverse times the determinant, and stays defined for singular
+    /// matrices.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    ///
+    /// let m = Matrix3::from_array_2d([
+    /// 	[1.0, 2.0, 0.0],
+    /// 	[0.0, 1.0, 0.0],
+    /// 	[0.0, 0.0, 3.0],]);
+    ///
+    /// assert!(m.adjugate() == Matrix3::from_array_2d([
+    /// 	[3.0, -6.0, 0.0],
+    /// 	[0.0, 3.0, 0.0],
+    /// 	[0.0, 0.0, 1.0],]));
+    /// ```
+
+    pub fn adjugate(&self) -> Matrix3<F> {
+        self.cofactor().transpose()
+    }
+
+    /// Cofactor of a single element: the determinant of the 2x2 minor left
+    /// after removing its row and column, negated when `row + col` is odd.
+    /// Named `cofactor_at` and indexed like `at` because `cofactor` already
+    /// returns the whole cofactor matrix.
+    /// ```
+    /// use math3d::matrices::{Col, Matrix3, Row};
+    ///
+    /// let m = Matrix3::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 10.0);
+    ///
+    /// assert_eq!(m.cofactor_at(Row(0), Col(1)), -(4.0 * 10.0 - 6.0 * 7.0));
+    /// assert_eq!(m.cofactor_at(Row(0), Col(1)), m.cofactor()[0][1]);
+    /// ```
+
+    pub fn cofactor_at(&self, row: Row, col: Col) -> F {
+        let (i1, i2) = ((row.0 + 1) % 3, (row.0 + 2) % 3);
+        let (j1, j2) = ((col.0 + 1) % 3, (col.0 + 2) % 3);
+        self[i1][j1] * self[i2][j2] - self[i1][j2] * self[i2][j1]
+    }
+
+    /// Inverse and determinant computed together from one cofactor matrix,
+    /// for callers that need both. The determinant is the first row dotted
+    /// with its cofactors and the inverse the transposed cofactors divided by
+    /// it.
+    ///
+    /// Singular matrices give a zero determinant and non-finite elements in
+    /// the inverse; check the determinant before using it.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    ///
+    /// let m = Matrix3::from_array_2d([
+    /// 	[2.0, 0.0, 0.0],
+    /// 	[0.0, 4.0, 0.0],
+    /// 	[1.0, 0.0, 1.0],]);
+    ///
+    /// let (inverse, det) = m.inverse_and_det();
+    ///
+    /// assert_eq!(det, 8.0);
+    /// assert!(inverse * m == Matrix3::identity());
+    /// ```
+
+    pub fn inverse_and_det(&self) -> (Matrix3<F>, F) {
+        let cofactor = self.cofactor();
+        let det = (0..3).fold(F::zero(), |acc, j| acc + self[0][j] * cofactor[0][j]);
+        (cofactor.transpose() / det, det)
+    }
+
+    /// Normal matrix, the inverse-transpose of the matrix. Normals multiplied
+    /// by it stay perpendicular to surfaces transformed by the matrix, also
+    /// under non-uniform scaling.
+    ///
+    /// Singular matrices give non-finite elements; `transform_normals_slice`
+    /// handles them.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    ///
+    /// let m = Matrix3::from_array_2d([
+    /// 	[2.0, 0.0, 0.0],
+    /// 	[0.0, 4.0, 0.0],
+    /// 	[0.0, 0.0, 1.0],]);
+    ///
+    /// assert!(m.normal_matrix() == Matrix3::from_array_2d([
+    /// 	[0.5, 0.0, 0.0],
+    /// 	[0.0, 0.25, 0.0],
+    /// 	[0.0, 0.0, 1.0],]));
+    /// ```
+
+    pub fn normal_matrix(&self) -> Matrix3<F> {
+        self.cofactor() / self.determinant()
+    }
+
+    /// Transforms a buffer of unit normals in place by the normal matrix and
+    /// renormalizes them, with normals as row vectors (`n * N`) like
+    /// `Vector3 * Matrix3`.
+    ///
+    /// The inverse-transpose is computed once for the whole buffer. It is
+    /// only needed up to scale, so the signed cofactor matrix is used instead,
+    /// which keeps normals of flattening matrices finite. Zero-length normals
+    /// are left untouched.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    /// use math3d::vectors::Vector3;
+    ///
+    /// let m = Matrix3::from_array_2d([
+    /// 	[1.0, 0.0, 0.0],
+    /// 	[1.0, 1.0, 0.0],
+    /// 	[0.0, 0.0, 1.0],]);
+    ///
+    /// let mut normals = [Vector3::new(1.0, 0.0, 0.0)];
+    /// m.transform_normals_slice(&mut normals);
+    /// ```
+
+    pub fn transform_normals_slice(&self, normals: &mut [Vector3<F>]) {
+        let normal_matrix = self.signed_cofactor();
+        for n in normals.iter_mut() {
+            *n = transform_normal(*n, normal_matrix);
+        }
+    }
+
+    /// Transforms vectors stored in an interleaved vertex buffer in place, as
+    /// row vectors (`v * M`) like `Vector3 * Matrix3`. Each of the `count`
+    /// vertices is `stride` bytes long and holds three native-endian `f32`s
+    /// `offset` bytes into it; the other attributes are left untouched.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the last vector does not fit in `bytes`.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    ///
+    /// // Tangents followed by a four byte color.
+    /// let mut bytes = Vec::new();
+    /// for v in [[1.0f32, 0.0, 0.0], [0.0, 1.0, 0.0]] {
+    /// 	v.iter().for_each(|c| bytes.extend(c.to_ne_bytes()));
+    /// 	bytes.extend([255u8; 4]);
+    /// }
+    ///
+    /// Matrix3::<f64>::identity().mul_scalar(2.0).transform_vectors_strided(&mut bytes, 16, 0, 2);
+    ///
+    /// assert_eq!(f32::from_ne_bytes(bytes[20..24].try_into().unwrap()), 2.0);
+    /// assert_eq!(bytes[28..32], [255; 4]);
+    /// ```
+
+    pub fn transform_vectors_strided(&self, bytes: &mut [u8], stride: usize, offset: usize, count: usize) {
+        map_strided_bytes(bytes, stride, offset, count, |v| v * *self);
+    }
+
+    /// Typed version of `transform_vectors_strided` for vertex buffers held as
+    /// `f32`s, with `stride` and `offset` counted in `f32`s.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the last vector does not fit in `data`.
+
+    pub fn transform_vectors_strided_f32(&self, data: &mut [f32], stride: usize, offset: usize, count: usize) {
+        map_strided_f32(data, stride, offset, count, |v| v * *self);
+    }
+
+    /// Cofactor matrix with the sign of the determinant, the normal matrix up
+    /// to a positive scale.
+
+    fn signed_cofactor(&self) -> Matrix3<F> {
+        if self.determinant() < F::zero() {
+            self.cofactor() * -F::one()
+        } else {
+            self.cofactor()
+        }
+    }
+}
+
+impl<F: Float + Send + Sync> Matrix3<F> {
+    /// Parallel version of `transform_normals_slice`, splitting the buffer
+    /// into chunks transformed on the rayon thread pool.
+    /// ```
+    /// use math3d::matrices::Matrix3;
+    /// use math3d::vectors::Vector3;
+    ///
+    /// let m = Matrix3::identity();
+    /// let mut normals = vec![Vector3::new(0.0, 1.0, 0.0); 10000];
+    ///
+    /// m.transform_normals_slice_par(&mut normals);
+    /// ```
+
+    pub fn transform_normals_slice_par(&self, normals: &mut [Vector3<F>]) {
+        let normal_matrix = self.signed_cofactor();
+        normals.par_chunks_mut(PAR_CHUNK_SIZE).for_each(|chunk| {
+            for n in chunk.iter_mut() {
+                *n = transform_normal(*n, normal_matrix);
+            }
+        });
+    }
+}
+
+/// Number of matrices handled by one task in the parallel matrix products,
+/// smaller than `PAR_CHUNK_SIZE` as each element is more work.
+
+const PAR_MATRIX_CHUNK_SIZE: usize = 1024;
+
+/// Transforms one normal by a matrix proportional to the normal matrix and
+/// renormalizes it.
+
+fn transform_normal<F: Float>(n: Vector3<F>, normal_matrix: Matrix3<F>) -> Vector3<F> {
+    let transformed = n * normal_matrix;
+    if transformed.magnitude() > F::zero() {
+        transformed.normalized()
+    } else {
+        n
+    }
+}
+
+/// Applies `f` in place to `count` vectors of three native-endian `f32`s,
+/// found `offset` bytes into every `stride` bytes of `bytes`.
+
+fn map_strided_bytes<F: Float, M: Fn(Vector3<F>) -> Vector3<F>>(bytes: &mut [u8], stride: usize, offset: usize, count: usize, f: M) {
+    const SIZE: usize = 3 * std::mem::size_of::<f32>();
+    assert_strided_fits(bytes.len(), stride, offset, count, SIZE);
+    for i in 0..count {
+        let start = i * stride + offset;
+        let element = &mut bytes[start..start + SIZE];
+        let mut v = Vector3::zero();
+        for (j, chunk) in element.chunks_exact(4).enumerate() {
+            v[j] = cast(f32::from_ne_bytes(chunk.try_into().unwrap()));
+        }
+        let v = f(v);
+        for (j, chunk) in element.chunks_exact_mut(4).enumerate() {
+            chunk.copy_from_slice(&cast::<F, f32>(v[j]).to_ne_bytes());
+        }
+    }
+}
+
+/// Applies `f` in place to `count` vectors of three `f32`s, found `offset`
+/// elements into every `stride` elements of `data`.
+
+fn map_strided_f32<F: Float, M: Fn(Vector3<F>) -> Vector3<F>>(data: &mut [f32], stride: usize, offset: usize, count: usize, f: M) {
+    assert_strided_fits(data.len(), stride, offset, count, 3);
+    for i in 0..count {
+        let start = i * stride + offset;
+        let element = &mut data[start..start + 3];
+        let v = f(Vector3::new(cast(element[0]), cast(element[1]), cast(element[2])));
+        for (j, e) in element.iter_mut().enumerate() {
+            *e = cast(v[j]);
+        }
+    }
+}
+
+fn assert_strided_fits(len: usize, stride: usize, offset: usize, count: usize, size: usize) {
+    assert!(
+        count == 0 || (count - 1) * stride + offset + size <= len,
+        "{} elements of stride {} at offset {} do not fit in a buffer of length {}",
+        count,
+        stride,
+        offset,
+        len
+    );
+}
+
+/// Formats row-major elements as rows of right-aligned columns, prefixing
+/// the marked elements with `*`.
+
+fn table_rows(elements: &[f64], columns: usize, precision: usize, marked: &[bool]) -> Vec<String> {
+    let cells: Vec<String> = elements
+        .iter()
+        .zip(marked)
+        .map(|(a, m)| format!("{}{:.*}", if *m { "*" } else { "" }, precision, a))
+        .collect();
+    let widths: Vec<usize> = (0..columns)
+        .map(|c| cells.iter().skip(c).step_by(columns).map(|cell| cell.len()).max().unwrap_or(0))
+        .collect();
+    cells
+        .chunks(columns)
+        .map(|row| {
+            let row: Vec<String> = row.iter().zip(&widths).map(|(cell, w)| format!("{:>1$}", cell, w)).collect();
+            format!("[ {} ]", row.join("  "))
+        })
+        .collect()
+}
+
+/// Side by side tables of two row-major matrices with the elements that
+/// differ by more than `tolerance` marked, and a summary line.
+
+fn diff_string(a: &[f64], b: &[f64], columns: usize, tolerance: f64) -> String {
+    let differences: Vec<f64> = a.iter().zip(b).map(|(x, y)| if x == y { 0.0 } else { (x - y).abs() }).collect();
+    let marked: Vec<bool> = differences.iter().map(|d| d.is_nan() || *d > tolerance).collect();
+    let left = table_rows(a, columns, 6, &marked);
+    let right = table_rows(b, columns, 6, &marked);
+    let mut out: String = left.iter().zip(&right).map(|(l, r)| format!("{}   {}\n", l, r)).collect();
+
+    let count = marked.iter().filter(|m| **m).count();
+    out += &format!("{} of {} elements differ by more than {}", count, a.len(), tolerance);
+    let largest = (0..a.len()).filter(|i| marked[*i]).max_by(|i, j| differences[*i].total_cmp(&differences[*j]));
+    if let Some(i) = largest {
+        out += &format!(", the largest by {} at row {}, column {}", differences[i], i / columns, i % columns);
+    }
+    out
+}
+
+/// Moore-Penrose pseudo-inverse of a square matrix using a one-sided Jacobi
+/// singular value decomposition.
+///
+/// The columns of `a` are orthogonalized with plane rotations, which are
+/// accumulated into `V`. After convergence `a = U Σ Vᵀ` where the column norms
+/// are the singular values, and the pseudo-inverse is `V Σ⁺ Uᵀ`. Singular
+/// values at or below `tolerance` (by default `max(σ) * N * ε`) are dropped.
+
+fn pseudo_inverse<F: Float, const N: usize>(mut a: [[F; N]; N], tolerance: Option<F>) -> [[F; N]; N] {
+    let two = F::from(2.0).unwrap();
+    let mut v = [[F::zero(); N]; N];
+    for (i, row) in v.iter_mut().enumerate() {
+        row[i] = F::one();
+    }
+
+    for _ in 0..64 {
+        let mut rotated = false;
+        for p in 0..N {
+            for q in (p + 1)..N {
+                let mut alpha = F::zero();
+                let mut beta = F::zero();
+                let mut gamma = F::zero();
+                for row in a.iter() {
+                    alpha = alpha + row[p] * row[p];
+                    beta = beta + row[q] * row[q];
+                    gamma = gamma + row[p] * row[q];
+                }
+                if gamma == F::zero() || gamma.abs() <= F::epsilon() * (alpha * beta).sqrt() {
+                    continue;
+                }
+                rotated = true;
+
+                let zeta = (beta - alpha) / (two * gamma);
+                let t = zeta.signum() / (zeta.abs() + (F::one() + zeta * zeta).sqrt());
+                let c = F::one() / (F::one() + t * t).sqrt();
+                let s = c * t;
+
+                for row in a.iter_mut().chain(v.iter_mut()) {
+                    let (up, uq) = (row[p], row[q]);
+                    row[p] = c * up - s * uq;
+                    row[q] = s * up + c * uq;
                 }
             }
         }
+        if !rotated {
+            break;
+        }
+    }
 
-        for i in 0..3 {
-            for j in 0..3 {
-                res[i][j] = res[i][j] / det;
+    let mut sigma = [F::zero(); N];
+    for (k, s) in sigma.iter_mut().enumerate() {
+        *s = a.iter().fold(F::zero(), |acc, row| acc + row[k] * row[k]).sqrt();
+    }
+    let max_sigma = sigma.iter().fold(F::zero(), |acc, &s| acc.max(s));
+    let tolerance = tolerance.unwrap_or(max_sigma * F::from(N).unwrap() * F::epsilon());
+
+    let mut res = [[F::zero(); N]; N];
+    for k in 0..N {
+        if sigma[k] <= tolerance {
+            continue;
+        }
+        let inv_sq = F::one() / (sigma[k] * sigma[k]);
+        for i in 0..N {
+            for j in 0..N {
+                res[i][j] = res[i][j] + v[i][k] * a[j][k] * inv_sq;
             }
         }
+    }
+    res
+}
 
-        Matrix3::from_array_2d(res)
+/// Cyclic Jacobi eigenvalue iteration for a symmetric N x N matrix.
+///
+/// Returns the (unsorted) eigenvalues and a matrix whose columns are the
+/// eigenvectors.
+
+fn jacobi_eigen<F: Float, const N: usize>(mut a: [[F; N]; N]) -> ([F; N], [[F; N]; N]) {
+    let two = F::from(2.0).unwrap();
+    let mut v = [[F::zero(); N]; N];
+    for (i, row) in v.iter_mut().enumerate() {
+        row[i] = F::one();
+    }
+
+    for _ in 0..64 {
+        let mut off = F::zero();
+        let mut total = F::zero();
+        for (i, row) in a.iter().enumerate() {
+            for (j, &x) in row.iter().enumerate() {
+                total = total + x * x;
+                if i != j {
+                    off = off + x * x;
+                }
+            }
+        }
+        if off <= F::epsilon() * F::epsilon() * total {
+            break;
+        }
+
+        for p in 0..N {
+            for q in (p + 1)..N {
+                if a[p][q] == F::zero() {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (two * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + F::one()).sqrt());
+                let c = F::one() / (t * t + F::one()).sqrt();
+                let s = t * c;
+
+                for row in a.iter_mut() {
+                    let (akp, akq) = (row[p], row[q]);
+                    row[p] = c * akp - s * akq;
+                    row[q] = s * akp + c * akq;
+                }
+                let (head, tail) = a.split_at_mut(q);
+                for (apk, aqk) in head[p].iter_mut().zip(tail[0].iter_mut()) {
+                    let (x, y) = (*apk, *aqk);
+                    *apk = c * x - s * y;
+                    *aqk = s * x + c * y;
+                }
+                for row in v.iter_mut() {
+                    let (vkp, vkq) = (row[p], row[q]);
+                    row[p] = c * vkp - s * vkq;
+                    row[q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+
+    let mut values = [F::zero(); N];
+    for (i, value) in values.iter_mut().enumerate() {
+        *value = a[i][i];
     }
+    (values, v)
 }
 
 impl<F: Float> core::fmt::Display for Matrix3<F> {
@@ -513,58 +1923,163 @@ impl<F: Float> std::cmp::PartialEq for Matrix3<F> {
     }
 }
 
-impl<F: Float> std::ops::Mul for Matrix3<F> {
-    type Output = Matrix3<F>;
+/// The zero matrix; see `identity` for the identity.
 
-    fn mul(self, rhs: Matrix3<F>) -> Matrix3<F> {
-        self.mul(rhs)
+impl<F: Float> Default for Matrix3<F> {
+    fn default() -> Matrix3<F> {
+        Matrix3::from_array([F::zero(); 9])
     }
 }
 
-impl<F: Float> std::ops::Mul<F> for Matrix3<F> {
-    type Output = Matrix3<F>;
+impl<F: Float> num::Zero for Matrix3<F> {
+    fn zero() -> Matrix3<F> {
+        Matrix3::from_array([F::zero(); 9])
+    }
 
-    fn mul(self, rhs: F) -> Matrix3<F> {
-        self.mul_scalar(rhs)
+    fn is_zero(&self) -> bool {
+        *self == Matrix3::from_array([F::zero(); 9])
     }
 }
 
-impl<F: Float> std::ops::Div for Matrix3<F> {
-    type Output = Matrix3<F>;
-
-    fn div(self, rhs: Matrix3<F>) -> Matrix3<F> {
-        self.div(rhs)
+impl<F: Float> num::One for Matrix3<F> {
+    fn one() -> Matrix3<F> {
+        Matrix3::identity()
     }
 }
 
-impl<F: Float> std::ops::Div<F> for Matrix3<F> {
+impl<F: Float> std::ops::Add for Matrix3<F> {
     type Output = Matrix3<F>;
 
-    fn div(self, rhs: F) -> Matrix3<F> {
-        self.div_scalar(rhs)
+    fn add(self, rhs: Matrix3<F>) -> Matrix3<F> {
+        Matrix3 { m: [self.m[0] + rhs.m[0], self.m[1] + rhs.m[1], self.m[2] + rhs.m[2]] }
     }
 }
 
-impl<F: Float> std::ops::Index<usize> for Matrix3<F> {
-    type Output = Vector3<F>;
+impl<F: Float> std::ops::Sub for Matrix3<F> {
+    type Output = Matrix3<F>;
 
-    fn index(&self, index: usize) -> &Self::Output {
-        &self.m[index]
+    fn sub(self, rhs: Matrix3<F>) -> Matrix3<F> {
+        Matrix3 { m: [self.m[0] - rhs.m[0], self.m[1] - rhs.m[1], self.m[2] - rhs.m[2]] }
     }
 }
 
-impl<F: Float> std::ops::IndexMut<usize> for Matrix3<F> {
+impl<F: Float> std::ops::Mul for Matrix3<F> {
+    type Output = Matrix3<F>;
+
+    fn mul(self, rhs: Matrix3<F>) -> Matrix3<F> {
+        self.mul(rhs)
+    }
+}
+
+impl<F: Float> std::ops::Mul<F> for Matrix3<F> {
+    type Output = Matrix3<F>;
+
+    fn mul(self, rhs: F) -> Matrix3<F> {
+        self.mul_scalar(rhs)
+    }
+}
+
+/// Column-vector product `M * v`, see `Matrix3::product_vector`.
+
+impl<F: Float> std::ops::Mul<Vector3<F>> for Matrix3<F> {
+    type Output = Vector3<F>;
+
+    fn mul(self, rhs: Vector3<F>) -> Vector3<F> {
+        self.product_vector(rhs)
+    }
+}
+
+impl<F: Float> std::ops::Div for Matrix3<F> {
+    type Output = Matrix3<F>;
+
+    fn div(self, rhs: Matrix3<F>) -> Matrix3<F> {
+        self.div(rhs)
+    }
+}
+
+impl<F: Float> std::ops::Div<F> for Matrix3<F> {
+    type Output = Matrix3<F>;
+
+    fn div(self, rhs: F) -> Matrix3<F> {
+        self.div_scalar(rhs)
+    }
+}
+
+impl<F: Float> std::ops::AddAssign for Matrix3<F> {
+    fn add_assign(&mut self, other: Matrix3<F>) {
+        *self = *self + other;
+    }
+}
+
+impl<F: Float> std::ops::SubAssign for Matrix3<F> {
+    fn sub_assign(&mut self, other: Matrix3<F>) {
+        *self = *self - other;
+    }
+}
+
+impl<F: Float> std::ops::MulAssign for Matrix3<F> {
+    fn mul_assign(&mut self, other: Matrix3<F>) {
+        *self = *self * other;
+    }
+}
+
+impl<F: Float> std::ops::MulAssign<F> for Matrix3<F> {
+    fn mul_assign(&mut self, other: F) {
+        *self = *self * other;
+    }
+}
+
+impl<F: Float> std::ops::DivAssign for Matrix3<F> {
+    fn div_assign(&mut self, other: Matrix3<F>) {
+        *self = *self / other;
+    }
+}
+
+impl<F: Float> std::ops::DivAssign<F> for Matrix3<F> {
+    fn div_assign(&mut self, other: F) {
+        *self = *self / other;
+    }
+}
+
+impl<F: Float> std::ops::Index<usize> for Matrix3<F> {
+    type Output = Vector3<F>;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.m[index]
+    }
+}
+
+impl<F: Float> std::ops::IndexMut<usize> for Matrix3<F> {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
         &mut self.m[index]
     }
 }
 
+/// Element at `(row, column)`, the same as `m[row][column]`. `at` and `set`
+/// are the typed forms of this index.
+
+impl<F: Float> std::ops::Index<(usize, usize)> for Matrix3<F> {
+    type Output = F;
+
+    fn index(&self, (row, col): (usize, usize)) -> &F {
+        &self.m[row][col]
+    }
+}
+
+impl<F: Float> std::ops::IndexMut<(usize, usize)> for Matrix3<F> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut F {
+        &mut self.m[row][col]
+    }
+}
+
 // //////////////////////////////////////////////////////////////////////////////////////
 //
 // Matrix4
 //
 // //////////////////////////////////////////////////////////////////////////////////////
 
+use crate::points::Point3;
+use crate::quaternion::Quaternion;
 use crate::vectors::Vector4;
 
 #[derive(Debug, Clone, Copy)]
@@ -712,6 +2227,110 @@ impl<F: Float> Matrix4<F> {
         }
     }
 
+    /// Create a matrix from 16 values in column-major order, the inverse of
+    /// `to_cols_array`.
+    ///
+    /// ```
+    /// use math3d::camera::translation;
+    /// use math3d::matrices::Matrix4;
+    ///
+    /// let m = Matrix4::from_cols_array([
+    /// 	1.0, 0.0, 0.0, 5.0,
+    /// 	0.0, 1.0, 0.0, 6.0,
+    /// 	0.0, 0.0, 1.0, 7.0,
+    /// 	0.0, 0.0, 0.0, 1.0]);
+    ///
+    /// assert!(m.approx_eq(translation(5.0, 6.0, 7.0), 0.0));
+    /// ```
+
+    pub fn from_cols_array(m: [F; 16]) -> Matrix4<F> {
+        Matrix4::from_array(m).transpose()
+    }
+
+    /// Translation matrix, with the translation in the last row for row
+    /// vectors: `p * M` moves the point `p` by `translation`.
+    ///
+    /// ```
+    /// use math3d::matrices::Matrix4;
+    /// use math3d::points::Point3;
+    /// use math3d::vectors::Vector3;
+    ///
+    /// let m = Matrix4::from_translation(Vector3::new(1.0, 2.0, 3.0));
+    ///
+    /// assert!(m.transform_point3(Point3::new(1.0, 1.0, 1.0)) == Point3::new(2.0, 3.0, 4.0));
+    /// ```
+
+    pub fn from_translation(translation: Vector3<F>) -> Matrix4<F> {
+        let mut m = Matrix4::identity();
+        m.m[3] = Vector4::new_from_vector3(translation, F::one());
+        m
+    }
+
+    /// Scale matrix with the components of `scale` on the diagonal.
+    ///
+    /// ```
+    /// use math3d::matrices::Matrix4;
+    /// use math3d::vectors::Vector3;
+    ///
+    /// let m = Matrix4::from_scale(Vector3::new(1.0, 2.0, 3.0));
+    ///
+    /// assert!(m.transform_vector3(Vector3::new(1.0, 1.0, 1.0)) == Vector3::new(1.0, 2.0, 3.0));
+    /// ```
+
+    pub fn from_scale(scale: Vector3<F>) -> Matrix4<F> {
+        let mut m = Matrix4::identity();
+        for i in 0..3 {
+            m.m[i][i] = scale[i];
+        }
+        m
+    }
+
+    /// Rotation matrix of a unit quaternion, the same as
+    /// `Quaternion::rotation_matrix4`.
+    pub fn from_rotation(rotation: Quaternion<F>) -> Matrix4<F> {
+        rotation.rotation_matrix4()
+    }
+
+    /// Rotation by `angle` degrees about a unit `axis`, like
+    /// `Quaternion::from_axis_angle`.
+    ///
+    /// ```
+    /// use math3d::matrices::Matrix4;
+    /// use math3d::vectors::Vector3;
+    ///
+    /// let m = Matrix4::<f64>::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 90.0);
+    ///
+    /// assert!((m.transform_vector3(Vector3::new(1.0, 0.0, 0.0)) - Vector3::new(0.0, 1.0, 0.0)).magnitude() < 1e-12);
+    /// ```
+
+    pub fn from_axis_angle(axis: Vector3<F>, angle: F) -> Matrix4<F> {
+        Matrix4::from_rotation(Quaternion::from_axis_angle(axis, angle))
+    }
+
+    /// Model matrix that scales, then rotates, then translates a point, the
+    /// product `from_scale(s) * from_rotation(r) * from_translation(t)` for
+    /// row vectors, built without the matrix products.
+    ///
+    /// ```
+    /// use math3d::matrices::Matrix4;
+    /// use math3d::points::Point3;
+    /// use math3d::quaternion::Quaternion;
+    /// use math3d::vectors::Vector3;
+    ///
+    /// let m = Matrix4::from_trs(Vector3::new(1.0, 0.0, 0.0), Quaternion::identity(), Vector3::new(2.0, 2.0, 2.0));
+    ///
+    /// assert!(m.transform_point3(Point3::new(1.0, 1.0, 1.0)) == Point3::new(3.0, 2.0, 2.0));
+    /// ```
+
+    pub fn from_trs(translation: Vector3<F>, rotation: Quaternion<F>, scale: Vector3<F>) -> Matrix4<F> {
+        let mut m = rotation.rotation_matrix4();
+        for i in 0..3 {
+            m.m[i] *= scale[i];
+        }
+        m.m[3] = Vector4::new_from_vector3(translation, F::one());
+        m
+    }
+
     /// Index into matrix.
     ///
     /// ```
@@ -723,166 +2342,1021 @@ impl<F: Float> Matrix4<F> {
     /// assert!(m[3][3] == 1.0);
     /// ```
 
-    pub fn index(&self, index: usize) -> &F {
-        let v = &self.m[index / 4];
-        &v[index % 4]
-    }
+    pub fn index(&self, index: usize) -> &F {
+        let v = &self.m[index / 4];
+        &v[index % 4]
+    }
+
+    /// To array f32.
+    ///
+    /// ```
+    /// use math3d::matrices::Matrix4;
+    ///
+    /// let m = Matrix4::identity();
+    ///
+    /// let af32 = m.to_array_f32();
+    ///
+    /// assert!(af32[0] == 1.0);
+    /// assert!(af32[15] == 1.0);
+    /// ```
+
+    pub fn to_array_f32(&self) -> [f32; 16] {
+        [
+            self.m[0][0].to_f32().unwrap(),
+            self.m[0][1].to_f32().unwrap(),
+            self.m[0][2].to_f32().unwrap(),
+            self.m[0][3].to_f32().unwrap(),
+            self.m[1][0].to_f32().unwrap(),
+            self.m[1][1].to_f32().unwrap(),
+            self.m[1][2].to_f32().unwrap(),
+            self.m[1][3].to_f32().unwrap(),
+            self.m[2][0].to_f32().unwrap(),
+            self.m[2][1].to_f32().unwrap(),
+            self.m[2][2].to_f32().unwrap(),
+            self.m[2][3].to_f32().unwrap(),
+            self.m[3][0].to_f32().unwrap(),
+            self.m[3][1].to_f32().unwrap(),
+            self.m[3][2].to_f32().unwrap(),
+            self.m[3][3].to_f32().unwrap(),
+        ]
+    }
+
+    /// To [f32; 16] array, or an error if an element is out of `f32` range
+    /// or would be rounded by more than `max_error`.
+    ///
+    /// ```
+    /// use math3d::matrices::Matrix4;
+    /// use math3d::scalar::F32ConversionError;
+    ///
+    /// let mut m = Matrix4::<f64>::identity();
+    /// m[3][0] = 1e40;
+    ///
+    /// assert_eq!(m.try_to_array_f32(1.0), Err(F32ConversionError::OutOfRange { index: 12 }));
+    /// ```
+
+    pub fn try_to_array_f32(&self, max_error: F) -> Result<[f32; 16], F32ConversionError> {
+        try_to_array_f32(std::array::from_fn(|i| *self.index(i)), max_error)
+    }
+
+	 /// To array f64
+	 ///
+	 /// ```
+	 /// use math3d::matrices::Matrix4;
+	 ///
+	 /// let m = Matrix4::identity();
+	 ///
+	 /// let af64 = m.to_array_f64();
+	 ///
+	 /// assert!(af64[0] == 1.0);
+	 /// assert!(af64[15] == 1.0);
+	 /// ```
+
+	 pub fn to_array_f64(&self) -> [f64; 16] {
+		 [
+			 self.m[0][0].to_f64().unwrap(),
+			 self.m[0][1].to_f64().unwrap(),
+			 self.m[0][2].to_f64().unwrap(),
+			 self.m[0][3].to_f64().unwrap(),
+			 self.m[1][0].to_f64().unwrap(),
+			 self.m[1][1].to_f64().unwrap(),
+			 self.m[1][2].to_f64().unwrap(),
+			 self.m[1][3].to_f64().unwrap(),
+			 self.m[2][0].to_f64().unwrap(),
+			 self.m[2][1].to_f64().unwrap(),
+			 self.m[2][2].to_f64().unwrap(),
+			 self.m[2][3].to_f64().unwrap(),
+			 self.m[3][0].to_f64().unwrap(),
+			 self.m[3][1].to_f64().unwrap(),
+			 self.m[3][2].to_f64().unwrap(),
+			 self.m[3][3].to_f64().unwrap(),
+		 ]
+	 }
+
+	/// To array of vectors.
+	///
+	/// ```
+	/// use math3d::matrices::Matrix4;
+	///
+	/// let m = Matrix4::identity();
+	///
+	/// let av = m.to_array_vectors();
+	///
+	/// assert!(av[0][0] == 1.0);
+	/// assert!(av[3][3] == 1.0);
+	/// ```
+
+	pub fn to_array_vectors(&self) -> [Vector4<F>; 4] {
+		self.m
+	}
+
+	/// To [F; 16] array in column-major order, the order in which GLSL, WGSL
+	/// and HLSL by default store matrices. A shader matrix filled from it has
+	/// the same rows and columns as this one, so it is applied to row vectors
+	/// as `v * m`, with the translation in its last row.
+	///
+	/// ```
+	/// use math3d::camera::translation;
+	/// use math3d::matrices::Matrix4;
+	///
+	/// let m = translation(5.0, 6.0, 7.0);
+	/// let cols = m.to_cols_array();
+	///
+	/// assert_eq!(&cols[..4], &[1.0, 0.0, 0.0, 5.0]);
+	/// assert!(Matrix4::from_cols_array(cols).approx_eq(m, 0.0));
+	/// ```
+
+	pub fn to_cols_array(&self) -> [F; 16] {
+		let cols = self.to_cols_array_2d();
+		std::array::from_fn(|i| cols[i / 4][i % 4])
+	}
+
+	/// To array of the columns.
+	///
+	/// ```
+	/// use math3d::camera::translation;
+	///
+	/// assert_eq!(translation(5.0, 6.0, 7.0).to_cols_array_2d()[3], [0.0, 0.0, 0.0, 1.0]);
+	/// ```
+
+	pub fn to_cols_array_2d(&self) -> [[F; 4]; 4] {
+		self.transpose().to_array_2d()
+	}
+
+	/// Apply a function to each element.
+	///
+	/// ```
+	/// use math3d::matrices::Matrix4;
+	///
+	/// let m = Matrix4::<f64>::identity().map(|a| a * 2.0);
+	///
+	/// assert!(m[2][2] == 2.0);
+	/// ```
+
+	pub fn map<M: FnMut(F) -> F>(&self, mut f: M) -> Matrix4<F> {
+		Matrix4 {
+			m: self.m.map(|row| row.map(&mut f)),
+		}
+	}
+
+	/// Converts the elements to another float type, e.g. to upload an `f64`
+	/// model matrix to an `f32` uniform buffer.
+	///
+	/// ```
+	/// use math3d::matrices::Matrix4;
+	///
+	/// let m: Matrix4<f32> = Matrix4::<f64>::identity().map(|a| a * 2.0).cast();
+	///
+	/// assert_eq!(m[2][2], 2.0f32);
+	/// ```
+
+	pub fn cast<T: Float>(&self) -> Matrix4<T> {
+		Matrix4 {
+			m: self.m.map(|row| row.cast()),
+		}
+	}
+
+	/// Whether all elements are finite, neither infinite nor NaN.
+	///
+	/// ```
+	/// use math3d::matrices::Matrix4;
+	///
+	/// let mut m = Matrix4::<f64>::identity();
+	/// assert!(m.is_finite());
+	///
+	/// m[3][0] = f64::NAN;
+	/// assert!(!m.is_finite() && m.is_nan());
+	/// ```
+
+	pub fn is_finite(&self) -> bool {
+		self.m.iter().all(|row| row.is_finite())
+	}
+
+	/// Whether any element is NaN.
+	pub fn is_nan(&self) -> bool {
+		self.m.iter().any(|row| row.is_nan())
+	}
+
+	/// Whether every element is within `epsilon` of the other matrix's.
+	///
+	/// ```
+	/// use math3d::matrices::Matrix4;
+	///
+	/// let m = Matrix4::<f64>::identity() * 3.0;
+	///
+	/// assert!((m * (1.0 / 3.0)).approx_eq(Matrix4::identity(), 1e-12));
+	/// ```
+
+	pub fn approx_eq(&self, other: Matrix4<F>, epsilon: F) -> bool {
+		(0..4).all(|i| self.m[i].approx_eq(other.m[i], epsilon))
+	}
+
+	/// Element-wise `FloatExt::relative_eq`.
+	pub fn relative_eq(&self, other: Matrix4<F>, epsilon: F, max_relative: F) -> bool {
+		(0..4).all(|i| self.m[i].relative_eq(other.m[i], epsilon, max_relative))
+	}
+
+	/// Elements as aligned rows with `precision` decimals, one row per line.
+	///
+	/// ```
+	/// use math3d::matrices::Matrix4;
+	///
+	/// let table = Matrix4::<f64>::identity().to_table_string(2);
+	///
+	/// assert_eq!(table.lines().next(), Some("[ 1.00  0.00  0.00  0.00 ]"));
+	/// ```
+
+	pub fn to_table_string(&self, precision: usize) -> String {
+		table_rows(&self.to_array_f64(), 4, precision, &[false; 16]).join("\n")
+	}
+
+	/// Both matrices side by side with the elements differing by more than
+	/// `tolerance` marked with `*`, followed by a summary of the largest
+	/// difference.
+	///
+	/// ```
+	/// use math3d::matrices::Matrix4;
+	///
+	/// let a = Matrix4::<f64>::identity();
+	/// let mut b = a;
+	/// b[3][0] = 2.0;
+	///
+	/// assert!(a.diff_string(b, 1e-9).ends_with("1 of 16 elements differ by more than 0.000000001, the largest by 2 at row 3, column 0"));
+	/// ```
+
+	pub fn diff_string(&self, other: Matrix4<F>, tolerance: F) -> String {
+		diff_string(&self.to_array_f64(), &other.to_array_f64(), 4, tolerance.to_f64().unwrap())
+	}
+
+	/// Multiply 4x4 matrix by 4x4 matrix.
+	///
+	/// ```
+	/// use math3d::matrices::Matrix4;
+	///
+	/// let m1 = Matrix4::identity();
+	/// let m2 = Matrix4::identity();
+	///
+	/// let m3 = m1 * m2;
+	///
+	/// assert!(m3 == m2);
+	/// ```
+
+	pub fn product(&self, other: Matrix4<F>) -> Matrix4<F> {
+		let mut m = Matrix4::zero();
+
+		for i in 0..4 {
+			for j in 0..4 {
+				for k in 0..4 {
+					m[i][j] = m[i][j] + self[i][k] * other[k][j];
+				}
+			}
+		}
+		m
+	}
+
+	/// Multiply 4x4 matrix by 4x1 column vector, `M * v`. This is the
+	/// transpose of the row-vector product `v * M` that the transforms in
+	/// this crate are built for; with them, a point is moved by `v * M` and
+	/// `M * v` applies the transposed matrix.
+	///
+	/// ```
+	/// use math3d::camera::translation;
+	/// use math3d::vectors::Vector4;
+	///
+	/// let m = translation(1.0, 2.0, 3.0);
+	/// let p = Vector4::new(0.0, 0.0, 0.0, 1.0);
+	///
+	/// assert!(p * m == Vector4::new(1.0, 2.0, 3.0, 1.0));
+	/// assert!(m.transpose() * p == Vector4::new(1.0, 2.0, 3.0, 1.0));
+	/// ```
+
+	pub fn product_vector(&self, other: Vector4<F>) -> Vector4<F> {
+		let mut v = Vector4::zero();
+
+		for i in 0..4 {
+			for j in 0..4 {
+				v[i] = v[i] + self[i][j] * other[j];
+			}
+		}
+		v
+	}
+
+	/// Transforms a point as the row vector `(x, y, z, 1) * M`, applying the
+	/// translation. Projective matrices give a `w` other than one, which is
+	/// divided out; a `w` of zero gives non-finite coordinates.
+	///
+	/// ```
+	/// use math3d::camera::{scale, translation};
+	/// use math3d::points::Point3;
+	///
+	/// let m = scale(2.0, 2.0, 2.0) * translation(1.0, 0.0, 0.0);
+	///
+	/// assert!(m.transform_point3(Point3::new(1.0, 1.0, 1.0)) == Point3::new(3.0, 2.0, 2.0));
+	/// ```
+
+	pub fn transform_point3(&self, point: Point3<F>) -> Point3<F> {
+		let v = point.to_vector4() * *self;
+		let w = v[3];
+		if w == F::one() {
+			Point3::from_vector(v.truncate())
+		} else {
+			Point3::from_vector(v.truncate() / w)
+		}
+	}
+
+	/// Transforms a direction as the row vector `(x, y, z, 0) * M`, ignoring
+	/// the translation.
+	///
+	/// ```
+	/// use math3d::camera::{scale, translation};
+	/// use math3d::vectors::Vector3;
+	///
+	/// let m = scale(2.0, 2.0, 2.0) * translation(1.0, 0.0, 0.0);
+	///
+	/// assert!(m.transform_vector3(Vector3::new(1.0, 1.0, 1.0)) == Vector3::new(2.0, 2.0, 2.0));
+	/// ```
+
+	pub fn transform_vector3(&self, vector: Vector3<F>) -> Vector3<F> {
+		(Vector4::new_from_vector3(vector, F::zero()) * *self).truncate()
+	}
+
+	/// Element at the given row and column, the same as `m[(row, col)]`
+	/// with the `Row` and `Col` types guarding against swapped indices.
+	///
+	/// ```
+	/// use math3d::matrices::{Col, Matrix4, Row};
+	///
+	/// let m = Matrix4::<f64>::identity();
+	///
+	/// assert_eq!(m.at(Row(3), Col(3)), 1.0);
+	/// assert_eq!(m.at(Row(3), Col(3)), m[(3, 3)]);
+	/// ```
+
+	pub fn at(&self, row: Row, col: Col) -> F {
+		self[(row.0, col.0)]
+	}
+
+	/// Set the element at the given row and column.
+	///
+	/// ```
+	/// use math3d::matrices::{Col, Matrix4, Row};
+	///
+	/// let mut m = Matrix4::identity();
+	/// m.set(Row(0), Col(3), 5.0);
+	///
+	/// assert_eq!(m[0][3], 5.0);
+	/// ```
+
+	pub fn set(&mut self, row: Row, col: Col, value: F) {
+		self[(row.0, col.0)] = value;
+	}
+
+	/// Iterator over the elements in row-major order.
+	///
+	/// ```
+	/// use math3d::matrices::Matrix4;
+	///
+	/// let m = Matrix4::<f64>::identity() * 2.0;
+	///
+	/// assert_eq!(m.iter().filter(|a| **a != 0.0).count(), 4);
+	/// assert_eq!(m.iter().map(|a| a * a).sum::<f64>().sqrt(), 4.0);
+	/// ```
+
+	pub fn iter(&self) -> impl Iterator<Item = &F> + '_ {
+		self.m.iter().flat_map(|row| row.iter())
+	}
+
+	/// Mutable iterator over the elements in row-major order.
+	pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut F> + '_ {
+		self.m.iter_mut().flat_map(|row| row.iter_mut())
+	}
+
+	/// Iterator over the rows, as returned by `row`.
+	///
+	/// ```
+	/// use math3d::matrices::Matrix4;
+	/// use math3d::vectors::Vector4;
+	///
+	/// let mut m = Matrix4::<f64>::identity();
+	/// m[3][0] = 5.0;
+	///
+	/// assert!(m.rows().last().unwrap() == Vector4::new(5.0, 0.0, 0.0, 1.0));
+	/// assert!(m.cols().next().unwrap() == Vector4::new(1.0, 0.0, 0.0, 5.0));
+	/// ```
+
+	pub fn rows(&self) -> std::array::IntoIter<Vector4<F>, 4> {
+		[self.row(0), self.row(1), self.row(2), self.row(3)].into_iter()
+	}
+
+	/// Iterator over the columns, as returned by `column`.
+	pub fn cols(&self) -> std::array::IntoIter<Vector4<F>, 4> {
+		[self.column(0), self.column(1), self.column(2), self.column(3)].into_iter()
+	}
+
+	/// Row `i`, the same as `m[i]`. Row 3 holds the translation. `rows`
+	/// iterates over these; single elements are read with `at` or
+	/// `m[(row, col)]`.
+	///
+	/// # Panics
+	///
+	/// Panics if `i` is out of bounds.
+	///
+	/// ```
+	/// use math3d::camera::translation;
+	/// use math3d::vectors::Vector4;
+	///
+	/// let m = translation(1.0, 2.0, 3.0);
+	///
+	/// assert!(m.row(3) == Vector4::new(1.0, 2.0, 3.0, 1.0));
+	/// assert!(m.column(3) == Vector4::new(0.0, 0.0, 0.0, 1.0));
+	/// ```
+
+	pub fn row(&self, i: usize) -> Vector4<F> {
+		self.m[i]
+	}
+
+	/// Column `j`. With column vectors, as in OpenGL, these are the rows of
+	/// the transposed matrix.
+	///
+	/// # Panics
+	///
+	/// Panics if `j` is out of bounds.
+
+	pub fn column(&self, j: usize) -> Vector4<F> {
+		Vector4::new(self[(0, j)], self[(1, j)], self[(2, j)], self[(3, j)])
+	}
+
+	/// Replace row `i`.
+	///
+	/// # Panics
+	///
+	/// Panics if `i` is out of bounds.
+
+	pub fn set_row(&mut self, i: usize, row: Vector4<F>) {
+		self.m[i] = row;
+	}
+
+	/// Replace column `j`.
+	///
+	/// # Panics
+	///
+	/// Panics if `j` is out of bounds.
+	///
+	/// ```
+	/// use math3d::matrices::Matrix4;
+	/// use math3d::vectors::Vector4;
+	///
+	/// let mut m = Matrix4::<f64>::identity();
+	/// m.set_column(3, Vector4::new(1.0, 2.0, 3.0, 1.0));
+	///
+	/// assert_eq!(m[(2, 3)], 3.0);
+	/// assert!(m.transpose() == math3d::camera::translation(1.0, 2.0, 3.0));
+	/// ```
+
+	pub fn set_column(&mut self, j: usize, column: Vector4<F>) {
+		for (i, value) in column.into_iter().enumerate() {
+			self[(i, j)] = value;
+		}
+	}
+
+	/// Transpose 4x4 matrix.
+	///
+	/// ```
+	/// use math3d::matrices::Matrix4;
+	///
+	/// let m = Matrix4::identity();
+	///
+	/// let m_t = m.transpose();
+	///
+	/// assert!(m_t == m);
+	/// ```
+
+	pub fn transpose(&self) -> Matrix4<F> {
+		let mut m = Matrix4::zero();
+
+		for i in 0..4 {
+			for j in 0..4 {
+				m[i][j] = self[j][i];
+			}
+		}
+		m
+	}
+
+	/// Moore-Penrose pseudo-inverse computed from the singular value
+	/// decomposition.
+	///
+	/// Singular values below `max(σ) * 4 * ε` are treated as zero, so singular
+	/// and rank-deficient matrices produce the least-squares minimum-norm
+	/// solution instead of failing. For invertible matrices the result equals
+	/// the inverse.
+	///
+	/// ```
+	/// use math3d::matrices::Matrix4;
+	///
+	/// let mut m = Matrix4::identity();
+	/// m[0][0] = 2.0;
+	///
+	/// let p = m.pinv();
+	///
+	/// assert!(p[0][0] == 0.5);
+	/// ```
+
+	pub fn pinv(&self) -> Matrix4<F> {
+		Matrix4::from_array_2d(pseudo_inverse(self.to_array_2d(), None))
+	}
+
+	/// Moore-Penrose pseudo-inverse treating singular values at or below
+	/// `tolerance` as zero.
+	///
+	/// ```
+	/// use math3d::matrices::Matrix4;
+	///
+	/// let mut m = Matrix4::identity();
+	/// m[3][3] = 1e-9;
+	///
+	/// let p = m.pinv_with_tolerance(1e-6);
+	///
+	/// assert!(p[3][3] == 0.0);
+	/// ```
+
+	pub fn pinv_with_tolerance(&self, tolerance: F) -> Matrix4<F> {
+		Matrix4::from_array_2d(pseudo_inverse(self.to_array_2d(), Some(tolerance)))
+	}
+
+	/// Sum of the diagonal elements.
+	///
+	/// ```
+	/// use math3d::camera::scale;
+	///
+	/// assert_eq!(scale(2.0, 3.0, 4.0).trace(), 10.0);
+	/// ```
+
+	pub fn trace(&self) -> F {
+		self[0][0] + self[1][1] + self[2][2] + self[3][3]
+	}
+
+	/// Frobenius norm, the square root of the sum of the squared elements.
+	///
+	/// ```
+	/// use math3d::camera::translation;
+	///
+	/// assert_eq!(translation(1.0, 2.0, 2.0).frobenius_norm(), 13.0_f64.sqrt());
+	/// ```
+
+	pub fn frobenius_norm(&self) -> F {
+		self.iter().fold(F::zero(), |acc, x| acc + *x * *x).sqrt()
+	}
+
+	/// Inverse and determinant computed together by cofactor expansion, with
+	/// the 2x2 minors of the top and bottom row pairs shared between the
+	/// determinant and every cofactor.
+	///
+	/// Singular matrices give a zero determinant and non-finite elements in
+	/// the inverse; check the determinant before using it.
+	///
+	/// ```
+	/// use math3d::camera::translation;
+	/// use math3d::matrices::Matrix4;
+	///
+	/// let m = translation(1.0, 2.0, 3.0) * 2.0;
+	/// let (inverse, det) = m.inverse_and_det();
+	///
+	/// assert_eq!(det, 16.0);
+	/// assert!(inverse * m == Matrix4::identity());
+	/// ```
+
+	pub fn inverse_and_det(&self) -> (Matrix4<F>, F) {
+		let (adjugate, det) = self.adjugate_and_det();
+		(adjugate / det, det)
+	}
+
+	/// Adjugate matrix, the transpose of the cofactor matrix. Equals the
+	/// inverse times the determinant, and stays defined for singular
+	/// matrices.
+	///
+	/// ```
+	/// use math3d::camera::scale;
+	///
+	/// let m = scale(2.0, 3.0, 0.0);
+	///
+	/// assert!(m.adjugate() == scale(0.0, 0.0, 6.0));
+	/// ```
+
+	pub fn adjugate(&self) -> Matrix4<F> {
+		self.adjugate_and_det().0
+	}
+
+	/// Cofactor matrix, the transpose of the adjugate.
+	///
+	/// ```
+	/// use math3d::camera::translation;
+	/// use math3d::matrices::Matrix4;
+	///
+	/// let m = translation(1.0, 2.0, 3.0);
+	///
+	/// assert!(m.cofactor() == m.inverse().transpose());
+	/// ```
+
+	pub fn cofactor(&self) -> Matrix4<F> {
+		self.adjugate().transpose()
+	}
+
+	/// Cofactor of a single element: the determinant of the 3x3 minor left
+	/// after removing its row and column, negated when `row + col` is odd.
+	/// Named `cofactor_at` and indexed like `at` because `cofactor` already
+	/// returns the whole cofactor matrix.
+	///
+	/// ```
+	/// use math3d::camera::translation;
+	/// use math3d::matrices::{Col, Row};
+	///
+	/// let m = translation(1.0, 2.0, 3.0);
+	///
+	/// assert_eq!(m.cofactor_at(Row(3), Col(3)), 1.0);
+	/// assert_eq!(m.cofactor_at(Row(0), Col(3)), -1.0);
+	/// assert_eq!(m.cofactor_at(Row(0), Col(3)), m.cofactor()[0][3]);
+	/// ```
+
+	pub fn cofactor_at(&self, row: Row, col: Col) -> F {
+		let skip = |k: usize, removed: usize| if k < removed { k } else { k + 1 };
+		let mut minor = Matrix3::identity();
+		for i in 0..3 {
+			for j in 0..3 {
+				minor[i][j] = self[skip(i, row.0)][skip(j, col.0)];
+			}
+		}
+		let det = minor.determinant();
+		if (row.0 + col.0).is_multiple_of(2) { det } else { -det }
+	}
+
+	/// The six 2x2 minors of the top row pair, the six of the bottom row
+	/// pair, and the determinant they expand to.
+
+	fn pair_minors(&self) -> ([F; 6], [F; 6], F) {
+		let m = self.to_array_2d();
+		let s0 = m[0][0] * m[1][1] - m[1][0] * m[0][1];
+		let s1 = m[0][0] * m[1][2] - m[1][0] * m[0][2];
+		let s2 = m[0][0] * m[1][3] - m[1][0] * m[0][3];
+		let s3 = m[0][1] * m[1][2] - m[1][1] * m[0][2];
+		let s4 = m[0][1] * m[1][3] - m[1][1] * m[0][3];
+		let s5 = m[0][2] * m[1][3] - m[1][2] * m[0][3];
+		let c0 = m[2][0] * m[3][1] - m[3][0] * m[2][1];
+		let c1 = m[2][0] * m[3][2] - m[3][0] * m[2][2];
+		let c2 = m[2][0] * m[3][3] - m[3][0] * m[2][3];
+		let c3 = m[2][1] * m[3][2] - m[3][1] * m[2][2];
+		let c4 = m[2][1] * m[3][3] - m[3][1] * m[2][3];
+		let c5 = m[2][2] * m[3][3] - m[3][2] * m[2][3];
+		let det = s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0;
+		([s0, s1, s2, s3, s4, s5], [c0, c1, c2, c3, c4, c5], det)
+	}
+
+	/// Adjugate and determinant by cofactor expansion, with the 2x2 minors of
+	/// the top and bottom row pairs shared between the determinant and every
+	/// cofactor.
 
-    /// To array f32.
-    ///
-    /// ```
-    /// use math3d::matrices::Matrix4;
-    ///
-    /// let m = Matrix4::identity();
-    ///
-    /// let af32 = m.to_array_f32();
-    ///
-    /// assert!(af32[0] == 1.0);
-    /// assert!(af32[15] == 1.0);
-    /// ```
+	fn adjugate_and_det(&self) -> (Matrix4<F>, F) {
+		let m = self.to_array_2d();
+		let ([s0, s1, s2, s3, s4, s5], [c0, c1, c2, c3, c4, c5], det) = self.pair_minors();
 
-    pub fn to_array_f32(&self) -> [f32; 16] {
-        [
-            self.m[0][0].to_f32().unwrap(),
-            self.m[0][1].to_f32().unwrap(),
-            self.m[0][2].to_f32().unwrap(),
-            self.m[0][3].to_f32().unwrap(),
-            self.m[1][0].to_f32().unwrap(),
-            self.m[1][1].to_f32().unwrap(),
-            self.m[1][2].to_f32().unwrap(),
-            self.m[1][3].to_f32().unwrap(),
-            self.m[2][0].to_f32().unwrap(),
-            self.m[2][1].to_f32().unwrap(),
-            self.m[2][2].to_f32().unwrap(),
-            self.m[2][3].to_f32().unwrap(),
-            self.m[3][0].to_f32().unwrap(),
-            self.m[3][1].to_f32().unwrap(),
-            self.m[3][2].to_f32().unwrap(),
-            self.m[3][3].to_f32().unwrap(),
-        ]
-    }
+		let adjugate = [
+			[
+				m[1][1] * c5 - m[1][2] * c4 + m[1][3] * c3,
+				-m[0][1] * c5 + m[0][2] * c4 - m[0][3] * c3,
+				m[3][1] * s5 - m[3][2] * s4 + m[3][3] * s3,
+				-m[2][1] * s5 + m[2][2] * s4 - m[2][3] * s3,
+			],
+			[
+				-m[1][0] * c5 + m[1][2] * c2 - m[1][3] * c1,
+				m[0][0] * c5 - m[0][2] * c2 + m[0][3] * c1,
+				-m[3][0] * s5 + m[3][2] * s2 - m[3][3] * s1,
+				m[2][0] * s5 - m[2][2] * s2 + m[2][3] * s1,
+			],
+			[
+				m[1][0] * c4 - m[1][1] * c2 + m[1][3] * c0,
+				-m[0][0] * c4 + m[0][1] * c2 - m[0][3] * c0,
+				m[3][0] * s4 - m[3][1] * s2 + m[3][3] * s0,
+				-m[2][0] * s4 + m[2][1] * s2 - m[2][3] * s0,
+			],
+			[
+				-m[1][0] * c3 + m[1][1] * c1 - m[1][2] * c0,
+				m[0][0] * c3 - m[0][1] * c1 + m[0][2] * c0,
+				-m[3][0] * s3 + m[3][1] * s1 - m[3][2] * s0,
+				m[2][0] * s3 - m[2][1] * s1 + m[2][2] * s0,
+			],
+		];
+		(Matrix4::from_array_2d(adjugate), det)
+	}
 
-	 /// To array f64
-	 ///
-	 /// ```
-	 /// use math3d::matrices::Matrix4;
-	 ///
-	 /// let m = Matrix4::identity();
-	 ///
-	 /// let af64 = m.to_array_f64();
-	 ///
-	 /// assert!(af64[0] == 1.0);
-	 /// assert!(af64[15] == 1.0);
-	 /// ```
+	/// Determinant by Laplace expansion over the 2x2 minors of the top and
+	/// bottom row pairs.
+	///
+	/// ```
+	/// use math3d::camera::{scale, translation};
+	///
+	/// let m = translation(1.0, 2.0, 3.0) * scale(2.0, 3.0, 4.0);
+	///
+	/// assert_eq!(m.determinant(), 24.0);
+	/// ```
 
-	 pub fn to_array_f64(&self) -> [f64; 16] {
-		 [
-			 self.m[0][0].to_f64().unwrap(),
-			 self.m[0][1].to_f64().unwrap(),
-			 self.m[0][2].to_f64().unwrap(),
-			 self.m[0][3].to_f64().unwrap(),
-			 self.m[1][0].to_f64().unwrap(),
-			 self.m[1][1].to_f64().unwrap(),
-			 self.m[1][2].to_f64().unwrap(),
-			 self.m[1][3].to_f64().unwrap(),
-			 self.m[2][0].to_f64().unwrap(),
-			 self.m[2][1].to_f64().unwrap(),
-			 self.m[2][2].to_f64().unwrap(),
-			 self.m[2][3].to_f64().unwrap(),
-			 self.m[3][0].to_f64().unwrap(),
-			 self.m[3][1].to_f64().unwrap(),
-			 self.m[3][2].to_f64().unwrap(),
-			 self.m[3][3].to_f64().unwrap(),
-		 ]
-	 }
+	pub fn determinant(&self) -> F {
+		self.pair_minors().2
+	}
 
-	/// To array of vectors.
+	/// Inverse of the matrix, the adjugate divided by the determinant.
+	///
+	/// Singular matrices give non-finite elements; use `try_inverse` to
+	/// detect them.
 	///
 	/// ```
+	/// use math3d::camera::translation;
 	/// use math3d::matrices::Matrix4;
 	///
-	/// let m = Matrix4::identity();
+	/// let m = translation(1.0, 2.0, 3.0);
 	///
-	/// let av = m.to_array_vectors();
+	/// assert!(m.inverse() == translation(-1.0, -2.0, -3.0));
+	/// assert!(m * m.inverse() == Matrix4::identity());
+	/// ```
+
+	pub fn inverse(&self) -> Matrix4<F> {
+		self.inverse_and_det().0
+	}
+
+	/// Inverse of the matrix, or `None` if it is singular.
 	///
-	/// assert!(av[0][0] == 1.0);
-	/// assert!(av[3][3] == 1.0);
+	/// ```
+	/// use math3d::camera::scale;
+	///
+	/// assert!(scale(2.0, 4.0, 8.0).try_inverse() == Some(scale(0.5, 0.25, 0.125)));
+	/// assert!(scale(1.0, 0.0, 1.0).try_inverse().is_none());
 	/// ```
 
-	pub fn to_array_vectors(&self) -> [Vector4<F>; 4] {
-		self.m
+	pub fn try_inverse(&self) -> Option<Matrix4<F>> {
+		let (inverse, det) = self.inverse_and_det();
+		if det == F::zero() || !det.is_finite() {
+			return None;
+		}
+		Some(inverse)
 	}
 
-	/// Multiply 4x4 matrix by 4x4 matrix.
+	/// Inverse of an affine matrix, a linear 3x3 block followed by a
+	/// translation with `[0, 0, 0, 1]` as the last column. Only the 3x3 block
+	/// is inverted and the translation is mapped back through it, which is
+	/// much cheaper than `inverse`. Other matrices, such as projections, give
+	/// wrong results.
 	///
 	/// ```
+	/// use math3d::camera::{scale, translation};
 	/// use math3d::matrices::Matrix4;
 	///
-	/// let m1 = Matrix4::identity();
-	/// let m2 = Matrix4::identity();
+	/// let m = scale(2.0, 4.0, 8.0) * translation(1.0, 2.0, 3.0);
 	///
-	/// let m3 = m1 * m2;
+	/// assert!(m * m.inverse_affine() == Matrix4::identity());
+	/// ```
+
+	pub fn inverse_affine(&self) -> Matrix4<F> {
+		self.affine_inverse_with(self.linear_part().inverse())
+	}
+
+	/// Inverse of a rigid matrix, a rotation followed by a translation such
+	/// as a view or camera-to-world matrix. The rotation block is transposed
+	/// instead of inverted. Matrices with scale or shear give wrong results;
+	/// use `inverse_affine` for them.
 	///
-	/// assert!(m3 == m2);
+	/// ```
+	/// use math3d::camera::translation;
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let rotation = Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 90.0);
+	/// let m = rotation.rotation_matrix4() * translation(1.0, 2.0, 3.0);
+	///
+	/// assert!(m.inverse_rigid().approx_eq(m.inverse(), 1e-12));
 	/// ```
 
-	pub fn product(&self, other: Matrix4<F>) -> Matrix4<F> {
-		let mut m = Matrix4::zero();
+	pub fn inverse_rigid(&self) -> Matrix4<F> {
+		self.affine_inverse_with(self.linear_part().transpose())
+	}
 
-		for i in 0..4 {
-			for j in 0..4 {
-				for k in 0..4 {
-					m[i][j] = m[i][j] + self[i][k] * other[k][j];
-				}
+	fn linear_part(&self) -> Matrix3<F> {
+		Matrix3::from_vectors(
+			Vector3::new(self[0][0], self[0][1], self[0][2]),
+			Vector3::new(self[1][0], self[1][1], self[1][2]),
+			Vector3::new(self[2][0], self[2][1], self[2][2]),
+		)
+	}
+
+	/// Affine matrix with `linear_inverse` as the 3x3 block and the
+	/// translation of `self` undone through it.
+
+	fn affine_inverse_with(&self, linear_inverse: Matrix3<F>) -> Matrix4<F> {
+		let translation = -(Vector3::new(self[3][0], self[3][1], self[3][2]) * linear_inverse);
+		let mut m = Matrix4::identity();
+		for i in 0..3 {
+			for j in 0..3 {
+				m[i][j] = linear_inverse[i][j];
 			}
+			m[3][i] = translation[i];
 		}
 		m
 	}
 
-	/// Multiply 4x4 matrix by 4x1 vector.
+	/// Right division `self * other^-1`, or `None` if `other` is singular.
+	/// The `/` operator computes the same product but gives non-finite
+	/// elements for a singular divisor.
 	///
 	/// ```
+	/// use math3d::camera::{scale, translation};
 	/// use math3d::matrices::Matrix4;
 	///
-	/// let m = Matrix4::identity();
-	///
-	/// let v = m * Vector4::new(1.0, 2.0, 3.0, 4.0);
+	/// let a = translation(1.0, 2.0, 3.0);
+	/// let b = scale(2.0, 4.0, 8.0);
 	///
-	/// assert!(v == Vector4::new(1.0, 2.0, 3.0, 4.0));
+	/// assert!(a.checked_div(b).unwrap() * b == a);
+	/// assert!(a.checked_div(scale(1.0, 0.0, 1.0)).is_none());
 	/// ```
 
-	pub fn product_vector(&self, other: Vector4<F>) -> Vector4<F> {
-		let mut v = Vector4::zero();
+	pub fn checked_div(&self, other: Matrix4<F>) -> Option<Matrix4<F>> {
+		other.try_inverse().map(|inverse| *self * inverse)
+	}
+
+	/// Projection matrix with the near plane replaced by an arbitrary clip
+	/// plane, following Lengyel's oblique near-plane clipping. Used to clip
+	/// geometry behind a mirror or below water when rendering reflections,
+	/// without a user clip plane in the shader.
+	///
+	/// `self` must be a perspective or orthographic projection for row
+	/// vectors with clip-space depth in `[-w, w]`, such as
+	/// `Camera::projection`. `clip_plane` holds the view-space plane
+	/// equation `(a, b, c, d)`, `a x + b y + c z + d = 0`, with the camera on
+	/// its negative side (`d < 0`); points on the positive side are kept.
+	/// The far plane tilts with it, which costs some depth precision.
+	///
+	/// ```
+	/// use math3d::camera::Camera;
+	/// use math3d::points::Point3;
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector4;
+	///
+	/// let camera = Camera::<f64>::new(Point3::new(0.0, 0.0, 0.0), Quaternion::identity(), 1.0, 1.0, 0.1, 100.0);
+	///
+	/// // Clip everything closer than 5 units ahead: -z - 5 >= 0.
+	/// let m = camera.projection().with_oblique_near_plane(Vector4::new(0.0, 0.0, -1.0, -5.0));
+	/// let p = Vector4::new(0.3, -0.2, -5.0, 1.0) * m;
+	///
+	/// assert!((p[2] + p[3]).abs() < 1e-12);
+	/// ```
 
+	pub fn with_oblique_near_plane(&self, clip_plane: Vector4<F>) -> Matrix4<F> {
+		// Clip-space corner of the frustum opposite to the plane, which the
+		// far plane must still pass through.
+		let corner = Vector4::new(clip_plane[0].signum(), clip_plane[1].signum(), F::one(), F::one()) * self.inverse();
+		let c = clip_plane * (F::from(2.0).unwrap() / clip_plane.dot(corner));
+		let mut m = *self;
 		for i in 0..4 {
-			for j in 0..4 {
-				v[i] = v[i] + self[i][j] * other[j];
-			}
+			m[i][2] = c[i] - m[i][3];
 		}
-		v
+		m
 	}
 
-	/// Transpose 4x4 matrix.
+	/// Rotation about a pivot point instead of the origin: a translation of
+	/// `-pivot`, the rotation, then a translation back to `pivot`, in the
+	/// row-vector convention of `Vector4 * Matrix4`. The pivot maps to itself.
 	///
 	/// ```
 	/// use math3d::matrices::Matrix4;
+	/// use math3d::points::Point3;
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::{Vector3, Vector4};
 	///
-	/// let m = Matrix4::identity();
+	/// let q = Quaternion::<f64>::from_axis_angle(Vector3::unit_z(), 90.0);
+	/// let m = Matrix4::rotation_about_point(q, Point3::new(1.0, 0.0, 0.0));
 	///
-	/// let m_t = m.transpose();
+	/// let v = Vector4::new(2.0, 0.0, 0.0, 1.0) * m;
 	///
-	/// assert!(m_t == m);
+	/// assert!((v - Vector4::new(1.0, 1.0, 0.0, 1.0)).magnitude() < 1e-12);
 	/// ```
 
-	pub fn transpose(&self) -> Matrix4<F> {
-		let mut m = Matrix4::zero();
+	pub fn rotation_about_point(rotation: Quaternion<F>, pivot: Point3<F>) -> Matrix4<F> {
+		let c = pivot.to_vector();
+		let t = c - rotation.rotate_vector(c);
+		let mut m = rotation.rotation_matrix4();
+		m[3] = Vector4::new(t[0], t[1], t[2], F::one());
+		m
+	}
 
-		for i in 0..4 {
-			for j in 0..4 {
-				m[i][j] = self[j][i];
-			}
+	/// Multiplies every matrix in `locals` by `parent`, writing
+	/// `locals[i] * parent` to `out[i]`. With the row-vector convention used
+	/// by `Vector4 * Matrix4`, this turns local transforms of the children of
+	/// a scene graph node into world transforms.
+	///
+	/// # Panics
+	///
+	/// Panics if `locals` and `out` have different lengths.
+	///
+	/// ```
+	/// use math3d::camera::translation;
+	/// use math3d::matrices::Matrix4;
+	///
+	/// let parent = translation(1.0, 0.0, 0.0);
+	/// let locals = [translation(0.0, 2.0, 0.0), Matrix4::identity()];
+	/// let mut world = [Matrix4::zero(); 2];
+	///
+	/// Matrix4::mul_slice(&parent, &locals, &mut world);
+	///
+	/// assert!(world[0] == translation(1.0, 2.0, 0.0));
+	/// ```
+
+	pub fn mul_slice(parent: &Matrix4<F>, locals: &[Matrix4<F>], out: &mut [Matrix4<F>]) {
+		assert_eq!(locals.len(), out.len(), "locals and out differ in length");
+		for (local, world) in locals.iter().zip(out.iter_mut()) {
+			*world = local.product(*parent);
 		}
-		m
+	}
+
+	/// Transforms positions stored in an interleaved vertex buffer in place,
+	/// as points with `w = 1` multiplied as row vectors (`p * M`) like
+	/// `Vector4 * Matrix4`. Each of the `count` vertices is `stride` bytes
+	/// long and holds three native-endian `f32`s `offset` bytes into it; the
+	/// other attributes are left untouched. The matrix is assumed affine, so
+	/// there is no perspective divide.
+	///
+	/// # Panics
+	///
+	/// Panics if the last position does not fit in `bytes`.
+	///
+	/// ```
+	/// use math3d::camera::translation;
+	///
+	/// // A four byte color followed by the position.
+	/// let mut bytes = Vec::new();
+	/// for p in [[0.0f32, 0.0, 0.0], [1.0, 2.0, 3.0]] {
+	/// 	bytes.extend([255u8; 4]);
+	/// 	p.iter().for_each(|c| bytes.extend(c.to_ne_bytes()));
+	/// }
+	///
+	/// translation(10.0, 0.0, 0.0).transform_positions_strided(&mut bytes, 16, 4, 2);
+	///
+	/// assert_eq!(f32::from_ne_bytes(bytes[20..24].try_into().unwrap()), 11.0);
+	/// assert_eq!(bytes[16..20], [255; 4]);
+	/// ```
+
+	pub fn transform_positions_strided(&self, bytes: &mut [u8], stride: usize, offset: usize, count: usize) {
+		map_strided_bytes(bytes, stride, offset, count, |p| self.transform_position(p));
+	}
+
+	/// Typed version of `transform_positions_strided` for vertex buffers held
+	/// as `f32`s, with `stride` and `offset` counted in `f32`s.
+	///
+	/// # Panics
+	///
+	/// Panics if the last position does not fit in `data`.
+	///
+	/// ```
+	/// use math3d::camera::scale;
+	///
+	/// // Position and texture coordinates.
+	/// let mut data = [1.0f32, 1.0, 1.0, 0.5, 0.5, -1.0, 0.0, 2.0, 1.0, 1.0];
+	///
+	/// scale(2.0, 2.0, 2.0).transform_positions_strided_f32(&mut data, 5, 0, 2);
+	///
+	/// assert_eq!(data, [2.0, 2.0, 2.0, 0.5, 0.5, -2.0, 0.0, 4.0, 1.0, 1.0]);
+	/// ```
+
+	pub fn transform_positions_strided_f32(&self, data: &mut [f32], stride: usize, offset: usize, count: usize) {
+		map_strided_f32(data, stride, offset, count, |p| self.transform_position(p));
+	}
+
+	fn transform_position(&self, p: Vector3<F>) -> Vector3<F> {
+		(Vector4::new_from_vector3(p, F::one()) * *self).truncate()
+	}
+
+	fn from_array_2d(m: [[F; 4]; 4]) -> Matrix4<F> {
+		Matrix4::from_vectors(
+			Vector4::new(m[0][0], m[0][1], m[0][2], m[0][3]),
+			Vector4::new(m[1][0], m[1][1], m[1][2], m[1][3]),
+			Vector4::new(m[2][0], m[2][1], m[2][2], m[2][3]),
+			Vector4::new(m[3][0], m[3][1], m[3][2], m[3][3]),
+		)
+	}
+
+	fn to_array_2d(self) -> [[F; 4]; 4] {
+		self.m.map(|v| [v[0], v[1], v[2], v[3]])
+	}
+}
+
+impl<F: Float + Send + Sync> Matrix4<F> {
+	/// Parallel version of `mul_slice`, splitting the buffers into chunks
+	/// multiplied on the rayon thread pool.
+	///
+	/// # Panics
+	///
+	/// Panics if `locals` and `out` have different lengths.
+
+	pub fn mul_slice_par(parent: &Matrix4<F>, locals: &[Matrix4<F>], out: &mut [Matrix4<F>]) {
+		assert_eq!(locals.len(), out.len(), "locals and out differ in length");
+		locals
+			.par_chunks(PAR_MATRIX_CHUNK_SIZE)
+			.zip(out.par_chunks_mut(PAR_MATRIX_CHUNK_SIZE))
+			.for_each(|(locals, out)| Matrix4::mul_slice(parent, locals, out));
 	}
 }
 
@@ -902,6 +3376,60 @@ impl<F: Float> std::cmp::PartialEq for Matrix4<F> {
     }
 }
 
+/// The zero matrix; see `identity` for the identity.
+
+impl<F: Float> Default for Matrix4<F> {
+    fn default() -> Matrix4<F> {
+        Matrix4::zero()
+    }
+}
+
+impl<F: Float> num::Zero for Matrix4<F> {
+    fn zero() -> Matrix4<F> {
+        Matrix4::zero()
+    }
+
+    fn is_zero(&self) -> bool {
+        *self == Matrix4::zero()
+    }
+}
+
+impl<F: Float> num::One for Matrix4<F> {
+    fn one() -> Matrix4<F> {
+        Matrix4::identity()
+    }
+}
+
+impl<F: Float> std::ops::Add for Matrix4<F> {
+    type Output = Matrix4<F>;
+
+    fn add(self, rhs: Matrix4<F>) -> Matrix4<F> {
+        Matrix4 {
+            m: [
+                self.m[0] + rhs.m[0],
+                self.m[1] + rhs.m[1],
+                self.m[2] + rhs.m[2],
+                self.m[3] + rhs.m[3],
+            ],
+        }
+    }
+}
+
+impl<F: Float> std::ops::Sub for Matrix4<F> {
+    type Output = Matrix4<F>;
+
+    fn sub(self, rhs: Matrix4<F>) -> Matrix4<F> {
+        Matrix4 {
+            m: [
+                self.m[0] - rhs.m[0],
+                self.m[1] - rhs.m[1],
+                self.m[2] - rhs.m[2],
+                self.m[3] - rhs.m[3],
+            ],
+        }
+    }
+}
+
 impl<F: Float> std::ops::Mul for Matrix4<F> {
     type Output = Matrix4<F>;
 
@@ -914,15 +3442,28 @@ impl<F: Float> std::ops::Mul<F> for Matrix4<F> {
     type Output = Matrix4<F>;
 
     fn mul(self, rhs: F) -> Matrix4<F> {
-        todo!()
+        self.map(|a| a * rhs)
+    }
+}
+
+/// Column-vector product `M * v`, see `Matrix4::product_vector`.
+
+impl<F: Float> std::ops::Mul<Vector4<F>> for Matrix4<F> {
+    type Output = Vector4<F>;
+
+    fn mul(self, rhs: Vector4<F>) -> Vector4<F> {
+        self.product_vector(rhs)
     }
 }
 
+/// Right division by a matrix, `a / b == a * b^-1`, so that `(a / b) * b == a`.
+/// A singular divisor gives non-finite elements; see `checked_div`.
+
 impl<F: Float> std::ops::Div for Matrix4<F> {
     type Output = Matrix4<F>;
 
     fn div(self, rhs: Matrix4<F>) -> Matrix4<F> {
-        todo!()
+        self.product(rhs.inverse_and_det().0)
     }
 }
 
@@ -930,7 +3471,43 @@ impl<F: Float> std::ops::Div<F> for Matrix4<F> {
     type Output = Matrix4<F>;
 
     fn div(self, rhs: F) -> Matrix4<F> {
-        todo!()
+        self.map(|a| a / rhs)
+    }
+}
+
+impl<F: Float> std::ops::AddAssign for Matrix4<F> {
+    fn add_assign(&mut self, other: Matrix4<F>) {
+        *self = *self + other;
+    }
+}
+
+impl<F: Float> std::ops::SubAssign for Matrix4<F> {
+    fn sub_assign(&mut self, other: Matrix4<F>) {
+        *self = *self - other;
+    }
+}
+
+impl<F: Float> std::ops::MulAssign for Matrix4<F> {
+    fn mul_assign(&mut self, other: Matrix4<F>) {
+        *self = *self * other;
+    }
+}
+
+impl<F: Float> std::ops::MulAssign<F> for Matrix4<F> {
+    fn mul_assign(&mut self, other: F) {
+        *self = *self * other;
+    }
+}
+
+impl<F: Float> std::ops::DivAssign for Matrix4<F> {
+    fn div_assign(&mut self, other: Matrix4<F>) {
+        *self = *self / other;
+    }
+}
+
+impl<F: Float> std::ops::DivAssign<F> for Matrix4<F> {
+    fn div_assign(&mut self, other: F) {
+        *self = *self / other;
     }
 }
 
@@ -947,3 +3524,22 @@ impl<F: Float> std::ops::IndexMut<usize> for Matrix4<F> {
         &mut self.m[index]
     }
 }
+
+/// Element at `(row, column)`, the same as `m[row][column]`. `at` and `set`
+/// are the typed forms of this index.
+
+impl<F: Float> std::ops::Index<(usize, usize)> for Matrix4<F> {
+    type Output = F;
+
+    fn index(&self, (row, col): (usize, usize)) -> &F {
+        &self.m[row][col]
+    }
+}
+
+impl<F: Float> std::ops::IndexMut<(usize, usize)> for Matrix4<F> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut F {
+        &mut self.m[row][col]
+    }
+}
+
+impl_scalar_lhs_mul!(Matrix2, Matrix3, Matrix4);
@@ -1,10 +1,15 @@
 use num::Float;
+use crate::batch::{self, PAR_CHUNK_SIZE};
+use crate::geometry::Plane;
+use crate::matrices::Matrix3;
 use crate::quaternion::Quaternion;
+use crate::scalar::FloatExt;
 use crate::vectors::Vector3;
 use crate::vectors::Vector4;
+use rayon::prelude::*;
 use serde_derive::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
 pub struct Point3<F: Float> {
 	xyz: Vector3<F>,
 }
@@ -45,7 +50,7 @@ impl<F :Float> Point3<F> {
 
 	pub fn identity() -> Point3<F> {
 		Point3 {
-			xyz: Vector3::identity(),
+			xyz: Vector3::unit_x(),
 		}
 	}
 
@@ -85,6 +90,81 @@ impl<F :Float> Point3<F> {
 		self.xyz
 	}
 
+	/// Converts the coordinates to another float type.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::points::Point3;
+	///
+	/// let point: Point3<f32> = Point3::<f64>::new(1.0, 2.0, 3.0).cast();
+	///
+	/// assert_eq!(point, Point3::new(1.0f32, 2.0, 3.0));
+	/// ```
+
+	pub fn cast<T: Float>(&self) -> Point3<T> {
+		Point3 {
+			xyz: self.xyz.cast(),
+		}
+	}
+
+	/// Returns the x coordinate.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::points::Point3;
+	///
+	/// let mut point = Point3::new(1.0, 2.0, 3.0);
+	/// point.set_z(point.x() + point.y());
+	///
+	/// assert_eq!(point.z(), 3.0);
+	/// ```
+
+	pub fn x(&self) -> F {
+		self.xyz.x()
+	}
+
+	/// Returns the y coordinate.
+	pub fn y(&self) -> F {
+		self.xyz.y()
+	}
+
+	/// Returns the z coordinate.
+	pub fn z(&self) -> F {
+		self.xyz.z()
+	}
+
+	/// Sets the x coordinate.
+	pub fn set_x(&mut self, x: F) {
+		self.xyz.set_x(x);
+	}
+
+	/// Sets the y coordinate.
+	pub fn set_y(&mut self, y: F) {
+		self.xyz.set_y(y);
+	}
+
+	/// Sets the z coordinate.
+	pub fn set_z(&mut self, z: F) {
+		self.xyz.set_z(z);
+	}
+
+	/// Mutable reference to the x coordinate.
+	pub fn x_mut(&mut self) -> &mut F {
+		self.xyz.x_mut()
+	}
+
+	/// Mutable reference to the y coordinate.
+	pub fn y_mut(&mut self) -> &mut F {
+		self.xyz.y_mut()
+	}
+
+	/// Mutable reference to the z coordinate.
+	pub fn z_mut(&mut self) -> &mut F {
+		self.xyz.z_mut()
+	}
+
 	/// To vector4.
 	///
 	/// # Example
@@ -102,7 +182,7 @@ impl<F :Float> Point3<F> {
 	/// ```
 
 	pub fn to_vector4(&self) -> Vector4<F> {
-		Vector4::new(self.xyz[0], self.xyz[1], self.xyz[2], F::one())
+		self.xyz.extend(F::one())
 	}
 
 	/// Distance to another point.
@@ -126,6 +206,27 @@ impl<F :Float> Point3<F> {
 		(self.xyz - other.xyz).magnitude()
 	}
 
+	/// Whether every coordinate is within `epsilon` of the other point's.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::points::Point3;
+	///
+	/// let point = Point3::new(0.1 + 0.2, 0.0, 1.0);
+	///
+	/// assert!(point.approx_eq(Point3::new(0.3, 0.0, 1.0), 1e-12));
+	/// ```
+
+	pub fn approx_eq(&self, other: Point3<F>, epsilon: F) -> bool {
+		self.xyz.approx_eq(other.xyz, epsilon)
+	}
+
+	/// Coordinate-wise `FloatExt::relative_eq`.
+	pub fn relative_eq(&self, other: Point3<F>, epsilon: F, max_relative: F) -> bool {
+		self.xyz.relative_eq(other.xyz, epsilon, max_relative)
+	}
+
 	/// Rotates the point around the euler angles.
 	///
 	/// # Arguments
@@ -161,6 +262,30 @@ impl<F :Float> Point3<F> {
 	pub fn normalize(&self) -> Point3<F> {
 		Point3::from_vector(self.xyz.normalized())
 	}
+
+	/// Mirror image of the point across a plane.
+	///
+	/// # Arguments
+	///
+	/// * `plane` - The mirror plane.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Plane;
+	/// use math3d::points::Point3;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let plane = Plane::new(Vector3::new(0.0, 1.0, 0.0), -1.0);
+	/// let point = Point3::new(1.0, 3.0, 0.0);
+	///
+	/// assert!(point.mirrored_across(&plane) == Point3::new(1.0, -1.0, 0.0));
+	/// ```
+
+	pub fn mirrored_across(&self, plane: &Plane<F>) -> Point3<F> {
+		let two = F::from(2.0).unwrap();
+		Point3::from_vector(self.xyz - plane.normal() * (two * plane.signed_distance(*self)))
+	}
 }
 
 impl<F: Float> core::fmt::Display for Point3<F> {
@@ -175,6 +300,14 @@ impl<F: Float> core::cmp::PartialEq for Point3<F> {
 	}
 }
 
+/// The origin.
+
+impl<F: Float> Default for Point3<F> {
+	fn default() -> Point3<F> {
+		Point3::from_vector(Vector3::zero())
+	}
+}
+
 impl<F: Float> std::ops::Index<usize> for Point3<F> {
 	type Output = F;
 
@@ -188,3 +321,279 @@ impl<F: Float> std::ops::IndexMut<usize> for Point3<F> {
 		&mut self.xyz[index]
 	}
 }
+
+impl<F: Float> From<[F; 3]> for Point3<F> {
+	fn from(a: [F; 3]) -> Point3<F> {
+		Point3::from_vector(Vector3::from(a))
+	}
+}
+
+impl<F: Float> From<Point3<F>> for [F; 3] {
+	fn from(p: Point3<F>) -> [F; 3] {
+		p.xyz.into()
+	}
+}
+
+impl<F: Float> From<(F, F, F)> for Point3<F> {
+	fn from(a: (F, F, F)) -> Point3<F> {
+		Point3::from([a.0, a.1, a.2])
+	}
+}
+
+impl<F: Float> From<Point3<F>> for (F, F, F) {
+	fn from(p: Point3<F>) -> (F, F, F) {
+		let [x, y, z] = p.into();
+		(x, y, z)
+	}
+}
+
+impl<F: Float> TryFrom<&[F]> for Point3<F> {
+	type Error = std::array::TryFromSliceError;
+
+	/// Fails unless the slice has exactly 3 elements.
+	fn try_from(slice: &[F]) -> Result<Point3<F>, Self::Error> {
+		<[F; 3]>::try_from(slice).map(Point3::from)
+	}
+}
+
+/// Moves every point by `offset` in place, e.g. to rebase a streamed world
+/// chunk onto a new floating origin without copying the buffer.
+///
+/// # Example
+///
+/// ```
+/// use math3d::points::{self, Point3};
+/// use math3d::vectors::Vector3;
+///
+/// let mut cloud = [Point3::new(1000.0, 0.0, 0.0), Point3::new(1001.0, 2.0, 0.0)];
+/// points::translate_in_place(&mut cloud, Vector3::new(-1000.0, 0.0, 0.0));
+///
+/// assert!(cloud[1] == Point3::new(1.0, 2.0, 0.0));
+/// ```
+
+pub fn translate_in_place<F: Float>(points: &mut [Point3<F>], offset: Vector3<F>) {
+	for p in points.iter_mut() {
+		p.xyz += offset;
+	}
+}
+
+/// Parallel version of `translate_in_place`.
+
+pub fn translate_in_place_par<F: Float + Send + Sync>(points: &mut [Point3<F>], offset: Vector3<F>) {
+	points
+		.par_chunks_mut(PAR_CHUNK_SIZE)
+		.for_each(|chunk| translate_in_place(chunk, offset));
+}
+
+/// Scales every point in place uniformly by `factor` about `pivot`, which
+/// stays fixed.
+///
+/// # Example
+///
+/// ```
+/// use math3d::points::{self, Point3};
+///
+/// let mut cloud = [Point3::new(2.0, 1.0, 1.0), Point3::new(1.0, 1.0, 1.0)];
+/// points::scale_about(&mut cloud, Point3::new(1.0, 1.0, 1.0), 3.0);
+///
+/// assert!(cloud == [Point3::new(4.0, 1.0, 1.0), Point3::new(1.0, 1.0, 1.0)]);
+/// ```
+
+pub fn scale_about<F: Float>(points: &mut [Point3<F>], pivot: Point3<F>, factor: F) {
+	for p in points.iter_mut() {
+		p.xyz = pivot.xyz + (p.xyz - pivot.xyz) * factor;
+	}
+}
+
+/// Parallel version of `scale_about`.
+
+pub fn scale_about_par<F: Float + Send + Sync>(points: &mut [Point3<F>], pivot: Point3<F>, factor: F) {
+	points
+		.par_chunks_mut(PAR_CHUNK_SIZE)
+		.for_each(|chunk| scale_about(chunk, pivot, factor));
+}
+
+/// Weighted mean of the points, `Σ wᵢ pᵢ / Σ wᵢ`. Returns `None` if there
+/// are no points or the weights sum to zero.
+///
+/// # Panics
+///
+/// Panics if `points` and `weights` have different lengths.
+///
+/// # Example
+///
+/// ```
+/// use math3d::points::{self, Point3};
+///
+/// let cloud = [Point3::new(0.0, 0.0, 0.0), Point3::new(4.0, 0.0, 0.0)];
+///
+/// assert!(points::weighted_centroid(&cloud, &[3.0, 1.0]) == Some(Point3::new(1.0, 0.0, 0.0)));
+/// ```
+
+pub fn weighted_centroid<F: Float>(points: &[Point3<F>], weights: &[F]) -> Option<Point3<F>> {
+	assert_eq!(points.len(), weights.len(), "points and weights differ in length");
+	batch::weighted_mean(points.iter().zip(weights).map(|(p, w)| (p.xyz, *w))).map(Point3::from_vector)
+}
+
+/// Center of mass and total mass of a set of point masses, or `None` if the
+/// total mass is zero.
+///
+/// # Panics
+///
+/// Panics if `points` and `masses` have different lengths.
+
+pub fn center_of_mass<F: Float>(points: &[Point3<F>], masses: &[F]) -> Option<(Point3<F>, F)> {
+	let center = weighted_centroid(points, masses)?;
+	Some((center, masses.iter().fold(F::zero(), |total, m| total + *m)))
+}
+
+/// Weighted second moment of the points about `origin`,
+/// `Σ wᵢ (pᵢ - o)ᵀ (pᵢ - o)` with the offsets as row vectors. About the
+/// centroid and divided by the total weight it is the covariance matrix used
+/// to fit oriented boxes; for point masses about the center of mass,
+/// `trace(S) I - S` is the inertia tensor.
+///
+/// # Panics
+///
+/// Panics if `points` and `weights` have different lengths.
+///
+/// # Example
+///
+/// ```
+/// use math3d::points::{self, Point3};
+///
+/// let cloud = [Point3::new(-1.0, 0.0, 0.0), Point3::new(1.0, 0.0, 0.0)];
+/// let s = points::second_moment(&cloud, &[1.0, 1.0], Point3::new(0.0, 0.0, 0.0));
+///
+/// assert_eq!(s[0][0], 2.0);
+/// assert_eq!(s[1][1], 0.0);
+/// ```
+
+pub fn second_moment<F: Float>(points: &[Point3<F>], weights: &[F], origin: Point3<F>) -> Matrix3<F> {
+	assert_eq!(points.len(), weights.len(), "points and weights differ in length");
+	let mut m = [[F::zero(); 3]; 3];
+	for (p, w) in points.iter().zip(weights) {
+		let d = p.xyz - origin.xyz;
+		for i in 0..3 {
+			for j in 0..3 {
+				m[i][j] = m[i][j] + *w * d[i] * d[j];
+			}
+		}
+	}
+	Matrix3::from_array_2d(m)
+}
+
+// //////////////////////////////////////////////////////////////////////////////////////
+//
+// Point2
+//
+// //////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct Point2<F: Float> {
+	x: F,
+	y: F,
+}
+
+impl<F: Float> Point2<F> {
+
+	/// Creates a new point.
+	///
+	/// # Arguments
+	///
+	/// * `x` - The x component of the point.
+	/// * `y` - The y component of the point.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::points::Point2;
+	///
+	/// let point = Point2::new(1.0, 2.0);
+	/// ```
+
+	pub fn new(x: F, y: F) -> Point2<F> {
+		Point2 {
+			x,
+			y,
+		}
+	}
+
+	/// Returns the x component.
+	pub fn x(&self) -> F {
+		self.x
+	}
+
+	/// Returns the y component.
+	pub fn y(&self) -> F {
+		self.y
+	}
+
+	/// Distance between two points.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::points::Point2;
+	///
+	/// let point = Point2::new(1.0, 2.0);
+	///
+	/// assert_eq!(point.distance_to(Point2::new(4.0, 6.0)), 5.0);
+	/// ```
+
+	pub fn distance_to(&self, other: Point2<F>) -> F {
+		(self.x - other.x).hypot(self.y - other.y)
+	}
+
+	/// Whether every coordinate is within `epsilon` of the other point's.
+	pub fn approx_eq(&self, other: Point2<F>, epsilon: F) -> bool {
+		self.x.approx_eq(other.x, epsilon) && self.y.approx_eq(other.y, epsilon)
+	}
+
+	/// Coordinate-wise `FloatExt::relative_eq`.
+	pub fn relative_eq(&self, other: Point2<F>, epsilon: F, max_relative: F) -> bool {
+		self.x.relative_eq(other.x, epsilon, max_relative) && self.y.relative_eq(other.y, epsilon, max_relative)
+	}
+}
+
+impl<F: Float> core::fmt::Display for Point2<F> {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		write!(f, "p: [{:.4}, {:.4}]", self.x.to_f64().unwrap(), self.y.to_f64().unwrap())
+	}
+}
+
+impl<F: Float> core::cmp::PartialEq for Point2<F> {
+	fn eq(&self, other: &Point2<F>) -> bool {
+		self.x == other.x && self.y == other.y
+	}
+}
+
+/// The origin.
+
+impl<F: Float> Default for Point2<F> {
+	fn default() -> Point2<F> {
+		Point2::new(F::zero(), F::zero())
+	}
+}
+
+impl<F: Float> std::ops::Index<usize> for Point2<F> {
+	type Output = F;
+
+	fn index(&self, index: usize) -> &F {
+		match index {
+			0 => &self.x,
+			1 => &self.y,
+			_ => panic!("Index out of bounds"),
+		}
+	}
+}
+
+impl<F: Float> std::ops::IndexMut<usize> for Point2<F> {
+	fn index_mut(&mut self, index: usize) -> &mut F {
+		match index {
+			0 => &mut self.x,
+			1 => &mut self.y,
+			_ => panic!("Index out of bounds"),
+		}
+	}
+}
@@ -0,0 +1,298 @@
+//! # Pose
+//!
+//! Position and orientation of a rigid body, with helpers for predicting the
+//! motion of remote objects from sparse updates.
+//!
+//! # Example
+//!
+//! ```
+//! use math3d::pose::Pose;
+//! use math3d::points::Point3;
+//! use math3d::quaternion::Quaternion;
+//! use math3d::vectors::Vector3;
+//!
+//! let pose = Pose::new(Point3::new(0.0, 0.0, 0.0), Quaternion::identity());
+//! let predicted = pose.extrapolate(Vector3::new(1.0, 0.0, 0.0), Vector3::zero(), 0.5);
+//!
+//! println!("{}", predicted);
+//! ```
+
+use num::Float;
+use crate::points::Point3;
+use crate::quaternion::Quaternion;
+use crate::vectors::Vector3;
+
+// //////////////////////////////////////////////////////////////////////////////////////
+//
+// Pose
+//
+// //////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Copy, Clone, Debug)]
+pub struct Pose<F: Float> {
+	position: Point3<F>,
+	orientation: Quaternion<F>,
+}
+
+impl<F: Float> Pose<F> {
+
+	/// Creates a new pose.
+	///
+	/// # Arguments
+	///
+	/// * `position` - Position of the body.
+	/// * `orientation` - Unit quaternion rotating body space to world space.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::pose::Pose;
+	/// use math3d::points::Point3;
+	/// use math3d::quaternion::Quaternion;
+	///
+	/// let pose = Pose::new(Point3::new(1.0, 2.0, 3.0), Quaternion::identity());
+	/// ```
+
+	pub fn new(position: Point3<F>, orientation: Quaternion<F>) -> Pose<F> {
+		Pose {
+			position,
+			orientation,
+		}
+	}
+
+	/// Returns the position.
+	pub fn position(&self) -> Point3<F> {
+		self.position
+	}
+
+	/// Returns the orientation.
+	pub fn orientation(&self) -> Quaternion<F> {
+		self.orientation
+	}
+
+	/// Predicts the pose after `dt` assuming constant velocities.
+	///
+	/// # Arguments
+	///
+	/// * `linear_velocity` - World-space velocity in units per second.
+	/// * `angular_velocity` - World-space angular velocity in radians per
+	///   second; its direction is the rotation axis.
+	/// * `dt` - Time to extrapolate by, in seconds.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::pose::Pose;
+	/// use math3d::points::Point3;
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let pose = Pose::new(Point3::new(0.0, 0.0, 0.0), Quaternion::identity());
+	/// let predicted = pose.extrapolate(Vector3::new(2.0, 0.0, 0.0), Vector3::zero(), 0.5);
+	///
+	/// assert!(predicted.position() == Point3::new(1.0, 0.0, 0.0));
+	/// ```
+
+	pub fn extrapolate(&self, linear_velocity: Vector3<F>, angular_velocity: Vector3<F>, dt: F) -> Pose<F> {
+		let position = Point3::from_vector(self.position.to_vector() + linear_velocity * dt);
+
+		let speed = angular_velocity.magnitude();
+		let orientation = if speed > F::zero() {
+			let delta = Quaternion::from_axis_angle(angular_velocity / speed, (speed * dt).to_degrees());
+			(delta * self.orientation).versor()
+		} else {
+			self.orientation
+		};
+
+		Pose::new(position, orientation)
+	}
+
+	/// Interpolates linearly between the positions and spherically between
+	/// the orientations of two poses. `t` is clamped to `[0, 1]`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::pose::Pose;
+	/// use math3d::points::Point3;
+	/// use math3d::quaternion::Quaternion;
+	///
+	/// let a = Pose::new(Point3::new(0.0, 0.0, 0.0), Quaternion::identity());
+	/// let b = Pose::new(Point3::new(2.0, 0.0, 0.0), Quaternion::identity());
+	///
+	/// assert!(a.interpolate(&b, 0.5).position() == Point3::new(1.0, 0.0, 0.0));
+	/// ```
+
+	pub fn interpolate(&self, other: &Pose<F>, t: F) -> Pose<F> {
+		let t = t.max(F::zero()).min(F::one());
+		let from = self.position.to_vector();
+		let to = other.position.to_vector();
+		Pose::new(
+			Point3::from_vector(from + (to - from) * t),
+			self.orientation.slerp(other.orientation, t),
+		)
+	}
+}
+
+impl<F: Float> core::fmt::Display for Pose<F> {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		write!(f, "p: {}, o: {}", self.position, self.orientation)
+	}
+}
+
+impl<F: Float> core::cmp::PartialEq for Pose<F> {
+	fn eq(&self, other: &Pose<F>) -> bool {
+		self.position == other.position && self.orientation == other.orientation
+	}
+}
+
+// //////////////////////////////////////////////////////////////////////////////////////
+//
+// DeadReckoner
+//
+// //////////////////////////////////////////////////////////////////////////////////////
+
+/// Predicts the pose of a remote body between network updates and smooths
+/// out the corrections when a new update arrives.
+///
+/// Corrections use projective velocity blending: after an update, the
+/// velocity is blended from the one in use to the received one over
+/// `blend_time`, a projection from the last displayed pose with that
+/// velocity is blended towards a projection of the received pose, and once
+/// the blend is over the body follows the received trajectory exactly. This
+/// avoids both the snapping of applying updates directly and the
+/// oscillation of blending positions alone.
+///
+/// # Example
+///
+/// ```
+/// use math3d::pose::{DeadReckoner, Pose};
+/// use math3d::points::Point3;
+/// use math3d::quaternion::Quaternion;
+/// use math3d::vectors::Vector3;
+///
+/// let start = Pose::new(Point3::new(0.0, 0.0, 0.0), Quaternion::identity());
+/// let mut reckoner = DeadReckoner::new(start, Vector3::new(1.0, 0.0, 0.0), Vector3::zero(), 0.2);
+///
+/// reckoner.advance(0.1);
+///
+/// let received = Pose::new(Point3::new(0.2, 0.0, 0.0), Quaternion::identity());
+/// reckoner.receive(received, Vector3::new(1.0, 0.0, 0.0), Vector3::zero());
+///
+/// let displayed = reckoner.advance(0.1);
+/// ```
+
+#[derive(Copy, Clone, Debug)]
+pub struct DeadReckoner<F: Float> {
+	blend_time: F,
+	elapsed: F,
+	start: Pose<F>,
+	start_linear_velocity: Vector3<F>,
+	start_angular_velocity: Vector3<F>,
+	received: Pose<F>,
+	received_linear_velocity: Vector3<F>,
+	received_angular_velocity: Vector3<F>,
+}
+
+impl<F: Float> DeadReckoner<F> {
+
+	/// Creates a dead reckoner starting from a known pose.
+	///
+	/// # Arguments
+	///
+	/// * `pose` - Initial pose.
+	/// * `linear_velocity` - Initial world-space velocity.
+	/// * `angular_velocity` - Initial world-space angular velocity in radians
+	///   per second.
+	/// * `blend_time` - Time over which corrections are blended in, usually
+	///   close to the interval between updates.
+
+	pub fn new(pose: Pose<F>, linear_velocity: Vector3<F>, angular_velocity: Vector3<F>, blend_time: F) -> DeadReckoner<F> {
+		DeadReckoner {
+			blend_time,
+			elapsed: F::zero(),
+			start: pose,
+			start_linear_velocity: linear_velocity,
+			start_angular_velocity: angular_velocity,
+			received: pose,
+			received_linear_velocity: linear_velocity,
+			received_angular_velocity: angular_velocity,
+		}
+	}
+
+	/// Applies an update received from the network. The update is treated as
+	/// valid at the current time; compensate for latency by extrapolating it
+	/// before passing it in.
+	///
+	/// The currently displayed pose and velocities become the start of a new
+	/// blend towards the received state.
+
+	pub fn receive(&mut self, pose: Pose<F>, linear_velocity: Vector3<F>, angular_velocity: Vector3<F>) {
+		self.start = self.pose();
+		self.start_linear_velocity = self.linear_velocity();
+		self.start_angular_velocity = self.angular_velocity();
+		self.received = pose;
+		self.received_linear_velocity = linear_velocity;
+		self.received_angular_velocity = angular_velocity;
+		self.elapsed = F::zero();
+	}
+
+	/// Advances time by `dt` and returns the pose to display.
+
+	pub fn advance(&mut self, dt: F) -> Pose<F> {
+		self.elapsed = self.elapsed + dt;
+		self.pose()
+	}
+
+	/// The pose to display at the current time.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::pose::{DeadReckoner, Pose};
+	/// use math3d::points::Point3;
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let start = Pose::new(Point3::new(0.0, 0.0, 0.0), Quaternion::identity());
+	/// let mut reckoner = DeadReckoner::new(start, Vector3::new(1.0, 0.0, 0.0), Vector3::zero(), 0.2);
+	///
+	/// reckoner.advance(0.5);
+	///
+	/// assert!(reckoner.pose().position() == Point3::new(0.5, 0.0, 0.0));
+	/// ```
+
+	pub fn pose(&self) -> Pose<F> {
+		let blend = self.blend();
+		let projected = self.start.extrapolate(self.linear_velocity(), self.angular_velocity(), self.elapsed);
+		let target = self.received.extrapolate(
+			self.received_linear_velocity,
+			self.received_angular_velocity,
+			self.elapsed,
+		);
+		projected.interpolate(&target, blend)
+	}
+
+	/// The blended linear velocity at the current time.
+
+	pub fn linear_velocity(&self) -> Vector3<F> {
+		let blend = self.blend();
+		self.start_linear_velocity + (self.received_linear_velocity - self.start_linear_velocity) * blend
+	}
+
+	/// The blended angular velocity at the current time.
+
+	pub fn angular_velocity(&self) -> Vector3<F> {
+		let blend = self.blend();
+		self.start_angular_velocity + (self.received_angular_velocity - self.start_angular_velocity) * blend
+	}
+
+	/// Progress of the current correction in `[0, 1]`.
+
+	fn blend(&self) -> F {
+		if self.blend_time <= F::zero() {
+			return F::one();
+		}
+		(self.elapsed / self.blend_time).max(F::zero()).min(F::one())
+	}
+}
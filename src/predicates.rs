@@ -0,0 +1,278 @@
+//! # Predicates
+//!
+//! Adaptive exact geometric predicates after Shewchuk, "Adaptive Precision
+//! Floating-Point Arithmetic and Fast Robust Geometric Predicates".
+//!
+//! Each predicate first evaluates its determinant in plain floating point
+//! together with an error bound, and only falls back to exact expansion
+//! arithmetic when the sign cannot be trusted. The sign of the result is
+//! always correct as long as no intermediate value overflows or underflows;
+//! the magnitude is only an approximation.
+//!
+//! # Example
+//!
+//! ```
+//! use math3d::points::Point2;
+//! use math3d::predicates::orient2d;
+//!
+//! let a = Point2::new(0.0, 0.0);
+//! let b = Point2::new(1.0, 0.0);
+//! let c = Point2::new(0.0, 1.0);
+//!
+//! assert!(orient2d(a, b, c) > 0.0);
+//! ```
+
+use num::Float;
+use crate::points::Point2;
+
+/// Orientation of three points.
+///
+/// Returns a positive value if `a`, `b` and `c` are in counter-clockwise
+/// order, a negative value if they are in clockwise order and zero if they are
+/// collinear. The value approximates twice the signed area of the triangle.
+///
+/// # Example
+///
+/// ```
+/// use math3d::points::Point2;
+/// use math3d::predicates::orient2d;
+///
+/// let a = Point2::new(12.0, 12.0);
+/// let b = Point2::new(24.0, 24.0);
+/// let c = Point2::new(0.5, 0.5);
+///
+/// assert_eq!(orient2d(a, b, c), 0.0);
+/// ```
+
+pub fn orient2d<F: Float>(a: Point2<F>, b: Point2<F>, c: Point2<F>) -> F {
+	let left = (a.x() - c.x()) * (b.y() - c.y());
+	let right = (a.y() - c.y()) * (b.x() - c.x());
+	let det = left - right;
+
+	let bound = ccw_error_bound::<F>() * (left.abs() + right.abs());
+	if det.abs() > bound {
+		return det;
+	}
+
+	let ax = ExactFloat::from_difference(a.x(), c.x());
+	let ay = ExactFloat::from_difference(a.y(), c.y());
+	let bx = ExactFloat::from_difference(b.x(), c.x());
+	let by = ExactFloat::from_difference(b.y(), c.y());
+	ax.product(&by).difference(&ay.product(&bx)).estimate()
+}
+
+/// Position of `d` relative to the circle through `a`, `b` and `c`.
+///
+/// With `a`, `b` and `c` in counter-clockwise order, returns a positive value
+/// if `d` lies inside the circle, a negative value if it lies outside and zero
+/// if the four points are cocircular. The sign is reversed when `a`, `b` and
+/// `c` are in clockwise order.
+///
+/// # Example
+///
+/// ```
+/// use math3d::points::Point2;
+/// use math3d::predicates::incircle;
+///
+/// let a = Point2::new(0.0, 0.0);
+/// let b = Point2::new(1.0, 0.0);
+/// let c = Point2::new(1.0, 1.0);
+///
+/// assert!(incircle(a, b, c, Point2::new(0.5, 0.5)) > 0.0);
+/// assert_eq!(incircle(a, b, c, Point2::new(0.0, 1.0)), 0.0);
+/// ```
+
+pub fn incircle<F: Float>(a: Point2<F>, b: Point2<F>, c: Point2<F>, d: Point2<F>) -> F {
+	let (adx, ady) = (a.x() - d.x(), a.y() - d.y());
+	let (bdx, bdy) = (b.x() - d.x(), b.y() - d.y());
+	let (cdx, cdy) = (c.x() - d.x(), c.y() - d.y());
+
+	let (bdxcdy, cdxbdy) = (bdx * cdy, cdx * bdy);
+	let (cdxady, adxcdy) = (cdx * ady, adx * cdy);
+	let (adxbdy, bdxady) = (adx * bdy, bdx * ady);
+	let alift = adx * adx + ady * ady;
+	let blift = bdx * bdx + bdy * bdy;
+	let clift = cdx * cdx + cdy * cdy;
+
+	let det = alift * (bdxcdy - cdxbdy) + blift * (cdxady - adxcdy) + clift * (adxbdy - bdxady);
+	let permanent = (bdxcdy.abs() + cdxbdy.abs()) * alift
+		+ (cdxady.abs() + adxcdy.abs()) * blift
+		+ (adxbdy.abs() + bdxady.abs()) * clift;
+	if det.abs() > incircle_error_bound::<F>() * permanent {
+		return det;
+	}
+
+	let adx = ExactFloat::from_difference(a.x(), d.x());
+	let ady = ExactFloat::from_difference(a.y(), d.y());
+	let bdx = ExactFloat::from_difference(b.x(), d.x());
+	let bdy = ExactFloat::from_difference(b.y(), d.y());
+	let cdx = ExactFloat::from_difference(c.x(), d.x());
+	let cdy = ExactFloat::from_difference(c.y(), d.y());
+
+	let alift = adx.product(&adx).sum(&ady.product(&ady));
+	let blift = bdx.product(&bdx).sum(&bdy.product(&bdy));
+	let clift = cdx.product(&cdx).sum(&cdy.product(&cdy));
+
+	let bc = bdx.product(&cdy).difference(&cdx.product(&bdy));
+	let ca = cdx.product(&ady).difference(&adx.product(&cdy));
+	let ab = adx.product(&bdy).difference(&bdx.product(&ady));
+
+	alift
+		.product(&bc)
+		.sum(&blift.product(&ca))
+		.sum(&clift.product(&ab))
+		.estimate()
+}
+
+/// Half a unit in the last place of one, the relative rounding error of a
+/// single floating point operation.
+
+fn half_epsilon<F: Float>() -> F {
+	F::epsilon() / F::from(2.0).unwrap()
+}
+
+/// Relative error bound of the floating point evaluation in `orient2d`.
+
+fn ccw_error_bound<F: Float>() -> F {
+	let e = half_epsilon::<F>();
+	(F::from(3.0).unwrap() + F::from(16.0).unwrap() * e) * e
+}
+
+/// Relative error bound of the floating point evaluation in `incircle`.
+
+fn incircle_error_bound<F: Float>() -> F {
+	let e = half_epsilon::<F>();
+	(F::from(10.0).unwrap() + F::from(96.0).unwrap() * e) * e
+}
+
+/// `2^ceil(p / 2) + 1` for a `p`-bit significand, used to split a float into
+/// two halves whose products are exact.
+
+fn splitter<F: Float>() -> F {
+	let bits = (-F::epsilon().log2()).round() + F::one();
+	let half = (bits / F::from(2.0).unwrap()).ceil();
+	F::from(2.0).unwrap().powf(half) + F::one()
+}
+
+/// Sum of two floats as a rounded value and its exact rounding error.
+
+fn two_sum<F: Float>(a: F, b: F) -> (F, F) {
+	let x = a + b;
+	let b_virtual = x - a;
+	let a_virtual = x - b_virtual;
+	(x, (a - a_virtual) + (b - b_virtual))
+}
+
+/// Product of two floats as a rounded value and its exact rounding error.
+
+fn two_product<F: Float>(a: F, b: F) -> (F, F) {
+	let x = a * b;
+	let (a_hi, a_lo) = split(a);
+	let (b_hi, b_lo) = split(b);
+	let err = x - a_hi * b_hi - a_lo * b_hi - a_hi * b_lo;
+	(x, a_lo * b_lo - err)
+}
+
+/// Splits a float into a high and a low half that each fit in half of the
+/// significand.
+
+fn split<F: Float>(a: F) -> (F, F) {
+	let c = splitter::<F>() * a;
+	let hi = c - (c - a);
+	(hi, a - hi)
+}
+
+/// Arbitrary precision float represented as a nonoverlapping expansion: a sum
+/// of floats ordered by increasing magnitude, with zero components removed.
+
+struct ExactFloat<F: Float> {
+	components: Vec<F>,
+}
+
+impl<F: Float> ExactFloat<F> {
+
+	/// Exact difference of two floats.
+
+	fn from_difference(a: F, b: F) -> ExactFloat<F> {
+		let (x, y) = two_sum(a, -b);
+		ExactFloat::from_components(vec![y, x])
+	}
+
+	fn from_components(mut components: Vec<F>) -> ExactFloat<F> {
+		components.retain(|c| *c != F::zero());
+		ExactFloat {
+			components,
+		}
+	}
+
+	/// Exact sum with a single float.
+
+	fn grow(&self, b: F) -> ExactFloat<F> {
+		let mut components = Vec::with_capacity(self.components.len() + 1);
+		let mut q = b;
+		for &e in &self.components {
+			let (sum, error) = two_sum(q, e);
+			components.push(error);
+			q = sum;
+		}
+		components.push(q);
+		ExactFloat::from_components(components)
+	}
+
+	/// Exact sum of two expansions.
+
+	fn sum(&self, other: &ExactFloat<F>) -> ExactFloat<F> {
+		other
+			.components
+			.iter()
+			.fold(ExactFloat::from_components(self.components.clone()), |acc, &c| acc.grow(c))
+	}
+
+	/// Exact difference of two expansions.
+
+	fn difference(&self, other: &ExactFloat<F>) -> ExactFloat<F> {
+		self.sum(&ExactFloat {
+			components: other.components.iter().map(|c| -*c).collect(),
+		})
+	}
+
+	/// Exact product with a single float.
+
+	fn scale(&self, b: F) -> ExactFloat<F> {
+		let mut components = Vec::with_capacity(2 * self.components.len());
+		let mut iter = self.components.iter();
+		let mut q = match iter.next() {
+			Some(&e) => {
+				let (product, error) = two_product(e, b);
+				components.push(error);
+				product
+			}
+			None => return ExactFloat::from_components(components),
+		};
+		for &e in iter {
+			let (product, product_error) = two_product(e, b);
+			let (sum, error) = two_sum(q, product_error);
+			components.push(error);
+			let (sum, error) = two_sum(product, sum);
+			components.push(error);
+			q = sum;
+		}
+		components.push(q);
+		ExactFloat::from_components(components)
+	}
+
+	/// Exact product of two expansions.
+
+	fn product(&self, other: &ExactFloat<F>) -> ExactFloat<F> {
+		other
+			.components
+			.iter()
+			.fold(ExactFloat::from_components(Vec::new()), |acc, &c| acc.sum(&self.scale(c)))
+	}
+
+	/// The largest component, which has the sign of the whole expansion.
+
+	fn estimate(&self) -> F {
+		self.components.last().copied().unwrap_or_else(F::zero)
+	}
+}
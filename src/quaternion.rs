@@ -17,10 +17,15 @@
 //! ```
 
 use num::Float;
+use crate::batch::PAR_CHUNK_SIZE;
 
-use crate::vectors::Vector3;
+use crate::scalar::{cast, impl_scalar_lhs_mul, FloatExt};
+use crate::vectors::{Vector3, Vector4};
 use crate::matrices::Matrix3;
 use crate::matrices::Matrix4;
+use rayon::prelude::*;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_derive::{Deserialize, Serialize};
 
 /// Structure representing a quaternion.
 ///
@@ -33,6 +38,7 @@ use crate::matrices::Matrix4;
 /// ```
 
 #[derive(Debug, Copy, Clone)]
+#[repr(C)]
 pub struct Quaternion<F: Float> {
     /// Real part of the quaternion.
     w: F,
@@ -137,6 +143,25 @@ impl<F: Float> Quaternion<F> {
 		(self.w, self.v[0], self.v[1], self.v[2])
     }
 
+    /// Converts the components to another float type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use math3d::quaternion::Quaternion;
+    ///
+    /// let q: Quaternion<f32> = Quaternion::<f64>::new(1.0, [2.0, 3.0, 4.0]).cast();
+    ///
+    /// assert_eq!(q.decompose(), (1.0, 2.0, 3.0, 4.0));
+    /// ```
+
+    pub fn cast<T: Float>(&self) -> Quaternion<T> {
+        Quaternion {
+            w: cast(self.w),
+            v: self.v.cast(),
+        }
+    }
+
     /// Create an identity quaternion.
     ///
     /// # Examples
@@ -183,7 +208,40 @@ impl<F: Float> Quaternion<F> {
         }
     }
 
-    /// From the given euler angles, create a quaternion.
+    /// Axis and angle of the rotation, the inverse of `from_axis_angle`.
+    ///
+    /// Returns a unit axis and an angle in degrees in `[0, 180]`; `q` and
+    /// `-q` give the same result. The identity rotation has no axis and
+    /// returns the x axis with a zero angle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use math3d::quaternion::Quaternion;
+    /// use math3d::vectors::Vector3;
+    ///
+    /// let q = Quaternion::<f64>::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 90.0);
+    /// let (axis, angle) = q.to_axis_angle();
+    ///
+    /// assert!((angle - 90.0).abs() < 1e-12);
+    /// assert!(axis == Vector3::new(0.0, 0.0, 1.0));
+    /// ```
+
+    pub fn to_axis_angle(&self) -> (Vector3<F>, F) {
+        let q = self.versor();
+        let (w, v) = if q.w < F::zero() { (-q.w, -q.v) } else { (q.w, q.v) };
+        let angle = (F::from(2.0).unwrap() * w.clamped_acos()).to_degrees();
+        let s = v.magnitude();
+        if s == F::zero() {
+            return (Vector3::new(F::one(), F::zero(), F::zero()), F::zero());
+        }
+        (v / s, angle)
+    }
+
+    /// From the given euler angles in degrees, create a quaternion.
+    ///
+    /// The angles are intrinsic rotations about x, then the new y, then the
+    /// new z; see `from_euler_angles_rad`.
     ///
     /// # Arguments
     ///
@@ -200,28 +258,85 @@ impl<F: Float> Quaternion<F> {
     /// ```
 
     pub fn from_euler_angles(x: F, y: F, z: F) -> Quaternion<F> {
-        let half_x = x.to_radians() / F::from(2.0).unwrap();
-        let half_y = y.to_radians() / F::from(2.0).unwrap();
-        let half_z = z.to_radians() / F::from(2.0).unwrap();
+        Quaternion::from_euler_angles_rad(x.to_radians(), y.to_radians(), z.to_radians())
+    }
 
-        let sin_x = half_x.sin();
-        let sin_y = half_y.sin();
-        let sin_z = half_z.sin();
+    /// From the given euler angles in radians, create a quaternion.
+    ///
+    /// The angles are intrinsic XYZ rotations: about x, then about the
+    /// rotated y, then about the twice rotated z. This is the same as the
+    /// product of the single axis rotations `qx * qy * qz`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use math3d::quaternion::Quaternion;
+    /// use math3d::vectors::Vector3;
+    ///
+    /// let q = Quaternion::<f64>::from_euler_angles_rad(0.0, 0.0, std::f64::consts::FRAC_PI_2);
+    ///
+    /// assert!((q.rotate_vector(Vector3::new(1.0, 0.0, 0.0)) - Vector3::new(0.0, 1.0, 0.0)).magnitude() < 1e-12);
+    /// ```
 
-        let cos_x = half_x.cos();
-        let cos_y = half_y.cos();
-        let cos_z = half_z.cos();
+    pub fn from_euler_angles_rad(x: F, y: F, z: F) -> Quaternion<F> {
+        let two = F::from(2.0).unwrap();
+        let (sin_x, cos_x) = (x / two).sin_cos();
+        let (sin_y, cos_y) = (y / two).sin_cos();
+        let (sin_z, cos_z) = (z / two).sin_cos();
 
         Quaternion {
-            w: cos_x * cos_y * cos_z + sin_x * sin_y * sin_z,
-			v: Vector3::new(
-				cos_x * sin_y * cos_z - sin_x * cos_y * sin_z,
-				cos_x * cos_y * sin_z - sin_x * sin_y * cos_z,
-				cos_x * sin_y * sin_z + sin_x * cos_y * cos_z,
-			),
+            w: cos_x * cos_y * cos_z - sin_x * sin_y * sin_z,
+            v: Vector3::new(
+                sin_x * cos_y * cos_z + cos_x * sin_y * sin_z,
+                cos_x * sin_y * cos_z - sin_x * cos_y * sin_z,
+                cos_x * cos_y * sin_z + sin_x * sin_y * cos_z,
+            ),
         }
     }
 
+    /// Intrinsic XYZ euler angles of the rotation in degrees, the inverse of
+    /// `from_euler_angles`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use math3d::quaternion::Quaternion;
+    ///
+    /// let (x, y, z) = Quaternion::<f64>::from_euler_angles(30.0, -45.0, 120.0).to_euler_angles();
+    ///
+    /// assert!((x - 30.0).abs() < 1e-9 && (y + 45.0).abs() < 1e-9 && (z - 120.0).abs() < 1e-9);
+    /// ```
+
+    pub fn to_euler_angles(&self) -> (F, F, F) {
+        let (x, y, z) = self.to_euler_angles_rad();
+        (x.to_degrees(), y.to_degrees(), z.to_degrees())
+    }
+
+    /// Intrinsic XYZ euler angles of the rotation in radians, the inverse of
+    /// `from_euler_angles_rad`.
+    ///
+    /// The y angle is in `[-π/2, π/2]` and the x and z angles in `[-π, π]`.
+    /// At gimbal lock, when y is ±π/2, only the sum or difference of x and z
+    /// is determined; z is then reported as zero.
+
+    pub fn to_euler_angles_rad(&self) -> (F, F, F) {
+        let q = self.versor();
+        let (w, x, y, z) = (q.w, q.v[0], q.v[1], q.v[2]);
+        let (one, two) = (F::one(), F::from(2.0).unwrap());
+
+        let sin_y = two * (x * z + w * y);
+        if sin_y.abs() >= one - F::from(1e-12).unwrap() {
+            let pitch = F::from(std::f64::consts::FRAC_PI_2).unwrap() * sin_y.signum();
+            let roll = (two * (y * z + w * x)).atan2(one - two * (x * x + z * z));
+            return (roll, pitch, F::zero());
+        }
+
+        let roll = (-two * (y * z - w * x)).atan2(one - two * (x * x + y * y));
+        let pitch = sin_y.asin();
+        let yaw = (-two * (x * y - w * z)).atan2(one - two * (y * y + z * z));
+        (roll, pitch, yaw)
+    }
+
     /// The sum of two quaternions:
     ///
     /// $$ q = q1 + q2 $$
@@ -420,6 +535,35 @@ impl<F: Float> Quaternion<F> {
         }
     }
 
+    /// Cheap renormalization for quaternions that have drifted slightly off
+    /// unit length, such as orientations updated by an integrator every step.
+    /// Uses the first-order correction
+    ///
+    /// $$q' = q \frac{3 - |q|^2}{2}$$
+    ///
+    /// which needs no square root and cuts a norm error of `e` down to about
+    /// `1.5 e²`. Apply it every step to keep the drift from growing; far from
+    /// unit length use `versor` instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use math3d::quaternion::Quaternion;
+    ///
+    /// let q = Quaternion::<f64>::new(1.001, [0.0, 0.0, 0.0]);
+    ///
+    /// assert!((q.renormalize_fast().norm() - 1.0).abs() < 2e-6);
+    /// ```
+
+    pub fn renormalize_fast(&self) -> Quaternion<F> {
+        let n2 = self.w * self.w + self.v.dot(self.v);
+        let k = (F::from(3.0).unwrap() - n2) / F::from(2.0).unwrap();
+        Quaternion {
+            w: self.w * k,
+            v: self.v * k,
+        }
+    }
+
     /// Quarternion inverse is defined as:
     ///
     /// $$q_1^* = \frac{q_1}{|q_1|} + \frac{-i}{|q_1|} $$
@@ -505,6 +649,228 @@ impl<F: Float> Quaternion<F> {
         self.exp() * self.pow(n - F::from(1.0).unwrap())
     }
 
+	/// Dot product of two quaternions as 4D vectors.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::quaternion::Quaternion;
+	///
+	/// let q1 = Quaternion::new(1.0, [2.0, 3.0, 4.0]);
+	///
+	/// assert_eq!(q1.dot(q1), 30.0);
+	/// ```
+
+	pub fn dot(&self, other: Quaternion<F>) -> F {
+		self.w * other.w + self.v.dot(other.v)
+	}
+
+	/// Whether all four components are finite.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::quaternion::Quaternion;
+	///
+	/// assert!(Quaternion::<f64>::identity().is_finite());
+	/// assert!(!Quaternion::new(f64::INFINITY, [0.0, 0.0, 0.0]).is_finite());
+	/// ```
+
+	pub fn is_finite(&self) -> bool {
+		self.w.is_finite() && self.v.is_finite()
+	}
+
+	/// Whether any component is NaN.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::quaternion::Quaternion;
+	///
+	/// assert!(Quaternion::new(1.0, [0.0, f64::NAN, 0.0]).is_nan());
+	/// ```
+
+	pub fn is_nan(&self) -> bool {
+		self.w.is_nan() || self.v.is_nan()
+	}
+
+	/// Whether the norm is within `epsilon` of one, as required of rotations.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::quaternion::Quaternion;
+	///
+	/// assert!(Quaternion::<f64>::identity().is_normalized(1e-12));
+	/// assert!(!Quaternion::new(1.0, [1.0, 0.0, 0.0]).is_normalized(1e-6));
+	/// ```
+
+	pub fn is_normalized(&self, epsilon: F) -> bool {
+		(self.norm() - F::one()).abs() <= epsilon
+	}
+
+	/// Whether every component is within `epsilon` of the other quaternion's.
+	///
+	/// This compares components, not rotations: `q` and `-q` are the same
+	/// rotation but not approximately equal. Compare `q.dot(other).abs()` to
+	/// one to test for the same rotation.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let q1 = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 90.0);
+	/// let q2 = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 45.0);
+	///
+	/// assert!((q2 * q2).approx_eq(q1, 1e-12));
+	/// ```
+
+	pub fn approx_eq(&self, other: Quaternion<F>, epsilon: F) -> bool {
+		self.w.approx_eq(other.w, epsilon) && self.v.approx_eq(other.v, epsilon)
+	}
+
+	/// Component-wise `FloatExt::relative_eq`.
+
+	pub fn relative_eq(&self, other: Quaternion<F>, epsilon: F, max_relative: F) -> bool {
+		self.w.relative_eq(other.w, epsilon, max_relative) && self.v.relative_eq(other.v, epsilon, max_relative)
+	}
+
+	/// Whether the two quaternions describe the same rotation to within
+	/// `epsilon` per component. `q` and `-q` rotate vectors the same way, so
+	/// either sign matches; use this rather than `==` or `approx_eq` to
+	/// compare or deduplicate rotations.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let q = Quaternion::<f64>::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 90.0);
+	/// let r = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, -1.0), 270.0);
+	///
+	/// assert!(!q.approx_eq(r, 1e-12));
+	/// assert!(q.represents_same_rotation(r, 1e-12));
+	/// ```
+
+	pub fn represents_same_rotation(&self, other: Quaternion<F>, epsilon: F) -> bool {
+		self.approx_eq(other, epsilon) || self.approx_eq(-other, epsilon)
+	}
+
+	/// Normalized linear interpolation between two unit quaternions along the
+	/// shorter arc. `t` is clamped to `[0, 1]`; see `lerp_unclamped`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let q1 = Quaternion::identity();
+	/// let q2 = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 90.0);
+	///
+	/// assert!(q1.lerp(q2, 2.0) == q1.lerp(q2, 1.0));
+	/// ```
+
+	pub fn lerp(&self, other: Quaternion<F>, t: F) -> Quaternion<F> {
+		self.lerp_unclamped(other, t.max(F::zero()).min(F::one()))
+	}
+
+	/// Normalized linear interpolation between two unit quaternions along the
+	/// shorter arc, accepting any `t`.
+	///
+	/// The components are interpolated linearly and the result is normalized,
+	/// so the rotation angle is not linear in `t`. Inside `[0, 1]` the error
+	/// is small for nearby rotations, but outside that range it grows quickly:
+	/// as `t` goes to infinity the result converges to a fixed rotation
+	/// instead of spinning further. Use `slerp_unclamped` to extrapolate.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let q1 = Quaternion::identity();
+	/// let q2 = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 10.0);
+	///
+	/// let q3 = q1.lerp_unclamped(q2, 1.5);
+	/// ```
+
+	pub fn lerp_unclamped(&self, other: Quaternion<F>, t: F) -> Quaternion<F> {
+		let other = if self.dot(other) < F::zero() { -other } else { other };
+		(*self * (F::one() - t) + other * t).versor()
+	}
+
+	/// Spherical linear interpolation between two unit quaternions along the
+	/// shorter arc. `t` is clamped to `[0, 1]`; see `slerp_unclamped`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let q1 = Quaternion::identity();
+	/// let q2 = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 90.0);
+	///
+	/// let q3 = q1.slerp(q2, 0.5);
+	/// ```
+
+	pub fn slerp(&self, other: Quaternion<F>, t: F) -> Quaternion<F> {
+		self.slerp_unclamped(other, t.max(F::zero()).min(F::one()))
+	}
+
+	/// Spherical linear interpolation between two unit quaternions along the
+	/// shorter arc, accepting any `t`.
+	///
+	/// The rotation angle is linear in `t`, so values outside `[0, 1]`
+	/// extrapolate at the constant angular velocity that takes `self` to
+	/// `other`; `t = 2` applies the rotation from `self` to `other` twice. This
+	/// is what client-side prediction needs beyond the last received pose.
+	///
+	/// Extrapolation amplifies errors: an angular error `e` in either input
+	/// becomes roughly `|t| * e` in the result, and any change in angular
+	/// velocity since `other` is not accounted for, so the prediction error
+	/// grows linearly with `t - 1`. Keep the extrapolation horizon short and
+	/// correct towards received poses as they arrive.
+	///
+	/// Falls back to `lerp_unclamped` when the rotations are nearly identical.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let q1 = Quaternion::identity();
+	/// let q2 = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 30.0);
+	///
+	/// // Rotates by 60 degrees about z.
+	/// let q3 = q1.slerp_unclamped(q2, 2.0);
+	/// ```
+
+	pub fn slerp_unclamped(&self, other: Quaternion<F>, t: F) -> Quaternion<F> {
+		let mut cos = self.dot(other);
+		let other = if cos < F::zero() {
+			cos = -cos;
+			-other
+		} else {
+			other
+		};
+		if cos > F::one() - F::from(1e-6).unwrap() {
+			return self.lerp_unclamped(other, t);
+		}
+
+		let theta = cos.clamped_acos();
+		let sin = theta.sin();
+		let a = ((F::one() - t) * theta).sin() / sin;
+		let b = (t * theta).sin() / sin;
+		(*self * a + other * b).versor()
+	}
+
 	/// Rotating a vector by a quaternion is defined as:
 	///
 	/// $$v_1 = q_1 \cdot v_1 \cdot q_1^* $$
@@ -529,7 +895,144 @@ impl<F: Float> Quaternion<F> {
 		(*self * p_in * self.conjugate()).v
 	}
 
-	/// Quaternion rotation to Matrix3
+	/// Conjugation action of this rotation on another quaternion:
+	///
+	/// $$q_2 = q_1 \cdot q_2 \cdot q_1^{-1} $$
+	///
+	/// If `other` is a rotation expressed in some frame and this quaternion
+	/// maps that frame to another one, the result is the same rotation
+	/// expressed in the other frame: its axis is rotated by this quaternion
+	/// and its angle is unchanged. Compare with the plain product
+	/// `self * other`, which applies `other` first and then `self`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let to_world = Quaternion::<f64>::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 90.0);
+	/// let local = Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), 30.0);
+	///
+	/// let world = to_world.rotate_quaternion(local);
+	/// let expected = Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 30.0);
+	///
+	/// assert!(world.approx_eq(expected, 1e-12));
+	/// ```
+
+	pub fn rotate_quaternion(&self, other: Quaternion<F>) -> Quaternion<F> {
+		*self * other * self.inverse()
+	}
+
+	/// Rotation that applies this rotation first and `other` second, both
+	/// about the same fixed axes. This is the product `other * self`: in a
+	/// product the right-hand rotation is applied first.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let yaw = Quaternion::<f64>::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 90.0);
+	/// let pitch = Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), 90.0);
+	/// let v = Vector3::new(0.0, 0.0, 1.0);
+	///
+	/// let expected = pitch.rotate_vector(yaw.rotate_vector(v));
+	///
+	/// assert!(yaw.then(pitch).rotate_vector(v).approx_eq(expected, 1e-12));
+	/// assert!(yaw.then(pitch) == pitch * yaw);
+	/// ```
+
+	pub fn then(&self, other: Quaternion<F>) -> Quaternion<F> {
+		other * *self
+	}
+
+	/// Mirror image of the rotation across the plane through the origin with
+	/// the given unit normal. The vector part of a rotation is an axial vector,
+	/// so it is reflected with the opposite sign to an ordinary vector:
+	///
+	/// $$q' = (w, 2 (\vec{v} \cdot \hat{n}) \hat{n} - \vec{v})$$
+	///
+	/// Rotating a mirrored vector by the mirrored quaternion gives the mirror
+	/// image of the rotated vector.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let q1 = Quaternion::<f64>::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 90.0);
+	/// let q2 = q1.mirrored(Vector3::new(1.0, 0.0, 0.0));
+	///
+	/// // A counter-clockwise turn about z mirrors to a clockwise one.
+	/// assert_eq!(q2.vector().z(), -q1.vector().z());
+	/// ```
+
+	pub fn mirrored(&self, plane_normal: Vector3<F>) -> Quaternion<F> {
+		Quaternion {
+			w: self.w,
+			v: -self.v.mirrored(plane_normal),
+		}
+	}
+
+	/// Unit quaternion from a rotation vector, whose direction is the rotation
+	/// axis and whose length is the angle in radians. This is the exponential
+	/// map of the rotation group:
+	///
+	/// $$q = (\cos\frac{|\vec{r}|}{2}, \sin\frac{|\vec{r}|}{2} \hat{r})$$
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let q = Quaternion::from_rotation_vector(Vector3::new(0.0, 0.0, std::f64::consts::PI));
+	///
+	/// assert!(q.real().abs() < 1e-12);
+	/// ```
+
+	pub fn from_rotation_vector(r: Vector3<F>) -> Quaternion<F> {
+		let angle = r.magnitude();
+		if angle == F::zero() {
+			return Quaternion::identity();
+		}
+		let half_angle = angle / F::from(2.0).unwrap();
+		Quaternion {
+			w: half_angle.cos(),
+			v: r * (half_angle.sin() / angle),
+		}
+	}
+
+	/// Rotation vector of a unit quaternion, the inverse of
+	/// `from_rotation_vector`. `q` and `-q` give the same vector, the one for
+	/// the shorter rotation with an angle in `[0, π]`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let q = Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 90.0);
+	/// let r = q.to_rotation_vector();
+	///
+	/// assert!((r.y() - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+	/// ```
+
+	pub fn to_rotation_vector(&self) -> Vector3<F> {
+		let (w, v) = if self.w < F::zero() { (-self.w, -self.v) } else { (self.w, self.v) };
+		let s = v.magnitude();
+		if s == F::zero() {
+			return Vector3::zero();
+		}
+		v * (F::from(2.0).unwrap() * s.atan2(w) / s)
+	}
+
+	/// Quaternion rotation to Matrix3 for row vectors, the convention of
+	/// `Vector3 * Matrix3`. Same as `rotation_matrix_row_major`.
 	///
 	/// (2w^2 − 1 + 2x^2) (2xy + 2wz) (2xz − 2wy)
 	/// (2xy − 2wz) (2w^2 − 1 + 2y^2) (2yz + 2wx)
@@ -539,13 +1042,65 @@ impl<F: Float> Quaternion<F> {
 	///
 	/// ```
 	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
 	///
-	/// let q1 = Quaternion::from_axis_angle([1.0, 0.0, 0.0], 90.0);
+	/// let q1 = Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), 90.0);
+	/// let v1 = Vector3::new(0.0, 1.0, 0.0);
 	///
-	/// let m1 = q1.to_matrix();
+	/// let v2 = v1 * q1.rotation_matrix();
 	/// ```
 
 	pub fn rotation_matrix(&self) -> Matrix3<F> {
+		self.rotation_matrix_row_major()
+	}
+
+	/// Homogeneous 4x4 rotation matrix for row vectors, `rotation_matrix`
+	/// embedded in the upper left block with no translation, ready to be
+	/// composed with other `Matrix4` transforms.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::{Vector3, Vector4};
+	///
+	/// let q1 = Quaternion::<f64>::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 90.0);
+	/// let v1 = Vector4::new(1.0, 0.0, 0.0, 1.0) * q1.rotation_matrix4();
+	///
+	/// assert!((v1 - Vector4::new(0.0, 1.0, 0.0, 1.0)).magnitude() < 1e-12);
+	/// ```
+
+	pub fn rotation_matrix4(&self) -> Matrix4<F> {
+		let (zero, one) = (F::zero(), F::one());
+		let r = self.rotation_matrix();
+		Matrix4::from_vectors(
+			Vector4::new(r[0][0], r[0][1], r[0][2], zero),
+			Vector4::new(r[1][0], r[1][1], r[1][2], zero),
+			Vector4::new(r[2][0], r[2][1], r[2][2], zero),
+			Vector4::new(zero, zero, zero, one),
+		)
+	}
+
+	/// Rotation matrix for row vectors multiplied from the left, `v * M`,
+	/// as done by `Vector3 * Matrix3`. For any vector `v`,
+	/// `v * q.rotation_matrix_row_major()` equals `q.rotate_vector(v)`.
+	///
+	/// This is the transpose of `rotation_matrix_col_major`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let q1 = Quaternion::<f64>::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 90.0);
+	/// let m1 = q1.rotation_matrix_row_major();
+	///
+	/// // The x axis is rotated onto the y axis.
+	/// assert!((m1[0][1] - 1.0).abs() < 1e-12);
+	/// ```
+
+	pub fn rotation_matrix_row_major(&self) -> Matrix3<F> {
 		let two = F::from(2.0).unwrap();
 		let mut m = Matrix3::identity();
 		m[0][0] = two * self.w * self.w - F::one() + two * self.v[0] * self.v[0];
@@ -559,6 +1114,271 @@ impl<F: Float> Quaternion<F> {
 		m[2][2] = two * self.w * self.w - F::one() + two * self.v[2] * self.v[2];
 		m
 	}
+
+	/// Rotation matrix for column vectors multiplied from the right, `M * v`,
+	/// the convention of most mathematical texts and of `Matrix4::product_vector`.
+	///
+	/// This is the transpose of `rotation_matrix_row_major`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let q1 = Quaternion::<f64>::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 90.0);
+	/// let m1 = q1.rotation_matrix_col_major();
+	///
+	/// // The x axis is rotated onto the y axis.
+	/// assert!((m1[1][0] - 1.0).abs() < 1e-12);
+	/// ```
+
+	pub fn rotation_matrix_col_major(&self) -> Matrix3<F> {
+		self.rotation_matrix_row_major().transpose()
+	}
+
+	/// Rotation best aligning two pairs of directions, the TRIAD solution to
+	/// Wahba's problem for two observations: the returned `q` rotates `a.0`
+	/// exactly onto the direction of `b.0`, and `a.1` as close as possible to
+	/// `b.1` within the plane they span with the first direction.
+	///
+	/// Pass the more reliable observation first. The vectors do not need to
+	/// be unit length. Returns `None` if either pair is parallel or contains a
+	/// zero vector.
+	///
+	/// # Arguments
+	///
+	/// * `a` - Directions in the source frame, e.g. reference directions.
+	/// * `b` - The same directions observed in the target frame.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let x = Vector3::new(1.0, 0.0, 0.0);
+	/// let y = Vector3::new(0.0, 1.0, 0.0);
+	/// let z = Vector3::new(0.0, 0.0, 1.0);
+	///
+	/// let q = Quaternion::solve_alignment((x, y), (y, -x)).unwrap();
+	///
+	/// assert!((q.rotate_vector(z) - z).magnitude() < 1e-12);
+	/// ```
+
+	pub fn solve_alignment(a: (Vector3<F>, Vector3<F>), b: (Vector3<F>, Vector3<F>)) -> Option<Quaternion<F>> {
+		let (r1, r2, r3) = triad(a.0, a.1)?;
+		let (s1, s2, s3) = triad(b.0, b.1)?;
+
+		let mut m = Matrix3::from_vectors(Vector3::zero(), Vector3::zero(), Vector3::zero());
+		for i in 0..3 {
+			for j in 0..3 {
+				m[i][j] = r1[i] * s1[j] + r2[i] * s2[j] + r3[i] * s3[j];
+			}
+		}
+		Some(Quaternion::from_rotation_matrix(m))
+	}
+
+	/// Tilt-compensated orientation of a resting sensor from one
+	/// accelerometer and one magnetometer reading, an electronic compass.
+	///
+	/// The returned `q` rotates sensor-frame vectors into a world frame with
+	/// y up, magnetic north along -z and east along +x. At rest the
+	/// accelerometer measures the reaction to gravity, which points up, so it
+	/// fixes the tilt exactly; the magnetometer only sets the heading, and
+	/// its dip below the horizon is ignored. This is `solve_alignment` of the
+	/// two readings onto up and north.
+	///
+	/// Returns `None` if either reading is zero or they are parallel, as at
+	/// the magnetic poles.
+	///
+	/// # Arguments
+	///
+	/// * `accel` - Accelerometer reading in the sensor frame, in any unit.
+	/// * `mag` - Magnetometer reading in the sensor frame, in any unit.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// // Sensor lying flat and facing east: north is to its left, along -x,
+	/// // and the field dips 60 degrees below the horizon.
+	/// let accel = Vector3::new(0.0, 9.81, 0.0);
+	/// let mag = Vector3::new(-0.5, -0.866, 0.0) * 48.0;
+	///
+	/// let q = Quaternion::from_accel_mag(accel, mag).unwrap();
+	///
+	/// assert!((q.rotate_vector(Vector3::new(0.0, 0.0, -1.0)) - Vector3::new(1.0, 0.0, 0.0)).magnitude() < 1e-12);
+	/// ```
+
+	pub fn from_accel_mag(accel: Vector3<F>, mag: Vector3<F>) -> Option<Quaternion<F>> {
+		let up = Vector3::new(F::zero(), F::one(), F::zero());
+		let north = Vector3::new(F::zero(), F::zero(), -F::one());
+		Quaternion::solve_alignment((accel, mag), (up, north))
+	}
+
+	/// Unit quaternion from a rotation matrix for row vectors, the inverse of
+	/// `rotation_matrix`. Transpose column-vector matrices first.
+	///
+	/// Uses the largest of `w`, `x`, `y` and `z` as the pivot, which keeps the
+	/// result accurate for every rotation angle. The matrix must be
+	/// orthonormal with a positive determinant.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let q1 = Quaternion::<f64>::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 90.0);
+	/// let q2 = Quaternion::from_rotation_matrix(q1.rotation_matrix());
+	///
+	/// assert!((q1.dot(q2).abs() - 1.0).abs() < 1e-12);
+	/// ```
+
+	pub fn from_rotation_matrix(m: Matrix3<F>) -> Quaternion<F> {
+		let one = F::one();
+		let half = F::from(0.5).unwrap();
+		let trace = m[0][0] + m[1][1] + m[2][2];
+
+		let (w, x, y, z) = if trace > m[0][0].max(m[1][1]).max(m[2][2]) {
+			let w = half * (one + trace).sqrt();
+			let s = F::from(4.0).unwrap() * w;
+			(w, (m[1][2] - m[2][1]) / s, (m[2][0] - m[0][2]) / s, (m[0][1] - m[1][0]) / s)
+		} else if m[0][0] >= m[1][1] && m[0][0] >= m[2][2] {
+			let x = half * (one + m[0][0] - m[1][1] - m[2][2]).sqrt();
+			let s = F::from(4.0).unwrap() * x;
+			((m[1][2] - m[2][1]) / s, x, (m[0][1] + m[1][0]) / s, (m[0][2] + m[2][0]) / s)
+		} else if m[1][1] >= m[2][2] {
+			let y = half * (one - m[0][0] + m[1][1] - m[2][2]).sqrt();
+			let s = F::from(4.0).unwrap() * y;
+			((m[2][0] - m[0][2]) / s, (m[0][1] + m[1][0]) / s, y, (m[1][2] + m[2][1]) / s)
+		} else {
+			let z = half * (one - m[0][0] - m[1][1] + m[2][2]).sqrt();
+			let s = F::from(4.0).unwrap() * z;
+			((m[0][1] - m[1][0]) / s, (m[0][2] + m[2][0]) / s, (m[1][2] + m[2][1]) / s, z)
+		};
+		Quaternion::new(w, [x, y, z]).versor()
+	}
+}
+
+/// Orthonormal frame with its first axis along `primary` and its second in
+/// the plane of `primary` and `secondary`. `None` if they are parallel or
+/// either is zero.
+
+fn triad<F: Float>(primary: Vector3<F>, secondary: Vector3<F>) -> Option<(Vector3<F>, Vector3<F>, Vector3<F>)> {
+	let normal = primary.cross(secondary);
+	let (p, n) = (primary.magnitude(), normal.magnitude());
+	if p == F::zero() || n == F::zero() {
+		return None;
+	}
+	let t1 = primary / p;
+	let t2 = normal / n;
+	Some((t1, t2, t1.cross(t2)))
+}
+
+/// Prefix products of the rotations, `out[i] = q[0] * q[1] * ... * q[i]`.
+/// With `q[0]` the root of a joint chain and every other rotation local to
+/// its parent, these are the global rotations of the joints. Incremental
+/// rotations measured in the body frame, such as gyroscope steps,
+/// accumulate the same way.
+///
+/// # Example
+///
+/// ```
+/// use math3d::quaternion::{self, Quaternion};
+/// use math3d::vectors::Vector3;
+///
+/// let step = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 30.0);
+/// let chain = quaternion::cumulative_compose(&[step; 3]);
+///
+/// let expected = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 90.0);
+/// assert!((chain[2] - expected).norm() < 1e-12);
+/// ```
+
+pub fn cumulative_compose<F: Float>(rotations: &[Quaternion<F>]) -> Vec<Quaternion<F>> {
+	let mut out = rotations.to_vec();
+	compose_in_place(&mut out);
+	out
+}
+
+/// Parallel version of `cumulative_compose`. Chunks are scanned
+/// independently and then rotated by the product of the chunks before them,
+/// so the result may differ from `cumulative_compose` in the last bits.
+
+pub fn cumulative_compose_par<F: Float + Send + Sync>(rotations: &[Quaternion<F>]) -> Vec<Quaternion<F>> {
+	let mut out = rotations.to_vec();
+	out.par_chunks_mut(PAR_CHUNK_SIZE).for_each(compose_in_place);
+
+	let offsets: Vec<Quaternion<F>> = out
+		.chunks(PAR_CHUNK_SIZE)
+		.scan(Quaternion::identity(), |product, chunk| {
+			let offset = *product;
+			*product = offset * chunk[chunk.len() - 1];
+			Some(offset)
+		})
+		.collect();
+
+	out.par_chunks_mut(PAR_CHUNK_SIZE)
+		.zip(offsets)
+		.skip(1)
+		.for_each(|(chunk, offset)| {
+			for q in chunk {
+				*q = offset * *q;
+			}
+		});
+	out
+}
+
+fn compose_in_place<F: Float>(rotations: &mut [Quaternion<F>]) {
+	for i in 1..rotations.len() {
+		rotations[i] = rotations[i - 1] * rotations[i];
+	}
+}
+
+/// Continuous rotation angles in degrees about `axis` for a sequence of
+/// orientations, such as the samples of a steering wheel or a propeller.
+/// Each angle is the twist of the orientation about the axis, with multiples
+/// of 360 degrees added so that it never jumps between samples; the total
+/// keeps growing past ±180 degrees and counts full turns.
+///
+/// The first angle is in `[-180, 180]`. Consecutive samples must be less
+/// than 180 degrees apart about the axis, otherwise the direction of the
+/// turn between them is ambiguous and the shorter one is taken. Rotations
+/// about other axes are ignored; the axis need not be normalized.
+///
+/// # Example
+///
+/// ```
+/// use math3d::quaternion::{self, Quaternion};
+/// use math3d::vectors::Vector3;
+///
+/// let axis = Vector3::new(0.0, 0.0, 1.0);
+/// let samples: Vec<Quaternion<f64>> = (0..=10).map(|i| Quaternion::from_axis_angle(axis, i as f64 * 100.0)).collect();
+/// let angles = quaternion::unwrap_angles(&samples, axis);
+///
+/// assert!((angles[10] - 1000.0).abs() < 1e-9);
+/// ```
+
+pub fn unwrap_angles<F: Float>(sequence: &[Quaternion<F>], axis: Vector3<F>) -> Vec<F> {
+	let axis = axis.normalized();
+	let two = F::from(2.0).unwrap();
+	let turn = two * F::from(std::f64::consts::PI).unwrap();
+	let wrap = |angle: F| angle - (angle / turn).round() * turn;
+	let twist = |q: &Quaternion<F>| two * q.v.dot(axis).atan2(q.w);
+
+	let mut angles = Vec::with_capacity(sequence.len());
+	let mut previous = F::zero();
+	let mut total = F::zero();
+	for (i, q) in sequence.iter().enumerate() {
+		let angle = twist(q);
+		total = if i == 0 { wrap(angle) } else { total + wrap(angle - previous) };
+		previous = angle;
+		angles.push(total.to_degrees());
+	}
+	angles
 }
 
 impl<F: Float> core::fmt::Display for Quaternion<F> {
@@ -574,12 +1394,74 @@ impl<F: Float> core::fmt::Display for Quaternion<F> {
     }
 }
 
+/// Human-readable form of a quaternion, e.g. `{"w": 1.0, "x": 0.0, "y": 0.0, "z": 0.0}`.
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Quaternion")]
+struct QuaternionMap<F> {
+	w: F,
+	x: F,
+	y: F,
+	z: F,
+}
+
+/// Serializes as a `{w, x, y, z}` map for human-readable formats such as JSON
+/// and as a compact `(w, x, y, z)` tuple for binary formats.
+
+impl<F: Float + Serialize> Serialize for Quaternion<F> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let (w, x, y, z) = self.decompose();
+		if serializer.is_human_readable() {
+			QuaternionMap { w, x, y, z }.serialize(serializer)
+		} else {
+			(w, x, y, z).serialize(serializer)
+		}
+	}
+}
+
+impl<'de, F: Float + Deserialize<'de>> Deserialize<'de> for Quaternion<F> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Quaternion<F>, D::Error> {
+		let (w, x, y, z) = if deserializer.is_human_readable() {
+			let q = QuaternionMap::deserialize(deserializer)?;
+			(q.w, q.x, q.y, q.z)
+		} else {
+			<(F, F, F, F)>::deserialize(deserializer)?
+		};
+		Ok(Quaternion::new(w, [x, y, z]))
+	}
+}
+
 impl<F: Float> std::cmp::PartialEq for Quaternion<F> {
     fn eq(&self, other: &Quaternion<F>) -> bool {
         self.w == other.w && self.v == other.v
     }
 }
 
+/// The identity rotation, so a defaulted orientation leaves vectors as they
+/// are; see `num::Zero` for the zero quaternion.
+
+impl<F: Float> Default for Quaternion<F> {
+	fn default() -> Quaternion<F> {
+		Quaternion::identity()
+	}
+}
+
+impl<F: Float> num::Zero for Quaternion<F> {
+	fn zero() -> Quaternion<F> {
+		Quaternion::new(F::zero(), [F::zero(); 3])
+	}
+
+	fn is_zero(&self) -> bool {
+		*self == Quaternion::new(F::zero(), [F::zero(); 3])
+	}
+}
+
+impl<F: Float> num::One for Quaternion<F> {
+	fn one() -> Quaternion<F> {
+		Quaternion::identity()
+	}
+}
+
 impl<F: Float> std::ops::Add for Quaternion<F> {
     type Output = Quaternion<F>;
 
@@ -596,6 +1478,17 @@ impl<F: Float> std::ops::Sub for Quaternion<F> {
     }
 }
 
+impl<F: Float> std::ops::Neg for Quaternion<F> {
+    type Output = Quaternion<F>;
+
+    fn neg(self) -> Quaternion<F> {
+        Quaternion {
+            w: -self.w,
+            v: -self.v,
+        }
+    }
+}
+
 impl<F: Float> std::ops::Mul for Quaternion<F> {
     type Output = Quaternion<F>;
 
@@ -634,6 +1527,42 @@ impl<F: Float> std::ops::Div<F> for Quaternion<F> {
     }
 }
 
+impl<F: Float> std::ops::AddAssign for Quaternion<F> {
+    fn add_assign(&mut self, other: Quaternion<F>) {
+        *self = *self + other;
+    }
+}
+
+impl<F: Float> std::ops::SubAssign for Quaternion<F> {
+    fn sub_assign(&mut self, other: Quaternion<F>) {
+        *self = *self - other;
+    }
+}
+
+impl<F: Float> std::ops::MulAssign for Quaternion<F> {
+    fn mul_assign(&mut self, other: Quaternion<F>) {
+        *self = *self * other;
+    }
+}
+
+impl<F: Float> std::ops::MulAssign<F> for Quaternion<F> {
+    fn mul_assign(&mut self, other: F) {
+        *self = *self * other;
+    }
+}
+
+impl<F: Float> std::ops::DivAssign for Quaternion<F> {
+    fn div_assign(&mut self, other: Quaternion<F>) {
+        *self = *self / other;
+    }
+}
+
+impl<F: Float> std::ops::DivAssign<F> for Quaternion<F> {
+    fn div_assign(&mut self, other: F) {
+        *self = *self / other;
+    }
+}
+
 impl<F: Float> std::ops::Index<usize> for Quaternion<F> {
 	type Output = F;
 
@@ -658,4 +1587,115 @@ impl<F: Float> std::ops::IndexMut<usize> for Quaternion<F> {
 			_ => panic!("Index out of bounds"),
 		}
 	}
-}
\ No newline at end of file
+}
+
+/// Conversions to and from arrays and tuples in w, x, y, z order, matching `decompose`.
+
+impl<F: Float> From<[F; 4]> for Quaternion<F> {
+	fn from(a: [F; 4]) -> Quaternion<F> {
+		Quaternion::new(a[0], [a[1], a[2], a[3]])
+	}
+}
+
+impl<F: Float> From<Quaternion<F>> for [F; 4] {
+	fn from(q: Quaternion<F>) -> [F; 4] {
+		let (w, x, y, z) = q.decompose();
+		[w, x, y, z]
+	}
+}
+
+impl<F: Float> From<(F, F, F, F)> for Quaternion<F> {
+	fn from(a: (F, F, F, F)) -> Quaternion<F> {
+		Quaternion::from([a.0, a.1, a.2, a.3])
+	}
+}
+
+impl<F: Float> From<Quaternion<F>> for (F, F, F, F) {
+	fn from(q: Quaternion<F>) -> (F, F, F, F) {
+		q.decompose()
+	}
+}
+
+impl<F: Float> TryFrom<&[F]> for Quaternion<F> {
+	type Error = std::array::TryFromSliceError;
+
+	/// Fails unless the slice has exactly 4 elements.
+	fn try_from(slice: &[F]) -> Result<Quaternion<F>, Self::Error> {
+		<[F; 4]>::try_from(slice).map(Quaternion::from)
+	}
+}
+
+impl<F: Float> From<(F, Vector3<F>)> for Quaternion<F> {
+	fn from(a: (F, Vector3<F>)) -> Quaternion<F> {
+		Quaternion { w: a.0, v: a.1 }
+	}
+}
+
+/// Views the components as a `[w, x, y, z]` array without copying.
+
+impl<F: Float> AsRef<[F; 4]> for Quaternion<F> {
+	fn as_ref(&self) -> &[F; 4] {
+		// SAFETY: `Quaternion` and `Vector3` are both `repr(C)` and hold only
+		// fields of type `F`, so a quaternion has the layout of `[F; 4]`.
+		unsafe { &*(self as *const Quaternion<F> as *const [F; 4]) }
+	}
+}
+
+/// Quaternion compared by the bit patterns of its components, for use as a
+/// key in `HashMap`, `HashSet` or `BTreeMap`.
+///
+/// Unlike `==` on quaternions, `0.0` and `-0.0` are different keys and NaN
+/// components equal themselves. The order is total and consistent with
+/// equality, but it is not a numeric order.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashSet;
+/// use math3d::quaternion::{Quaternion, QuaternionBits};
+///
+/// let q = Quaternion::<f64>::identity();
+/// let keys: HashSet<_> = [q, q, -q].into_iter().map(QuaternionBits).collect();
+///
+/// assert_eq!(keys.len(), 2);
+/// ```
+
+#[derive(Debug, Copy, Clone)]
+pub struct QuaternionBits<F: Float>(pub Quaternion<F>);
+
+impl<F: Float> QuaternionBits<F> {
+	fn key(&self) -> [(i8, i16, u64); 4] {
+		self.0.as_ref().map(|a| {
+			let (mantissa, exponent, sign) = a.integer_decode();
+			(sign, exponent, mantissa)
+		})
+	}
+}
+
+impl<F: Float> PartialEq for QuaternionBits<F> {
+	fn eq(&self, other: &QuaternionBits<F>) -> bool {
+		self.key() == other.key()
+	}
+}
+
+impl<F: Float> Eq for QuaternionBits<F> {}
+
+impl<F: Float> std::hash::Hash for QuaternionBits<F> {
+	fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+		self.key().hash(state)
+	}
+}
+
+impl<F: Float> PartialOrd for QuaternionBits<F> {
+	fn partial_cmp(&self, other: &QuaternionBits<F>) -> Option<std::cmp::Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl<F: Float> Ord for QuaternionBits<F> {
+	fn cmp(&self, other: &QuaternionBits<F>) -> std::cmp::Ordering {
+		self.key().cmp(&other.key())
+	}
+}
+
+impl_scalar_lhs_mul!(Quaternion);
@@ -0,0 +1,243 @@
+//! # Scalar
+//!
+//! Helpers on the scalar type shared by all the other modules.
+//!
+//! # Example
+//!
+//! ```
+//! use math3d::scalar::FloatExt;
+//!
+//! // A dot product of unit vectors that drifted just above one.
+//! let cos = 1.0000000000000002_f64;
+//!
+//! assert!(cos.acos().is_nan());
+//! assert_eq!(cos.clamped_acos(), 0.0);
+//! ```
+
+use num::Float;
+
+/// Extension methods for `Float` types, implemented for every `F: Float`.
+
+pub trait FloatExt: Float {
+
+	/// Clamps the value to `[-1, 1]`, the domain of `acos` and `asin`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::scalar::FloatExt;
+	///
+	/// assert_eq!((-1.5_f64).clamp_unit(), -1.0);
+	/// ```
+
+	fn clamp_unit(self) -> Self {
+		self.max(-Self::one()).min(Self::one())
+	}
+
+	/// Arc cosine of the value clamped to `[-1, 1]`, so cosines computed as
+	/// dot products give a valid angle even after rounding pushes them
+	/// slightly out of range.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::scalar::FloatExt;
+	///
+	/// assert_eq!((-1.0000001_f64).clamped_acos(), std::f64::consts::PI);
+	/// ```
+
+	fn clamped_acos(self) -> Self {
+		self.clamp_unit().acos()
+	}
+
+	/// Arc sine of the value clamped to `[-1, 1]`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::scalar::FloatExt;
+	///
+	/// assert_eq!(1.0000001_f64.clamped_asin(), std::f64::consts::FRAC_PI_2);
+	/// ```
+
+	fn clamped_asin(self) -> Self {
+		self.clamp_unit().asin()
+	}
+
+	/// Whether the value is within `epsilon` of `other`. Suited to values of
+	/// known magnitude, such as unit vectors and rotation matrices.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::scalar::FloatExt;
+	///
+	/// assert!((0.1_f64 + 0.2).approx_eq(0.3, 1e-12));
+	/// ```
+
+	fn approx_eq(self, other: Self, epsilon: Self) -> bool {
+		(self - other).abs() <= epsilon
+	}
+
+	/// Whether the value is within `epsilon` of `other`, or within
+	/// `max_relative` times the larger magnitude of the two. The absolute
+	/// bound handles values near zero and the relative one scales with large
+	/// values; `max_relative` of a few `F::epsilon()` allows for a few units
+	/// in the last place. Equal infinities compare equal, NaN never does.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::scalar::FloatExt;
+	///
+	/// assert!(1e12_f64.relative_eq(1e12 + 1.0, 1e-12, 1e-9));
+	/// assert!(!1e12_f64.approx_eq(1e12 + 1.0, 1e-12));
+	/// ```
+
+	fn relative_eq(self, other: Self, epsilon: Self, max_relative: Self) -> bool {
+		if self == other {
+			return true;
+		}
+		let diff = (self - other).abs();
+		diff <= epsilon || diff <= max_relative * self.abs().max(other.abs())
+	}
+}
+
+impl<F: Float> FloatExt for F {}
+
+/// Error of a checked conversion of an array to `f32`, naming the first
+/// offending element by its index in the array.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum F32ConversionError {
+	/// The element is finite but too large in magnitude for `f32`.
+	OutOfRange { index: usize },
+	/// Rounding the element to `f32` moves it by more than the tolerance.
+	PrecisionLoss { index: usize },
+}
+
+impl core::fmt::Display for F32ConversionError {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		match self {
+			F32ConversionError::OutOfRange { index } => write!(f, "element {} is out of f32 range", index),
+			F32ConversionError::PrecisionLoss { index } => write!(f, "element {} loses precision as f32", index),
+		}
+	}
+}
+
+impl std::error::Error for F32ConversionError {}
+
+/// Converts every value to `f32`, failing if a finite value overflows `f32`
+/// or moves by more than `max_error` when rounded. Infinities and NaN are
+/// carried over as they are.
+
+pub(crate) fn try_to_array_f32<F: Float, const N: usize>(values: [F; N], max_error: F) -> Result<[f32; N], F32ConversionError> {
+	let mut out = [0.0; N];
+	for (index, (value, out)) in values.into_iter().zip(out.iter_mut()).enumerate() {
+		*out = match value.to_f32() {
+			Some(single) if single.is_finite() || !value.is_finite() => single,
+			_ => return Err(F32ConversionError::OutOfRange { index }),
+		};
+		if (F::from(*out).unwrap() - value).abs() > max_error {
+			return Err(F32ConversionError::PrecisionLoss { index });
+		}
+	}
+	Ok(out)
+}
+
+/// Converts a float to another float type. Values are rounded to the
+/// nearest representable value, and finite values too large for the target
+/// saturate to infinity.
+
+pub(crate) fn cast<F: Float, T: Float>(value: F) -> T {
+	T::from(value).unwrap()
+}
+
+/// Packs a value into an unsigned small float with a 5 bit exponent and
+/// `mantissa_bits` bits of mantissa, as in the 11 and 10 bit channels of
+/// packed GPU formats. Rounds to nearest, ties to even; negative values
+/// give zero, values above the largest finite one infinity and NaN a quiet
+/// NaN.
+
+pub(crate) fn pack_unsigned_small_float<F: Float>(value: F, mantissa_bits: u32) -> u32 {
+	let infinity = 31 << mantissa_bits;
+	let value = value.to_f64().unwrap();
+	if value.is_nan() {
+		return infinity | 1 << (mantissa_bits - 1);
+	}
+	if value <= 0.0 {
+		return 0;
+	}
+	if value.is_infinite() {
+		return infinity;
+	}
+
+	let bits = value.to_bits();
+	let exponent = ((bits >> 52) & 0x7ff) as i32 - 1023 + 15;
+	if exponent >= 31 {
+		return infinity;
+	}
+	let significand = (bits & ((1 << 52) - 1)) | 1 << 52;
+	// Bits of the f64 significand dropped below the last mantissa bit; a
+	// subnormal result drops one more for every step of exponent below 1.
+	let shift = 52 - mantissa_bits + (1 - exponent).max(0) as u32;
+	if shift > 53 {
+		return 0;
+	}
+	let mut packed = if exponent < 1 {
+		(significand >> shift) as u32
+	} else {
+		(exponent as u32) << mantissa_bits | ((bits & ((1 << 52) - 1)) >> shift) as u32
+	};
+	let remainder = significand & ((1 << shift) - 1);
+	let half = 1 << (shift - 1);
+	// A carry out of the mantissa moves on to the next exponent, and from
+	// the largest finite value to infinity.
+	if remainder > half || (remainder == half && packed & 1 == 1) {
+		packed += 1;
+	}
+	packed
+}
+
+/// Unpacks an unsigned small float written by `pack_unsigned_small_float`.
+
+pub(crate) fn unpack_unsigned_small_float<F: Float>(packed: u32, mantissa_bits: u32) -> F {
+	let exponent = (packed >> mantissa_bits & 31) as i32;
+	let mantissa = packed & ((1 << mantissa_bits) - 1);
+	let value = match exponent {
+		31 if mantissa == 0 => f64::INFINITY,
+		31 => f64::NAN,
+		0 => mantissa as f64 * 2.0f64.powi(-14 - mantissa_bits as i32),
+		_ => (mantissa | 1 << mantissa_bits) as f64 * 2.0f64.powi(exponent - 15 - mantissa_bits as i32),
+	};
+	F::from(value).unwrap()
+}
+
+/// Implements `scalar * value` for `f32` and `f64` scalars by delegating to
+/// the `value * scalar` product of each listed type, so expressions can be
+/// written in either order. Generic impls over `F: Float` are not allowed on
+/// foreign scalar types, hence one impl per concrete float.
+
+macro_rules! impl_scalar_lhs_mul {
+	($($ty:ident),*) => {
+		$(
+			impl std::ops::Mul<$ty<f32>> for f32 {
+				type Output = $ty<f32>;
+
+				fn mul(self, other: $ty<f32>) -> $ty<f32> {
+					other * self
+				}
+			}
+
+			impl std::ops::Mul<$ty<f64>> for f64 {
+				type Output = $ty<f64>;
+
+				fn mul(self, other: $ty<f64>) -> $ty<f64> {
+					other * self
+				}
+			}
+		)*
+	};
+}
+
+pub(crate) use impl_scalar_lhs_mul;
@@ -0,0 +1,318 @@
+//! # Shapes
+//!
+//! Triangle meshes of canonical primitives: cube, UV sphere, icosphere,
+//! cylinder and plane, for demos and test benches.
+//!
+//! All shapes are centered on the origin with y up. Triangles wind
+//! counter-clockwise seen from outside, and vertices on hard edges and
+//! texture seams are duplicated so every vertex has a single normal and
+//! texture coordinate.
+//!
+//! # Example
+//!
+//! ```
+//! use math3d::shapes;
+//!
+//! let mesh = shapes::uv_sphere(1.0, 32, 16);
+//!
+//! assert_eq!(mesh.positions.len(), mesh.normals.len());
+//! assert_eq!(mesh.indices.len() % 3, 0);
+//! ```
+
+use std::collections::HashMap;
+use num::Float;
+use crate::points::{Point2, Point3};
+use crate::scalar::FloatExt;
+use crate::vectors::Vector3;
+
+/// Indexed triangle mesh with one normal and one texture coordinate per
+/// vertex. Every three consecutive indices form a triangle.
+
+#[derive(Clone, Debug)]
+pub struct Mesh<F: Float> {
+	pub positions: Vec<Point3<F>>,
+	pub normals: Vec<Vector3<F>>,
+	pub uvs: Vec<Point2<F>>,
+	pub indices: Vec<u32>,
+}
+
+impl<F: Float> Mesh<F> {
+
+	/// Creates an empty mesh.
+	pub fn new() -> Mesh<F> {
+		Mesh {
+			positions: Vec::new(),
+			normals: Vec::new(),
+			uvs: Vec::new(),
+			indices: Vec::new(),
+		}
+	}
+
+	/// Number of triangles.
+	pub fn triangle_count(&self) -> usize {
+		self.indices.len() / 3
+	}
+
+	fn vertex(&mut self, position: Vector3<F>, normal: Vector3<F>, u: F, v: F) -> u32 {
+		self.positions.push(Point3::from_vector(position));
+		self.normals.push(normal);
+		self.uvs.push(Point2::new(u, v));
+		(self.positions.len() - 1) as u32
+	}
+
+	fn triangle(&mut self, a: u32, b: u32, c: u32) {
+		self.indices.extend_from_slice(&[a, b, c]);
+	}
+}
+
+impl<F: Float> Default for Mesh<F> {
+	fn default() -> Mesh<F> {
+		Mesh::new()
+	}
+}
+
+/// Axis-aligned cube with the given edge length: six faces of two
+/// triangles, each with its own four vertices and texture coordinates
+/// covering the unit square.
+///
+/// # Example
+///
+/// ```
+/// use math3d::shapes;
+///
+/// let cube = shapes::cube(2.0);
+///
+/// assert_eq!(cube.positions.len(), 24);
+/// assert_eq!(cube.triangle_count(), 12);
+/// ```
+
+pub fn cube<F: Float>(size: F) -> Mesh<F> {
+	let (zero, one) = (F::zero(), F::one());
+	let half = size / F::from(2.0).unwrap();
+	let faces = [
+		(Vector3::new(one, zero, zero), Vector3::new(zero, one, zero)),
+		(Vector3::new(-one, zero, zero), Vector3::new(zero, one, zero)),
+		(Vector3::new(zero, one, zero), Vector3::new(zero, zero, -one)),
+		(Vector3::new(zero, -one, zero), Vector3::new(zero, zero, one)),
+		(Vector3::new(zero, zero, one), Vector3::new(zero, one, zero)),
+		(Vector3::new(zero, zero, -one), Vector3::new(zero, one, zero)),
+	];
+
+	let mut mesh = Mesh::new();
+	for (normal, up) in faces {
+		let right = up.cross(normal);
+		let corner = |s: F, t: F| (normal + right * s + up * t) * half;
+		let a = mesh.vertex(corner(-one, -one), normal, zero, zero);
+		let b = mesh.vertex(corner(one, -one), normal, one, zero);
+		let c = mesh.vertex(corner(one, one), normal, one, one);
+		let d = mesh.vertex(corner(-one, one), normal, zero, one);
+		mesh.triangle(a, b, c);
+		mesh.triangle(a, c, d);
+	}
+	mesh
+}
+
+/// Sphere tessellated along meridians and parallels.
+///
+/// # Arguments
+///
+/// * `radius` - Radius of the sphere.
+/// * `segments` - Number of meridian slices around the y axis, at least 3.
+/// * `rings` - Number of parallel bands from pole to pole, at least 2.
+///
+/// Texture coordinates wrap once around the equator in `u` and run from the
+/// south pole at `v = 0` to the north pole at `v = 1`.
+
+pub fn uv_sphere<F: Float>(radius: F, segments: usize, rings: usize) -> Mesh<F> {
+	assert!(segments >= 3 && rings >= 2, "a UV sphere needs at least 3 segments and 2 rings");
+	let pi = F::from(std::f64::consts::PI).unwrap();
+	let two = F::from(2.0).unwrap();
+
+	let mut mesh = Mesh::new();
+	for i in 0..=rings {
+		let v = F::from(i).unwrap() / F::from(rings).unwrap();
+		let theta = v * pi;
+		for j in 0..=segments {
+			let u = F::from(j).unwrap() / F::from(segments).unwrap();
+			let phi = u * two * pi;
+			let normal = Vector3::new(theta.sin() * phi.sin(), theta.cos(), theta.sin() * phi.cos());
+			mesh.vertex(normal * radius, normal, u, F::one() - v);
+		}
+	}
+
+	let stride = (segments + 1) as u32;
+	for i in 0..rings as u32 {
+		for j in 0..segments as u32 {
+			let a = i * stride + j;
+			let (b, c, d) = (a + stride, a + stride + 1, a + 1);
+			if i + 1 < rings as u32 {
+				mesh.triangle(a, b, c);
+			}
+			if i > 0 {
+				mesh.triangle(a, c, d);
+			}
+		}
+	}
+	mesh
+}
+
+/// Sphere made by subdividing an icosahedron, with evenly sized triangles.
+///
+/// Each subdivision splits every triangle into four, so the sphere has
+/// `20 * 4^subdivisions` triangles. Vertices are shared between triangles,
+/// and texture coordinates are the spherical coordinates of each vertex,
+/// which distorts textures near the seam at the back of the sphere.
+///
+/// # Example
+///
+/// ```
+/// use math3d::shapes;
+///
+/// let sphere = shapes::icosphere(1.0, 2);
+///
+/// assert_eq!(sphere.triangle_count(), 320);
+/// assert_eq!(sphere.positions.len(), 162);
+/// ```
+
+pub fn icosphere<F: Float>(radius: F, subdivisions: u32) -> Mesh<F> {
+	let t = (1.0 + 5.0_f64.sqrt()) / 2.0;
+	let corners = [
+		[-1.0, t, 0.0], [1.0, t, 0.0], [-1.0, -t, 0.0], [1.0, -t, 0.0],
+		[0.0, -1.0, t], [0.0, 1.0, t], [0.0, -1.0, -t], [0.0, 1.0, -t],
+		[t, 0.0, -1.0], [t, 0.0, 1.0], [-t, 0.0, -1.0], [-t, 0.0, 1.0],
+	];
+	let mut triangles = vec![
+		[0, 11, 5], [0, 5, 1], [0, 1, 7], [0, 7, 10], [0, 10, 11],
+		[1, 5, 9], [5, 11, 4], [11, 10, 2], [10, 7, 6], [7, 1, 8],
+		[3, 9, 4], [3, 4, 2], [3, 2, 6], [3, 6, 8], [3, 8, 9],
+		[4, 9, 5], [2, 4, 11], [6, 2, 10], [8, 6, 7], [9, 8, 1],
+	];
+
+	let mut directions: Vec<Vector3<F>> = corners
+		.iter()
+		.map(|c| Vector3::from_array(c.map(|x| F::from(x).unwrap())).normalized())
+		.collect();
+	for _ in 0..subdivisions {
+		let mut midpoints = HashMap::new();
+		let mut midpoint = |a: u32, b: u32| {
+			*midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+				directions.push((directions[a as usize] + directions[b as usize]).normalized());
+				(directions.len() - 1) as u32
+			})
+		};
+		triangles = triangles
+			.iter()
+			.flat_map(|&[a, b, c]| {
+				let (ab, bc, ca) = (midpoint(a, b), midpoint(b, c), midpoint(c, a));
+				[[a, ab, ca], [b, bc, ab], [c, ca, bc], [ab, bc, ca]]
+			})
+			.collect();
+	}
+
+	let pi = F::from(std::f64::consts::PI).unwrap();
+	let half = F::from(0.5).unwrap();
+	let mut mesh = Mesh::new();
+	for normal in directions {
+		let u = half + normal[0].atan2(normal[2]) / (pi + pi);
+		let v = F::one() - normal[1].clamped_acos() / pi;
+		mesh.vertex(normal * radius, normal, u, v);
+	}
+	for [a, b, c] in triangles {
+		mesh.triangle(a, b, c);
+	}
+	mesh
+}
+
+/// Closed cylinder along the y axis from `-height / 2` to `height / 2`.
+///
+/// The side has `segments` quads with smooth radial normals and texture
+/// coordinates wrapping once around it; each cap is a fan of flat-shaded
+/// triangles with texture coordinates mapping the unit disk to the unit
+/// square.
+
+pub fn cylinder<F: Float>(radius: F, height: F, segments: usize) -> Mesh<F> {
+	assert!(segments >= 3, "a cylinder needs at least 3 segments");
+	let (zero, one) = (F::zero(), F::one());
+	let two = F::from(2.0).unwrap();
+	let half = F::from(0.5).unwrap();
+	let pi = F::from(std::f64::consts::PI).unwrap();
+	let top = height / two;
+	let around = |j: usize| {
+		let phi = F::from(j).unwrap() / F::from(segments).unwrap() * two * pi;
+		Vector3::new(phi.sin(), zero, phi.cos())
+	};
+
+	let mut mesh = Mesh::new();
+	for j in 0..=segments {
+		let normal = around(j);
+		let u = F::from(j).unwrap() / F::from(segments).unwrap();
+		mesh.vertex(normal * radius + Vector3::new(zero, top, zero), normal, u, one);
+		mesh.vertex(normal * radius - Vector3::new(zero, top, zero), normal, u, zero);
+	}
+	for j in 0..segments as u32 {
+		let (a, b, c, d) = (2 * j, 2 * j + 1, 2 * j + 3, 2 * j + 2);
+		mesh.triangle(a, b, c);
+		mesh.triangle(a, c, d);
+	}
+
+	for side in [one, -one] {
+		let normal = Vector3::new(zero, side, zero);
+		let center = mesh.vertex(normal * top, normal, half, half);
+		for j in 0..segments {
+			let direction = around(j);
+			let position = direction * radius + normal * top;
+			mesh.vertex(position, normal, half + direction[0] * half, half - direction[2] * side * half);
+		}
+		for j in 0..segments as u32 {
+			let (a, b) = (center + 1 + j, center + 1 + (j + 1) % segments as u32);
+			if side > zero {
+				mesh.triangle(center, a, b);
+			} else {
+				mesh.triangle(center, b, a);
+			}
+		}
+	}
+	mesh
+}
+
+/// Flat grid in the xz plane facing up, `width` along x and `depth` along z,
+/// split into `columns` by `rows` quads. Texture coordinates cover the unit
+/// square with `u` along x and `v` along -z.
+///
+/// # Example
+///
+/// ```
+/// use math3d::shapes;
+///
+/// let plane = shapes::plane(4.0, 2.0, 4, 2);
+///
+/// assert_eq!(plane.positions.len(), 15);
+/// assert_eq!(plane.triangle_count(), 16);
+/// ```
+
+pub fn plane<F: Float>(width: F, depth: F, columns: usize, rows: usize) -> Mesh<F> {
+	assert!(columns > 0 && rows > 0, "a plane needs at least one column and one row");
+	let (zero, one) = (F::zero(), F::one());
+	let half = F::from(0.5).unwrap();
+	let normal = Vector3::new(zero, one, zero);
+
+	let mut mesh = Mesh::new();
+	for i in 0..=rows {
+		let v = F::from(i).unwrap() / F::from(rows).unwrap();
+		for j in 0..=columns {
+			let u = F::from(j).unwrap() / F::from(columns).unwrap();
+			let position = Vector3::new((u - half) * width, zero, (v - half) * depth);
+			mesh.vertex(position, normal, u, one - v);
+		}
+	}
+
+	let stride = (columns + 1) as u32;
+	for i in 0..rows as u32 {
+		for j in 0..columns as u32 {
+			let a = i * stride + j;
+			mesh.triangle(a, a + stride, a + stride + 1);
+			mesh.triangle(a, a + stride + 1, a + 1);
+		}
+	}
+	mesh
+}
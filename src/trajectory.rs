@@ -0,0 +1,186 @@
+//! # Trajectory
+//!
+//! Ballistics under constant gravity: launch velocities that hit a target at
+//! a given speed, and the position, apex and time of flight of a projectile.
+//!
+//! Gravity is a vector, so "up" is always against it and the functions work
+//! the same for any world orientation.
+//!
+//! # Example
+//!
+//! ```
+//! use math3d::trajectory::{self, Projectile};
+//! use math3d::points::Point3;
+//! use math3d::vectors::Vector3;
+//!
+//! let origin = Point3::new(0.0, 0.0, 0.0);
+//! let target = Point3::new(20.0, 2.0, 0.0);
+//! let gravity = Vector3::new(0.0, -9.81, 0.0);
+//!
+//! let (_low, high) = trajectory::solve_launch_angle(origin, target, 25.0, gravity).unwrap();
+//! let projectile = Projectile::new(origin, high, gravity);
+//! let t = projectile.time_of_flight(2.0).unwrap();
+//!
+//! assert!(projectile.position_at(t).distance_to(target) < 1e-9);
+//! assert!(projectile.apex()[1] > 20.0);
+//! ```
+
+use num::Float;
+use crate::points::Point3;
+use crate::vectors::Vector3;
+
+/// Launch velocities of the given speed that carry a projectile from
+/// `origin` to `target` under `gravity`, as `(low, high)`: the flat and the
+/// lofted arc. Both are the same when the target is at the edge of the
+/// range, directly above the origin, or when there is no gravity. Directly
+/// below the origin, `low` fires straight down and `high` straight up, so
+/// the projectile falls back past the origin onto the target.
+///
+/// Returns `None` if the target is out of range at this speed or coincides
+/// with the origin.
+///
+/// # Example
+///
+/// ```
+/// use math3d::trajectory;
+/// use math3d::points::Point3;
+/// use math3d::vectors::Vector3;
+///
+/// let gravity = Vector3::<f64>::new(0.0, 0.0, -10.0);
+/// let target = Point3::new(10.0, 0.0, 0.0);
+/// let (low, high) = trajectory::solve_launch_angle(Point3::new(0.0, 0.0, 0.0), target, 10.0, gravity).unwrap();
+///
+/// // Maximum range: both solutions are the 45 degree shot.
+/// assert!((low - high).magnitude() < 1e-6);
+/// assert!((low.angle_between(Vector3::new(1.0, 0.0, 0.0)).to_degrees() - 45.0).abs() < 1e-3);
+///
+/// assert!(trajectory::solve_launch_angle(Point3::new(0.0, 0.0, 0.0), target, 9.0, gravity).is_none());
+/// ```
+
+pub fn solve_launch_angle<F: Float>(
+	origin: Point3<F>,
+	target: Point3<F>,
+	speed: F,
+	gravity: Vector3<F>,
+) -> Option<(Vector3<F>, Vector3<F>)> {
+	let offset = target.to_vector() - origin.to_vector();
+	let g = gravity.magnitude();
+	if g == F::zero() {
+		let velocity = offset.try_normalize()? * speed;
+		return Some((velocity, velocity));
+	}
+
+	let up = -gravity / g;
+	let height = offset.dot(up);
+	let horizontal = offset - up * height;
+	let distance = horizontal.magnitude();
+	let v2 = speed * speed;
+	let two = F::from(2.0).unwrap();
+
+	if distance == F::zero() {
+		if height == F::zero() || v2 < two * g * height {
+			return None;
+		}
+		let velocity = up * speed;
+		return if height > F::zero() { Some((velocity, velocity)) } else { Some((-velocity, velocity)) };
+	}
+
+	let discriminant = v2 * v2 - g * (g * distance * distance + two * height * v2);
+	if discriminant < F::zero() {
+		return None;
+	}
+	// tan(angle) = (v² ± sqrt(discriminant)) / (g x), so the launch direction
+	// is proportional to (g x, v² ± sqrt(discriminant)) in the vertical plane.
+	let root = discriminant.sqrt();
+	let forward = horizontal * g;
+	let launch = |rise: F| (forward + up * rise).normalized() * speed;
+	Some((launch(v2 - root), launch(v2 + root)))
+}
+
+/// Projectile launched from `origin` with `velocity` under constant
+/// `gravity`, without drag.
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Projectile<F: Float> {
+	pub origin: Point3<F>,
+	pub velocity: Vector3<F>,
+	pub gravity: Vector3<F>,
+}
+
+impl<F: Float> Projectile<F> {
+
+	/// Creates a new projectile.
+	pub fn new(origin: Point3<F>, velocity: Vector3<F>, gravity: Vector3<F>) -> Projectile<F> {
+		Projectile {
+			origin,
+			velocity,
+			gravity,
+		}
+	}
+
+	/// Position at time `t` after launch.
+	///
+	/// $$\vec{x}(t) = \vec{x}_0 + \vec{v}_0 t + \frac{1}{2} \vec{g} t^2$$
+
+	pub fn position_at(&self, t: F) -> Point3<F> {
+		let half = F::from(0.5).unwrap();
+		Point3::from_vector(self.origin.to_vector() + self.velocity * t + self.gravity * (half * t * t))
+	}
+
+	/// Velocity at time `t` after launch.
+	pub fn velocity_at(&self, t: F) -> Vector3<F> {
+		self.velocity + self.gravity * t
+	}
+
+	/// Time after launch at which the projectile is highest, zero if it is
+	/// launched level or downwards or there is no gravity.
+	pub fn time_to_apex(&self) -> F {
+		let g2 = self.gravity.magnitude_squared();
+		if g2 == F::zero() {
+			return F::zero();
+		}
+		(-self.velocity.dot(self.gravity) / g2).max(F::zero())
+	}
+
+	/// Highest point of the trajectory.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::trajectory::Projectile;
+	/// use math3d::points::Point3;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let projectile = Projectile::new(Point3::new(0.0, 0.0, 0.0), Vector3::new(3.0, 10.0, 0.0), Vector3::new(0.0, -10.0, 0.0));
+	///
+	/// assert_eq!(projectile.time_to_apex(), 1.0);
+	/// assert!(projectile.apex() == Point3::new(3.0, 5.0, 0.0));
+	/// ```
+
+	pub fn apex(&self) -> Point3<F> {
+		self.position_at(self.time_to_apex())
+	}
+
+	/// Time after launch at which the projectile comes down to
+	/// `landing_height` above the origin, measured against gravity. This is
+	/// the later of the two crossings, so a flat shot that meets its target
+	/// on the way up has already passed it by then. Returns
+	/// `None` if it never reaches that height, or if there is no gravity to
+	/// tell up from down.
+
+	pub fn time_of_flight(&self, landing_height: F) -> Option<F> {
+		let g = self.gravity.magnitude();
+		if g == F::zero() {
+			return None;
+		}
+
+		// Height above the origin is h(t) = vy t - g t² / 2; take the later root.
+		let vy = -self.velocity.dot(self.gravity) / g;
+		let discriminant = vy * vy - F::from(2.0).unwrap() * g * landing_height;
+		if discriminant < F::zero() {
+			return None;
+		}
+		let t = (vy + discriminant.sqrt()) / g;
+		if t >= F::zero() { Some(t) } else { None }
+	}
+}
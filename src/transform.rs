@@ -0,0 +1,309 @@
+//! # Transform
+//!
+//! Transform combining a translation, a rotation and a scale.
+//!
+//! # Example
+//!
+//! ```
+//! use math3d::transform::Transform;
+//! use math3d::quaternion::Quaternion;
+//! use math3d::vectors::Vector3;
+//!
+//! let t = Transform::new(
+//! 	Vector3::new(1.0, 2.0, 3.0),
+//! 	Quaternion::identity(),
+//! 	Vector3::new(1.0, 1.0, 1.0),
+//! );
+//!
+//! println!("{}", t);
+//! ```
+
+use num::Float;
+use crate::camera::{scale, translation};
+use crate::geometry::Plane;
+use crate::matrices::Matrix4;
+use crate::points::Point3;
+use crate::quaternion::Quaternion;
+use crate::vectors::Vector3;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Copy, Clone, Debug)]
+pub struct Transform<F: Float> {
+	translation: Vector3<F>,
+	rotation: Quaternion<F>,
+	scale: Vector3<F>,
+}
+
+impl<F: Float> Transform<F> {
+
+	/// Creates a new transform.
+	///
+	/// # Arguments
+	///
+	/// * `translation` - Translation applied last.
+	/// * `rotation` - Rotation applied after scaling.
+	/// * `scale` - Per-axis scale applied first.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::transform::Transform;
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let t = Transform::new(Vector3::zero(), Quaternion::identity(), Vector3::new(2.0, 2.0, 2.0));
+	/// ```
+
+	pub fn new(translation: Vector3<F>, rotation: Quaternion<F>, scale: Vector3<F>) -> Transform<F> {
+		Transform {
+			translation,
+			rotation,
+			scale,
+		}
+	}
+
+	/// Identity transform.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::transform::Transform;
+	///
+	/// let t = Transform::<f32>::identity();
+	/// ```
+
+	pub fn identity() -> Transform<F> {
+		Transform {
+			translation: Vector3::zero(),
+			rotation: Quaternion::identity(),
+			scale: Vector3::new(F::one(), F::one(), F::one()),
+		}
+	}
+
+	/// Returns the translation.
+	pub fn translation(&self) -> Vector3<F> {
+		self.translation
+	}
+
+	/// Returns the rotation.
+	pub fn rotation(&self) -> Quaternion<F> {
+		self.rotation
+	}
+
+	/// Returns the scale.
+	pub fn scale(&self) -> Vector3<F> {
+		self.scale
+	}
+
+	/// Applies the transform to a point: scale, then rotation, then translation.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::transform::Transform;
+	/// use math3d::points::Point3;
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let t = Transform::new(Vector3::new(1.0, 0.0, 0.0), Quaternion::identity(), Vector3::new(2.0, 2.0, 2.0));
+	///
+	/// assert!(t.transform_point(Point3::new(1.0, 1.0, 1.0)) == Point3::new(3.0, 2.0, 2.0));
+	/// ```
+
+	pub fn transform_point(&self, point: Point3<F>) -> Point3<F> {
+		let scaled = point.to_vector() * self.scale;
+		Point3::from_vector(self.rotation.rotate_vector(scaled) + self.translation)
+	}
+
+	/// Returns the transform as a matrix for row vectors, so that
+	/// `p.to_vector4() * t.matrix()` agrees with `transform_point`.
+
+	pub fn matrix(&self) -> Matrix4<F> {
+		Matrix4::from_trs(self.translation, self.rotation, self.scale)
+	}
+
+	/// Returns the inverse of `matrix`, undoing the translation, rotation and
+	/// scale in that order. The scale must not have zero components.
+
+	pub fn inverse_matrix(&self) -> Matrix4<F> {
+		let s = self.scale;
+		let t = self.translation;
+		let one = F::one();
+		translation(-t[0], -t[1], -t[2]) * self.rotation.conjugate().rotation_matrix4() * scale(one / s[0], one / s[1], one / s[2])
+	}
+
+	/// Rotates the transform in its local space: `rotation` is applied to the
+	/// scaled point before the transform's own rotation, turning it about its
+	/// own axes. The translation and scale are kept as is.
+	///
+	/// `t.pre_rotate(q).transform_point(p)` scales `p`, rotates it by `q`,
+	/// then by `t.rotation()`, and translates it.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::transform::Transform;
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// // Yawed a quarter turn, then pitched about its own x axis.
+	/// let yaw = Quaternion::<f64>::from_axis_angle(Vector3::unit_y(), 90.0);
+	/// let t = Transform::new(Vector3::zero(), yaw, Vector3::new(1.0, 1.0, 1.0));
+	/// let pitched = t.pre_rotate(Quaternion::from_axis_angle(Vector3::unit_x(), 90.0));
+	///
+	/// assert!(pitched.rotation() == yaw * Quaternion::from_axis_angle(Vector3::unit_x(), 90.0));
+	/// ```
+
+	pub fn pre_rotate(&self, rotation: Quaternion<F>) -> Transform<F> {
+		Transform {
+			translation: self.translation,
+			rotation: self.rotation * rotation,
+			scale: self.scale,
+		}
+	}
+
+	/// Rotates the transform about the parent space axes through its own
+	/// origin: `rotation` is applied after the transform's own rotation and
+	/// the translation is kept, as is the scale. Use `rotate_around` to swing
+	/// the translation around a pivot as well.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::transform::Transform;
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let t = Transform::new(Vector3::<f64>::new(1.0, 0.0, 0.0), Quaternion::identity(), Vector3::new(1.0, 1.0, 1.0));
+	/// let q = Quaternion::from_axis_angle(Vector3::unit_z(), 90.0);
+	///
+	/// assert!(t.post_rotate(q).translation() == t.translation());
+	/// assert!(t.post_rotate(q).rotation() == q * t.rotation());
+	/// ```
+
+	pub fn post_rotate(&self, rotation: Quaternion<F>) -> Transform<F> {
+		Transform {
+			translation: self.translation,
+			rotation: rotation * self.rotation,
+			scale: self.scale,
+		}
+	}
+
+	/// Rotates the transform about a pivot point in the parent space: the
+	/// rotation is applied after this transform's own rotation and the
+	/// translation swings around `pivot`. The scale is kept as is.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::transform::Transform;
+	/// use math3d::points::Point3;
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let t = Transform::new(Vector3::<f64>::new(2.0, 0.0, 0.0), Quaternion::identity(), Vector3::new(1.0, 1.0, 1.0));
+	/// let q = Quaternion::from_axis_angle(Vector3::unit_z(), 90.0);
+	///
+	/// let rotated = t.rotate_around(Point3::new(1.0, 0.0, 0.0), q);
+	///
+	/// assert!((rotated.translation() - Vector3::new(1.0, 1.0, 0.0)).magnitude() < 1e-12);
+	/// ```
+
+	pub fn rotate_around(&self, pivot: Point3<F>, rotation: Quaternion<F>) -> Transform<F> {
+		let pivot = pivot.to_vector();
+		Transform {
+			translation: pivot + rotation.rotate_vector(self.translation - pivot),
+			rotation: rotation * self.rotation,
+			scale: self.scale,
+		}
+	}
+
+	/// Mirror image of the transform across a plane, such that
+	/// `t.mirrored(&plane).transform_point(p')` is the mirror image of
+	/// `t.transform_point(p)` when `p'` is `p` mirrored across the plane's
+	/// normal through the local origin.
+	///
+	/// The translation is mirrored across the plane and the rotation with
+	/// [`Quaternion::mirrored`], which keeps it a proper rotation. The scale is
+	/// kept as is, which is exact for uniform scales and for scale axes parallel
+	/// or perpendicular to the plane normal.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::geometry::Plane;
+	/// use math3d::transform::Transform;
+	/// use math3d::quaternion::Quaternion;
+	/// use math3d::vectors::Vector3;
+	///
+	/// let plane = Plane::new(Vector3::new(1.0, 0.0, 0.0), 0.0);
+	/// let t = Transform::new(Vector3::new(2.0, 1.0, 0.0), Quaternion::identity(), Vector3::new(1.0, 1.0, 1.0));
+	///
+	/// assert!(t.mirrored(&plane).translation() == Vector3::new(-2.0, 1.0, 0.0));
+	/// ```
+
+	pub fn mirrored(&self, plane: &Plane<F>) -> Transform<F> {
+		Transform {
+			translation: Point3::from_vector(self.translation).mirrored_across(plane).to_vector(),
+			rotation: self.rotation.mirrored(plane.normal()),
+			scale: self.scale,
+		}
+	}
+}
+
+impl<F: Float> core::fmt::Display for Transform<F> {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		write!(f, "t: {}, r: {}, s: {}", self.translation, self.rotation, self.scale)
+	}
+}
+
+impl<F: Float> core::cmp::PartialEq for Transform<F> {
+	fn eq(&self, other: &Transform<F>) -> bool {
+		self.translation == other.translation && self.rotation == other.rotation && self.scale == other.scale
+	}
+}
+
+/// Human-readable form of a transform, a `{translation, rotation, scale}` map.
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Transform")]
+struct TransformMap<F: Float> {
+	translation: Vector3<F>,
+	rotation: Quaternion<F>,
+	scale: Vector3<F>,
+}
+
+/// Binary form of a transform.
+
+type TransformTuple<F> = ((F, F, F), Quaternion<F>, (F, F, F));
+
+/// Serializes as a `{translation, rotation, scale}` map for human-readable
+/// formats such as JSON and as a compact `((x, y, z), rotation, (x, y, z))`
+/// tuple for binary formats.
+
+impl<F: Float + Serialize> Serialize for Transform<F> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		if serializer.is_human_readable() {
+			TransformMap {
+				translation: self.translation,
+				rotation: self.rotation,
+				scale: self.scale,
+			}.serialize(serializer)
+		} else {
+			(self.translation.decompose(), self.rotation, self.scale.decompose()).serialize(serializer)
+		}
+	}
+}
+
+impl<'de, F: Float + Deserialize<'de>> Deserialize<'de> for Transform<F> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Transform<F>, D::Error> {
+		if deserializer.is_human_readable() {
+			let t = TransformMap::deserialize(deserializer)?;
+			Ok(Transform::new(t.translation, t.rotation, t.scale))
+		} else {
+			let (t, rotation, s) = TransformTuple::deserialize(deserializer)?;
+			Ok(Transform::new(Vector3::new(t.0, t.1, t.2), rotation, Vector3::new(s.0, s.1, s.2)))
+		}
+	}
+}
@@ -0,0 +1,309 @@
+//! # Triangulate
+//!
+//! Triangulation of planar point sets.
+//!
+//! # Example
+//!
+//! ```
+//! use math3d::points::Point2;
+//! use math3d::triangulate::delaunay;
+//!
+//! let points = [
+//! 	Point2::new(0.0, 0.0),
+//! 	Point2::new(1.0, 0.0),
+//! 	Point2::new(1.0, 1.0),
+//! 	Point2::new(0.0, 1.0),
+//! ];
+//!
+//! let triangles = delaunay(&points);
+//!
+//! assert_eq!(triangles.len(), 2);
+//! ```
+
+use std::collections::HashMap;
+use num::Float;
+use crate::points::Point2;
+use crate::predicates::{incircle, orient2d};
+
+/// Vertex index standing for the point at infinity that closes the convex
+/// hull with ghost triangles.
+
+const INFINITE: usize = usize::MAX;
+
+/// Delaunay triangulation of a set of points.
+///
+/// Returns triangles as indices into `points`, each in counter-clockwise
+/// order. The triangles cover the convex hull of the points and no point lies
+/// strictly inside the circumcircle of any triangle. Cocircular points, such
+/// as the corners of a grid, are triangulated arbitrarily but validly.
+///
+/// Duplicate points are ignored, so only the first of them appears in the
+/// result. Fewer than three points, or points that are all collinear, give
+/// no triangles.
+///
+/// The triangulation is built incrementally with the Bowyer–Watson algorithm
+/// using the exact `orient2d` and `incircle` predicates, so it is robust to
+/// nearly degenerate input. The points are inserted in spatially coherent
+/// order and located by walking from the previous insertion.
+///
+/// # Example
+///
+/// ```
+/// use math3d::points::Point2;
+/// use math3d::triangulate::delaunay;
+///
+/// let points = [
+/// 	Point2::new(0.0, 0.0),
+/// 	Point2::new(2.0, 0.0),
+/// 	Point2::new(1.0, 2.0),
+/// 	Point2::new(1.0, 0.5),
+/// ];
+///
+/// assert_eq!(delaunay(&points).len(), 3);
+/// ```
+
+pub fn delaunay<F: Float>(points: &[Point2<F>]) -> Vec<[u32; 3]> {
+	let mut mesh = match Mesh::seed(points) {
+		Some(mesh) => mesh,
+		None => return Vec::new(),
+	};
+
+	let seed = mesh.triangles[0].vertices;
+	for i in insertion_order(points) {
+		if !seed.contains(&i) {
+			mesh.insert(i);
+		}
+	}
+	mesh.finite_triangles()
+}
+
+/// Triangle of the mesh, with the neighbor across the edge opposite to each
+/// vertex. Ghost triangles have `INFINITE` as their last vertex.
+
+#[derive(Copy, Clone)]
+struct Triangle {
+	vertices: [usize; 3],
+	neighbors: [usize; 3],
+	alive: bool,
+}
+
+impl Triangle {
+	fn is_ghost(&self) -> bool {
+		self.vertices[2] == INFINITE
+	}
+
+	/// Index of the edge running from `a` to `b`, named after the vertex
+	/// opposite to it.
+
+	fn edge(&self, a: usize, b: usize) -> Option<usize> {
+		(0..3).find(|&i| self.vertices[(i + 1) % 3] == a && self.vertices[(i + 2) % 3] == b)
+	}
+}
+
+/// Triangulation of the points inserted so far, closed with a ghost triangle
+/// on every convex hull edge.
+
+struct Mesh<'a, F: Float> {
+	points: &'a [Point2<F>],
+	triangles: Vec<Triangle>,
+	last: usize,
+}
+
+impl<'a, F: Float> Mesh<'a, F> {
+
+	/// Starts the mesh from the first three points that are not collinear.
+
+	fn seed(points: &'a [Point2<F>]) -> Option<Mesh<'a, F>> {
+		let a = 0;
+		let b = (1..points.len()).find(|&i| points[i] != points[a])?;
+		let c = (b + 1..points.len()).find(|&i| orient2d(points[a], points[b], points[i]) != F::zero())?;
+		let (b, c) = if orient2d(points[a], points[b], points[c]) > F::zero() { (b, c) } else { (c, b) };
+
+		let mut mesh = Mesh {
+			points,
+			triangles: Vec::new(),
+			last: 0,
+		};
+		let triangles = [[a, b, c], [b, a, INFINITE], [c, b, INFINITE], [a, c, INFINITE]];
+		let mut edges = HashMap::new();
+		for (t, vertices) in triangles.iter().enumerate() {
+			mesh.triangles.push(Triangle {
+				vertices: *vertices,
+				neighbors: [INFINITE; 3],
+				alive: true,
+			});
+			for i in 0..3 {
+				edges.insert((vertices[(i + 1) % 3], vertices[(i + 2) % 3]), (t, i));
+			}
+		}
+		for (&(u, v), &(t, i)) in &edges {
+			mesh.triangles[t].neighbors[i] = edges[&(v, u)].0;
+		}
+		Some(mesh)
+	}
+
+	/// Inserts a point, replacing the triangles whose circumcircles contain it
+	/// with a fan of triangles around it.
+
+	fn insert(&mut self, p: usize) {
+		let start = self.locate(p);
+		if !self.conflicts(start, p) {
+			// Only duplicates of existing vertices are in conflict with nothing.
+			return;
+		}
+
+		let mut cavity = vec![start];
+		self.triangles[start].alive = false;
+		let mut boundary = Vec::new();
+		let mut next = 0;
+		while next < cavity.len() {
+			let t = cavity[next];
+			next += 1;
+			for i in 0..3 {
+				let n = self.triangles[t].neighbors[i];
+				if !self.triangles[n].alive {
+					continue;
+				}
+				if self.conflicts(n, p) {
+					self.triangles[n].alive = false;
+					cavity.push(n);
+				} else {
+					let v = self.triangles[t].vertices;
+					boundary.push((v[(i + 1) % 3], v[(i + 2) % 3], n));
+				}
+			}
+		}
+
+		let mut starting_at = HashMap::new();
+		let mut ending_at = HashMap::new();
+		let mut created = Vec::with_capacity(boundary.len());
+		for &(u, v, outside) in &boundary {
+			let vertices = if u == INFINITE {
+				[v, p, INFINITE]
+			} else if v == INFINITE {
+				[p, u, INFINITE]
+			} else {
+				[u, v, p]
+			};
+			let t = self.triangles.len();
+			self.triangles.push(Triangle {
+				vertices,
+				neighbors: [INFINITE; 3],
+				alive: true,
+			});
+			self.link(t, u, v, outside);
+			let across = self.triangles[outside].edge(v, u).unwrap();
+			self.triangles[outside].neighbors[across] = t;
+			starting_at.insert(u, t);
+			ending_at.insert(v, t);
+			created.push((t, u, v));
+		}
+		for &(t, u, v) in &created {
+			self.link(t, v, p, starting_at[&v]);
+			self.link(t, p, u, ending_at[&u]);
+		}
+
+		self.last = created
+			.iter()
+			.map(|&(t, _, _)| t)
+			.find(|&t| !self.triangles[t].is_ghost())
+			.unwrap_or(created[0].0);
+	}
+
+	/// Sets the neighbor of triangle `t` across its edge from `a` to `b`.
+
+	fn link(&mut self, t: usize, a: usize, b: usize, neighbor: usize) {
+		let i = self.triangles[t].edge(a, b).unwrap();
+		self.triangles[t].neighbors[i] = neighbor;
+	}
+
+	/// Finds a triangle in conflict with point `p` by walking towards it from
+	/// the triangle created last.
+
+	fn locate(&self, p: usize) -> usize {
+		let point = self.points[p];
+		let mut t = self.last;
+		if self.triangles[t].is_ghost() {
+			t = self.triangles[t].neighbors[2];
+		}
+		'walk: loop {
+			let triangle = &self.triangles[t];
+			if triangle.is_ghost() {
+				return t;
+			}
+			for i in 0..3 {
+				let a = self.points[triangle.vertices[(i + 1) % 3]];
+				let b = self.points[triangle.vertices[(i + 2) % 3]];
+				if orient2d(a, b, point) < F::zero() {
+					t = triangle.neighbors[i];
+					continue 'walk;
+				}
+			}
+			return t;
+		}
+	}
+
+	/// Whether point `p` lies strictly inside the circumcircle of triangle
+	/// `t`. The circumcircle of a ghost triangle is the open half-plane beyond
+	/// its hull edge together with the open edge itself.
+
+	fn conflicts(&self, t: usize, p: usize) -> bool {
+		let point = self.points[p];
+		let [a, b, c] = self.triangles[t].vertices;
+		let (a, b) = (self.points[a], self.points[b]);
+		if c != INFINITE {
+			return incircle(a, b, self.points[c], point) > F::zero();
+		}
+
+		let orientation = orient2d(a, b, point);
+		if orientation != F::zero() {
+			return orientation > F::zero();
+		}
+		let along = |from: Point2<F>, to: Point2<F>| {
+			(point.x() - from.x()) * (to.x() - from.x()) + (point.y() - from.y()) * (to.y() - from.y())
+		};
+		along(a, b) > F::zero() && along(b, a) > F::zero()
+	}
+
+	fn finite_triangles(&self) -> Vec<[u32; 3]> {
+		self.triangles
+			.iter()
+			.filter(|t| t.alive && !t.is_ghost())
+			.map(|t| [t.vertices[0] as u32, t.vertices[1] as u32, t.vertices[2] as u32])
+			.collect()
+	}
+}
+
+/// Indices of the points sorted along a Z-order curve over their bounding
+/// box, so that consecutive insertions are close to each other.
+
+fn insertion_order<F: Float>(points: &[Point2<F>]) -> Vec<usize> {
+	let (mut min_x, mut min_y) = (F::infinity(), F::infinity());
+	let (mut max_x, mut max_y) = (F::neg_infinity(), F::neg_infinity());
+	for p in points {
+		min_x = min_x.min(p.x());
+		min_y = min_y.min(p.y());
+		max_x = max_x.max(p.x());
+		max_y = max_y.max(p.y());
+	}
+	let cells = F::from(u16::MAX).unwrap();
+	let scale_x = if max_x > min_x { cells / (max_x - min_x) } else { F::zero() };
+	let scale_y = if max_y > min_y { cells / (max_y - min_y) } else { F::zero() };
+
+	let spread = |v: u32| {
+		let mut v = v & 0xffff;
+		v = (v | (v << 8)) & 0x00ff_00ff;
+		v = (v | (v << 4)) & 0x0f0f_0f0f;
+		v = (v | (v << 2)) & 0x3333_3333;
+		(v | (v << 1)) & 0x5555_5555
+	};
+	let key = |p: &Point2<F>| {
+		let x = ((p.x() - min_x) * scale_x).to_u32().unwrap_or(0);
+		let y = ((p.y() - min_y) * scale_y).to_u32().unwrap_or(0);
+		spread(x) | (spread(y) << 1)
+	};
+
+	let mut order: Vec<usize> = (0..points.len()).collect();
+	order.sort_by_key(|&i| key(&points[i]));
+	order
+}
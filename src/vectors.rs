@@ -1,11 +1,515 @@
 //! # Vector
 //!
-//! Vector types Vector2, vector3 and Vector4
+//! Vector types Vector2, vector3, UnitVector3 and Vector4, and the boolean
+//! masks BVec3 and BVec4 returned by their component-wise comparisons.
 
 use num::Float;
-use crate::matrices::Matrix3;
+use crate::matrices::{Matrix2, Matrix3};
+use crate::scalar::{cast, impl_scalar_lhs_mul, pack_unsigned_small_float, unpack_unsigned_small_float, FloatExt};
 use serde_derive::{Deserialize, Serialize};
 
+// //////////////////////////////////////////////////////////////////////////////////////
+//
+// Vector2
+//
+// //////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct Vector2<F: Float> {
+	x: F,
+	y: F,
+}
+
+impl<F: Float> Vector2<F> {
+
+	/// Creates a new vector.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector2;
+	///
+	/// let v = Vector2::new(1.0, 2.0);
+	/// ```
+
+	pub fn new(x: F, y: F) -> Vector2<F> {
+		Vector2 {
+			x,
+			y,
+		}
+	}
+
+	/// Construct a zero vector.
+	pub fn zero() -> Vector2<F> {
+		Vector2::new(F::zero(), F::zero())
+	}
+
+	/// Construct a vector with all components set to one.
+	pub fn identity() -> Vector2<F> {
+		Vector2::new(F::one(), F::one())
+	}
+
+	/// Unit vector along the x axis.
+	pub fn unit_x() -> Vector2<F> {
+		Vector2::new(F::one(), F::zero())
+	}
+
+	/// Unit vector along the y axis.
+	pub fn unit_y() -> Vector2<F> {
+		Vector2::new(F::zero(), F::one())
+	}
+
+	/// Creates a vector from an array.
+	pub fn from_array(v: [F; 2]) -> Vector2<F> {
+		Vector2::new(v[0], v[1])
+	}
+
+	/// Returns the x component.
+	pub fn x(&self) -> F {
+		self.x
+	}
+
+	/// Returns the y component.
+	pub fn y(&self) -> F {
+		self.y
+	}
+
+	/// Sets the x component.
+	pub fn set_x(&mut self, x: F) {
+		self.x = x;
+	}
+
+	/// Sets the y component.
+	pub fn set_y(&mut self, y: F) {
+		self.y = y;
+	}
+
+	/// Applies a function to each component.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector2;
+	///
+	/// let v = Vector2::new(1.0, -2.0);
+	///
+	/// assert!(v.map(|c| c * 2.0) == Vector2::new(2.0, -4.0));
+	/// ```
+
+	pub fn map<M: FnMut(F) -> F>(&self, mut f: M) -> Vector2<F> {
+		Vector2 {
+			x: f(self.x),
+			y: f(self.y),
+		}
+	}
+
+	/// Converts the components to another float type, e.g. to hand an `f64`
+	/// simulation result to an `f32` renderer.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector2;
+	///
+	/// let v: Vector2<f32> = Vector2::<f64>::new(1.5, -2.0).cast();
+	///
+	/// assert_eq!(v, Vector2::new(1.5f32, -2.0));
+	/// ```
+
+	pub fn cast<T: Float>(&self) -> Vector2<T> {
+		Vector2 {
+			x: cast(self.x),
+			y: cast(self.y),
+		}
+	}
+
+	/// Combines the components of two vectors pairwise.
+	pub fn zip_with<M: FnMut(F, F) -> F>(&self, other: Vector2<F>, mut f: M) -> Vector2<F> {
+		Vector2 {
+			x: f(self.x, other.x),
+			y: f(self.y, other.y),
+		}
+	}
+
+	/// Folds the components in x, y order into a single value.
+	pub fn fold<B, M: FnMut(B, F) -> B>(&self, init: B, mut f: M) -> B {
+		let acc = f(init, self.x);
+		f(acc, self.y)
+	}
+
+	/// Sum of the components.
+	pub fn element_sum(&self) -> F {
+		self.x + self.y
+	}
+
+	/// Product of the components.
+	pub fn element_product(&self) -> F {
+		self.x * self.y
+	}
+
+	/// Smallest component.
+	pub fn min_element(&self) -> F {
+		self.x.min(self.y)
+	}
+
+	/// Largest component.
+	pub fn max_element(&self) -> F {
+		self.x.max(self.y)
+	}
+
+	/// Dot product of two vectors.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector2;
+	///
+	/// assert_eq!(Vector2::new(1.0, 2.0).dot(Vector2::new(3.0, 4.0)), 11.0);
+	/// ```
+
+	pub fn dot(&self, other: Vector2<F>) -> F {
+		self.zip_with(other, |a, b| a * b).fold(F::zero(), |acc, c| acc + c)
+	}
+
+	/// The vector turned a quarter turn counter-clockwise, `(-y, x)`.
+	pub fn perp(&self) -> Vector2<F> {
+		Vector2::new(-self.y, self.x)
+	}
+
+	/// Perp dot product, the z component of the 3D cross product:
+	///
+	/// $$a_x b_y - a_y b_x$$
+	///
+	/// Positive when `other` lies counter-clockwise of the vector, negative
+	/// when clockwise and zero when they are parallel, which makes it the
+	/// basis of winding and side-of-line tests.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector2;
+	///
+	/// let forward = Vector2::new(1.0, 0.0);
+	///
+	/// assert_eq!(forward.perp_dot(Vector2::new(1.0, 2.0)), 2.0);
+	/// assert_eq!(forward.perp_dot(Vector2::new(1.0, -2.0)), -2.0);
+	/// ```
+
+	pub fn perp_dot(&self, other: Vector2<F>) -> F {
+		self.x * other.y - self.y * other.x
+	}
+
+	/// The vector rotated counter-clockwise by `angle` radians.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector2;
+	///
+	/// let v = Vector2::new(1.0, 0.0).rotate(std::f64::consts::FRAC_PI_2);
+	///
+	/// assert!(v.approx_eq(Vector2::new(0.0, 1.0), 1e-12));
+	/// ```
+
+	pub fn rotate(&self, angle: F) -> Vector2<F> {
+		let (sin, cos) = angle.sin_cos();
+		Vector2::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+	}
+
+	/// Angle of the vector from the positive x axis in radians, in `[-π, π]`,
+	/// positive counter-clockwise.
+	pub fn angle(&self) -> F {
+		self.y.atan2(self.x)
+	}
+
+	/// Length of the vector.
+	pub fn magnitude(&self) -> F {
+		self.x.hypot(self.y)
+	}
+
+	/// Squared length of the vector, cheaper than `magnitude` for comparisons.
+	pub fn magnitude_squared(&self) -> F {
+		self.dot(*self)
+	}
+
+	/// Distance between two vectors treated as points.
+	pub fn distance(&self, other: Vector2<F>) -> F {
+		(*self - other).magnitude()
+	}
+
+	/// Squared distance between two vectors treated as points.
+	pub fn distance_squared(&self, other: Vector2<F>) -> F {
+		(*self - other).magnitude_squared()
+	}
+
+	/// Whether all components are finite, neither infinite nor NaN.
+	pub fn is_finite(&self) -> bool {
+		self.x.is_finite() && self.y.is_finite()
+	}
+
+	/// Whether any component is NaN.
+	pub fn is_nan(&self) -> bool {
+		self.x.is_nan() || self.y.is_nan()
+	}
+
+	/// Whether the magnitude is within `epsilon` of one.
+	pub fn is_normalized(&self, epsilon: F) -> bool {
+		(self.magnitude() - F::one()).abs() <= epsilon
+	}
+
+	/// Whether every component is within `epsilon` of the other vector's.
+	pub fn approx_eq(&self, other: Vector2<F>, epsilon: F) -> bool {
+		self.x.approx_eq(other.x, epsilon) && self.y.approx_eq(other.y, epsilon)
+	}
+
+	/// Component-wise `FloatExt::relative_eq`.
+	pub fn relative_eq(&self, other: Vector2<F>, epsilon: F, max_relative: F) -> bool {
+		self.x.relative_eq(other.x, epsilon, max_relative) && self.y.relative_eq(other.y, epsilon, max_relative)
+	}
+
+	/// Unit vector in the same direction.
+	pub fn normalized(&self) -> Vector2<F> {
+		*self / self.magnitude()
+	}
+
+	/// Unit vector in the same direction, or `None` if the vector is zero,
+	/// too small to normalize or not finite.
+	pub fn try_normalize(&self) -> Option<Vector2<F>> {
+		let mag = self.magnitude();
+		if mag > F::zero() && mag.is_finite() {
+			Some(*self / mag)
+		} else {
+			None
+		}
+	}
+
+	/// Unit vector in the same direction, or `fallback` if the vector cannot
+	/// be normalized.
+	pub fn normalize_or(&self, fallback: Vector2<F>) -> Vector2<F> {
+		self.try_normalize().unwrap_or(fallback)
+	}
+
+	/// Component-wise minimum of two vectors.
+	pub fn min(&self, other: Vector2<F>) -> Vector2<F> {
+		self.zip_with(other, F::min)
+	}
+
+	/// Component-wise maximum of two vectors.
+	pub fn max(&self, other: Vector2<F>) -> Vector2<F> {
+		self.zip_with(other, F::max)
+	}
+
+	/// Clamps each component between the matching components of `min` and
+	/// `max`.
+	pub fn clamp(&self, min: Vector2<F>, max: Vector2<F>) -> Vector2<F> {
+		self.max(min).min(max)
+	}
+
+	/// Component-wise absolute value.
+	pub fn abs(&self) -> Vector2<F> {
+		self.map(F::abs)
+	}
+
+	/// Rounds each component down.
+	pub fn floor(&self) -> Vector2<F> {
+		self.map(F::floor)
+	}
+
+	/// Rounds each component up.
+	pub fn ceil(&self) -> Vector2<F> {
+		self.map(F::ceil)
+	}
+
+	/// Rounds each component to the nearest integer, halfway cases away from
+	/// zero.
+	pub fn round(&self) -> Vector2<F> {
+		self.map(F::round)
+	}
+
+	/// Linear interpolation between two vectors. `t` is clamped to `[0, 1]`;
+	/// see `lerp_unclamped`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector2;
+	///
+	/// let v1 = Vector2::new(0.0, 0.0);
+	/// let v2 = Vector2::new(2.0, 4.0);
+	///
+	/// assert!(v1.lerp(v2, 0.5) == Vector2::new(1.0, 2.0));
+	/// ```
+
+	pub fn lerp(&self, other: Vector2<F>, t: F) -> Vector2<F> {
+		self.lerp_unclamped(other, t.max(F::zero()).min(F::one()))
+	}
+
+	/// Linear interpolation between two vectors, extrapolating for `t`
+	/// outside `[0, 1]`.
+	pub fn lerp_unclamped(&self, other: Vector2<F>, t: F) -> Vector2<F> {
+		self.zip_with(other, |a, b| a + (b - a) * t)
+	}
+}
+
+impl<F: Float> core::fmt::Display for Vector2<F> {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		write!(f, "[{:.4}, {:.4}]", self.x.to_f64().unwrap(), self.y.to_f64().unwrap())
+	}
+}
+
+impl<F: Float> core::cmp::PartialEq for Vector2<F> {
+	fn eq(&self, other: &Vector2<F>) -> bool {
+		self.x == other.x && self.y == other.y
+	}
+}
+
+impl<F: Float> Default for Vector2<F> {
+	fn default() -> Vector2<F> {
+		Vector2::zero()
+	}
+}
+
+impl<F: Float> num::Zero for Vector2<F> {
+	fn zero() -> Vector2<F> {
+		Vector2::zero()
+	}
+
+	fn is_zero(&self) -> bool {
+		*self == Vector2::zero()
+	}
+}
+
+impl<F: Float> std::ops::Add for Vector2<F> {
+	type Output = Vector2<F>;
+
+	fn add(self, other: Vector2<F>) -> Vector2<F> {
+		self.zip_with(other, |a, b| a + b)
+	}
+}
+
+impl<F: Float> std::ops::Sub for Vector2<F> {
+	type Output = Vector2<F>;
+
+	fn sub(self, other: Vector2<F>) -> Vector2<F> {
+		self.zip_with(other, |a, b| a - b)
+	}
+}
+
+impl<F: Float> std::ops::Mul<F> for Vector2<F> {
+	type Output = Vector2<F>;
+
+	fn mul(self, other: F) -> Vector2<F> {
+		self.map(|c| c * other)
+	}
+}
+
+/// Row-vector product `v * M`, the convention of the rotation matrices in
+/// this crate. `Matrix2 * Vector2` is the column-vector product.
+
+impl<F: Float> std::ops::Mul<Matrix2<F>> for Vector2<F> {
+	type Output = Vector2<F>;
+
+	fn mul(self, other: Matrix2<F>) -> Vector2<F> {
+		Vector2::new(self.dot(other.column(0)), self.dot(other.column(1)))
+	}
+}
+
+impl<F: Float> std::ops::Div<F> for Vector2<F> {
+	type Output = Vector2<F>;
+
+	fn div(self, other: F) -> Vector2<F> {
+		self.map(|c| c / other)
+	}
+}
+
+impl<F: Float> std::ops::AddAssign for Vector2<F> {
+	fn add_assign(&mut self, other: Vector2<F>) {
+		*self = *self + other;
+	}
+}
+
+impl<F: Float> std::ops::SubAssign for Vector2<F> {
+	fn sub_assign(&mut self, other: Vector2<F>) {
+		*self = *self - other;
+	}
+}
+
+impl<F: Float> std::ops::MulAssign<F> for Vector2<F> {
+	fn mul_assign(&mut self, other: F) {
+		*self = *self * other;
+	}
+}
+
+impl<F: Float> std::ops::DivAssign<F> for Vector2<F> {
+	fn div_assign(&mut self, other: F) {
+		*self = *self / other;
+	}
+}
+
+impl<F: Float> std::ops::Neg for Vector2<F> {
+	type Output = Vector2<F>;
+
+	fn neg(self) -> Vector2<F> {
+		self.map(|c| -c)
+	}
+}
+
+impl<F: Float> std::ops::Index<usize> for Vector2<F> {
+	type Output = F;
+
+	fn index(&self, index: usize) -> &F {
+		match index {
+			0 => &self.x,
+			1 => &self.y,
+			_ => panic!("Index out of bounds"),
+		}
+	}
+}
+
+impl<F: Float> std::ops::IndexMut<usize> for Vector2<F> {
+	fn index_mut(&mut self, index: usize) -> &mut F {
+		match index {
+			0 => &mut self.x,
+			1 => &mut self.y,
+			_ => panic!("Index out of bounds"),
+		}
+	}
+}
+
+impl<F: Float> From<[F; 2]> for Vector2<F> {
+	fn from(a: [F; 2]) -> Vector2<F> {
+		Vector2::new(a[0], a[1])
+	}
+}
+
+impl<F: Float> From<Vector2<F>> for [F; 2] {
+	fn from(v: Vector2<F>) -> [F; 2] {
+		[v.x, v.y]
+	}
+}
+
+impl<F: Float> From<(F, F)> for Vector2<F> {
+	fn from(a: (F, F)) -> Vector2<F> {
+		Vector2::from([a.0, a.1])
+	}
+}
+
+impl<F: Float> From<Vector2<F>> for (F, F) {
+	fn from(v: Vector2<F>) -> (F, F) {
+		let [x, y] = v.into();
+		(x, y)
+	}
+}
+
+impl<F: Float> TryFrom<&[F]> for Vector2<F> {
+	type Error = std::array::TryFromSliceError;
+
+	/// Fails unless the slice has exactly 2 elements.
+	fn try_from(slice: &[F]) -> Result<Vector2<F>, Self::Error> {
+		<[F; 2]>::try_from(slice).map(Vector2::from)
+	}
+}
+
 // //////////////////////////////////////////////////////////////////////////////////////
 //
 // Vector3
@@ -13,6 +517,7 @@ use serde_derive::{Deserialize, Serialize};
 // //////////////////////////////////////////////////////////////////////////////////////
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[repr(C)]
 pub struct Vector3<F: Float> {
 	x: F,
 	y: F,
@@ -39,613 +544,2662 @@ impl<F: Float> Vector3<F> {
 		}
 	}
 
-	pub fn identity() -> Vector3<F> {
-		Vector3 {
-			x: F::one(),
-			y: F::zero(),
-			z: F::zero(),
+	/// The unit x vector, kept for compatibility. Unlike `Vector2::identity`
+	/// and `Vector4::identity` it does not set every component to one.
+
+	#[deprecated(note = "use `unit_x`, which returns the same vector")]
+	pub fn identity() -> Vector3<F> {
+		Vector3::unit_x()
+	}
+
+	/// Unit vector along the x axis.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// assert!(Vector3::unit_x() == Vector3::new(1.0, 0.0, 0.0));
+	/// ```
+
+	pub fn unit_x() -> Vector3<F> {
+		Vector3::new(F::one(), F::zero(), F::zero())
+	}
+
+	/// Unit vector along the y axis.
+	pub fn unit_y() -> Vector3<F> {
+		Vector3::new(F::zero(), F::one(), F::zero())
+	}
+
+	/// Unit vector along the z axis.
+	pub fn unit_z() -> Vector3<F> {
+		Vector3::new(F::zero(), F::zero(), F::one())
+	}
+
+    /// From array.
+	///
+	/// # Arguments
+	///
+	/// * `v` - The array to create the vector from.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v = Vector3::from_array([1.0, 2.0, 3.0]);
+	/// ```
+
+	pub fn from_array(v: [F; 3]) -> Vector3<F> {
+		Vector3 {
+			x: v[0],
+			y: v[1],
+			z: v[2],
+		}
+	}
+
+	/// Views a slice of exactly 3 elements as a vector without copying, or
+	/// `None` for any other length.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let data = [1.0, 2.0, 3.0];
+	///
+	/// assert!(Vector3::from_slice(&data) == Some(&Vector3::new(1.0, 2.0, 3.0)));
+	/// assert!(Vector3::from_slice(&data[..2]).is_none());
+	/// ```
+
+	pub fn from_slice(slice: &[F]) -> Option<&Vector3<F>> {
+		Vector3::cast_slice(slice).filter(|v| v.len() == 1).map(|v| &v[0])
+	}
+
+	/// Mutable version of `from_slice`.
+	pub fn from_slice_mut(slice: &mut [F]) -> Option<&mut Vector3<F>> {
+		Vector3::cast_slice_mut(slice).filter(|v| v.len() == 1).map(|v| &mut v[0])
+	}
+
+	/// Views a flat `[x0, y0, z0, x1, y1, z1, ...]` buffer as vectors without
+	/// copying, or `None` if its length is not a multiple of 3.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let positions = vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+	/// let vectors = Vector3::cast_slice(&positions).unwrap();
+	///
+	/// assert_eq!(vectors.len(), 2);
+	/// assert!(vectors[1] == Vector3::new(3.0, 4.0, 5.0));
+	/// assert!(Vector3::cast_slice(&positions[1..]).is_none());
+	/// ```
+
+	pub fn cast_slice(slice: &[F]) -> Option<&[Vector3<F>]> {
+		if !slice.len().is_multiple_of(3) {
+			return None;
+		}
+		// SAFETY: `Vector3` is `repr(C)` with three fields of type `F`, so it
+		// has the size of `[F; 3]` and the alignment of `F`, and every group of
+		// 3 elements is a valid vector.
+		Some(unsafe { std::slice::from_raw_parts(slice.as_ptr() as *const Vector3<F>, slice.len() / 3) })
+	}
+
+	/// Mutable version of `cast_slice`. Writes through the vectors change the
+	/// underlying buffer.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let mut positions = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+	/// for v in Vector3::cast_slice_mut(&mut positions).unwrap() {
+	/// 	*v = *v * 2.0;
+	/// }
+	///
+	/// assert_eq!(positions, [0.0, 2.0, 4.0, 6.0, 8.0, 10.0]);
+	/// ```
+
+	pub fn cast_slice_mut(slice: &mut [F]) -> Option<&mut [Vector3<F>]> {
+		if !slice.len().is_multiple_of(3) {
+			return None;
+		}
+		// SAFETY: as in `cast_slice`.
+		Some(unsafe { std::slice::from_raw_parts_mut(slice.as_mut_ptr() as *mut Vector3<F>, slice.len() / 3) })
+	}
+
+	/// Views vectors as a flat `[x0, y0, z0, x1, ...]` buffer without copying,
+	/// the inverse of `cast_slice`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let vectors = [Vector3::new(1.0, 2.0, 3.0), Vector3::new(4.0, 5.0, 6.0)];
+	///
+	/// assert_eq!(Vector3::flatten_slice(&vectors), &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+	/// ```
+
+	pub fn flatten_slice(vectors: &[Vector3<F>]) -> &[F] {
+		// SAFETY: as in `cast_slice`.
+		unsafe { std::slice::from_raw_parts(vectors.as_ptr() as *const F, vectors.len() * 3) }
+	}
+
+	/// Decompose the vector into a tuple of 3 values.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let vector = Vector3::new(1.0, 2.0, 3.0);
+	/// let (x, y, z) = vector.decompose();
+	///
+	/// assert_eq!(x, 1.0);
+	/// assert_eq!(y, 2.0);
+	/// assert_eq!(z, 3.0);
+	/// ```
+
+	pub fn decompose(&self) -> (F, F, F) {
+		(self.x, self.y, self.z)
+	}
+
+	/// Homogeneous Vector4 with this vector as xyz and the given w, 1 for
+	/// points and 0 for directions.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::{Vector3, Vector4};
+	///
+	/// let v = Vector3::new(1.0, 2.0, 3.0).extend(1.0);
+	///
+	/// assert!(v == Vector4::new(1.0, 2.0, 3.0, 1.0));
+	/// ```
+
+	pub fn extend(&self, w: F) -> Vector4<F> {
+		Vector4::new_from_vector3(*self, w)
+	}
+
+	/// Applies a function to each component.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v = Vector3::new(1.0, -2.0, 3.0);
+	///
+	/// assert!(v.map(|c: f64| c.abs()) == Vector3::new(1.0, 2.0, 3.0));
+	/// ```
+
+	pub fn map<M: FnMut(F) -> F>(&self, mut f: M) -> Vector3<F> {
+		Vector3 {
+			x: f(self.x),
+			y: f(self.y),
+			z: f(self.z),
+		}
+	}
+
+	/// Converts the components to another float type.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v: Vector3<f32> = Vector3::<f64>::new(1.0, -2.0, 0.1).cast();
+	///
+	/// assert_eq!(v, Vector3::new(1.0f32, -2.0, 0.1));
+	/// ```
+
+	pub fn cast<T: Float>(&self) -> Vector3<T> {
+		Vector3 {
+			x: cast(self.x),
+			y: cast(self.y),
+			z: cast(self.z),
+		}
+	}
+
+	/// Packs the vector into the 32 bit `R11G11B10_FLOAT` format of HDR color
+	/// and lighting buffers: unsigned floats with a 5 bit exponent and 6, 6
+	/// and 5 bits of mantissa, x in the lowest bits. Rounds to nearest even;
+	/// negative components become zero. The largest finite value is 65024
+	/// for the 11 bit x and y channels and 64512 for the 10 bit z channel,
+	/// and components that round beyond it become infinity.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let color = Vector3::new(1.0, 0.5, 2.0);
+	///
+	/// assert_eq!(color.pack_r11g11b10f(), 0x801c_03c0);
+	/// assert!(Vector3::<f64>::unpack_r11g11b10f(0x801c_03c0) == color);
+	///
+	/// let bright = Vector3::<f64>::unpack_r11g11b10f(Vector3::new(65100.0, 1e6, 65000.0).pack_r11g11b10f());
+	/// assert!(bright == Vector3::new(65024.0, f64::INFINITY, 64512.0));
+	/// let brighter = Vector3::<f64>::unpack_r11g11b10f(Vector3::new(0.0, 0.0, 65024.0).pack_r11g11b10f());
+	/// assert!(brighter.z().is_infinite());
+	/// ```
+
+	pub fn pack_r11g11b10f(&self) -> u32 {
+		pack_unsigned_small_float(self.x, 6)
+			| pack_unsigned_small_float(self.y, 6) << 11
+			| pack_unsigned_small_float(self.z, 5) << 22
+	}
+
+	/// Unpacks a vector from the `R11G11B10_FLOAT` format, the inverse of
+	/// `pack_r11g11b10f`.
+	pub fn unpack_r11g11b10f(packed: u32) -> Vector3<F> {
+		Vector3 {
+			x: unpack_unsigned_small_float(packed & 0x7ff, 6),
+			y: unpack_unsigned_small_float(packed >> 11 & 0x7ff, 6),
+			z: unpack_unsigned_small_float(packed >> 22, 5),
+		}
+	}
+
+	/// Iterates over references to the components in x, y, z order.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v = Vector3::new(1.0, -2.0, 3.0);
+	///
+	/// assert_eq!(v.iter().sum::<f64>(), 2.0);
+	/// ```
+
+	pub fn iter(&self) -> std::array::IntoIter<&F, 3> {
+		[&self.x, &self.y, &self.z].into_iter()
+	}
+
+	/// Iterates over mutable references to the components in x, y, z order.
+	pub fn iter_mut(&mut self) -> std::array::IntoIter<&mut F, 3> {
+		[&mut self.x, &mut self.y, &mut self.z].into_iter()
+	}
+
+	/// Combines the components of two vectors pairwise.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(1.0, 5.0, 3.0);
+	/// let v2 = Vector3::new(4.0, 2.0, 6.0);
+	///
+	/// assert!(v1.zip_with(v2, f64::max) == Vector3::new(4.0, 5.0, 6.0));
+	/// ```
+
+	pub fn zip_with<M: FnMut(F, F) -> F>(&self, other: Vector3<F>, mut f: M) -> Vector3<F> {
+		Vector3 {
+			x: f(self.x, other.x),
+			y: f(self.y, other.y),
+			z: f(self.z, other.z),
+		}
+	}
+
+	/// Folds the components in x, y, z order into a single value.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v = Vector3::new(1.0, 2.0, 3.0);
+	///
+	/// assert_eq!(v.fold(0.0, |acc, c| acc + c), 6.0);
+	/// ```
+
+	pub fn fold<B, M: FnMut(B, F) -> B>(&self, init: B, mut f: M) -> B {
+		let acc = f(init, self.x);
+		let acc = f(acc, self.y);
+		f(acc, self.z)
+	}
+
+	/// Sum of the components, `a_x + a_y + a_z`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// assert_eq!(Vector3::new(1.0, 2.0, 3.0).element_sum(), 6.0);
+	/// ```
+
+	pub fn element_sum(&self) -> F {
+		self.x + self.y + self.z
+	}
+
+	/// Product of the components, `a_x a_y a_z`, such as the volume of a box
+	/// with these extents.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// assert_eq!(Vector3::new(2.0, 3.0, 4.0).element_product(), 24.0);
+	/// ```
+
+	pub fn element_product(&self) -> F {
+		self.x * self.y * self.z
+	}
+
+	/// Smallest component.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// assert_eq!(Vector3::new(2.0, -3.0, 4.0).min_element(), -3.0);
+	/// ```
+
+	pub fn min_element(&self) -> F {
+		self.x.min(self.y).min(self.z)
+	}
+
+	/// Largest component.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// assert_eq!(Vector3::new(2.0, -3.0, 4.0).max_element(), 4.0);
+	/// ```
+
+	pub fn max_element(&self) -> F {
+		self.x.max(self.y).max(self.z)
+	}
+
+	/// Get the value of x component.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v = Vector3::new(1.0, 2.0, 3.0);
+	/// assert_eq!(v.x(), 1.0);
+	/// ```
+
+	pub fn x(&self) -> F {
+		self.x
+	}
+
+	/// Get the value of y component.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v = Vector3::new(1.0, 2.0, 3.0);
+	/// assert_eq!(v.y(), 2.0);
+	/// ```
+
+	pub fn y(&self) -> F {
+		self.y
+	}
+
+	/// Get the value of z component.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v = Vector3::new(1.0, 2.0, 3.0);
+	/// assert_eq!(v.z(), 3.0);
+	/// ```
+
+	pub fn z(&self) -> F {
+		self.z
+	}
+
+	/// Set the value of x component.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let mut v = Vector3::new(1.0, 2.0, 3.0);
+	/// v.set_x(5.0);
+	///
+	/// assert_eq!(v.x(), 5.0);
+	/// ```
+
+	pub fn set_x(&mut self, x: F) {
+		self.x = x;
+	}
+
+	/// Set the value of y component.
+	pub fn set_y(&mut self, y: F) {
+		self.y = y;
+	}
+
+	/// Set the value of z component.
+	pub fn set_z(&mut self, z: F) {
+		self.z = z;
+	}
+
+	/// Mutable reference to the x component.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let mut v = Vector3::new(1.0, 2.0, 3.0);
+	/// *v.x_mut() += 4.0;
+	///
+	/// assert_eq!(v.x(), 5.0);
+	/// ```
+
+	pub fn x_mut(&mut self) -> &mut F {
+		&mut self.x
+	}
+
+	/// Mutable reference to the y component.
+	pub fn y_mut(&mut self) -> &mut F {
+		&mut self.y
+	}
+
+	/// Mutable reference to the z component.
+	pub fn z_mut(&mut self) -> &mut F {
+		&mut self.z
+	}
+
+	/// Sum of two vectors is defined as:
+	///
+	/// $$\vec{a} + \vec{b} = \left(\begin{array}{c} a_x + b_x \\ a_y + b_y \\ a_z + b_z \end{array}\right)$$
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
+	/// let v2 = Vector3::new(4.0, 5.0, 6.0);
+	///
+	/// assert!(v1 + v2 == Vector3::new(5.0, 7.0, 9.0));
+	/// ```
+
+	pub fn sum(&self, other: Vector3<F>) -> Vector3<F> {
+		self.zip_with(other, |a, b| a + b)
+	}
+
+	/// Scalar sum of a vector is defined as:
+	///
+	/// $$\vec{a} + c = \left(\begin{array}{c} a_x + c \\ a_y + c \\ a_z + c \end{array}\right)$$
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
+	///
+	/// assert!(v1 + 2.0 == Vector3::new(3.0, 4.0, 5.0));
+	/// ```
+
+	pub fn sum_scalar(&self, scalar: F) -> Vector3<F> {
+		self.map(|a| a + scalar)
+	}
+
+	/// Subtraction of two vectors is defined as:
+	///
+	/// $$\vec{a} - \vec{b} = \left(\begin{array}{c} a_x - b_x \\ a_y - b_y \\ a_z - b_z \end{array}\right)$$
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
+	/// let v2 = Vector3::new(4.0, 5.0, 6.0);
+	///
+	/// assert!(v1 - v2 == Vector3::new(-3.0, -3.0, -3.0));
+	/// ```
+
+	pub fn difference(&self, other: Vector3<F>) -> Vector3<F> {
+		self.zip_with(other, |a, b| a - b)
+	}
+
+	/// Scalar subtraction of a vector is defined as:
+	///
+	/// $$\vec{a} - c = \left(\begin{array}{c} a_x - c \\ a_y - c \\ a_z - c \end{array}\right)$$
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
+	///
+	/// assert!(v1 - 2.0 == Vector3::new(-1.0, 0.0, 1.0));
+	/// ```
+
+	pub fn difference_scalar(&self, scalar: F) -> Vector3<F> {
+		self.map(|a| a - scalar)
+	}
+
+	/// The product of two vectors is defined as:
+	///
+	/// $$\vec{a} \times \vec{b} = \left(\begin{array}{c} a_x \cdot b_x + a_y \cdot b_y + a_z \cdot b_z \end{array}\right)$$
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
+	/// let v2 = Vector3::new(4.0, 5.0, 6.0);
+	///
+	/// assert!(v1 * v2 == Vector3::new(4.0, 10.0, 18.0));
+	/// ```
+
+    pub fn product(&self, other: Vector3<F>) -> Vector3<F> {
+		self.zip_with(other, |a, b| a * b)
+    }
+
+	// Scalar multiplication of a vector is defined as:
+	///
+	/// $$\vec{a} \times c = \left(\begin{array}{c} a_x \times c \\ a_y \times c \\ a_z \times c \end{array}\right)$$
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
+	///
+	/// assert!(v1.product_scalar(2.0) == Vector3::new(2.0, 4.0, 6.0));
+	/// ```
+
+    pub fn product_scalar(&self, s: F) -> Vector3<F> {
+		self.map(|a| a * s)
+    }
+
+	/// Matrix multiplication of a vector is defined as:
+	///
+	/// $$\vec{a} \times \vec{b} = \left(\begin{array}{c} a_x \times b_x + a_y \times b_y + a_z \times b_z \end{array}\right)$$
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
+	/// let v2 = Vector3::new(4.0, 5.0, 6.0);
+	///
+	/// assert!(v1.product_matrix(v2) == Vector3::new(4.0, 10.0, 18.0));
+	/// ```
+
+	pub fn product_matrix(&self, other: Matrix3<F>) -> Vector3<F> {
+		let mut result = Vector3::<F>::zero();
+
+		for i in 0..3 {
+			for j in 0..3 {
+				result[i] = result[i] + self[j] * other[j][i];
+			}
+		}
+		result
+	}
+
+	/// The quotient of two vectors is defined as:
+	///
+	/// $$\vec{a} \div \vec{b} = \left(\begin{array}{c} a_x \div b_x + a_y \div b_y + a_z \div b_z \end{array}\right)$$
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
+	/// let v2 = Vector3::new(4.0, 5.0, 6.0);
+	///
+	/// assert!(v1 / v2 == Vector3::new(0.25, 0.4, 0.5));
+	/// ```
+
+    pub fn quotient(&self, other: Vector3<F>) -> Vector3<F> {
+		self.zip_with(other, |a, b| a / b)
+    }
+
+	/// The scalar quotient of two vectors is defined as:
+	///
+	/// $$\vec{a} \div c = \left(\begin{array}{c} a_x \div c \\ a_y \div c \\ a_z \div c \end{array}\right)$$
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
+	///
+	/// assert!(v1.quotient_scalar(2.0) == Vector3::new(0.5, 1.0, 1.5));
+
+	pub fn quotient_scalar(&self, s: F) -> Vector3<F> {
+		self.map(|a| a / s)
+	}
+
+	/// Dot product of a vector is defined as the sum of the products of the corresponding components:
+	///
+	/// $$\vec{a} \cdot \vec{b} = a_x \cdot b_x + a_y \cdot b_y + a_z \cdot b_z$$
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
+	///
+	/// assert_eq!(v1.dot(), 14.0);
+	/// ```
+
+    pub fn dot(&self, other: Vector3<F>) -> F {
+		self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+	/// Cross product of two vectors is defined as:
+	///
+	/// $$\vec{a} \times \vec{b} = \left(\begin{array}{c} a_y \times b_z - a_z \times b_y \\ a_z \times b_x - a_x \times b_z \\ a_x \times b_y - a_y \times b_x \end{array}\right)$$
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
+	///
+	/// assert_eq!(v1.cross(Vector3::new(4.0, 5.0, 6.0)), Vector3::new(-3.0, 6.0, -3.0));
+	/// ```
+
+	pub fn cross(&self, other: Vector3<F>) -> Vector3<F> {
+		Vector3 {
+			x: self.y * other.z - self.z * other.y,
+			y: self.z * other.x - self.x * other.z,
+			z: self.x * other.y - self.y * other.x,
+		}
+	}
+
+	/// Scalar triple product, `a · (b × c)`: the signed volume of the
+	/// parallelepiped spanned by the three vectors, positive when they form a
+	/// right-handed system.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(2.0, 0.0, 0.0);
+	///
+	/// assert_eq!(v1.scalar_triple(Vector3::new(0.0, 3.0, 0.0), Vector3::new(0.0, 0.0, 4.0)), 24.0);
+	/// ```
+
+	pub fn scalar_triple(&self, b: Vector3<F>, c: Vector3<F>) -> F {
+		self.dot(b.cross(c))
+	}
+
+	/// Vector triple product, `a × (b × c)`, which equals
+	/// `b (a · c) - c (a · b)`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(1.0, 0.0, 0.0);
+	///
+	/// assert_eq!(v1.vector_triple(Vector3::new(1.0, 1.0, 0.0), Vector3::new(0.0, 0.0, 1.0)), Vector3::new(0.0, 0.0, -1.0));
+	/// ```
+
+	pub fn vector_triple(&self, b: Vector3<F>, c: Vector3<F>) -> Vector3<F> {
+		self.cross(b.cross(c))
+	}
+
+	/// Two unit vectors completing this unit vector to a right-handed
+	/// orthonormal basis `(t, b, self)` with `t × b = self`, such as a
+	/// tangent frame around a surface normal.
+	///
+	/// Uses the branchless construction of Duff et al., "Building an
+	/// Orthonormal Basis, Revisited", which is continuous everywhere except
+	/// across the plane `z = 0` for normals pointing down. The input must be
+	/// normalized.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let n = Vector3::<f64>::new(1.0, 2.0, 3.0).normalized();
+	/// let (t, b) = n.orthonormal_basis();
+	///
+	/// assert!(t.dot(n).abs() < 1e-12 && b.dot(n).abs() < 1e-12 && t.dot(b).abs() < 1e-12);
+	/// assert!((t.cross(b) - n).magnitude() < 1e-12);
+	/// ```
+
+	pub fn orthonormal_basis(&self) -> (Vector3<F>, Vector3<F>) {
+		let one = F::one();
+		let sign = if self.z < F::zero() { -one } else { one };
+		let a = -one / (sign + self.z);
+		let b = self.x * self.y * a;
+		(
+			Vector3::new(one + sign * self.x * self.x * a, sign * b, -sign * self.x),
+			Vector3::new(b, sign + self.y * self.y * a, -self.y),
+		)
+	}
+
+	/// Angle between two non-zero vectors in radians, in `[0, π]`.
+	///
+	/// Computed with `atan2` of the cross and dot products, which stays
+	/// accurate for nearly parallel and nearly opposite vectors where the
+	/// arc cosine of the normalized dot product loses precision.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(1.0, 0.0, 0.0);
+	/// let v2 = Vector3::new(0.0, 3.0, 0.0);
+	///
+	/// assert_eq!(v1.angle_between(v2), std::f64::consts::FRAC_PI_2);
+	/// ```
+
+	pub fn angle_between(&self, other: Vector3<F>) -> F {
+		self.cross(other).magnitude().atan2(self.dot(other))
+	}
+
+	/// Angle between two non-zero vectors in radians, in `[-π, π]`, positive
+	/// when the rotation from `self` to `other` is counter-clockwise looking
+	/// down `axis`, that is when `self × other` points along `axis`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let x = Vector3::new(1.0, 0.0, 0.0);
+	/// let y = Vector3::new(0.0, 1.0, 0.0);
+	/// let z = Vector3::new(0.0, 0.0, 1.0);
+	///
+	/// assert_eq!(x.signed_angle(y, z), std::f64::consts::FRAC_PI_2);
+	/// assert_eq!(y.signed_angle(x, z), -std::f64::consts::FRAC_PI_2);
+	/// ```
+
+	pub fn signed_angle(&self, other: Vector3<F>, axis: Vector3<F>) -> F {
+		let angle = self.angle_between(other);
+		if self.cross(other).dot(axis) < F::zero() {
+			-angle
+		} else {
+			angle
+		}
+	}
+
+	/// The vector rotated by `angle` radians about a non-zero `axis`,
+	/// counter-clockwise looking down the axis, using Rodrigues' formula:
+	///
+	/// $$\vec{v} \cos\theta + (\hat{k} \times \vec{v}) \sin\theta + \hat{k} (\hat{k} \cdot \vec{v}) (1 - \cos\theta)$$
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v = Vector3::new(1.0, 0.0, 1.0);
+	/// let rotated = v.rotated_about(Vector3::new(0.0, 0.0, 2.0), std::f64::consts::FRAC_PI_2);
+	///
+	/// assert!((rotated - Vector3::new(0.0, 1.0, 1.0)).magnitude() < 1e-12);
+	/// ```
+
+	pub fn rotated_about(&self, axis: Vector3<F>, angle: F) -> Vector3<F> {
+		let k = axis.normalized();
+		let (sin, cos) = angle.sin_cos();
+		*self * cos + k.cross(*self) * sin + k * (k.dot(*self) * (F::one() - cos))
+	}
+
+	/// The vector rotated by `angle` radians about the x axis.
+	pub fn rotated_x(&self, angle: F) -> Vector3<F> {
+		let (sin, cos) = angle.sin_cos();
+		Vector3::new(self.x, self.y * cos - self.z * sin, self.y * sin + self.z * cos)
+	}
+
+	/// The vector rotated by `angle` radians about the y axis.
+	pub fn rotated_y(&self, angle: F) -> Vector3<F> {
+		let (sin, cos) = angle.sin_cos();
+		Vector3::new(self.x * cos + self.z * sin, self.y, self.z * cos - self.x * sin)
+	}
+
+	/// The vector rotated by `angle` radians about the z axis.
+	pub fn rotated_z(&self, angle: F) -> Vector3<F> {
+		let (sin, cos) = angle.sin_cos();
+		Vector3::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos, self.z)
+	}
+
+	/// The magnitude of a vector is defined as:
+	///
+	/// $$\sqrt{a_x^2 + a_y^2 + a_z^2}$$
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(1.0, 0.0, 0.0);
+	///
+	/// assert_eq!(v1.magnitude(), 1.0);
+	/// ```
+
+	pub fn magnitude(&self) -> F {
+		self.magnitude_squared().sqrt()
+	}
+
+	/// Squared magnitude, `a_x^2 + a_y^2 + a_z^2`. Avoids the square root of
+	/// `magnitude` when only comparing lengths.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(1.0, 2.0, 2.0);
+	///
+	/// assert_eq!(v1.magnitude_squared(), 9.0);
+	/// ```
+
+	pub fn magnitude_squared(&self) -> F {
+		self.x * self.x + self.y * self.y + self.z * self.z
+	}
+
+	/// Distance between two vectors treated as points, the magnitude of their
+	/// difference.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(1.0, 1.0, 1.0);
+	/// let v2 = Vector3::new(3.0, 3.0, 2.0);
+	///
+	/// assert_eq!(v1.distance(v2), 3.0);
+	/// ```
+
+	pub fn distance(&self, other: Vector3<F>) -> F {
+		(*self - other).magnitude()
+	}
+
+	/// Squared distance between two vectors treated as points. Compare it
+	/// against a squared radius in broad-phase checks to skip the square root.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(1.0, 1.0, 1.0);
+	/// let v2 = Vector3::new(3.0, 3.0, 2.0);
+	///
+	/// assert_eq!(v1.distance_squared(v2), 9.0);
+	/// ```
+
+	pub fn distance_squared(&self, other: Vector3<F>) -> F {
+		(*self - other).magnitude_squared()
+	}
+
+	/// Whether all components are finite, neither infinite nor NaN. Use it to
+	/// validate data from simulation or user input before it reaches code
+	/// that assumes real numbers.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// assert!(Vector3::new(1.0, 2.0, 3.0).is_finite());
+	/// assert!(!Vector3::new(1.0, f64::INFINITY, 3.0).is_finite());
+	/// ```
+
+	pub fn is_finite(&self) -> bool {
+		self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+	}
+
+	/// Whether any component is NaN.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// assert!(Vector3::new(0.0, f64::NAN, 0.0).is_nan());
+	/// ```
+
+	pub fn is_nan(&self) -> bool {
+		self.x.is_nan() || self.y.is_nan() || self.z.is_nan()
+	}
+
+	/// Whether the magnitude is within `epsilon` of one. False for vectors
+	/// with NaN components.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// assert!(Vector3::new(0.6, 0.8, 0.0).is_normalized(1e-12));
+	/// assert!(!Vector3::new(1.0, 1.0, 0.0).is_normalized(1e-6));
+	/// ```
+
+	pub fn is_normalized(&self, epsilon: F) -> bool {
+		(self.magnitude() - F::one()).abs() <= epsilon
+	}
+
+	/// Whether every component is within `epsilon` of the other vector's, for
+	/// comparing computed results without exact float equality.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(0.1 + 0.2, 1.0, 0.0);
+	///
+	/// assert!(v1 != Vector3::new(0.3, 1.0, 0.0));
+	/// assert!(v1.approx_eq(Vector3::new(0.3, 1.0, 0.0), 1e-12));
+	/// ```
+
+	pub fn approx_eq(&self, other: Vector3<F>, epsilon: F) -> bool {
+		self.x.approx_eq(other.x, epsilon) && self.y.approx_eq(other.y, epsilon) && self.z.approx_eq(other.z, epsilon)
+	}
+
+	/// Component-wise `FloatExt::relative_eq`, for values whose magnitude is
+	/// not known in advance.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(1e9, 0.0, -2e9);
+	///
+	/// assert!(v1.relative_eq(v1 * (1.0 + 1e-15), 1e-12, 1e-12));
+	/// ```
+
+	pub fn relative_eq(&self, other: Vector3<F>, epsilon: F, max_relative: F) -> bool {
+		self.x.relative_eq(other.x, epsilon, max_relative)
+			&& self.y.relative_eq(other.y, epsilon, max_relative)
+			&& self.z.relative_eq(other.z, epsilon, max_relative)
+	}
+
+	/// The normalized vector is defined as:
+	///
+	/// $$\frac{\vec{a}}{\sqrt{a_x^2 + a_y^2 + a_z^2}}$$
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
+	///
+	/// assert!(v1.normalized() == Vector3::new(1.0 / (1.0 as f64).sqrt(), 2.0 / (2.0 as f64).sqrt(), 3.0 / (3.0 as f64).sqrt()));
+	/// ```
+
+	pub fn normalized(&self) -> Vector3<F> {
+		let mag = self.magnitude();
+		self.map(|a| a / mag)
+	}
+
+	/// Unit vector in the same direction, or `None` if the vector is zero,
+	/// too small to normalize or not finite. Unlike `normalized`, never
+	/// produces NaN components.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// assert!(Vector3::new(0.0, 3.0, 4.0).try_normalize() == Some(Vector3::new(0.0, 0.6, 0.8)));
+	/// assert!(Vector3::<f64>::zero().try_normalize().is_none());
+	/// ```
+
+	pub fn try_normalize(&self) -> Option<Vector3<F>> {
+		let mag = self.magnitude();
+		if mag > F::zero() && mag.is_finite() {
+			Some(self.map(|a| a / mag))
+		} else {
+			None
+		}
+	}
+
+	/// Unit vector in the same direction, or `fallback` if the vector cannot
+	/// be normalized.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let up = Vector3::new(0.0, 1.0, 0.0);
+	///
+	/// assert!(Vector3::zero().normalize_or(up) == up);
+	/// ```
+
+	pub fn normalize_or(&self, fallback: Vector3<F>) -> Vector3<F> {
+		self.try_normalize().unwrap_or(fallback)
+	}
+
+	/// The opposite vector is defined as:
+	///
+	/// $$\vec{a} \times -1$$
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
+	///
+	/// assert!(v1.opposite() == Vector3::new(-1.0, -2.0, -3.0));
+	/// ```
+
+	pub fn opposite(&self) -> Vector3<F> {
+		self.map(|a| -a)
+	}
+
+	/// Mirror image of the vector across the plane through the origin with the
+	/// given unit normal:
+	///
+	/// $$\vec{a} - 2 (\vec{a} \cdot \hat{n}) \hat{n}$$
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
+	///
+	/// assert!(v1.mirrored(Vector3::new(0.0, 1.0, 0.0)) == Vector3::new(1.0, -2.0, 3.0));
+	/// ```
+
+	pub fn mirrored(&self, normal: Vector3<F>) -> Vector3<F> {
+		let two = F::from(2.0).unwrap();
+		*self - normal * (two * self.dot(normal))
+	}
+
+	/// Projection of the vector onto the line along `other`:
+	///
+	/// $$\frac{\vec{a} \cdot \vec{b}}{\vec{b} \cdot \vec{b}} \vec{b}$$
+	///
+	/// Projecting onto the zero vector gives the zero vector.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
+	///
+	/// assert!(v1.project_onto(Vector3::new(0.0, 2.0, 0.0)) == Vector3::new(0.0, 2.0, 0.0));
+	/// ```
+
+	pub fn project_onto(&self, other: Vector3<F>) -> Vector3<F> {
+		let length_squared = other.dot(other);
+		if length_squared == F::zero() {
+			return Vector3::zero();
+		}
+		other * (self.dot(other) / length_squared)
+	}
+
+	/// Rejection of the vector from `other`, the part perpendicular to it:
+	///
+	/// $$\vec{a} - \frac{\vec{a} \cdot \vec{b}}{\vec{b} \cdot \vec{b}} \vec{b}$$
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
+	///
+	/// assert!(v1.reject_from(Vector3::new(0.0, 2.0, 0.0)) == Vector3::new(1.0, 0.0, 3.0));
+	/// ```
+
+	pub fn reject_from(&self, other: Vector3<F>) -> Vector3<F> {
+		*self - self.project_onto(other)
+	}
+
+	/// Projection of the vector onto the plane through the origin with the
+	/// given normal, which need not be unit length. This is the same as the
+	/// rejection from the normal, e.g. the part of a velocity that slides
+	/// along a surface.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let velocity = Vector3::new(3.0, -4.0, 0.0);
+	///
+	/// assert!(velocity.project_onto_plane(Vector3::new(0.0, 1.0, 0.0)) == Vector3::new(3.0, 0.0, 0.0));
+	/// ```
+
+	pub fn project_onto_plane(&self, normal: Vector3<F>) -> Vector3<F> {
+		self.reject_from(normal)
+	}
+
+	/// Component-wise minimum of two vectors.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(1.0, 5.0, 3.0);
+	/// let v2 = Vector3::new(4.0, 2.0, 6.0);
+	///
+	/// assert!(v1.min(v2) == Vector3::new(1.0, 2.0, 3.0));
+	/// ```
+
+	pub fn min(&self, other: Vector3<F>) -> Vector3<F> {
+		self.zip_with(other, F::min)
+	}
+
+	/// Component-wise maximum of two vectors.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(1.0, 5.0, 3.0);
+	/// let v2 = Vector3::new(4.0, 2.0, 6.0);
+	///
+	/// assert!(v1.max(v2) == Vector3::new(4.0, 5.0, 6.0));
+	/// ```
+
+	pub fn max(&self, other: Vector3<F>) -> Vector3<F> {
+		self.zip_with(other, F::max)
+	}
+
+	/// Clamps each component between the matching components of `min` and
+	/// `max`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v = Vector3::new(-1.0, 0.5, 2.0);
+	/// let clamped = v.clamp(Vector3::zero(), Vector3::new(1.0, 1.0, 1.0));
+	///
+	/// assert!(clamped == Vector3::new(0.0, 0.5, 1.0));
+	/// ```
+
+	pub fn clamp(&self, min: Vector3<F>, max: Vector3<F>) -> Vector3<F> {
+		self.max(min).min(max)
+	}
+
+	/// Component-wise `<`, as a mask for `Vector3::select`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::{BVec3, Vector3};
+	///
+	/// let v1 = Vector3::new(1.0, 5.0, 3.0);
+	/// let v2 = Vector3::new(4.0, 2.0, 3.0);
+	///
+	/// assert_eq!(v1.cmplt(v2), BVec3::new(true, false, false));
+	/// ```
+
+	pub fn cmplt(&self, other: Vector3<F>) -> BVec3 {
+		BVec3::new(self.x < other.x, self.y < other.y, self.z < other.z)
+	}
+
+	/// Component-wise `<=`.
+	pub fn cmple(&self, other: Vector3<F>) -> BVec3 {
+		BVec3::new(self.x <= other.x, self.y <= other.y, self.z <= other.z)
+	}
+
+	/// Component-wise `>`.
+	pub fn cmpgt(&self, other: Vector3<F>) -> BVec3 {
+		BVec3::new(self.x > other.x, self.y > other.y, self.z > other.z)
+	}
+
+	/// Component-wise `>=`.
+	pub fn cmpge(&self, other: Vector3<F>) -> BVec3 {
+		BVec3::new(self.x >= other.x, self.y >= other.y, self.z >= other.z)
+	}
+
+	/// Component-wise exact equality.
+	pub fn cmpeq(&self, other: Vector3<F>) -> BVec3 {
+		BVec3::new(self.x == other.x, self.y == other.y, self.z == other.z)
+	}
+
+	/// Picks each component from `if_true` where the mask is set and from
+	/// `if_false` elsewhere.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v = Vector3::new(-1.0, 2.0, -3.0);
+	/// let positive = Vector3::select(v.cmpgt(Vector3::zero()), v, Vector3::zero());
+	///
+	/// assert!(positive == Vector3::new(0.0, 2.0, 0.0));
+	/// ```
+
+	pub fn select(mask: BVec3, if_true: Vector3<F>, if_false: Vector3<F>) -> Vector3<F> {
+		Vector3::new(
+			if mask.x { if_true.x } else { if_false.x },
+			if mask.y { if_true.y } else { if_false.y },
+			if mask.z { if_true.z } else { if_false.z },
+		)
+	}
+
+	/// Component-wise absolute value.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v = Vector3::new(-1.0, 2.0, -3.0);
+	///
+	/// assert!(v.abs() == Vector3::new(1.0, 2.0, 3.0));
+	/// ```
+
+	pub fn abs(&self) -> Vector3<F> {
+		self.map(F::abs)
+	}
+
+	/// Rounds each component down.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v = Vector3::new(1.5, -1.5, 2.0);
+	///
+	/// assert!(v.floor() == Vector3::new(1.0, -2.0, 2.0));
+	/// ```
+
+	pub fn floor(&self) -> Vector3<F> {
+		self.map(F::floor)
+	}
+
+	/// Rounds each component up.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v = Vector3::new(1.5, -1.5, 2.0);
+	///
+	/// assert!(v.ceil() == Vector3::new(2.0, -1.0, 2.0));
+	/// ```
+
+	pub fn ceil(&self) -> Vector3<F> {
+		self.map(F::ceil)
+	}
+
+	/// Rounds each component to the nearest integer, halfway cases away from
+	/// zero.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v = Vector3::new(1.5, -1.5, 2.4);
+	///
+	/// assert!(v.round() == Vector3::new(2.0, -2.0, 2.0));
+	/// ```
+
+	pub fn round(&self) -> Vector3<F> {
+		self.map(F::round)
+	}
+
+	/// Linear interpolation between two vectors. `t` is clamped to `[0, 1]`;
+	/// see `lerp_unclamped`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(0.0, 0.0, 0.0);
+	/// let v2 = Vector3::new(2.0, 4.0, 6.0);
+	///
+	/// assert!(v1.lerp(v2, 0.5) == Vector3::new(1.0, 2.0, 3.0));
+	/// assert!(v1.lerp(v2, 2.0) == v2);
+	/// ```
+
+	pub fn lerp(&self, other: Vector3<F>, t: F) -> Vector3<F> {
+		self.lerp_unclamped(other, t.max(F::zero()).min(F::one()))
+	}
+
+	/// Linear interpolation between two vectors, extrapolating for `t`
+	/// outside `[0, 1]`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v1 = Vector3::new(0.0, 0.0, 0.0);
+	/// let v2 = Vector3::new(1.0, 0.0, 0.0);
+	///
+	/// assert!(v1.lerp_unclamped(v2, 2.0) == Vector3::new(2.0, 0.0, 0.0));
+	/// ```
+
+	pub fn lerp_unclamped(&self, other: Vector3<F>, t: F) -> Vector3<F> {
+		self.zip_with(other, |a, b| a + (b - a) * t)
+	}
+
+	/// Spherical linear interpolation between two unit vectors, rotating at
+	/// constant angular speed along the great circle through them. `t` is
+	/// clamped to `[0, 1]`.
+	///
+	/// Nearly parallel vectors fall back to normalized linear interpolation.
+	/// Opposite vectors have no unique great circle; one through an arbitrary
+	/// perpendicular direction is used.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let x = Vector3::new(1.0, 0.0, 0.0);
+	/// let y = Vector3::new(0.0, 1.0, 0.0);
+	///
+	/// let half = x.slerp(y, 0.5);
+	///
+	/// assert!((half - Vector3::new(1.0, 1.0, 0.0).normalized()).magnitude() < 1e-12);
+	/// ```
+
+	pub fn slerp(&self, other: Vector3<F>, t: F) -> Vector3<F> {
+		let t = t.max(F::zero()).min(F::one());
+		let cos = self.dot(other);
+		let threshold = F::one() - F::from(1e-6).unwrap();
+
+		if cos > threshold {
+			return self.lerp_unclamped(other, t).normalized();
+		}
+		if cos < -threshold {
+			let pi = F::from(std::f64::consts::PI).unwrap();
+			let perpendicular = self.any_perpendicular();
+			let angle = pi * t;
+			return *self * angle.cos() + perpendicular * angle.sin();
+		}
+
+		let theta = cos.clamped_acos();
+		let sin = theta.sin();
+		*self * (((F::one() - t) * theta).sin() / sin) + other * ((t * theta).sin() / sin)
+	}
+
+	/// Unit vector perpendicular to this non-zero vector.
+
+	pub(crate) fn any_perpendicular(&self) -> Vector3<F> {
+		let axis = if self.x.abs() < self.y.abs().max(self.z.abs()) {
+			Vector3::new(F::one(), F::zero(), F::zero())
+		} else {
+			Vector3::new(F::zero(), F::one(), F::zero())
+		};
+		self.cross(axis).normalized()
+	}
+}
+
+impl<F: Float> core::fmt::Display for Vector3<F> {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		write!(f, "[{:.4}, {:.4}, {:.4}]", self.x().to_f64().unwrap(), self.y().to_f64().unwrap(), self.z().to_f64().unwrap())
+	}
+}
+
+impl<F: Float> core::cmp::PartialEq for Vector3<F> {
+	fn eq(&self, other: &Vector3<F>) -> bool {
+		self.x == other.x && self.y == other.y && self.z == other.z
+	}
+}
+
+impl<F: Float> Default for Vector3<F> {
+	fn default() -> Vector3<F> {
+		Vector3::zero()
+	}
+}
+
+impl<F: Float> num::Zero for Vector3<F> {
+	fn zero() -> Vector3<F> {
+		Vector3::zero()
+	}
+
+	fn is_zero(&self) -> bool {
+		*self == Vector3::zero()
+	}
+}
+
+/// The multiplicative identity of the component-wise product, all ones.
+
+impl<F: Float> num::One for Vector3<F> {
+	fn one() -> Vector3<F> {
+		Vector3::new(F::one(), F::one(), F::one())
+	}
+}
+
+impl<F: Float> std::ops::Add for Vector3<F> {
+	type Output = Vector3<F>;
+
+	fn add(self, other: Vector3<F>) -> Vector3<F> {
+		self.sum(other)
+	}
+}
+
+impl<F: Float> std::ops::Add<F> for Vector3<F> {
+	type Output = Vector3<F>;
+
+	fn add(self, other: F) -> Vector3<F> {
+		self.sum_scalar(other)
+	}
+}
+
+impl<F: Float> std::ops::Sub for Vector3<F> {
+	type Output = Vector3<F>;
+
+	fn sub(self, other: Vector3<F>) -> Vector3<F> {
+		self.difference(other)
+	}
+}
+
+impl<F: Float> std::ops::Sub<F> for Vector3<F> {
+	type Output = Vector3<F>;
+
+	fn sub(self, other: F) -> Vector3<F> {
+		self.difference_scalar(other)
+	}
+}
+
+impl<F: Float> std::ops::Mul for Vector3<F> {
+	type Output = Vector3<F>;
+
+	fn mul(self, other: Vector3<F>) -> Vector3<F> {
+		self.product(other)
+	}
+}
+
+impl<F: Float> std::ops::Mul<F> for Vector3<F> {
+	type Output = Vector3<F>;
+
+	fn mul(self, other: F) -> Vector3<F> {
+		self.product_scalar(other)
+	}
+}
+
+/// Row-vector product `v * M`, the convention of the rotation and transform
+/// matrices in this crate. `Matrix3 * Vector3` is the column-vector product.
+
+impl<F: Float> std::ops::Mul<Matrix3<F>> for Vector3<F> {
+	type Output = Vector3<F>;
+
+	fn mul(self, other: Matrix3<F>) -> Vector3<F> {
+		self.product_matrix(other)
+	}
+}
+
+impl<F: Float> std::ops::Div for Vector3<F> {
+	type Output = Vector3<F>;
+
+	fn div(self, other: Vector3<F>) -> Vector3<F> {
+		self.quotient(other)
+	}
+}
+
+impl<F: Float> std::ops::Div<F> for Vector3<F> {
+	type Output = Vector3<F>;
+
+	fn div(self, other: F) -> Vector3<F> {
+		self.quotient_scalar(other)
+	}
+}
+
+impl<F: Float> std::ops::AddAssign for Vector3<F> {
+	fn add_assign(&mut self, other: Vector3<F>) {
+		*self = *self + other;
+	}
+}
+
+impl<F: Float> std::ops::AddAssign<F> for Vector3<F> {
+	fn add_assign(&mut self, other: F) {
+		*self = *self + other;
+	}
+}
+
+impl<F: Float> std::ops::SubAssign for Vector3<F> {
+	fn sub_assign(&mut self, other: Vector3<F>) {
+		*self = *self - other;
+	}
+}
+
+impl<F: Float> std::ops::SubAssign<F> for Vector3<F> {
+	fn sub_assign(&mut self, other: F) {
+		*self = *self - other;
+	}
+}
+
+impl<F: Float> std::ops::MulAssign for Vector3<F> {
+	fn mul_assign(&mut self, other: Vector3<F>) {
+		*self = *self * other;
+	}
+}
+
+impl<F: Float> std::ops::MulAssign<F> for Vector3<F> {
+	fn mul_assign(&mut self, other: F) {
+		*self = *self * other;
+	}
+}
+
+impl<F: Float> std::ops::MulAssign<Matrix3<F>> for Vector3<F> {
+	fn mul_assign(&mut self, other: Matrix3<F>) {
+		*self = *self * other;
+	}
+}
+
+impl<F: Float> std::ops::DivAssign for Vector3<F> {
+	fn div_assign(&mut self, other: Vector3<F>) {
+		*self = *self / other;
+	}
+}
+
+impl<F: Float> std::ops::DivAssign<F> for Vector3<F> {
+	fn div_assign(&mut self, other: F) {
+		*self = *self / other;
+	}
+}
+
+impl<F: Float> std::ops::Neg for Vector3<F> {
+	type Output = Vector3<F>;
+
+	fn neg(self) -> Vector3<F> {
+		self.opposite()
+	}
+}
+
+impl<F: Float> std::ops::Index<usize> for Vector3<F> {
+	type Output = F;
+
+	fn index(&self, index: usize) -> &F {
+		match index {
+			0 => &self.x,
+			1 => &self.y,
+			2 => &self.z,
+			_ => panic!("Index out of bounds"),
+		}
+	}
+}
+
+impl<F: Float> std::ops::IndexMut<usize> for Vector3<F> {
+	fn index_mut(&mut self, index: usize) -> &mut F {
+		match index {
+			0 => &mut self.x,
+			1 => &mut self.y,
+			2 => &mut self.z,
+			_ => panic!("Index out of bounds"),
+		}
+	}
+}
+
+impl<F: Float> From<[F; 3]> for Vector3<F> {
+	fn from(a: [F; 3]) -> Vector3<F> {
+		Vector3::from_array(a)
+	}
+}
+
+impl<F: Float> From<Vector3<F>> for [F; 3] {
+	fn from(v: Vector3<F>) -> [F; 3] {
+		[v.x, v.y, v.z]
+	}
+}
+
+impl<F: Float> From<(F, F, F)> for Vector3<F> {
+	fn from(a: (F, F, F)) -> Vector3<F> {
+		Vector3::from([a.0, a.1, a.2])
+	}
+}
+
+impl<F: Float> From<Vector3<F>> for (F, F, F) {
+	fn from(v: Vector3<F>) -> (F, F, F) {
+		let [x, y, z] = v.into();
+		(x, y, z)
+	}
+}
+
+impl<F: Float> TryFrom<&[F]> for Vector3<F> {
+	type Error = std::array::TryFromSliceError;
+
+	/// Fails unless the slice has exactly 3 elements.
+	fn try_from(slice: &[F]) -> Result<Vector3<F>, Self::Error> {
+		<[F; 3]>::try_from(slice).map(Vector3::from)
+	}
+}
+
+impl<F: Float> IntoIterator for Vector3<F> {
+	type Item = F;
+	type IntoIter = std::array::IntoIter<F, 3>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		[self.x, self.y, self.z].into_iter()
+	}
+}
+
+impl<'a, F: Float> IntoIterator for &'a Vector3<F> {
+	type Item = &'a F;
+	type IntoIter = std::array::IntoIter<&'a F, 3>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+impl<'a, F: Float> IntoIterator for &'a mut Vector3<F> {
+	type Item = &'a mut F;
+	type IntoIter = std::array::IntoIter<&'a mut F, 3>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter_mut()
+	}
+}
+
+impl<F: Float> std::iter::FromIterator<F> for Vector3<F> {
+	/// Collects exactly three components in x, y, z order.
+	///
+	/// # Panics
+	///
+	/// Panics if the iterator does not yield exactly three items.
+
+	fn from_iter<I: IntoIterator<Item = F>>(iter: I) -> Vector3<F> {
+		let mut iter = iter.into_iter();
+		let mut next = || iter.next().expect("a Vector3 needs three components");
+		let v = Vector3::new(next(), next(), next());
+		assert!(iter.next().is_none(), "a Vector3 needs three components");
+		v
+	}
+}
+
+impl<F: Float> std::iter::Sum for Vector3<F> {
+	fn sum<I: Iterator<Item = Vector3<F>>>(iter: I) -> Vector3<F> {
+		iter.fold(Vector3::zero(), |acc, v| acc + v)
+	}
+}
+
+// //////////////////////////////////////////////////////////////////////////////////////
+//
+// UnitVector3
+//
+// //////////////////////////////////////////////////////////////////////////////////////
+
+/// Vector3 known to be of unit length, for directions, axes and normals.
+///
+/// The only ways to build one are normalizing a vector or asserting unit
+/// length with `new_unchecked`, so functions taking a `UnitVector3` can rely
+/// on it instead of normalizing again or documenting the requirement.
+///
+/// # Example
+///
+/// ```
+/// use math3d::vectors::{UnitVector3, Vector3};
+///
+/// let up = UnitVector3::new(Vector3::new(0.0, 2.0, 0.0)).unwrap();
+///
+/// assert!(up.to_vector() == Vector3::new(0.0, 1.0, 0.0));
+/// assert!(UnitVector3::new(Vector3::<f64>::zero()).is_none());
+/// ```
+
+#[derive(Copy, Clone, Debug)]
+pub struct UnitVector3<F: Float> {
+	v: Vector3<F>,
+}
+
+impl<F: Float> UnitVector3<F> {
+
+	/// Normalizes a vector. Returns `None` if its length is zero or not
+	/// finite.
+
+	pub fn new(v: Vector3<F>) -> Option<UnitVector3<F>> {
+		let magnitude = v.magnitude();
+		if magnitude == F::zero() || !magnitude.is_finite() {
+			return None;
+		}
+		Some(UnitVector3 {
+			v: v / magnitude,
+		})
+	}
+
+	/// Wraps a vector the caller guarantees to be of unit length.
+
+	pub fn new_unchecked(v: Vector3<F>) -> UnitVector3<F> {
+		UnitVector3 {
+			v,
+		}
+	}
+
+	/// The positive x axis.
+	pub fn x_axis() -> UnitVector3<F> {
+		UnitVector3::new_unchecked(Vector3::unit_x())
+	}
+
+	/// The positive y axis.
+	pub fn y_axis() -> UnitVector3<F> {
+		UnitVector3::new_unchecked(Vector3::unit_y())
+	}
+
+	/// The positive z axis.
+	pub fn z_axis() -> UnitVector3<F> {
+		UnitVector3::new_unchecked(Vector3::unit_z())
+	}
+
+	/// Returns the underlying vector.
+	pub fn to_vector(&self) -> Vector3<F> {
+		self.v
+	}
+
+	/// Dot product with a vector.
+	pub fn dot(&self, other: Vector3<F>) -> F {
+		self.v.dot(other)
+	}
+}
+
+impl<F: Float> core::fmt::Display for UnitVector3<F> {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		write!(f, "{}", self.v)
+	}
+}
+
+impl<F: Float> core::cmp::PartialEq for UnitVector3<F> {
+	fn eq(&self, other: &UnitVector3<F>) -> bool {
+		self.v == other.v
+	}
+}
+
+impl<F: Float> std::ops::Mul<F> for UnitVector3<F> {
+	type Output = Vector3<F>;
+
+	fn mul(self, other: F) -> Vector3<F> {
+		self.v * other
+	}
+}
+
+impl<F: Float> std::ops::Neg for UnitVector3<F> {
+	type Output = UnitVector3<F>;
+
+	fn neg(self) -> UnitVector3<F> {
+		UnitVector3::new_unchecked(-self.v)
+	}
+}
+
+impl<F: Float> std::ops::Index<usize> for UnitVector3<F> {
+	type Output = F;
+
+	fn index(&self, index: usize) -> &F {
+		&self.v[index]
+	}
+}
+
+// //////////////////////////////////////////////////////////////////////////////////////
+//
+// Vector4
+//
+// //////////////////////////////////////////////////////////////////////////////////////
+
+use crate::matrices::Matrix4;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Vector4<F: Float> {
+	v: [F; 4],
+}
+
+impl<F: Float> Vector4<F> {
+
+	/// Creates a new Vector4 from the given components.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector4;
+	///
+	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
+	///
+	/// assert!(v1 == Vector4::new(1.0, 2.0, 3.0, 4.0));
+	/// ```
+
+	pub fn new(x: F, y: F, z: F, w: F) -> Vector4<F> {
+		Vector4 {
+			v: [x, y, z, w],
+		}
+	}
+
+	/// Creates a new Vector4 from the given Vector3 and w component.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector4;
+	///
+	/// let v1 = Vector4::new_from_vector3(Vector3::new(1.0, 2.0, 3.0), 4.0);
+	///
+	/// assert!(v1 == Vector4::new(1.0, 2.0, 3.0, 4.0));
+	/// ```
+
+	pub fn new_from_vector3(v: Vector3<F>, w: F) -> Vector4<F> {
+		Vector4 {
+			v: [v.x, v.y, v.z, w],
+		}
+	}
+
+	/// Creates a new Vector4 from an array in x, y, z, w order.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector4;
+	///
+	/// let v1 = Vector4::from_array([1.0, 2.0, 3.0, 4.0]);
+	///
+	/// assert!(v1 == Vector4::new(1.0, 2.0, 3.0, 4.0));
+	/// ```
+
+	pub fn from_array(v: [F; 4]) -> Vector4<F> {
+		Vector4 { v }
+	}
+
+	/// Decomposes this Vector4 into its x, y, z and w components.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector4;
+	///
+	/// let (x, y, z, w) = Vector4::new(1.0, 2.0, 3.0, 4.0).decompose();
+	///
+	/// assert_eq!(x + y + z + w, 10.0);
+	/// ```
+
+	pub fn decompose(self) -> (F, F, F, F) {
+		(self.v[0], self.v[1], self.v[2], self.v[3])
+	}
+
+	/// The xyz components of this Vector4, dropping w.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::{Vector3, Vector4};
+	///
+	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
+	///
+	/// assert!(v1.truncate() == Vector3::new(1.0, 2.0, 3.0));
+	/// ```
+
+	pub fn truncate(self) -> Vector3<F> {
+		Vector3::new(self.v[0], self.v[1], self.v[2])
+	}
+
+	/// Cartesian coordinates of this homogeneous Vector4, xyz divided by w,
+	/// e.g. normalized device coordinates from a clip-space position. A zero
+	/// w, a direction, gives non-finite components.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::{Vector3, Vector4};
+	///
+	/// let clip = Vector4::new(1.0, -2.0, 3.0, 4.0);
+	///
+	/// assert!(clip.perspective_divide() == Vector3::new(0.25, -0.5, 0.75));
+	/// ```
+
+	pub fn perspective_divide(self) -> Vector3<F> {
+		self.truncate() / self.v[3]
+	}
+
+	/// Get the value of x component.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector4;
+	///
+	/// let v = Vector4::new(1.0, 2.0, 3.0, 4.0);
+	///
+	/// assert_eq!(v.x() + v.w(), 5.0);
+	/// ```
+
+	pub fn x(&self) -> F {
+		self.v[0]
+	}
+
+	/// Get the value of y component.
+	pub fn y(&self) -> F {
+		self.v[1]
+	}
+
+	/// Get the value of z component.
+	pub fn z(&self) -> F {
+		self.v[2]
+	}
+
+	/// Get the value of w component.
+	pub fn w(&self) -> F {
+		self.v[3]
+	}
+
+	/// Set the value of x component.
+	pub fn set_x(&mut self, x: F) {
+		self.v[0] = x;
+	}
+
+	/// Set the value of y component.
+	pub fn set_y(&mut self, y: F) {
+		self.v[1] = y;
+	}
+
+	/// Set the value of z component.
+	pub fn set_z(&mut self, z: F) {
+		self.v[2] = z;
+	}
+
+	/// Set the value of w component.
+	pub fn set_w(&mut self, w: F) {
+		self.v[3] = w;
+	}
+
+	/// Mutable reference to the x component.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector4;
+	///
+	/// let mut v = Vector4::new(1.0, 2.0, 3.0, 4.0);
+	/// *v.w_mut() *= 0.5;
+	///
+	/// assert_eq!(v.w(), 2.0);
+	/// ```
+
+	pub fn x_mut(&mut self) -> &mut F {
+		&mut self.v[0]
+	}
+
+	/// Mutable reference to the y component.
+	pub fn y_mut(&mut self) -> &mut F {
+		&mut self.v[1]
+	}
+
+	/// Mutable reference to the z component.
+	pub fn z_mut(&mut self) -> &mut F {
+		&mut self.v[2]
+	}
+
+	/// Mutable reference to the w component.
+	pub fn w_mut(&mut self) -> &mut F {
+		&mut self.v[3]
+	}
+
+	/// Creates a zero Vector4.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector4;
+	///
+	/// let v1 = Vector4::zero();
+	///
+	/// assert!(v1 == Vector4::new(0.0, 0.0, 0.0, 0.0));
+	/// ```
+
+	pub fn zero() -> Vector4<F> {
+		Vector4 {
+			v: [F::zero(), F::zero(), F::zero(), F::zero()],
+		}
+	}
+
+	/// Creates a identity Vector4.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector4;
+	///
+	/// let v1 = Vector4::identity();
+	///
+	/// assert!(v1 == Vector4::new(1.0, 1.0, 1.0, 1.0));
+	/// ```
+
+	pub fn identity() -> Vector4<F> {
+		Vector4 {
+			v: [F::one(), F::one(), F::one(), F::one()],
+		}
+	}
+
+	/// Unit vector along the x axis.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector4;
+	///
+	/// assert!(Vector4::unit_x() == Vector4::new(1.0, 0.0, 0.0, 0.0));
+	/// ```
+
+	pub fn unit_x() -> Vector4<F> {
+		Vector4::new(F::one(), F::zero(), F::zero(), F::zero())
+	}
+
+	/// Unit vector along the y axis.
+	pub fn unit_y() -> Vector4<F> {
+		Vector4::new(F::zero(), F::one(), F::zero(), F::zero())
+	}
+
+	/// Unit vector along the z axis.
+	pub fn unit_z() -> Vector4<F> {
+		Vector4::new(F::zero(), F::zero(), F::one(), F::zero())
+	}
+
+	/// Unit vector along the w axis.
+	pub fn unit_w() -> Vector4<F> {
+		Vector4::new(F::zero(), F::zero(), F::zero(), F::one())
+	}
+
+	/// Applies a function to each component.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector4;
+	///
+	/// let v1 = Vector4::new(1.0, -2.0, 3.0, -4.0);
+	///
+	/// assert!(v1.map(|c: f64| c.abs()) == Vector4::new(1.0, 2.0, 3.0, 4.0));
+	/// ```
+
+	pub fn map<M: FnMut(F) -> F>(self, f: M) -> Vector4<F> {
+		Vector4 {
+			v: self.v.map(f),
+		}
+	}
+
+	/// Converts the components to another float type.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector4;
+	///
+	/// let v: Vector4<f64> = Vector4::new(1.0f32, 2.0, 3.0, 1.0).cast();
+	///
+	/// assert_eq!(v, Vector4::new(1.0, 2.0, 3.0, 1.0));
+	/// ```
+
+	pub fn cast<T: Float>(self) -> Vector4<T> {
+		Vector4 {
+			v: self.v.map(cast),
+		}
+	}
+
+	/// Packs the vector into the 32 bit `R10G10B10A2_UNORM` format of normal
+	/// maps and vertex normals: x, y and z in 10 bits each from the lowest
+	/// bits up and w in the top 2. Components are clamped to `[0, 1]` and
+	/// rounded to the nearest step, NaN packs as zero.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector4;
+	///
+	/// let v = Vector4::new(1.0, 0.0, 0.5, 1.0);
+	///
+	/// assert_eq!(v.pack_rgb10a2(), 0xe000_03ff);
+	/// assert!((Vector4::<f64>::unpack_rgb10a2(0xe000_03ff) - v).magnitude() < 1.0 / 1023.0);
+	/// ```
+
+	pub fn pack_rgb10a2(self) -> u32 {
+		let unorm = |value: F, max: u32| {
+			let max_f = F::from(max).unwrap();
+			(value.max(F::zero()).min(F::one()) * max_f).round().to_u32().unwrap_or(0)
+		};
+		unorm(self.v[0], 1023) | unorm(self.v[1], 1023) << 10 | unorm(self.v[2], 1023) << 20 | unorm(self.v[3], 3) << 30
+	}
+
+	/// Unpacks a vector from the `R10G10B10A2_UNORM` format, the inverse of
+	/// `pack_rgb10a2` up to rounding.
+	pub fn unpack_rgb10a2(packed: u32) -> Vector4<F> {
+		let unorm = |bits: u32, max: u32| F::from(bits & max).unwrap() / F::from(max).unwrap();
+		Vector4::new(unorm(packed, 1023), unorm(packed >> 10, 1023), unorm(packed >> 20, 1023), unorm(packed >> 30, 3))
+	}
+
+	/// Iterates over references to the components in x, y, z, w order.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector4;
+	///
+	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
+	///
+	/// assert!(v1.iter().sum::<f64>() == 10.0);
+	/// ```
+
+	pub fn iter(&self) -> std::slice::Iter<'_, F> {
+		self.v.iter()
+	}
+
+	/// Iterates over mutable references to the components in x, y, z, w order.
+	pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, F> {
+		self.v.iter_mut()
+	}
+
+	/// Combines the components of two vectors pairwise.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector4;
+	///
+	/// let v1 = Vector4::new(1.0, 5.0, 3.0, 0.0);
+	/// let v2 = Vector4::new(4.0, 2.0, 6.0, -1.0);
+	///
+	/// assert!(v1.zip_with(v2, f64::max) == Vector4::new(4.0, 5.0, 6.0, 0.0));
+	/// ```
+
+	pub fn zip_with<M: FnMut(F, F) -> F>(self, other: Vector4<F>, mut f: M) -> Vector4<F> {
+		let mut result = self;
+		for i in 0..4 {
+			result[i] = f(self[i], other[i]);
 		}
+		result
 	}
 
-    /// From array.
-	///
-	/// # Arguments
-	///
-	/// * `v` - The array to create the vector from.
+	/// Folds the components in x, y, z, w order into a single value.
 	///
-	/// # Example
+	/// # Examples
 	///
 	/// ```
-	/// use math3d::vectors::Vector3;
+	/// use math3d::vectors::Vector4;
 	///
-	/// let v = Vector3::from_array([1.0, 2.0, 3.0]);
+	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
+	///
+	/// assert_eq!(v1.fold(0.0, |acc, c| acc + c), 10.0);
 	/// ```
 
-	pub fn from_array(v: [F; 3]) -> Vector3<F> {
-		Vector3 {
-			x: v[0],
-			y: v[1],
-			z: v[2],
-		}
+	pub fn fold<B, M: FnMut(B, F) -> B>(self, init: B, f: M) -> B {
+		self.v.iter().copied().fold(init, f)
 	}
 
-	/// Decompose the vector into a tuple of 3 values.
+	/// Sum of the components of this Vector4.
 	///
-	/// # Example
+	/// # Examples
 	///
 	/// ```
-	/// use math3d::vectors::Vector3;
+	/// use math3d::vectors::Vector4;
 	///
-	/// let vector = Vector3::new(1.0, 2.0, 3.0);
-	/// let (x, y, z) = vector.decompose();
+	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
 	///
-	/// assert_eq!(x, 1.0);
-	/// assert_eq!(y, 2.0);
-	/// assert_eq!(z, 3.0);
+	/// assert!(v1.element_sum() == 10.0);
 	/// ```
 
-	pub fn decompose(&self) -> (F, F, F) {
-		(self.x, self.y, self.z)
+	pub fn element_sum(self) -> F {
+		self.fold(F::zero(), |acc, c| acc + c)
 	}
 
-	/// Get the value of x component.
+	/// Product of the components of this Vector4.
 	///
 	/// # Examples
 	///
 	/// ```
-	/// use math3d::vector::Vector3;
+	/// use math3d::vectors::Vector4;
 	///
-	/// let v = Vector3::new(1.0, 2.0, 3.0);
-	/// assert_eq!(v.x(), 1.0);
+	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
+	///
+	/// assert!(v1.element_product() == 24.0);
 	/// ```
 
-    pub fn x(&self) -> &F {
-		&self.x
-    }
+	pub fn element_product(self) -> F {
+		self.fold(F::one(), |acc, c| acc * c)
+	}
 
-	/// Get the value of y component.
+	/// Smallest component of this Vector4.
 	///
 	/// # Examples
 	///
 	/// ```
-	/// use math3d::vector::Vector3;
+	/// use math3d::vectors::Vector4;
 	///
-	/// let v = Vector3::new(1.0, 2.0, 3.0);
-	/// assert_eq!(v.y(), 2.0);
+	/// let v1 = Vector4::new(1.0, -2.0, 3.0, 4.0);
+	///
+	/// assert!(v1.min_element() == -2.0);
 	/// ```
 
-    pub fn y(&self) -> &F {
-		&self.y
-    }
+	pub fn min_element(self) -> F {
+		self.fold(self.v[0], |acc, c| acc.min(c))
+	}
 
-	/// Get the value of z component.
+	/// Largest component of this Vector4.
 	///
 	/// # Examples
 	///
 	/// ```
-	/// use math3d::vector::Vector3;
+	/// use math3d::vectors::Vector4;
 	///
-	/// let v = Vector3::new(1.0, 2.0, 3.0);
-	/// assert_eq!(v.z(), 3.0);
+	/// let v1 = Vector4::new(1.0, -2.0, 3.0, 4.0);
+	///
+	/// assert!(v1.max_element() == 4.0);
 	/// ```
 
-    pub fn z(&self) -> &F {
-		&self.z
-    }
+	pub fn max_element(self) -> F {
+		self.fold(self.v[0], |acc, c| acc.max(c))
+	}
 
-	/// Sum of two vectors is defined as:
-	///
-	/// $$\vec{a} + \vec{b} = \left(\begin{array}{c} a_x + b_x \\ a_y + b_y \\ a_z + b_z \end{array}\right)$$
+	/// Sums the given Vector4 to this Vector4.
 	///
 	/// # Examples
 	///
 	/// ```
-	/// use math3d::vectors::Vector3;
+	/// use math3d::vectors::Vector4;
 	///
-	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
-	/// let v2 = Vector3::new(4.0, 5.0, 6.0);
+	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
+	/// let v2 = Vector4::new(5.0, 6.0, 7.0, 8.0);
 	///
-	/// assert!(v1 + v2 == Vector3::new(5.0, 7.0, 9.0));
+	/// let v3 = v1.sum(v2);
+	///
+	/// assert!(v3 == Vector4::new(6.0, 8.0, 10.0, 12.0));
 	/// ```
 
-	pub fn sum(&self, other: Vector3<F>) -> Vector3<F> {
-		Vector3 {
-			x: self.x + other.x,
-			y: self.y + other.y,
-			z: self.z + other.z,
-		}
+	pub fn sum(self, other: Vector4<F>) -> Vector4<F> {
+		self.zip_with(other, |a, b| a + b)
 	}
 
-	/// Scalar sum of a vector is defined as:
-	///
-	/// $$\vec{a} + c = \left(\begin{array}{c} a_x + c \\ a_y + c \\ a_z + c \end{array}\right)$$
+	/// Scalar sum of this Vector4 and the given scalar.
 	///
 	/// # Examples
 	///
 	/// ```
-	/// use math3d::vectors::Vector3;
+	/// use math3d::vectors::Vector4;
 	///
-	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
+	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
 	///
-	/// assert!(v1 + 2.0 == Vector3::new(3.0, 4.0, 5.0));
+	/// let v2 = v1.sum_scalar(5.0);
+	///
+	/// assert!(v2 == Vector4::new(6.0, 7.0, 8.0, 9.0));
 	/// ```
 
-	pub fn sum_scalar(&self, scalar: F) -> Vector3<F> {
-		Vector3 {
-			x: self.x + scalar,
-			y: self.y + scalar,
-			z: self.z + scalar,
-		}
+	pub fn sum_scalar(self, scalar: F) -> Vector4<F> {
+		self.map(|a| a + scalar)
 	}
 
-	/// Subtraction of two vectors is defined as:
-	///
-	/// $$\vec{a} - \vec{b} = \left(\begin{array}{c} a_x - b_x \\ a_y - b_y \\ a_z - b_z \end{array}\right)$$
+	/// Subtracts the given Vector4 from this Vector4.
 	///
 	/// # Examples
 	///
 	/// ```
-	/// use math3d::vectors::Vector3;
+	/// use math3d::vectors::Vector4;
 	///
-	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
-	/// let v2 = Vector3::new(4.0, 5.0, 6.0);
+	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
+	/// let v2 = Vector4::new(5.0, 6.0, 7.0, 8.0);
 	///
-	/// assert!(v1 - v2 == Vector3::new(-3.0, -3.0, -3.0));
+	/// let v3 = v1.difference(v2);
+	/// assert!(v3 == Vector4::new(-4.0, -4.0, -4.0, -4.0));
 	/// ```
 
-	pub fn difference(&self, other: Vector3<F>) -> Vector3<F> {
-		Vector3 {
-			x: self.x - other.x,
-			y: self.y - other.y,
-			z: self.z - other.z,
-		}
+	pub fn difference(self, other: Vector4<F>) -> Vector4<F> {
+		self.zip_with(other, |a, b| a - b)
 	}
 
-	/// Scalar subtraction of a vector is defined as:
-	///
-	/// $$\vec{a} - c = \left(\begin{array}{c} a_x - c \\ a_y - c \\ a_z - c \end{array}\right)$$
+
+	/// Scalar difference of this Vector4 and the given scalar.
 	///
 	/// # Examples
 	///
 	/// ```
-	/// use math3d::vectors::Vector3;
+	/// use math3d::vectors::Vector4;
 	///
-	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
+	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
 	///
-	/// assert!(v1 - 2.0 == Vector3::new(-1.0, 0.0, 1.0));
+	/// let v2 = v1.difference_scalar(5.0);
+	///
+	/// assert!(v2 == Vector4::new(-4.0, -3.0, -2.0, -1.0));
 	/// ```
 
-	pub fn difference_scalar(&self, scalar: F) -> Vector3<F> {
-		Vector3 {
-			x: self.x - scalar,
-			y: self.y - scalar,
-			z: self.z - scalar,
-		}
+	pub fn difference_scalar(self, scalar: F) -> Vector4<F> {
+		self.map(|a| a - scalar)
 	}
 
-	/// The product of two vectors is defined as:
-	///
-	/// $$\vec{a} \times \vec{b} = \left(\begin{array}{c} a_x \cdot b_x + a_y \cdot b_y + a_z \cdot b_z \end{array}\right)$$
+	/// Multiplies the given Vector4 with this Vector4.
 	///
 	/// # Examples
 	///
 	/// ```
-	/// use math3d::vectors::Vector3;
+	/// use math3d::vectors::Vector4;
 	///
-	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
-	/// let v2 = Vector3::new(4.0, 5.0, 6.0);
+	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
+	/// let v2 = Vector4::new(5.0, 6.0, 7.0, 8.0);
 	///
-	/// assert!(v1 * v2 == Vector3::new(4.0, 10.0, 18.0));
+	/// let v3 = v1.product(v2);
+	/// assert!(v3 == Vector4::new(5.0, 12.0, 21.0, 32.0));
 	/// ```
 
-    pub fn product(&self, other: Vector3<F>) -> Vector3<F> {
-        Vector3 {
-			x: self.x * other.x,
-			y: self.y * other.y,
-			z: self.z * other.z,
-        }
-    }
+	pub fn product(self, other: Vector4<F>) -> Vector4<F> {
+		self.zip_with(other, |a, b| a * b)
+	}
 
-	// Scalar multiplication of a vector is defined as:
-	///
-	/// $$\vec{a} \times c = \left(\begin{array}{c} a_x \times c \\ a_y \times c \\ a_z \times c \end{array}\right)$$
+	/// Scalar product of this Vector4 and the given scalar.
 	///
 	/// # Examples
 	///
 	/// ```
-	/// use math3d::vectors::Vector3;
+	/// use math3d::vectors::Vector4;
 	///
-	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
+	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
 	///
-	/// assert!(v1.product_scalar(2.0) == Vector3::new(2.0, 4.0, 6.0));
+	/// let v2 = v1.product_scalar(5.0);
+	///
+	/// assert!(v2 == Vector4::new(5.0, 10.0, 15.0, 20.0));
 	/// ```
 
-    pub fn product_scalar(&self, s: F) -> Vector3<F> {
-        Vector3 {
-			x: self.x * s,
-			y: self.y * s,
-			z: self.z * s,
-        }
-    }
+	pub fn product_scalar(self, scalar: F) -> Vector4<F> {
+		self.map(|a| a * scalar)
+	}
 
-	/// Matrix multiplication of a vector is defined as:
-	///
-	/// $$\vec{a} \times \vec{b} = \left(\begin{array}{c} a_x \times b_x + a_y \times b_y + a_z \times b_z \end{array}\right)$$
+	/// Multiply Vector4 with Matrix4, treating the vector as a row vector
+	/// like `Vector3 * Matrix3`: `v * M`. Use `Matrix4::product_vector` for
+	/// column vectors.
 	///
 	/// # Examples
 	///
 	/// ```
-	/// use math3d::vectors::Vector3;
+	/// use math3d::vectors::Vector4;
 	///
-	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
-	/// let v2 = Vector3::new(4.0, 5.0, 6.0);
+	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 1.0);
 	///
-	/// assert!(v1.product_matrix(v2) == Vector3::new(4.0, 10.0, 18.0));
+	/// let m1 = math3d::camera::translation(1.0, 0.0, 0.0);
+	///
+	/// let v2 = v1.product_matrix(m1);
+	///
+	/// assert!(v2 == Vector4::new(2.0, 2.0, 3.0, 1.0));
 	/// ```
 
-	pub fn product_matrix(&self, other: Matrix3<F>) -> Vector3<F> {
-		let mut result = Vector3::<F>::zero();
+	pub fn product_matrix(self, matrix: Matrix4<F>) -> Vector4<F> {
+		let mut result = Vector4::zero();
 
-		for i in 0..3 {
-			for j in 0..3 {
-				result[i] = result[i] + self[j] * other[j][i];
+		for i in 0..4 {
+			for j in 0..4 {
+				result[i] = result[i] + self[j] * matrix[j][i];
 			}
 		}
 		result
 	}
 
-	/// The quotient of two vectors is defined as:
-	///
-	/// $$\vec{a} \div \vec{b} = \left(\begin{array}{c} a_x \div b_x + a_y \div b_y + a_z \div b_z \end{array}\right)$$
+	/// Quotient of this Vector4 and the given Vector4.
 	///
 	/// # Examples
 	///
 	/// ```
-	/// use math3d::vectors::Vector3;
+	/// use math3d::vectors::Vector4;
 	///
-	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
-	/// let v2 = Vector3::new(4.0, 5.0, 6.0);
+	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
+	/// let v2 = Vector4::new(2.0, 2.0, 3.0, 4.0);
 	///
-	/// assert!(v1 / v2 == Vector3::new(0.25, 0.4, 0.5));
+	/// let v3 = v1.quotient(v2);
+	/// assert!(v3 == Vector4::new(0.5, 1.0, 1.5, 2.0));
 	/// ```
 
-    pub fn quotient(&self, other: Vector3<F>) -> Vector3<F> {
-        Vector3 {
-			x: self.x / other.x,
-			y: self.y / other.y,
-			z: self.z / other.z,
-        }
-    }
+	pub fn quotient(self, other: Vector4<F>) -> Vector4<F> {
+		self.zip_with(other, |a, b| a / b)
+	}
 
-	/// The scalar quotient of two vectors is defined as:
-	///
-	/// $$\vec{a} \div c = \left(\begin{array}{c} a_x \div c \\ a_y \div c \\ a_z \div c \end{array}\right)$$
+	/// Scalar quotient of this Vector4 and the given scalar.
 	///
 	/// # Examples
 	///
 	/// ```
-	/// use math3d::vectors::Vector3;
+	/// use math3d::vectors::Vector4;
 	///
-	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
+	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
 	///
-	/// assert!(v1.quotient_scalar(2.0) == Vector3::new(0.5, 1.0, 1.5));
+	/// let v2 = v1.quotient_scalar(2.0);
+	///
+	/// assert!(v2 == Vector4::new(0.5, 1.0, 1.5, 2.0));
+	/// ```
 
-	pub fn quotient_scalar(&self, s: F) -> Vector3<F> {
-		Vector3 {
-			x: self.x / s,
-			y: self.y / s,
-			z: self.z / s,
-		}
+	pub fn quotient_scalar(self, scalar: F) -> Vector4<F> {
+		self.map(|a| a / scalar)
 	}
 
-	/// Dot product of a vector is defined as the sum of the products of the corresponding components:
-	///
-	/// $$\vec{a} \cdot \vec{b} = a_x \cdot b_x + a_y \cdot b_y + a_z \cdot b_z$$
+	/// Returns the dot product of this Vector4 and the given Vector4.
 	///
 	/// # Examples
 	///
 	/// ```
-	/// use math3d::vector::Vector3;
+	/// use math3d::vectors::Vector4;
 	///
-	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
+	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
 	///
-	/// assert_eq!(v1.dot(), 14.0);
-	/// ```
+	/// let v2 = Vector4::new(2.0, 2.0, 3.0, 4.0);
+	///
+	/// let dot = v1.dot(v2);
+	///
+	/// assert!(dot == 40.0);
 
-    pub fn dot(&self, other: Vector3<F>) -> F {
-		self.x * other.x + self.y * other.y + self.z * other.z
-    }
+	pub fn dot(self, other: Vector4<F>) -> F {
+		self.product(other).fold(F::zero(), |acc, c| acc + c)
+	}
 
-	/// Cross product of two vectors is defined as:
-	///
-	/// $$\vec{a} \times \vec{b} = \left(\begin{array}{c} a_y \times b_z - a_z \times b_y \\ a_z \times b_x - a_x \times b_z \\ a_x \times b_y - a_y \times b_x \end{array}\right)$$
+	/// Magnitude of this Vector4.
 	///
 	/// # Examples
 	///
 	/// ```
-	/// use math3d::vector::Vector3;
+	/// use math3d::vectors::Vector4;
 	///
-	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
+	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
 	///
-	/// assert_eq!(v1.cross(Vector3::new(4.0, 5.0, 6.0)), Vector3::new(-3.0, 6.0, -3.0));
+	/// let mag = v1.magnitude();
+	///
+	/// assert!(mag == 5.477225575051661);
 	/// ```
 
-	pub fn cross(&self, other: Vector3<F>) -> Vector3<F> {
-		Vector3 {
-			x: self.y * other.z - self.z * other.y,
-			y: self.z * other.x - self.x * other.z,
-			z: self.x * other.y - self.y * other.x,
-		}
-	}
-
-	/// The magnitude of a vector is defined as:
-	///
-	/// $$\sqrt{a_x^2 + a_y^2 + a_z^2}$$
+	pub fn magnitude(self) -> F {
+		self.dot(self).sqrt()
+	}
+
+	/// Squared magnitude of this Vector4.
 	///
 	/// # Examples
 	///
 	/// ```
-	/// use math3d::vectors::Vector3;
+	/// use math3d::vectors::Vector4;
 	///
-	/// let v1 = Vector3::new(1.0, 0.0, 0.0);
+	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
 	///
-	/// assert_eq!(v1.magnitude(), 1.0);
+	/// assert!(v1.magnitude_squared() == 30.0);
 	/// ```
 
-	pub fn magnitude(&self) -> F {
-		(self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
+	pub fn magnitude_squared(self) -> F {
+		self.dot(self)
 	}
 
-	/// The normalized vector is defined as:
-	///
-	/// $$\frac{\vec{a}}{\sqrt{a_x^2 + a_y^2 + a_z^2}}$$
+	/// Distance between this Vector4 and the given Vector4.
 	///
 	/// # Examples
 	///
 	/// ```
-	/// use math3d::vectors::Vector3;
+	/// use math3d::vectors::Vector4;
 	///
-	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
+	/// let v1 = Vector4::new(1.0, 1.0, 1.0, 1.0);
 	///
-	/// assert!(v1.normalized() == Vector3::new(1.0 / (1.0 as f64).sqrt(), 2.0 / (2.0 as f64).sqrt(), 3.0 / (3.0 as f64).sqrt()));
+	/// assert!(v1.distance(Vector4::new(2.0, 2.0, 2.0, 2.0)) == 2.0);
 	/// ```
 
-	pub fn normalized(&self) -> Vector3<F> {
-		let mag = self.magnitude();
-		Vector3 {
-			x: self.x / mag,
-			y: self.y / mag,
-			z: self.z / mag,
-		}
+	pub fn distance(self, other: Vector4<F>) -> F {
+		(self - other).magnitude()
 	}
 
-	/// The opposite vector is defined as:
-	///
-	/// $$\vec{a} \times -1$$
+	/// Squared distance between this Vector4 and the given Vector4.
 	///
 	/// # Examples
 	///
 	/// ```
-	/// use math3d::vectors::Vector3;
+	/// use math3d::vectors::Vector4;
 	///
-	/// let v1 = Vector3::new(1.0, 2.0, 3.0);
+	/// let v1 = Vector4::new(1.0, 1.0, 1.0, 1.0);
 	///
-	/// assert!(v1.opposite() == Vector3::new(-1.0, -2.0, -3.0));
+	/// assert!(v1.distance_squared(Vector4::new(2.0, 2.0, 2.0, 2.0)) == 4.0);
 	/// ```
 
-	pub fn opposite(&self) -> Vector3<F> {
-		Vector3 {
-			x: -self.x,
-			y: -self.y,
-			z: -self.z,
-		}
-	}
-}
-
-impl<F: Float> core::fmt::Display for Vector3<F> {
-	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
-		write!(f, "[{:.4}, {:.4}, {:.4}]", self.x().to_f64().unwrap(), self.y().to_f64().unwrap(), self.z().to_f64().unwrap())
-	}
-}
-
-impl<F: Float> core::cmp::PartialEq for Vector3<F> {
-	fn eq(&self, other: &Vector3<F>) -> bool {
-		self.x == other.x && self.y == other.y && self.z == other.z
-	}
-}
-
-impl<F: Float> std::ops::Add for Vector3<F> {
-	type Output = Vector3<F>;
-
-	fn add(self, other: Vector3<F>) -> Vector3<F> {
-		self.sum(other)
-	}
-}
-
-impl<F: Float> std::ops::Add<F> for Vector3<F> {
-	type Output = Vector3<F>;
-
-	fn add(self, other: F) -> Vector3<F> {
-		self.sum_scalar(other)
-	}
-}
-
-impl<F: Float> std::ops::Sub for Vector3<F> {
-	type Output = Vector3<F>;
-
-	fn sub(self, other: Vector3<F>) -> Vector3<F> {
-		self.difference(other)
-	}
-}
-
-impl<F: Float> std::ops::Sub<F> for Vector3<F> {
-	type Output = Vector3<F>;
-
-	fn sub(self, other: F) -> Vector3<F> {
-		self.difference_scalar(other)
-	}
-}
-
-impl<F: Float> std::ops::Mul for Vector3<F> {
-	type Output = Vector3<F>;
-
-	fn mul(self, other: Vector3<F>) -> Vector3<F> {
-		self.product(other)
-	}
-}
-
-impl<F: Float> std::ops::Mul<F> for Vector3<F> {
-	type Output = Vector3<F>;
-
-	fn mul(self, other: F) -> Vector3<F> {
-		self.product_scalar(other)
-	}
-}
-
-impl<F: Float> std::ops::Mul<Matrix3<F>> for Vector3<F> {
-	type Output = Vector3<F>;
-
-	fn mul(self, other: Matrix3<F>) -> Vector3<F> {
-		self.product_matrix(other)
-	}
-}
-
-impl<F: Float> std::ops::Div for Vector3<F> {
-	type Output = Vector3<F>;
-
-	fn div(self, other: Vector3<F>) -> Vector3<F> {
-		self.quotient(other)
+	pub fn distance_squared(self, other: Vector4<F>) -> F {
+		(self - other).magnitude_squared()
 	}
-}
 
-impl<F: Float> std::ops::Div<F> for Vector3<F> {
-	type Output = Vector3<F>;
+	/// Whether all components of this Vector4 are finite.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector4;
+	///
+	/// assert!(!Vector4::new(1.0, 2.0, 3.0, f64::NEG_INFINITY).is_finite());
+	/// ```
 
-	fn div(self, other: F) -> Vector3<F> {
-		self.quotient_scalar(other)
+	pub fn is_finite(self) -> bool {
+		self.v.iter().all(|c| c.is_finite())
 	}
-}
 
-impl<F: Float> std::ops::Neg for Vector3<F> {
-	type Output = Vector3<F>;
+	/// Whether any component of this Vector4 is NaN.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector4;
+	///
+	/// assert!(Vector4::new(f64::NAN, 2.0, 3.0, 4.0).is_nan());
+	/// ```
 
-	fn neg(self) -> Vector3<F> {
-		self.opposite()
+	pub fn is_nan(self) -> bool {
+		self.v.iter().any(|c| c.is_nan())
 	}
-}
-
-impl<F: Float> std::ops::Index<usize> for Vector3<F> {
-	type Output = F;
 
-	fn index(&self, index: usize) -> &F {
-		match index {
-			0 => &self.x,
-			1 => &self.y,
-			2 => &self.z,
-			_ => panic!("Index out of bounds"),
-		}
-	}
-}
+	/// Whether the magnitude of this Vector4 is within `epsilon` of one.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use math3d::vectors::Vector4;
+	///
+	/// assert!(Vector4::new(0.5, 0.5, 0.5, 0.5).is_normalized(1e-12));
+	/// ```
 
-impl<F: Float> std::ops::IndexMut<usize> for Vector3<F> {
-	fn index_mut(&mut self, index: usize) -> &mut F {
-		match index {
-			0 => &mut self.x,
-			1 => &mut self.y,
-			2 => &mut self.z,
-			_ => panic!("Index out of bounds"),
-		}
+	pub fn is_normalized(self, epsilon: F) -> bool {
+		(self.magnitude() - F::one()).abs() <= epsilon
 	}
-}
-
-// //////////////////////////////////////////////////////////////////////////////////////
-//
-// Vector4
-//
-// //////////////////////////////////////////////////////////////////////////////////////
-
-use crate::matrices::Matrix4;
-
-#[derive(Clone, Copy, Debug)]
-pub struct Vector4<F: Float> {
-	v: [F; 4],
-}
-
-impl<F: Float> Vector4<F> {
 
-	/// Creates a new Vector4 from the given components.
+	/// Whether every component of this Vector4 is within `epsilon` of the
+	/// given Vector4's.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// use math3d::vectors::Vector4;
 	///
-	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
+	/// let v1 = Vector4::new(0.1 + 0.2, 1.0, 0.0, 1.0);
 	///
-	/// assert!(v1 == Vector4::new(1.0, 2.0, 3.0, 4.0));
+	/// assert!(v1.approx_eq(Vector4::new(0.3, 1.0, 0.0, 1.0), 1e-12));
 	/// ```
 
-	pub fn new(x: F, y: F, z: F, w: F) -> Vector4<F> {
-		Vector4 {
-			v: [x, y, z, w],
-		}
+	pub fn approx_eq(self, other: Vector4<F>, epsilon: F) -> bool {
+		(0..4).all(|i| self.v[i].approx_eq(other.v[i], epsilon))
 	}
 
-	/// Creates a new Vector4 from the given Vector3 and w component.
+	/// Component-wise `FloatExt::relative_eq` of this Vector4 and the given
+	/// Vector4.
+	pub fn relative_eq(self, other: Vector4<F>, epsilon: F, max_relative: F) -> bool {
+		(0..4).all(|i| self.v[i].relative_eq(other.v[i], epsilon, max_relative))
+	}
+
+	/// Unit vector in the same direction as this Vector4. The zero vector
+	/// gives NaN components; use `try_normalize` when that can happen.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// use math3d::vectors::Vector4;
 	///
-	/// let v1 = Vector4::new_from_vector3(Vector3::new(1.0, 2.0, 3.0), 4.0);
+	/// let v1 = Vector4::new(1.0, 1.0, 1.0, 1.0);
 	///
-	/// assert!(v1 == Vector4::new(1.0, 2.0, 3.0, 4.0));
+	/// assert!(v1.normalized() == Vector4::new(0.5, 0.5, 0.5, 0.5));
 	/// ```
 
-	pub fn new_from_vector3(v: Vector3<F>, w: F) -> Vector4<F> {
-		Vector4 {
-			v: [v.x, v.y, v.z, w],
-		}
+	pub fn normalized(self) -> Vector4<F> {
+		let mag = self.magnitude();
+		self.map(|a| a / mag)
 	}
 
-	/// Creates a zero Vector4.
+	/// Unit vector in the same direction, or `None` if the vector is zero,
+	/// too small to normalize or not finite.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// use math3d::vectors::Vector4;
 	///
-	/// let v1 = Vector4::zero();
-	///
-	/// assert!(v1 == Vector4::new(0.0, 0.0, 0.0, 0.0));
+	/// assert!(Vector4::new(0.0, 0.0, 2.0, 0.0).try_normalize() == Some(Vector4::new(0.0, 0.0, 1.0, 0.0)));
+	/// assert!(Vector4::<f64>::zero().try_normalize().is_none());
 	/// ```
 
-	pub fn zero() -> Vector4<F> {
-		Vector4 {
-			v: [F::zero(), F::zero(), F::zero(), F::zero()],
+	pub fn try_normalize(self) -> Option<Vector4<F>> {
+		let mag = self.magnitude();
+		if mag > F::zero() && mag.is_finite() {
+			Some(self.map(|a| a / mag))
+		} else {
+			None
 		}
 	}
 
-	/// Creates a identity Vector4.
+	/// Unit vector in the same direction, or `fallback` if the vector cannot
+	/// be normalized.
+	pub fn normalize_or(self, fallback: Vector4<F>) -> Vector4<F> {
+		self.try_normalize().unwrap_or(fallback)
+	}
+
+	/// The opposite of this Vector4, every component negated.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// use math3d::vectors::Vector4;
 	///
-	/// let v1 = Vector4::identity();
+	/// let v1 = Vector4::new(1.0, -2.0, 3.0, 0.0);
 	///
-	/// assert!(v1 == Vector4::new(1.0, 1.0, 1.0, 1.0));
+	/// assert!(v1.opposite() == Vector4::new(-1.0, 2.0, -3.0, 0.0));
 	/// ```
 
-	pub fn identity() -> Vector4<F> {
-		Vector4 {
-			v: [F::one(), F::one(), F::one(), F::one()],
-		}
+	pub fn opposite(self) -> Vector4<F> {
+		self.map(|a| -a)
 	}
 
-	/// Sums the given Vector4 to this Vector4.
+	/// Projection of this Vector4 onto the given Vector4. Projecting onto the
+	/// zero vector gives the zero vector.
 	///
 	/// # Examples
 	///
@@ -653,222 +3207,224 @@ impl<F: Float> Vector4<F> {
 	/// use math3d::vectors::Vector4;
 	///
 	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
-	/// let v2 = Vector4::new(5.0, 6.0, 7.0, 8.0);
-	///
-	/// let v3 = v1.sum(v2);
 	///
-	/// assert!(v3 == Vector4::new(6.0, 8.0, 10.0, 12.0));
+	/// assert!(v1.project_onto(Vector4::new(0.0, 0.0, 0.0, 2.0)) == Vector4::new(0.0, 0.0, 0.0, 4.0));
 	/// ```
 
-	pub fn sum(self, other: Vector4<F>) -> Vector4<F> {
-		Vector4 {
-			v: [self[0] + other[0], self[1] + other[1], self[2] + other[2], self[3] + other[3]],
+	pub fn project_onto(self, other: Vector4<F>) -> Vector4<F> {
+		let length_squared = other.magnitude_squared();
+		if length_squared == F::zero() {
+			return Vector4::zero();
 		}
+		other * (self.dot(other) / length_squared)
 	}
 
-	/// Scalar sum of this Vector4 and the given scalar.
+	/// Rejection of this Vector4 from the given Vector4, the part
+	/// perpendicular to it.
+	pub fn reject_from(self, other: Vector4<F>) -> Vector4<F> {
+		self - self.project_onto(other)
+	}
+
+	/// Component-wise minimum of two vectors.
 	///
-	/// # Examples
+	/// # Example
 	///
 	/// ```
 	/// use math3d::vectors::Vector4;
 	///
-	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
-	///
-	/// let v2 = v1.sum_scalar(5.0);
+	/// let v1 = Vector4::new(1.0, 5.0, 3.0, 8.0);
+	/// let v2 = Vector4::new(4.0, 2.0, 6.0, 7.0);
 	///
-	/// assert!(v2 == Vector4::new(6.0, 7.0, 8.0, 9.0));
+	/// assert!(v1.min(v2) == Vector4::new(1.0, 2.0, 3.0, 7.0));
 	/// ```
 
-	pub fn sum_scalar(self, scalar: F) -> Vector4<F> {
-		Vector4 {
-			v: [self[0] + scalar, self[1] + scalar, self[2] + scalar, self[3] + scalar],
-		}
+	pub fn min(self, other: Vector4<F>) -> Vector4<F> {
+		self.zip_with(other, F::min)
 	}
 
-	/// Subtracts the given Vector4 from this Vector4.
+	/// Component-wise maximum of two vectors.
 	///
-	/// # Examples
+	/// # Example
 	///
 	/// ```
 	/// use math3d::vectors::Vector4;
 	///
-	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
-	/// let v2 = Vector4::new(5.0, 6.0, 7.0, 8.0);
+	/// let v1 = Vector4::new(1.0, 5.0, 3.0, 8.0);
+	/// let v2 = Vector4::new(4.0, 2.0, 6.0, 7.0);
 	///
-	/// let v3 = v1.difference(v2);
-	/// assert!(v3 == Vector4::new(-4.0, -4.0, -4.0, -4.0));
+	/// assert!(v1.max(v2) == Vector4::new(4.0, 5.0, 6.0, 8.0));
 	/// ```
 
-	pub fn difference(self, other: Vector4<F>) -> Vector4<F> {
-		Vector4 {
-			v: [self[0] - other[0], self[1] - other[1], self[2] - other[2], self[3] - other[3]],
-		}
+	pub fn max(self, other: Vector4<F>) -> Vector4<F> {
+		self.zip_with(other, F::max)
 	}
 
-
-	/// Scalar difference of this Vector4 and the given scalar.
+	/// Clamps each component between the matching components of `min` and
+	/// `max`.
 	///
-	/// # Examples
+	/// # Example
 	///
 	/// ```
 	/// use math3d::vectors::Vector4;
 	///
-	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
-	///
-	/// let v2 = v1.difference_scalar(5.0);
+	/// let v = Vector4::new(-1.0, 0.5, 2.0, 1.0);
+	/// let clamped = v.clamp(Vector4::zero(), Vector4::identity());
 	///
-	/// assert!(v2 == Vector4::new(-4.0, -3.0, -2.0, -1.0));
+	/// assert!(clamped == Vector4::new(0.0, 0.5, 1.0, 1.0));
 	/// ```
 
-	pub fn difference_scalar(self, scalar: F) -> Vector4<F> {
-		Vector4 {
-			v: [self[0] - scalar, self[1] - scalar, self[2] - scalar, self[3] - scalar],
-		}
+	pub fn clamp(self, min: Vector4<F>, max: Vector4<F>) -> Vector4<F> {
+		self.max(min).min(max)
 	}
 
-	/// Multiplies the given Vector4 with this Vector4.
+	/// Component-wise `<`, as a mask for `Vector4::select`.
 	///
-	/// # Examples
+	/// # Example
 	///
 	/// ```
-	/// use math3d::vectors::Vector4;
-	///
-	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
-	/// let v2 = Vector4::new(5.0, 6.0, 7.0, 8.0);
+	/// use math3d::vectors::{BVec4, Vector4};
 	///
-	/// let v3 = v1.product(v2);
-	/// assert!(v3 == Vector4::new(5.0, 12.0, 21.0, 32.0));
+	/// let v1 = Vector4::new(1.0, 5.0, 3.0, 0.0);
+	/// let v2 = Vector4::new(4.0, 2.0, 3.0, 1.0);
+	///
+	/// assert_eq!(v1.cmplt(v2), BVec4::new(true, false, false, true));
 	/// ```
 
-	pub fn product(self, other: Vector4<F>) -> Vector4<F> {
-		Vector4 {
-			v: [self[0] * other[0], self[1] * other[1], self[2] * other[2], self[3] * other[3]],
-		}
+	pub fn cmplt(self, other: Vector4<F>) -> BVec4 {
+		self.compare(other, |a, b| a < b)
 	}
 
-	/// Scalar product of this Vector4 and the given scalar.
+	/// Component-wise `<=`.
+	pub fn cmple(self, other: Vector4<F>) -> BVec4 {
+		self.compare(other, |a, b| a <= b)
+	}
+
+	/// Component-wise `>`.
+	pub fn cmpgt(self, other: Vector4<F>) -> BVec4 {
+		self.compare(other, |a, b| a > b)
+	}
+
+	/// Component-wise `>=`.
+	pub fn cmpge(self, other: Vector4<F>) -> BVec4 {
+		self.compare(other, |a, b| a >= b)
+	}
+
+	/// Component-wise exact equality.
+	pub fn cmpeq(self, other: Vector4<F>) -> BVec4 {
+		self.compare(other, |a, b| a == b)
+	}
+
+	/// Picks each component from `if_true` where the mask is set and from
+	/// `if_false` elsewhere.
 	///
-	/// # Examples
+	/// # Example
 	///
 	/// ```
 	/// use math3d::vectors::Vector4;
 	///
-	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
-	///
-	/// let v2 = v1.product_scalar(5.0);
+	/// let v = Vector4::new(-1.0, 2.0, -3.0, 4.0);
+	/// let positive = Vector4::select(v.cmpgt(Vector4::zero()), v, Vector4::zero());
 	///
-	/// assert!(v2 == Vector4::new(5.0, 10.0, 15.0, 20.0));
+	/// assert!(positive == Vector4::new(0.0, 2.0, 0.0, 4.0));
 	/// ```
 
-	pub fn product_scalar(self, scalar: F) -> Vector4<F> {
-		Vector4 {
-			v: [self[0] * scalar, self[1] * scalar, self[2] * scalar, self[3] * scalar],
-		}
+	pub fn select(mask: BVec4, if_true: Vector4<F>, if_false: Vector4<F>) -> Vector4<F> {
+		let pick = |i: usize| if mask[i] { if_true.v[i] } else { if_false.v[i] };
+		Vector4::new(pick(0), pick(1), pick(2), pick(3))
+	}
+
+	fn compare<C: Fn(F, F) -> bool>(self, other: Vector4<F>, c: C) -> BVec4 {
+		BVec4::new(c(self.v[0], other.v[0]), c(self.v[1], other.v[1]), c(self.v[2], other.v[2]), c(self.v[3], other.v[3]))
 	}
 
-	/// Multiply Vector4 with Matrix4.
+	/// Component-wise absolute value.
 	///
-	/// # Examples
+	/// # Example
 	///
 	/// ```
 	/// use math3d::vectors::Vector4;
 	///
-	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
-	///
-	/// let m1 = math3d::matrices::Matrix4::identity();
+	/// let v = Vector4::new(-1.0, 2.0, -3.0, 4.0);
 	///
-	/// let v2 = v1.product_matrix(m1);
-	///
-	/// assert!(v2 == Vector4::new(1.0, 2.0, 3.0, 4.0));
+	/// assert!(v.abs() == Vector4::new(1.0, 2.0, 3.0, 4.0));
 	/// ```
 
-	pub fn product_matrix(self, matrix: Matrix4<F>) -> Vector4<F> {
-		let mut result = Vector4::zero();
-
-		for i in 0..4 {
-			for j in 0..4 {
-				result[i] = result[i] + self[j] * matrix[i][j];
-			}
-		}
-		result
+	pub fn abs(self) -> Vector4<F> {
+		self.map(F::abs)
 	}
 
-	/// Quotient of this Vector4 and the given Vector4.
+	/// Rounds each component down.
 	///
-	/// # Examples
+	/// # Example
 	///
 	/// ```
 	/// use math3d::vectors::Vector4;
 	///
-	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
-	/// let v2 = Vector4::new(2.0, 2.0, 3.0, 4.0);
+	/// let v = Vector4::new(1.5, -1.5, 2.0, 0.9);
 	///
-	/// let v3 = v1.quotient(v2);
-	/// assert!(v3 == Vector4::new(0.5, 1.0, 1.5, 2.0));
+	/// assert!(v.floor() == Vector4::new(1.0, -2.0, 2.0, 0.0));
 	/// ```
 
-	pub fn quotient(self, other: Vector4<F>) -> Vector4<F> {
-		Vector4 {
-			v: [self[0] / other[0], self[1] / other[1], self[2] / other[2], self[3] / other[3]],
-		}
+	pub fn floor(self) -> Vector4<F> {
+		self.map(F::floor)
 	}
 
-	/// Scalar quotient of this Vector4 and the given scalar.
+	/// Rounds each component up.
 	///
-	/// # Examples
+	/// # Example
 	///
 	/// ```
 	/// use math3d::vectors::Vector4;
 	///
-	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
-	///
-	/// let v2 = v1.quotient_scalar(2.0);
+	/// let v = Vector4::new(1.5, -1.5, 2.0, 0.1);
 	///
-	/// assert!(v2 == Vector4::new(0.5, 1.0, 1.5, 2.0));
+	/// assert!(v.ceil() == Vector4::new(2.0, -1.0, 2.0, 1.0));
 	/// ```
 
-	pub fn quotient_scalar(self, scalar: F) -> Vector4<F> {
-		Vector4 {
-			v: [self[0] / scalar, self[1] / scalar, self[2] / scalar, self[3] / scalar],
-		}
+	pub fn ceil(self) -> Vector4<F> {
+		self.map(F::ceil)
 	}
 
-	/// Returns the dot product of this Vector4 and the given Vector4.
+	/// Rounds each component to the nearest integer, halfway cases away from
+	/// zero.
 	///
-	/// # Examples
+	/// # Example
 	///
 	/// ```
 	/// use math3d::vectors::Vector4;
 	///
-	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
-	///
-	/// let v2 = Vector4::new(2.0, 2.0, 3.0, 4.0);
-	///
-	/// let dot = v1.dot(v2);
+	/// let v = Vector4::new(1.5, -1.5, 2.4, 0.6);
 	///
-	/// assert!(dot == 40.0);
+	/// assert!(v.round() == Vector4::new(2.0, -2.0, 2.0, 1.0));
+	/// ```
 
-	pub fn dot(self, other: Vector4<F>) -> F {
-		self[0] * other[0] + self[1] * other[1] + self[2] * other[2] + self[3] * other[3]
+	pub fn round(self) -> Vector4<F> {
+		self.map(F::round)
 	}
 
-	/// Magnitude of this Vector4.
+	/// Linear interpolation between two vectors. `t` is clamped to `[0, 1]`;
+	/// see `lerp_unclamped`.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// use math3d::vectors::Vector4;
 	///
-	/// let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
-	///
-	/// let mag = v1.magnitude();
+	/// let black = Vector4::new(0.0, 0.0, 0.0, 1.0);
+	/// let white = Vector4::new(1.0, 1.0, 1.0, 1.0);
 	///
-	/// assert!(mag == 5.477225575051661);
+	/// assert!(black.lerp(white, 0.5) == Vector4::new(0.5, 0.5, 0.5, 1.0));
 	/// ```
 
-	pub fn magnitude(self) -> F {
-		(self[0] * self[0] + self[1] * self[1] + self[2] * self[2] + self[3] * self[3]).sqrt()
+	pub fn lerp(self, other: Vector4<F>, t: F) -> Vector4<F> {
+		self.lerp_unclamped(other, t.max(F::zero()).min(F::one()))
+	}
+
+	/// Linear interpolation between two vectors, extrapolating for `t`
+	/// outside `[0, 1]`.
+
+	pub fn lerp_unclamped(self, other: Vector4<F>, t: F) -> Vector4<F> {
+		self.zip_with(other, |a, b| a + (b - a) * t)
 	}
 }
 
@@ -884,6 +3440,30 @@ impl<F: Float> core::cmp::PartialEq for Vector4<F> {
 	}
 }
 
+impl<F: Float> Default for Vector4<F> {
+	fn default() -> Vector4<F> {
+		Vector4::zero()
+	}
+}
+
+impl<F: Float> num::Zero for Vector4<F> {
+	fn zero() -> Vector4<F> {
+		Vector4::zero()
+	}
+
+	fn is_zero(&self) -> bool {
+		*self == Vector4::zero()
+	}
+}
+
+/// The multiplicative identity of the component-wise product, all ones.
+
+impl<F: Float> num::One for Vector4<F> {
+	fn one() -> Vector4<F> {
+		Vector4::identity()
+	}
+}
+
 impl<F: Float> std::ops::Add for Vector4<F> {
 	type Output = Vector4<F>;
 
@@ -932,6 +3512,9 @@ impl<F: Float> std::ops::Mul<F> for Vector4<F> {
 	}
 }
 
+/// Row-vector product `v * M`, the convention of the transform matrices in
+/// this crate. `Matrix4 * Vector4` is the column-vector product.
+
 impl<F: Float> std::ops::Mul<Matrix4<F>> for Vector4<F> {
 	type Output = Vector4<F>;
 
@@ -956,11 +3539,65 @@ impl<F: Float> std::ops::Div<F> for Vector4<F> {
 	}
 }
 
+impl<F: Float> std::ops::AddAssign for Vector4<F> {
+	fn add_assign(&mut self, other: Vector4<F>) {
+		*self = *self + other;
+	}
+}
+
+impl<F: Float> std::ops::AddAssign<F> for Vector4<F> {
+	fn add_assign(&mut self, other: F) {
+		*self = *self + other;
+	}
+}
+
+impl<F: Float> std::ops::SubAssign for Vector4<F> {
+	fn sub_assign(&mut self, other: Vector4<F>) {
+		*self = *self - other;
+	}
+}
+
+impl<F: Float> std::ops::SubAssign<F> for Vector4<F> {
+	fn sub_assign(&mut self, other: F) {
+		*self = *self - other;
+	}
+}
+
+impl<F: Float> std::ops::MulAssign for Vector4<F> {
+	fn mul_assign(&mut self, other: Vector4<F>) {
+		*self = *self * other;
+	}
+}
+
+impl<F: Float> std::ops::MulAssign<F> for Vector4<F> {
+	fn mul_assign(&mut self, other: F) {
+		*self = *self * other;
+	}
+}
+
+impl<F: Float> std::ops::MulAssign<Matrix4<F>> for Vector4<F> {
+	fn mul_assign(&mut self, other: Matrix4<F>) {
+		*self = *self * other;
+	}
+}
+
+impl<F: Float> std::ops::DivAssign for Vector4<F> {
+	fn div_assign(&mut self, other: Vector4<F>) {
+		*self = *self / other;
+	}
+}
+
+impl<F: Float> std::ops::DivAssign<F> for Vector4<F> {
+	fn div_assign(&mut self, other: F) {
+		*self = *self / other;
+	}
+}
+
 impl<F: Float> std::ops::Neg for Vector4<F> {
 	type Output = Vector4<F>;
 
 	fn neg(self) -> Vector4<F> {
-		todo!()
+		self.opposite()
 	}
 }
 
@@ -977,3 +3614,266 @@ impl<F: Float> std::ops::IndexMut<usize> for Vector4<F> {
 		&mut self.v[index]
 	}
 }
+
+impl<F: Float> From<[F; 4]> for Vector4<F> {
+	fn from(a: [F; 4]) -> Vector4<F> {
+		Vector4::from_array(a)
+	}
+}
+
+impl<F: Float> From<Vector4<F>> for [F; 4] {
+	fn from(v: Vector4<F>) -> [F; 4] {
+		v.v
+	}
+}
+
+impl<F: Float> From<(F, F, F, F)> for Vector4<F> {
+	fn from(a: (F, F, F, F)) -> Vector4<F> {
+		Vector4::from([a.0, a.1, a.2, a.3])
+	}
+}
+
+impl<F: Float> From<Vector4<F>> for (F, F, F, F) {
+	fn from(v: Vector4<F>) -> (F, F, F, F) {
+		let [x, y, z, w] = v.into();
+		(x, y, z, w)
+	}
+}
+
+impl<F: Float> TryFrom<&[F]> for Vector4<F> {
+	type Error = std::array::TryFromSliceError;
+
+	/// Fails unless the slice has exactly 4 elements.
+	fn try_from(slice: &[F]) -> Result<Vector4<F>, Self::Error> {
+		<[F; 4]>::try_from(slice).map(Vector4::from)
+	}
+}
+
+impl<F: Float> IntoIterator for Vector4<F> {
+	type Item = F;
+	type IntoIter = std::array::IntoIter<F, 4>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.v.into_iter()
+	}
+}
+
+impl<'a, F: Float> IntoIterator for &'a Vector4<F> {
+	type Item = &'a F;
+	type IntoIter = std::slice::Iter<'a, F>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+impl<'a, F: Float> IntoIterator for &'a mut Vector4<F> {
+	type Item = &'a mut F;
+	type IntoIter = std::slice::IterMut<'a, F>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter_mut()
+	}
+}
+
+impl<F: Float> std::iter::FromIterator<F> for Vector4<F> {
+	/// Collects exactly four components in x, y, z, w order.
+	///
+	/// # Panics
+	///
+	/// Panics if the iterator does not yield exactly four items.
+
+	fn from_iter<I: IntoIterator<Item = F>>(iter: I) -> Vector4<F> {
+		let mut iter = iter.into_iter();
+		let mut next = || iter.next().expect("a Vector4 needs four components");
+		let v = Vector4::new(next(), next(), next(), next());
+		assert!(iter.next().is_none(), "a Vector4 needs four components");
+		v
+	}
+}
+
+impl<F: Float> std::iter::Sum for Vector4<F> {
+	fn sum<I: Iterator<Item = Vector4<F>>>(iter: I) -> Vector4<F> {
+		iter.fold(Vector4::zero(), |acc, v| acc + v)
+	}
+}
+
+// //////////////////////////////////////////////////////////////////////////////////////
+//
+// BVec3
+//
+// //////////////////////////////////////////////////////////////////////////////////////
+
+/// Three component boolean mask, the result of the component-wise
+/// comparisons on Vector3.
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct BVec3 {
+	pub x: bool,
+	pub y: bool,
+	pub z: bool,
+}
+
+impl BVec3 {
+
+	/// Creates a new mask.
+	pub fn new(x: bool, y: bool, z: bool) -> BVec3 {
+		BVec3 { x, y, z }
+	}
+
+	/// Creates a mask with every component set to `value`.
+	pub fn splat(value: bool) -> BVec3 {
+		BVec3::new(value, value, value)
+	}
+
+	/// Whether every component is set.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector3;
+	///
+	/// let v = Vector3::new(1.0, 2.0, 3.0);
+	///
+	/// assert!(v.cmpgt(Vector3::zero()).all());
+	/// assert!(!v.cmpgt(Vector3::new(0.0, 2.0, 0.0)).all());
+	/// ```
+
+	pub fn all(&self) -> bool {
+		self.x && self.y && self.z
+	}
+
+	/// Whether any component is set.
+	pub fn any(&self) -> bool {
+		self.x || self.y || self.z
+	}
+}
+
+impl std::ops::Not for BVec3 {
+	type Output = BVec3;
+
+	fn not(self) -> BVec3 {
+		BVec3::new(!self.x, !self.y, !self.z)
+	}
+}
+
+impl std::ops::BitAnd for BVec3 {
+	type Output = BVec3;
+
+	fn bitand(self, rhs: BVec3) -> BVec3 {
+		BVec3::new(self.x & rhs.x, self.y & rhs.y, self.z & rhs.z)
+	}
+}
+
+impl std::ops::BitOr for BVec3 {
+	type Output = BVec3;
+
+	fn bitor(self, rhs: BVec3) -> BVec3 {
+		BVec3::new(self.x | rhs.x, self.y | rhs.y, self.z | rhs.z)
+	}
+}
+
+impl std::ops::Index<usize> for BVec3 {
+	type Output = bool;
+
+	fn index(&self, index: usize) -> &bool {
+		match index {
+			0 => &self.x,
+			1 => &self.y,
+			2 => &self.z,
+			_ => panic!("Index out of bounds"),
+		}
+	}
+}
+
+// //////////////////////////////////////////////////////////////////////////////////////
+//
+// BVec4
+//
+// //////////////////////////////////////////////////////////////////////////////////////
+
+/// Four component boolean mask, the result of the component-wise
+/// comparisons on Vector4.
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct BVec4 {
+	pub x: bool,
+	pub y: bool,
+	pub z: bool,
+	pub w: bool,
+}
+
+impl BVec4 {
+
+	/// Creates a new mask.
+	pub fn new(x: bool, y: bool, z: bool, w: bool) -> BVec4 {
+		BVec4 { x, y, z, w }
+	}
+
+	/// Creates a mask with every component set to `value`.
+	pub fn splat(value: bool) -> BVec4 {
+		BVec4::new(value, value, value, value)
+	}
+
+	/// Whether every component is set.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math3d::vectors::Vector4;
+	///
+	/// let v = Vector4::new(1.0, 2.0, 3.0, 4.0);
+	///
+	/// assert!(v.cmpgt(Vector4::zero()).all());
+	/// assert!(v.cmpeq(Vector4::new(0.0, 0.0, 0.0, 4.0)).any());
+	/// ```
+
+	pub fn all(&self) -> bool {
+		self.x && self.y && self.z && self.w
+	}
+
+	/// Whether any component is set.
+	pub fn any(&self) -> bool {
+		self.x || self.y || self.z || self.w
+	}
+}
+
+impl std::ops::Not for BVec4 {
+	type Output = BVec4;
+
+	fn not(self) -> BVec4 {
+		BVec4::new(!self.x, !self.y, !self.z, !self.w)
+	}
+}
+
+impl std::ops::BitAnd for BVec4 {
+	type Output = BVec4;
+
+	fn bitand(self, rhs: BVec4) -> BVec4 {
+		BVec4::new(self.x & rhs.x, self.y & rhs.y, self.z & rhs.z, self.w & rhs.w)
+	}
+}
+
+impl std::ops::BitOr for BVec4 {
+	type Output = BVec4;
+
+	fn bitor(self, rhs: BVec4) -> BVec4 {
+		BVec4::new(self.x | rhs.x, self.y | rhs.y, self.z | rhs.z, self.w | rhs.w)
+	}
+}
+
+impl std::ops::Index<usize> for BVec4 {
+	type Output = bool;
+
+	fn index(&self, index: usize) -> &bool {
+		match index {
+			0 => &self.x,
+			1 => &self.y,
+			2 => &self.z,
+			3 => &self.w,
+			_ => panic!("Index out of bounds"),
+		}
+	}
+}
+
+impl_scalar_lhs_mul!(Vector2, Vector3, Vector4);
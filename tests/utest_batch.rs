@@ -0,0 +1,57 @@
+use math3d::batch::{bounds, bounds_par, centroid, centroid_par, max, min, polyline_length};
+use math3d::vectors::Vector3;
+
+#[cfg(test)]
+
+fn cloud(count: usize) -> Vec<Vector3<f64>> {
+	(0..count)
+		.map(|i| {
+			let a = i as f64 * 0.37;
+			Vector3::new(a.sin() * 10.0, (a * 1.3).cos() * 4.0 + 1.0, (i % 11) as f64 - 5.0)
+		})
+		.collect()
+}
+
+#[test]
+fn test_batch_empty_and_single() {
+	let empty: [Vector3<f64>; 0] = [];
+	assert!(centroid(&empty).is_none() && centroid_par(&empty).is_none());
+	assert!(min(&empty).is_none() && max(&empty).is_none());
+	assert!(bounds(&empty).is_none() && bounds_par(&empty).is_none());
+	assert_eq!(polyline_length(&empty), 0.0);
+
+	let one = [Vector3::new(1.0, 2.0, 3.0)];
+	assert!(centroid(&one) == Some(one[0]));
+	assert!(bounds(&one) == Some((one[0], one[0])));
+	assert_eq!(polyline_length(&one), 0.0);
+}
+
+#[test]
+fn test_batch_bounds_match_min_max() {
+	let points = cloud(1000);
+	let (lo, hi) = bounds(&points).unwrap();
+	assert!(lo == min(&points).unwrap() && hi == max(&points).unwrap());
+	assert!(points.iter().all(|p| p.min(lo) == lo && p.max(hi) == hi));
+	assert!(lo[2] == -5.0 && hi[2] == 5.0);
+}
+
+#[test]
+fn test_batch_par_matches_serial() {
+	let points = cloud(100_000);
+	assert!(bounds_par(&points) == bounds(&points));
+	let difference = centroid_par(&points).unwrap() - centroid(&points).unwrap();
+	assert!(difference.magnitude() < 1e-12);
+}
+
+#[test]
+fn test_batch_polyline_length() {
+	let square = [
+		Vector3::new(0.0, 0.0, 0.0),
+		Vector3::new(1.0, 0.0, 0.0),
+		Vector3::new(1.0, 1.0, 0.0),
+		Vector3::new(0.0, 1.0, 0.0),
+		Vector3::new(0.0, 0.0, 0.0),
+	];
+	assert_eq!(polyline_length(&square), 4.0);
+	assert!(centroid(&square[..4]) == Some(Vector3::new(0.5, 0.5, 0.0)));
+}
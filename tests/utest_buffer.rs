@@ -0,0 +1,59 @@
+use math3d::buffer::Vec3Buffer;
+use math3d::matrices::Matrix3;
+use math3d::quaternion::Quaternion;
+use math3d::vectors::Vector3;
+
+#[cfg(test)]
+
+fn vectors(count: usize, seed: f64) -> Vec<Vector3<f64>> {
+	(0..count)
+		.map(|i| {
+			let a = i as f64 * seed;
+			Vector3::new(a.sin(), (a * 0.7).cos() * 2.0, a.fract() - 0.5)
+		})
+		.collect()
+}
+
+#[test]
+fn test_vec3_buffer_round_trip() {
+	let aos = vectors(100, 0.31);
+	let mut soa = Vec3Buffer::from(&aos[..]);
+	assert_eq!(soa.len(), 100);
+	assert!(soa.to_vectors() == aos);
+	assert!(soa.x().iter().zip(&aos).all(|(x, v)| *x == v.x()));
+
+	soa.set(7, Vector3::new(1.0, 2.0, 3.0));
+	assert!(soa.get(7) == Vector3::new(1.0, 2.0, 3.0));
+	soa.push(Vector3::new(4.0, 5.0, 6.0));
+	assert!(soa.iter().last() == Some(Vector3::new(4.0, 5.0, 6.0)));
+	assert!(Vec3Buffer::<f64>::default().is_empty());
+}
+
+#[test]
+fn test_vec3_buffer_bulk_ops_match_aos() {
+	let (a, b) = (vectors(257, 0.31), vectors(257, 1.7));
+	let (mut sa, sb) = (Vec3Buffer::from(&a[..]), Vec3Buffer::from(&b[..]));
+
+	let dots = sa.dot(&sb);
+	assert!(dots.iter().zip(a.iter().zip(&b)).all(|(d, (u, v))| (*d - u.dot(*v)).abs() < 1e-15));
+
+	sa.add(&sb);
+	sa.add_scaled(&sb, -2.0);
+	sa.scale(3.0);
+	let expected: Vec<_> = a.iter().zip(&b).map(|(u, v)| (*u + *v + *v * -2.0) * 3.0).collect();
+	assert!(sa.iter().zip(&expected).all(|(u, v)| (u - *v).magnitude() < 1e-12));
+
+	let q = Quaternion::from_axis_angle(Vector3::new(1.0, -1.0, 2.0).normalized(), 75.0);
+	let m = Matrix3::from_array_2d([[1.0, 2.0, 0.5], [0.0, -1.0, 3.0], [2.0, 0.0, 1.0]]);
+	let mut rotated = Vec3Buffer::from(&a[..]);
+	rotated.rotate(q);
+	rotated.transform(&m);
+	assert!(rotated.iter().zip(&a).all(|(r, v)| (r - q.rotate_vector(*v) * m).magnitude() < 1e-12));
+}
+
+#[test]
+#[should_panic(expected = "buffers differ in length")]
+fn test_vec3_buffer_length_mismatch_panics() {
+	let mut a = Vec3Buffer::splat(Vector3::new(1.0, 0.0, 0.0), 3);
+	a.add(&Vec3Buffer::splat(Vector3::new(1.0, 0.0, 0.0), 4));
+}
@@ -0,0 +1,220 @@
+use math3d::camera::{cursor_to_ground_point, horizontal_fov_from_vertical, motion_matrix, vertical_fov_from_horizontal, Camera, Viewport};
+use math3d::geometry::Plane;
+use math3d::matrices::Matrix4;
+use math3d::points::{Point2, Point3};
+use math3d::quaternion::Quaternion;
+use math3d::transform::Transform;
+use math3d::vectors::{Vector3, Vector4};
+
+#[cfg(test)]
+
+fn rotations() -> Vec<Quaternion<f64>> {
+	vec![
+		Quaternion::identity(),
+		Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 180.0),
+		Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), -179.0),
+		Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 181.0),
+		Quaternion::from_axis_angle(Vector3::new(1.0, -2.0, 0.5).normalized(), 73.0),
+	]
+}
+
+#[test]
+fn test_camera_view_matches_view_space() {
+	let point = Point3::new(-3.0, 0.5, 8.0);
+	for rotation in rotations() {
+		let camera = Camera::new(Point3::new(1.0, 2.0, 3.0), rotation, 1.0, 1.5, 0.1, 100.0);
+		let transformed = point.to_vector4() * camera.view();
+		let expected = camera.to_view_space(point);
+		for i in 0..3 {
+			assert!((transformed[i] - expected[i]).abs() < 1e-12);
+		}
+		assert!(transformed[3] == 1.0);
+	}
+}
+
+#[test]
+fn test_camera_from_view_matrix_round_trip() {
+	for rotation in rotations() {
+		let camera = Camera::new(Point3::new(-4.0, 2.5, 10.0), rotation, 0.9, 1.5, 0.1, 100.0);
+		let copy = Camera::from_view_matrix(camera.view(), 0.9, 1.5, 0.1, 100.0);
+
+		assert!(copy.position().distance_to(*camera.position()) < 1e-12);
+		assert!((copy.rotation().dot(*camera.rotation()).abs() - 1.0).abs() < 1e-12);
+		assert!(*copy.fov() == 0.9 && *copy.aspect() == 1.5 && *copy.near() == 0.1 && *copy.far() == 100.0);
+	}
+}
+
+#[test]
+fn test_camera_interpolate() {
+	let a = Camera::<f64>::new(Point3::new(1.0, 2.0, 3.0), Quaternion::identity(), 0.4, 1.0, 0.1, 100.0);
+	let rotation = Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 90.0);
+	let b = Camera::new(Point3::new(-3.0, 2.0, 7.0), rotation, 1.6, 2.0, 1.0, 50.0);
+
+	let start = a.interpolate(&b, -1.0);
+	assert!(start.position().distance_to(*a.position()) < 1e-12);
+	assert!((start.fov() - a.fov()).abs() < 1e-12);
+	let end = a.interpolate(&b, 1.0);
+	assert!(end.position().distance_to(*b.position()) < 1e-12);
+	assert!((end.fov() - b.fov()).abs() < 1e-12);
+	assert!((end.far() - 50.0).abs() < 1e-12);
+
+	let middle = a.interpolate(&b, 0.5);
+	assert!(middle.position().distance_to(Point3::new(-1.0, 2.0, 5.0)) < 1e-12);
+	assert!((*middle.aspect() - 1.5).abs() < 1e-12);
+	let expected = Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 45.0);
+	assert!(middle.rotation().dot(expected).abs() > 1.0 - 1e-12);
+	let zoom = |camera: &Camera<f64>| (camera.fov() / 2.0).tan();
+	assert!((zoom(&middle) * zoom(&middle) - zoom(&a) * zoom(&b)).abs() < 1e-12);
+	assert!(middle.clip_plane().is_none());
+
+	// Two poses of one reflection camera keep the shared clip plane.
+	let water = Plane::new(Vector3::new(0.0, 1.0, 0.0), 0.5);
+	let (ra, rb) = (a.reflected_across(&water), b.reflected_across(&water));
+	let blended = ra.interpolate(&rb, 0.3);
+	let plane = blended.clip_plane().unwrap();
+	assert!(plane.normal() == water.normal() && plane.d() == water.d());
+
+	let pond = Plane::new(Vector3::new(0.0, 1.0, 0.0), -2.0);
+	assert!(ra.interpolate(&b.reflected_across(&pond), 0.3).clip_plane().is_none());
+	assert!(ra.interpolate(&b, 0.3).clip_plane().is_none());
+}
+
+#[test]
+fn test_fov_conversions_round_trip() {
+	for &aspect in &[0.5, 1.0, 4.0 / 3.0, 16.0 / 9.0, 21.0 / 9.0] {
+		for &hfov in &[0.2_f64, 1.0, 1.5, 2.5, 3.0] {
+			let vfov = vertical_fov_from_horizontal(hfov, aspect);
+			assert!((horizontal_fov_from_vertical(vfov, aspect) - hfov).abs() < 1e-12);
+			assert!(((vfov / 2.0).tan() * aspect - (hfov / 2.0).tan()).abs() < 1e-12);
+		}
+	}
+
+	let mut camera = Camera::new(Point3::new(0.0, 0.0, 0.0), Quaternion::identity(), 1.0, 16.0 / 9.0, 0.1, 100.0);
+	camera.set_horizontal_fov(std::f64::consts::FRAC_PI_2);
+	let corners = camera.frustum_corners();
+	let near = *camera.near();
+	assert!((corners[1][0] - near).abs() < 1e-12);
+	assert!((corners[2][1] - near * 9.0 / 16.0).abs() < 1e-12);
+}
+
+fn assert_clip_near(a: Vector4<f64>, b: Vector4<f64>) {
+	for i in 0..4 {
+		assert!((a[i] - b[i]).abs() < 1e-9, "[{}]: {} != {}", i, a[i], b[i]);
+	}
+}
+
+#[test]
+fn test_camera_reproject_matrix_follows_camera_motion() {
+	let world = Point3::new(-2.0, 1.5, -6.0).to_vector4();
+	for rotation in rotations() {
+		let previous = Camera::new(Point3::new(0.5, 0.0, 2.0), rotation, 1.1, 1.5, 0.1, 100.0);
+		let turn = Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 3.0);
+		let current = Camera::new(Point3::new(0.7, 0.1, 1.8), turn * rotation, 1.0, 1.5, 0.1, 100.0);
+
+		let clip = world * current.view_projection();
+		let reprojected = clip * current.reproject_matrix(previous.view_projection());
+		assert_clip_near(reprojected, world * previous.view_projection());
+	}
+}
+
+#[test]
+fn test_motion_matrix_tracks_moving_object() {
+	let previous = Transform::new(
+		Vector3::new(1.0, 0.0, -5.0),
+		Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 20.0),
+		Vector3::new(1.0, 2.0, 0.5),
+	);
+	let current = Transform::new(
+		Vector3::new(1.2, 0.1, -5.0),
+		Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 25.0),
+		Vector3::new(1.0, 2.0, 0.5),
+	);
+	let camera = Camera::new(Point3::new(0.0, 0.0, 0.0), Quaternion::identity(), 1.0, 1.5, 0.1, 100.0);
+	let motion = motion_matrix(&previous, &current);
+
+	let local = Point3::new(0.5, -0.25, 1.0);
+	let now = current.transform_point(local).to_vector4();
+	let before = previous.transform_point(local).to_vector4();
+	assert_clip_near(now * motion, before);
+	assert_clip_near(now * motion * camera.view_projection(), before * camera.view_projection());
+}
+
+#[test]
+fn test_cursor_to_ground_point_inverts_projection() {
+	let ground = Plane::new(Vector3::new(0.0, 1.0, 0.0), 0.0);
+	let viewport = Viewport::new(10.0, 20.0, 1280.0, 720.0);
+	let rotation = Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), -20.0);
+	let camera = Camera::new(Point3::new(2.0, 8.0, 12.0), rotation, 0.9, 1280.0 / 720.0, 0.1, 100.0);
+
+	for target in [Point3::new(0.0, 0.0, 0.0), Point3::new(3.5, 0.0, -4.0), Point3::new(-3.0, 0.0, 3.0)] {
+		let clip = target.to_vector4() * camera.view_projection();
+		let (x, y) = viewport.from_ndc(clip[0] / clip[3], clip[1] / clip[3]);
+		let hit = cursor_to_ground_point(&camera, Point2::new(x, y), &viewport, &ground).unwrap();
+		assert!(hit.distance_to(target) < 1e-9);
+	}
+
+	// The top edge of the viewport looks above the horizon.
+	assert!(cursor_to_ground_point(&camera, Point2::new(650.0, 20.0), &viewport, &ground).is_none());
+}
+
+#[test]
+fn test_camera_reflected_across_plane() {
+	let rotation = Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 20.0) * Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), -25.0);
+	let camera = Camera::new(Point3::new(1.0, 6.0, 12.0), rotation, 1.0, 1.5, 0.1, 100.0);
+	let water = Plane::from_point_normal(Point3::new(0.0, 1.0, 0.0), Vector3::new(0.0, 1.0, 0.0));
+	let reflection = camera.reflected_across(&water);
+	let ndc = |camera: &Camera<f64>, p: Point3<f64>| {
+		let clip = p.to_vector4() * camera.view_projection();
+		Vector3::new(clip[0], clip[1], clip[2]) / clip[3]
+	};
+
+	assert!(reflection.position().distance_to(Point3::new(1.0, -4.0, 12.0)) < 1e-12);
+
+	// A point seen through the reflection lands where its mirror image does
+	// for the original camera, flipped left to right.
+	let p = Point3::new(-2.0, 3.0, 1.0);
+	let mirrored = Point3::new(-2.0, -1.0, 1.0);
+	let (a, b) = (ndc(&reflection, p), ndc(&camera, mirrored));
+	assert!((a.x() + b.x()).abs() < 1e-12);
+	assert!((a.y() - b.y()).abs() < 1e-12);
+
+	// The water surface is the near plane; anything below it is clipped.
+	assert!((ndc(&reflection, Point3::new(0.5, 1.0, -3.0)).z() + 1.0).abs() < 1e-9);
+	assert!(ndc(&reflection, Point3::new(0.5, 0.9, -3.0)).z() < -1.0);
+	assert!(ndc(&reflection, Point3::new(0.5, 1.1, -3.0)).z() > -1.0);
+
+	let identity = reflection.view_projection() * reflection.inverse_view_projection();
+	for i in 0..4 {
+		for j in 0..4 {
+			assert!((identity[i][j] - if i == j { 1.0 } else { 0.0 }).abs() < 1e-9);
+		}
+	}
+
+	// Reflecting from below keeps the side the camera looks from.
+	let diver = Camera::new(Point3::new(0.0, -3.0, 0.0), Quaternion::identity(), 1.0, 1.0, 0.1, 100.0);
+	let clip = diver.reflected_across(&water).clip_plane().unwrap().signed_distance(Point3::new(0.0, -3.0, 0.0));
+	assert!(clip > 0.0);
+}
+
+#[test]
+fn test_camera_inverse_view_and_projection() {
+	let identity = Matrix4::<f64>::identity();
+	for rotation in rotations() {
+		let mut camera = Camera::new(Point3::new(-2.0, 3.0, 7.0), rotation, 1.1, 16.0 / 9.0, 0.05, 500.0);
+
+		assert!((camera.view() * camera.inverse_view()).approx_eq(identity, 1e-12));
+		assert!(camera.inverse_view().approx_eq(camera.view().inverse_rigid(), 1e-12));
+		assert!((camera.projection() * camera.inverse_projection()).approx_eq(identity, 1e-9));
+		assert!(camera.inverse_view_projection().approx_eq(camera.inverse_projection() * camera.inverse_view(), 0.0));
+
+		// A clip-space point on the far plane unprojects to a world-space point
+		// at the far distance in front of the camera.
+		let far = (Vector4::new(0.0, 0.0, 1.0, 1.0) * camera.inverse_view_projection()).perspective_divide();
+		let forward = rotation.rotate_vector(Vector3::new(0.0, 0.0, -1.0));
+		assert!((far - (camera.position().to_vector() + forward * 500.0)).magnitude() < 1e-6);
+
+		camera.update_clip_plane(Some(Plane::from_point_normal(Point3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0))));
+		assert!((camera.projection() * camera.inverse_projection()).approx_eq(identity, 1e-9));
+		assert!((camera.view_projection() * camera.inverse_view_projection()).approx_eq(identity, 1e-9));
+	}
+}
@@ -0,0 +1,67 @@
+use math3d::control::{AttitudeController, Pid, SpringDamper};
+use math3d::dynamics::integrate_semi_implicit;
+use math3d::points::Point3;
+use math3d::quaternion::Quaternion;
+use math3d::vectors::Vector3;
+
+#[cfg(test)]
+
+#[test]
+fn test_spring_damper_critically_damped_does_not_overshoot() {
+	let spring = SpringDamper::critically_damped(25.0, 2.0);
+	let mut positions = [Point3::new(1.0, -2.0, 0.5)];
+	let mut velocities = [Vector3::zero()];
+	let start = positions[0].to_vector();
+
+	for _ in 0..5000 {
+		let force = spring.force(positions[0].to_vector(), velocities[0]);
+		integrate_semi_implicit(&mut positions, &mut velocities, &[force / 2.0], 0.001);
+		assert!(positions[0].to_vector().dot(start) >= 0.0);
+	}
+	assert!(positions[0].to_vector().magnitude() < 1e-3);
+}
+
+#[test]
+fn test_pid_integral_removes_steady_state_error() {
+	let mut pid = Pid::new(1.0, 2.0, 0.1);
+	let disturbance = Vector3::new(0.0, -3.0, 0.0);
+	let target = Vector3::new(1.0, 1.0, 0.0);
+	let mut value = Vector3::zero();
+
+	for _ in 0..5000 {
+		let output = pid.update(target - value, 0.01);
+		value += (output + disturbance) * 0.01;
+	}
+	assert!((value - target).magnitude() < 1e-6);
+
+	pid.reset();
+	assert!(pid.update(Vector3::new(1.0, 0.0, 0.0), 0.01) == Vector3::new(1.0 + 0.02, 0.0, 0.0));
+}
+
+#[test]
+fn test_attitude_controller_error_takes_shorter_arc() {
+	let controller = AttitudeController::new(1.0, 0.0);
+	let axis = Vector3::new(0.0, 1.0, 0.0);
+	let orientation = Quaternion::from_axis_angle(axis, 10.0);
+	let target = -Quaternion::from_axis_angle(axis, 300.0);
+
+	let error = controller.error(orientation, target);
+	assert!((error.y() + 70.0f64.to_radians()).abs() < 1e-9);
+}
+
+#[test]
+fn test_attitude_controller_converges() {
+	let controller = AttitudeController::critically_damped(20.0, 1.0);
+	let target = Quaternion::from_axis_angle(Vector3::new(1.0, 1.0, 0.0).normalized(), 120.0);
+	let mut orientation = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), -45.0);
+	let mut angular_velocity = Vector3::new(0.5, 0.0, 0.0);
+
+	let dt = 0.001;
+	for _ in 0..5000 {
+		let torque = controller.torque(orientation, target, angular_velocity);
+		angular_velocity += torque * dt;
+		orientation = (Quaternion::from_rotation_vector(angular_velocity * dt) * orientation).versor();
+	}
+	assert!(controller.error(orientation, target).magnitude() < 1e-4);
+	assert!(angular_velocity.magnitude() < 1e-3);
+}
@@ -0,0 +1,55 @@
+use math3d::camera::Camera;
+use math3d::debug_export::DebugMesh;
+use math3d::geometry::Aabb3;
+use math3d::points::Point3;
+use math3d::quaternion::Quaternion;
+use math3d::transform::Transform;
+use math3d::vectors::Vector3;
+
+#[cfg(test)]
+
+fn scene() -> DebugMesh<f64> {
+	let mut mesh = DebugMesh::new();
+	mesh.add_points(&[Point3::new(0.5, -1.0, 2.0)]);
+	mesh.set_color([255, 128, 0]);
+	mesh.add_triangles(&[[Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 0.0, 0.0), Point3::new(0.0, 1.0, 0.0)]]);
+	mesh.add_aabb(&Aabb3::new(Point3::new(-1.0, -1.0, -1.0), Point3::new(1.0, 2.0, 3.0)));
+	mesh.add_frustum(&Camera::new(Point3::new(0.0, 0.0, 5.0), Quaternion::identity(), 1.0, 1.5, 0.1, 10.0));
+	mesh.add_frame(Point3::new(1.0, 1.0, 1.0), Quaternion::identity(), 2.0);
+	mesh.add_transform(&Transform::new(Vector3::new(0.0, 0.0, 1.0), Quaternion::identity(), Vector3::new(1.0, 2.0, 3.0)), 1.0);
+	mesh
+}
+
+#[test]
+fn test_debug_export_obj() {
+	let mut out = Vec::new();
+	scene().write_obj(&mut out).unwrap();
+	let text = String::from_utf8(out).unwrap();
+	let count = |prefix: &str| text.lines().filter(|l| l.starts_with(prefix)).count();
+
+	// 1 point + 3 triangle corners + 8 box + 8 frustum + 6 + 6 axis ends.
+	assert_eq!(count("v "), 32);
+	assert_eq!(count("p "), 1);
+	assert_eq!(count("f "), 1);
+	assert_eq!(count("l "), 12 + 12 + 3 + 3);
+	assert!(text.contains("v 0.5 -1 2 0.7843 0.7843 0.7843\n"));
+	assert!(text.contains("f 2 3 4\n"));
+	assert!(text.contains("v 3 1 1 1.0000 0.0000 0.0000\n"));
+	assert!(text.contains("v 0 0 4 0.0000 0.0000 1.0000\n"));
+}
+
+#[test]
+fn test_debug_export_ply() {
+	let mut out = Vec::new();
+	scene().write_ply(&mut out).unwrap();
+	let text = String::from_utf8(out).unwrap();
+	let (header, body) = text.split_once("end_header\n").unwrap();
+
+	assert!(header.starts_with("ply\nformat ascii 1.0\n"));
+	assert!(header.contains("element vertex 32\n"));
+	assert!(header.contains("element face 1\n"));
+	assert!(header.contains("element edge 30\n"));
+	assert_eq!(body.lines().count(), 32 + 1 + 30);
+	assert!(body.contains("\n3 1 2 3\n"));
+	assert!(body.contains("\n-1 -1 -1 255 128 0\n"));
+}
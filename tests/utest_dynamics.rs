@@ -0,0 +1,114 @@
+use math3d::dynamics::{integrate_semi_implicit, integrate_semi_implicit_par, integrate_verlet, integrate_verlet_par, RigidBody, RigidState};
+use math3d::matrices::Matrix3;
+use math3d::points::Point3;
+use math3d::quaternion::Quaternion;
+use math3d::vectors::Vector3;
+
+#[cfg(test)]
+
+type Particles = (Vec<Point3<f64>>, Vec<Vector3<f64>>, Vec<Vector3<f64>>);
+
+fn particles(count: usize) -> Particles {
+	let positions = (0..count).map(|i| Point3::new(i as f64, 0.0, -(i as f64))).collect();
+	let velocities = (0..count).map(|i| Vector3::new(1.0, (i % 7) as f64, 0.0)).collect();
+	let accelerations = (0..count).map(|i| Vector3::new(0.0, -9.81, (i % 3) as f64)).collect();
+	(positions, velocities, accelerations)
+}
+
+#[test]
+fn test_semi_implicit_par_matches_serial() {
+	let (mut p1, mut v1, a) = particles(50_000);
+	let (mut p2, mut v2) = (p1.clone(), v1.clone());
+
+	for _ in 0..3 {
+		integrate_semi_implicit(&mut p1, &mut v1, &a, 0.01);
+		integrate_semi_implicit_par(&mut p2, &mut v2, &a, 0.01);
+	}
+	assert!(p1 == p2);
+	assert!(v1 == v2);
+}
+
+#[test]
+fn test_verlet_constant_acceleration_is_exact() {
+	let dt = 0.1;
+	let g = Vector3::new(0.0, -10.0, 0.0);
+	let v0 = Vector3::new(2.0, 5.0, 0.0);
+	let at = |t: f64| Point3::from_vector(v0 * t + g * (0.5 * t * t));
+
+	let mut positions = [at(dt); 3];
+	let mut previous = [at(0.0); 3];
+	let accelerations = [g; 3];
+	for _ in 0..20 {
+		integrate_verlet(&mut positions[..1], &mut previous[..1], &accelerations[..1], dt);
+		integrate_verlet_par(&mut positions[1..], &mut previous[1..], &accelerations[1..], dt);
+	}
+
+	for p in &positions {
+		assert!(p.distance_to(at(21.0 * dt)) < 1e-9);
+	}
+	assert!(previous[0].distance_to(at(20.0 * dt)) < 1e-9);
+}
+
+#[test]
+#[should_panic]
+fn test_integrate_mismatched_lengths() {
+	let (mut p, mut v, a) = particles(4);
+	integrate_semi_implicit(&mut p, &mut v[..3], &a, 0.1);
+}
+
+#[test]
+fn test_rigid_state_constant_force_is_exact() {
+	let body = RigidBody::new(2.0, Matrix3::identity());
+	let mut state = RigidState::new(Point3::new(0.0, 1.0, 0.0), Quaternion::identity());
+	state.velocity = Vector3::new(3.0, 4.0, 0.0);
+	let force = Vector3::new(0.0, -19.62, 0.0);
+
+	for _ in 0..50 {
+		state.step(force, Vector3::zero(), &body, 0.02);
+	}
+
+	// Velocity Verlet has no truncation error under constant acceleration:
+	// x = x0 + v0 t + a t^2 / 2 after t = 1.
+	let expected = Point3::new(3.0, 1.0 + 4.0 - 9.81 * 0.5, 0.0);
+	assert!(state.position.distance_to(expected) < 1e-12);
+	assert!((state.velocity - Vector3::new(3.0, 4.0 - 9.81, 0.0)).magnitude() < 1e-12);
+	assert!(state.orientation == Quaternion::identity());
+}
+
+#[test]
+fn test_rigid_state_torque_free_conserves_angular_momentum() {
+	let inertia = Matrix3::from_array_2d([[1.0, 0.0, 0.0], [0.0, 2.0, 0.0], [0.0, 0.0, 3.0]]);
+	let body = RigidBody::new(1.0, inertia);
+	let tilt = Quaternion::from_axis_angle(Vector3::new(1.0, 1.0, 0.0).normalized(), 30.0);
+	let mut state = RigidState::new(Point3::new(0.0, 0.0, 0.0), tilt);
+	state.angular_velocity = Vector3::new(0.1, 2.0, 0.2);
+
+	let momentum = |state: &RigidState<f64>| {
+		let omega = state.orientation.conjugate().rotate_vector(state.angular_velocity);
+		state.orientation.rotate_vector(omega * inertia)
+	};
+	let initial = momentum(&state);
+	for _ in 0..2000 {
+		state.step(Vector3::zero(), Vector3::zero(), &body, 0.001);
+	}
+	assert!((momentum(&state) - initial).magnitude() < 1e-2 * initial.magnitude());
+	assert!(state.orientation.is_normalized(1e-12));
+	assert!(state.position == Point3::new(0.0, 0.0, 0.0));
+}
+
+#[test]
+fn test_rigid_state_torque_spins_up_about_principal_axis() {
+	let inertia = Matrix3::from_array_2d([[2.0, 0.0, 0.0], [0.0, 2.0, 0.0], [0.0, 0.0, 4.0]]);
+	let body = RigidBody::new(1.0, inertia);
+	let orientation = Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), 90.0);
+	let mut state = RigidState::new(Point3::new(0.0, 0.0, 0.0), orientation);
+
+	// The body z axis points along world -y, so torque about -y spins it up
+	// about that axis at torque / I_zz.
+	let axis = orientation.rotate_vector(Vector3::new(0.0, 0.0, 1.0));
+	for _ in 0..100 {
+		state.step(Vector3::zero(), axis * 8.0, &body, 0.01);
+	}
+	assert!((state.angular_velocity - axis * 2.0).magnitude() < 1e-9);
+	assert!((state.orientation.rotate_vector(Vector3::new(0.0, 0.0, 1.0)) - axis).magnitude() < 1e-9);
+}
@@ -0,0 +1,145 @@
+use math3d::filter::{Ema, Mahony, OneEuro, OneEuroQuaternion};
+use math3d::quaternion::Quaternion;
+use math3d::vectors::Vector3;
+
+#[cfg(test)]
+
+/// Deterministic noise in [-1, 1].
+fn noise(i: usize) -> f64 {
+	((i as f64 * 12.9898).sin() * 43758.5453).rem_euclid(1.0) * 2.0 - 1.0
+}
+
+#[test]
+fn test_ema_is_independent_of_sample_rate() {
+	let target = Vector3::new(1.0, -2.0, 0.5);
+	let mut coarse = Ema::new(0.2);
+	let mut fine = Ema::new(0.2);
+	coarse.update(Vector3::zero(), 0.0);
+	fine.update(Vector3::zero(), 0.0);
+
+	for _ in 0..10 {
+		coarse.update(target, 0.02);
+		fine.update(target, 0.01);
+		fine.update(target, 0.01);
+	}
+	assert!((coarse.value().unwrap() - fine.value().unwrap()).magnitude() < 1e-12);
+
+	fine.reset();
+	assert!(fine.update(target, 0.01) == target);
+}
+
+#[test]
+fn test_one_euro_smooths_jitter_and_tracks_motion() {
+	let dt = 1.0 / 120.0;
+	let mut still = OneEuro::new(1.0, 0.5, 1.0);
+	let mut raw_error = 0.0;
+	let mut filtered_error = 0.0;
+	for i in 0..600 {
+		let sample = Vector3::new(noise(i), noise(i + 1000), 0.0) * 0.01;
+		let filtered = still.update(sample, dt);
+		if i > 100 {
+			raw_error += sample.magnitude();
+			filtered_error += filtered.magnitude();
+		}
+	}
+	assert!(filtered_error < 0.2 * raw_error);
+
+	// Fast motion: a higher beta lags less.
+	let mut lag = [0.0; 2];
+	for (k, beta) in [0.0, 1.0].iter().enumerate() {
+		let mut filter = OneEuro::new(1.0, *beta, 1.0);
+		for i in 0..120 {
+			let sample = Vector3::new(i as f64 * dt * 2.0, 0.0, 0.0);
+			lag[k] = (sample - filter.update(sample, dt)).magnitude();
+		}
+	}
+	assert!(lag[1] < 0.5 * lag[0]);
+}
+
+#[test]
+fn test_one_euro_quaternion_converges_across_sign_flips() {
+	let axis = Vector3::new(0.0, 1.0, 0.0);
+	let target = Quaternion::<f64>::from_axis_angle(axis, 60.0);
+	let mut filter = OneEuroQuaternion::new(2.0, 0.1, 1.0);
+	filter.update(Quaternion::identity(), 0.01);
+
+	for i in 0..500 {
+		let sample = if i % 2 == 0 { target } else { -target };
+		let smoothed = filter.update(sample, 0.01);
+		assert!((smoothed.norm() - 1.0).abs() < 1e-9);
+	}
+	assert!((filter.value().unwrap().dot(target).abs() - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_mahony_converges_and_learns_gyro_bias() {
+	let up = Vector3::new(0.0, 1.0, 0.0);
+	let field = Vector3::new(0.0, -40.0, -25.0);
+	let rate = Vector3::new(0.1, 0.3, -0.2);
+	let bias = Vector3::new(0.02, -0.01, 0.015);
+	let start = Quaternion::from_axis_angle(Vector3::new(1.0, 2.0, -1.0).normalized(), 70.0);
+	let dt = 0.01;
+
+	// The first update has no magnetometer reading, so the filter starts at
+	// the identity, 70 degrees off.
+	let mut filter = Mahony::new(2.0, 0.2);
+	let mut truth = start;
+	filter.update(rate + bias, truth.conjugate().rotate_vector(up) * 9.81, None, dt);
+	for i in 1..6000 {
+		truth = (truth * Quaternion::from_rotation_vector(rate * dt)).versor();
+		let gyro = rate + bias + Vector3::new(noise(3 * i), noise(3 * i + 1), noise(3 * i + 2)) * 0.01;
+		let to_sensor = truth.conjugate();
+		filter.update(gyro, to_sensor.rotate_vector(up) * 9.81, Some(to_sensor.rotate_vector(field)), dt);
+	}
+
+	let error = filter.orientation().unwrap().conjugate() * truth;
+	assert!(error.versor().to_rotation_vector().magnitude().to_degrees() < 0.5);
+	assert!((filter.gyro_bias() - bias).magnitude() < 0.005);
+
+	filter.reset();
+	assert!(filter.orientation().is_none());
+	assert!(filter.gyro_bias() == Vector3::zero());
+}
+
+#[test]
+fn test_mahony_without_magnetometer_corrects_tilt_only() {
+	let up = Vector3::new(0.0, 1.0, 0.0);
+	let truth = Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 50.0)
+		* Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), 30.0);
+	let accel = truth.conjugate().rotate_vector(up) * 9.81;
+
+	let mut filter = Mahony::new(1.5, 0.0);
+	for _ in 0..3000 {
+		filter.update(Vector3::zero(), accel, None, 0.01);
+	}
+
+	// Up is recovered, the heading is not observable.
+	let q = filter.orientation().unwrap();
+	assert!((q.rotate_vector(accel.normalized()) - up).magnitude() < 1e-6);
+
+	// Free fall only integrates the gyroscope.
+	let before = filter.orientation().unwrap();
+	let after = filter.update(Vector3::zero(), Vector3::zero(), None, 0.01);
+	assert!(after.approx_eq(before, 1e-12));
+}
+
+#[test]
+fn test_mahony_first_update_without_magnetometer_seeds_tilt() {
+	let up = Vector3::new(0.0, 1.0, 0.0);
+	let tilt = Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 2.0).normalized(), 70.0);
+	let accels = [
+		tilt.conjugate().rotate_vector(up) * 9.81,
+		Vector3::new(0.0, 9.81, 0.0),
+		Vector3::new(0.0, -9.81, 0.0),
+		Vector3::new(0.3, -9.8, 0.0),
+	];
+
+	for accel in accels {
+		let q = Mahony::new(1.5, 0.0).update(Vector3::zero(), accel, None, 0.01);
+		assert!((q.rotate_vector(accel.normalized()) - up).magnitude() < 1e-12);
+		assert!(q.is_normalized(1e-12));
+	}
+
+	let q = Mahony::new(1.5, 0.0).update(Vector3::zero(), Vector3::zero(), None, 0.01);
+	assert!(q == Quaternion::identity());
+}
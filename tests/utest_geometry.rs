@@ -0,0 +1,504 @@
+use math3d::geometry::{Aabb3, Ellipsoid, Obb, Plane, Sphere};
+use math3d::points::{Point2, Point3};
+use math3d::quaternion::Quaternion;
+use math3d::transform::Transform;
+use math3d::vectors::Vector3;
+
+#[cfg(test)]
+
+fn rotated_box_corners() -> Vec<Point3<f64>> {
+	let rotation = Quaternion::from_axis_angle(Vector3::new(1.0, 2.0, 3.0).normalized(), 37.0);
+	let mut points = Vec::new();
+	for &x in &[-1.0, 1.0] {
+		for &y in &[-2.0, 2.0] {
+			for &z in &[-3.0, 3.0] {
+				let p = rotation.rotate_vector(Vector3::new(x, y, z)) + Vector3::new(5.0, -1.0, 2.0);
+				points.push(Point3::from_vector(p));
+			}
+		}
+	}
+	points
+}
+
+#[test]
+fn test_obb_fit_empty() {
+	assert!(Obb::<f64>::fit(&[]).is_none());
+	assert!(Obb::<f64>::fit_refined(&[]).is_none());
+}
+
+#[test]
+fn test_obb_fit_rotated_box() {
+	let points = rotated_box_corners();
+	let obb = Obb::fit(&points).unwrap();
+
+	assert!(points.iter().all(|p| obb.contains(*p, 1e-9)));
+	assert!((obb.volume() - 48.0).abs() < 1e-6);
+	assert!((obb.center().to_vector() - Vector3::new(5.0, -1.0, 2.0)).magnitude() < 1e-9);
+}
+
+#[test]
+fn test_obb_fit_refined_beats_pca() {
+	// The corners of a 10 x 2 x 1 box plus a dense cluster along its
+	// diagonal, which tilts the principal axes away from the box edges.
+	let mut points = Vec::new();
+	for &x in &[0.0, 10.0] {
+		for &y in &[0.0, 2.0] {
+			for &z in &[0.0, 1.0] {
+				points.push(Point3::new(x, y, z));
+			}
+		}
+	}
+	for i in 0..=50 {
+		let t = i as f64 / 50.0;
+		points.push(Point3::new(10.0 * t, 2.0 * t, 0.5));
+	}
+
+	let pca = Obb::fit(&points).unwrap();
+	let refined = Obb::fit_refined(&points).unwrap();
+
+	assert!(points.iter().all(|p| pca.contains(*p, 1e-9)));
+	assert!(points.iter().all(|p| refined.contains(*p, 1e-9)));
+	assert!(pca.volume() > 21.0);
+	assert!((refined.volume() - 20.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_obb_swept_aabb_contains_sampled_motion() {
+	let rotation = Quaternion::from_axis_angle(Vector3::new(1.0, 2.0, -1.0).normalized(), 30.0);
+	let axes = [Vector3::unit_x(), Vector3::unit_y(), Vector3::unit_z()].map(|a| rotation.rotate_vector(a));
+	let obb = Obb::new(Point3::new(1.0, -2.0, 0.5), axes, Vector3::new(2.0, 0.5, 1.0));
+	let linear_velocity = Vector3::new(3.0, 1.0, -2.0);
+	let dt = 0.25;
+
+	for angular_velocity in [Vector3::zero(), Vector3::new(0.0, 2.0, 0.0), Vector3::new(-4.0, 6.0, 1.0), Vector3::new(0.0, 0.0, 40.0)] {
+		let swept = obb.swept_aabb(linear_velocity, angular_velocity, dt);
+		for step in 0..=100 {
+			let t = dt * step as f64 / 100.0;
+			let spin = Quaternion::from_rotation_vector(angular_velocity * t);
+			let center = obb.center().to_vector() + linear_velocity * t;
+			for corner in 0..8 {
+				let mut offset = Vector3::zero();
+				for (i, axis) in axes.iter().enumerate() {
+					let sign = if corner & (1 << i) != 0 { 1.0 } else { -1.0 };
+					offset += spin.rotate_vector(*axis) * (sign * obb.half_extents()[i]);
+				}
+				let p = center + offset;
+				let (min, max) = (swept.min(), swept.max());
+				assert!((0..3).all(|k| p[k] >= min[k] - 1e-9 && p[k] <= max[k] + 1e-9), "{} escapes", p);
+			}
+		}
+	}
+
+	// Without motion the swept box is the tight AABB of the box.
+	let still = obb.swept_aabb(Vector3::zero(), Vector3::zero(), dt);
+	for k in 0..3 {
+		let extent: f64 = axes.iter().enumerate().map(|(i, axis)| obb.half_extents()[i] * axis[k].abs()).sum();
+		assert!((still.max()[k] - obb.center()[k] - extent).abs() < 1e-12);
+		assert!((obb.center()[k] - still.min()[k] - extent).abs() < 1e-12);
+	}
+}
+
+fn assert_vector3_near(a: Vector3<f64>, b: Vector3<f64>) {
+	assert!((a - b).magnitude() < 1e-9, "{} != {}", a, b);
+}
+
+#[test]
+fn test_point_mirrored_across_plane() {
+	let plane = Plane::<f64>::from_point_normal(Point3::new(1.0, 1.0, 1.0), Vector3::new(1.0, 1.0, 0.0));
+	let point = Point3::new(3.0, -2.0, 4.0);
+	let mirrored = point.mirrored_across(&plane);
+
+	assert!((plane.signed_distance(mirrored) + plane.signed_distance(point)).abs() < 1e-9);
+	assert_vector3_near(mirrored.mirrored_across(&plane).to_vector(), point.to_vector());
+}
+
+#[test]
+fn test_quaternion_mirrored_rotates_mirrored_vectors() {
+	let normal = Vector3::new(1.0, -2.0, 0.5).normalized();
+	let q = Quaternion::from_axis_angle(Vector3::new(0.3, 1.0, -0.4).normalized(), 63.0);
+	let mirrored = q.mirrored(normal);
+	let v = Vector3::new(0.7, 1.5, -2.0);
+
+	assert_vector3_near(mirrored.rotate_vector(v.mirrored(normal)), q.rotate_vector(v).mirrored(normal));
+	assert!((mirrored.norm() - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_transform_mirrored_maps_mirrored_points() {
+	let plane = Plane::new(Vector3::new(0.0, 1.0, 1.0), -2.0);
+	let t = Transform::new(
+		Vector3::new(1.0, 2.0, 3.0),
+		Quaternion::from_axis_angle(Vector3::new(1.0, 1.0, 0.0).normalized(), 40.0),
+		Vector3::new(2.0, 2.0, 2.0),
+	);
+	let mirrored = t.mirrored(&plane);
+	let local = Vector3::new(0.5, -1.0, 2.0);
+
+	let expected = t.transform_point(Point3::from_vector(local)).mirrored_across(&plane);
+	let actual = mirrored.transform_point(Point3::from_vector(local.mirrored(plane.normal())));
+	assert_vector3_near(actual.to_vector(), expected.to_vector());
+}
+
+#[test]
+fn test_transform_rotate_around_matches_rotating_points() {
+	let t = Transform::new(
+		Vector3::new(1.0, 2.0, 3.0),
+		Quaternion::from_axis_angle(Vector3::new(1.0, 1.0, 0.0).normalized(), 40.0),
+		Vector3::new(2.0, 1.0, 0.5),
+	);
+	let pivot = Point3::new(-1.0, 0.5, 2.0);
+	let q = Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 2.0).normalized(), 75.0);
+	let rotated = t.rotate_around(pivot, q);
+
+	for local in [Vector3::new(0.5, -1.0, 2.0), Vector3::zero(), Vector3::new(3.0, 0.0, -1.0)] {
+		let world = t.transform_point(Point3::from_vector(local)).to_vector() - pivot.to_vector();
+		let expected = q.rotate_vector(world) + pivot.to_vector();
+		assert_vector3_near(rotated.transform_point(Point3::from_vector(local)).to_vector(), expected);
+	}
+	assert!(rotated.scale() == t.scale());
+}
+
+#[test]
+fn test_transform_matrix_and_inverse() {
+	let t = Transform::new(
+		Vector3::new(1.0, 2.0, 3.0),
+		Quaternion::from_axis_angle(Vector3::new(1.0, 1.0, 0.0).normalized(), 40.0),
+		Vector3::new(2.0, 1.0, 0.5),
+	);
+	let local = Point3::new(0.5, -1.0, 2.0);
+	let world = local.to_vector4() * t.matrix();
+	let expected = t.transform_point(local);
+	assert_vector3_near(Vector3::new(world[0], world[1], world[2]), expected.to_vector());
+	assert!((world[3] - 1.0).abs() < 1e-12);
+
+	let back = world * t.inverse_matrix();
+	assert_vector3_near(Vector3::new(back[0], back[1], back[2]), local.to_vector());
+}
+
+fn scattered_points(count: usize) -> Vec<Point3<f64>> {
+	(0..count)
+		.map(|i| {
+			let t = i as f64;
+			Point3::new((t * 0.37).sin() * 50.0, (t * 1.3).cos() * 20.0 + t * 1e-3, (t * 0.011).sin() * 7.0)
+		})
+		.collect()
+}
+
+#[test]
+fn test_aabb3_from_points_par_matches_serial() {
+	let points = scattered_points(100_000);
+	let serial = Aabb3::from_points(&points).unwrap();
+	let parallel = Aabb3::from_points_par(&points).unwrap();
+
+	assert!(serial.min() == parallel.min());
+	assert!(serial.max() == parallel.max());
+	assert!(points.iter().all(|p| parallel.contains(*p)));
+	assert!(Aabb3::<f64>::from_points_par(&[]).is_none());
+}
+
+#[test]
+fn test_sphere_from_points_par_matches_serial() {
+	let points = scattered_points(100_000);
+	let serial = Sphere::from_points(&points).unwrap();
+	let parallel = Sphere::from_points_par(&points).unwrap();
+
+	assert!(serial.center() == parallel.center());
+	assert_eq!(serial.radius(), parallel.radius());
+	assert!(points.iter().all(|p| parallel.contains(*p)));
+	assert!(Sphere::<f64>::from_points_par(&[]).is_none());
+}
+
+#[test]
+fn test_sphere_projected_radius() {
+	use math3d::camera::Camera;
+
+	let fov = std::f64::consts::FRAC_PI_3;
+	let rotation = Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 90.0);
+	let camera = Camera::new(Point3::new(1.0, 2.0, 3.0), rotation, fov, 1.5, 0.1, 100.0);
+	let near = Sphere::new(Point3::new(-4.0, 2.0, 3.0), 0.5);
+	let far = Sphere::new(Point3::new(-19.0, 2.0, 3.0), 0.5);
+
+	assert!(near.projected_radius(&camera) > 3.0 * far.projected_radius(&camera));
+	assert!(Sphere::new(Point3::new(1.0, 2.0, 3.5), 1.0).projected_radius(&camera).is_infinite());
+}
+
+#[test]
+fn test_aabb3_screen_rect() {
+	use math3d::camera::{Camera, Viewport};
+
+	let fov = std::f64::consts::FRAC_PI_2;
+	let camera = Camera::new(Point3::new(0.0, 0.0, 0.0), Quaternion::identity(), fov, 2.0, 0.1, 100.0);
+	let viewport = Viewport::new(0.0, 0.0, 400.0, 200.0);
+
+	// A unit square at depth 10 in front of the camera, right of center.
+	let aabb = Aabb3::new(Point3::new(1.0, -0.5, -10.0), Point3::new(2.0, 0.5, -10.0));
+	let (min, max) = aabb.screen_rect(&camera, &viewport).unwrap();
+	assert!((min.x() - 210.0).abs() < 1e-9 && (max.x() - 220.0).abs() < 1e-9);
+	assert!((min.y() - 95.0).abs() < 1e-9 && (max.y() - 105.0).abs() < 1e-9);
+
+	let behind = Aabb3::new(Point3::new(-1.0, -1.0, 1.0), Point3::new(1.0, 1.0, 2.0));
+	assert!(behind.screen_rect(&camera, &viewport).is_none());
+
+	let outside = Aabb3::new(Point3::new(50.0, -1.0, -10.0), Point3::new(51.0, 1.0, -9.0));
+	assert!(outside.screen_rect(&camera, &viewport).is_none());
+
+	let around = Aabb3::new(Point3::new(-1.0, -1.0, -1.0), Point3::new(1.0, 1.0, 1.0));
+	let (min, max) = around.screen_rect(&camera, &viewport).unwrap();
+	assert!(min == Point2::new(0.0, 0.0) && max == Point2::new(400.0, 200.0));
+}
+
+#[test]
+fn test_heightfield_height_and_normal() {
+	use math3d::geometry::Heightfield;
+
+	// A plane rising along x with slope 0.5, offset by the origin.
+	let (columns, rows) = (5, 4);
+	let heights = (0..columns * rows).map(|i| (i % columns) as f64).collect();
+	let terrain = Heightfield::new(Point3::new(-2.0, 1.0, 3.0), 2.0, columns, rows, heights);
+
+	assert_eq!(terrain.height_at(-2.0, 3.0), Some(1.0));
+	assert_eq!(terrain.height_at(3.0, 4.5), Some(1.0 + 2.5));
+	assert_eq!(terrain.height_at(6.0, 9.0), Some(5.0));
+	assert_eq!(terrain.height_at(6.1, 9.0), None);
+	assert_eq!(terrain.height_at(0.0, 2.9), None);
+
+	let normal = terrain.normal_at(1.3, 5.0).unwrap();
+	assert_vector3_near(normal, Vector3::new(-0.5, 1.0, 0.0).normalized());
+}
+
+#[test]
+fn test_heightfield_raycast() {
+	use math3d::geometry::Heightfield;
+
+	let (columns, rows) = (33, 17);
+	let height = |x: f64, z: f64| (x * 0.7).sin() + (z * 0.4).cos();
+	let heights = (0..columns * rows)
+		.map(|i| height((i % columns) as f64 * 0.5, (i / columns) as f64 * 0.5))
+		.collect();
+	let terrain = Heightfield::new(Point3::new(0.0, 0.0, 0.0), 0.5, columns, rows, heights);
+
+	// Straight down onto a sample hits exactly at its height.
+	let t = terrain.raycast(Point3::new(3.0, 10.0, 2.0), Vector3::new(0.0, -1.0, 0.0)).unwrap();
+	assert!((10.0 - t - height(3.0, 2.0)).abs() < 1e-12);
+
+	// A grazing ray across many cells agrees with a brute force search.
+	let origin = Point3::new(-3.0, 2.5, -1.0);
+	let direction = Vector3::new(1.0, -0.25, 0.45);
+	let t = terrain.raycast(origin, direction).unwrap();
+	let hit = origin.to_vector() + direction * t;
+	let mut brute = None;
+	for row in 0..rows - 1 {
+		for column in 0..columns - 1 {
+			let corners = [(column, row), (column + 1, row), (column + 1, row + 1), (column, row + 1)];
+			let [a, b, c, d] = corners.map(|(i, j)| terrain.sample_point(i, j).to_vector());
+			for (p, q, r) in [(a, b, c), (a, c, d)] {
+				let n = (q - p).cross(r - p);
+				let s = n.dot(p - origin.to_vector()) / n.dot(direction);
+				let x = origin.to_vector() + direction * s;
+				let inside = [(p, q), (q, r), (r, p)].iter().all(|&(u, v)| (v - u).cross(x - u).dot(n) >= -1e-12);
+				if s >= 0.0 && inside && brute.is_none_or(|b: f64| s < b) {
+					brute = Some(s);
+				}
+			}
+		}
+	}
+	assert!((t - brute.unwrap()).abs() < 1e-9);
+	assert!((hit[1] - terrain.height_at(hit[0], hit[2]).unwrap()).abs() < 0.1);
+
+	// Rays missing the grid or pointing away from the surface.
+	assert!(terrain.raycast(Point3::new(-1.0, 5.0, -1.0), Vector3::new(-1.0, 0.0, 0.0)).is_none());
+	assert!(terrain.raycast(Point3::new(3.0, 5.0, 2.0), Vector3::new(0.0, 1.0, 0.0)).is_none());
+	assert!(terrain.raycast(Point3::new(3.0, 5.0, 2.0), Vector3::new(1.0, 0.0, 0.0)).is_none());
+}
+
+#[test]
+fn test_heightfield_raycast_vertical() {
+	use math3d::geometry::Heightfield;
+
+	let (columns, rows) = (9, 400);
+	let heights = (0..columns * rows).map(|i| ((i % columns) + i / columns) as f64 * 0.25).collect();
+	let terrain = Heightfield::new(Point3::new(0.0, 0.0, 0.0), 1.0, columns, rows, heights);
+	let down = Vector3::new(0.0, -1.0, 0.0);
+
+	// Straight down hits the bilinear surface under the origin.
+	for (x, z) in [(0.5, 0.5), (3.25, 7.75), (8.0, 399.0), (4.0, 200.0)] {
+		let expected = terrain.height_at(x, z).unwrap();
+		let t = terrain.raycast(Point3::new(x, 500.0, z), down).unwrap();
+		assert!((500.0 - t - expected).abs() < 1e-9);
+	}
+
+	// Vertical rays that miss stay in their cell rather than walking the grid.
+	assert_eq!(terrain.raycast(Point3::new(2.5, -1.0, 0.5), down), None);
+	assert_eq!(terrain.raycast(Point3::new(2.5, 500.0, 0.5), -down), None);
+	assert_eq!(terrain.raycast(Point3::new(9.5, 500.0, 0.5), down), None);
+}
+
+#[test]
+fn test_heightfield_quadtree_raycast_and_update() {
+	use math3d::geometry::{Heightfield, HeightfieldQuadtree};
+
+	// Odd cell counts so the upper levels have partial nodes.
+	let (columns, rows) = (38, 23);
+	let height = |x: f64, z: f64| (x * 0.7).sin() + (z * 0.4).cos();
+	let heights = (0..columns * rows)
+		.map(|i| height((i % columns) as f64 * 0.5, (i / columns) as f64 * 0.5))
+		.collect();
+	let terrain = Heightfield::new(Point3::new(-4.0, 1.0, 2.0), 0.5, columns, rows, heights);
+	let mut tree = HeightfieldQuadtree::new(terrain);
+
+	let (low, high) = tree.height_range();
+	assert!(low >= -1.0 && high <= 3.0 && low < high);
+
+	let rays = |i: usize| {
+		let a = i as f64 * 0.37;
+		let origin = Point3::new(5.0 + (a * 1.3).sin() * 12.0, 3.0 + (a * 0.7).cos() * 2.0, 7.5 + (a * 0.9).sin() * 8.0);
+		let direction = Vector3::new(a.cos(), -0.05 - 0.5 * (a * 2.1).sin().abs(), a.sin() * 0.8);
+		(origin, direction)
+	};
+	let check = |tree: &HeightfieldQuadtree<f64>| {
+		let mut hits = 0;
+		for i in 0..200 {
+			let (origin, direction) = rays(i);
+			let expected = tree.heightfield().raycast(origin, direction);
+			let t = tree.raycast(origin, direction);
+			assert_eq!(t.is_some(), expected.is_some(), "ray {}", i);
+			if let (Some(t), Some(expected)) = (t, expected) {
+				assert!((t - expected).abs() < 1e-9, "ray {}: {} != {}", i, t, expected);
+				hits += 1;
+			}
+		}
+		assert!(hits > 30, "{} hits", hits);
+	};
+	check(&tree);
+
+	// Straight down and straight up.
+	let down = tree.raycast(Point3::new(1.0, 10.0, 4.0), Vector3::new(0.0, -1.0, 0.0)).unwrap();
+	assert!((10.0 - down - tree.heightfield().height_at(1.0, 4.0).unwrap()).abs() < 1e-12);
+	assert!(tree.raycast(Point3::new(1.0, 10.0, 4.0), Vector3::new(0.0, 1.0, 0.0)).is_none());
+	assert!(tree.raycast(Point3::new(-10.0, 1.0, 0.0), Vector3::new(-1.0, 0.0, 0.0)).is_none());
+
+	// A spike raised after building is found, through the updated bounds.
+	let spike = (20, 11);
+	let top = tree.heightfield().sample_point(spike.0, spike.1);
+	let ray_origin = Point3::new(top[0] - 5.0, 10.0, top[2]);
+	tree.set_height(spike.0, spike.1, 10.0);
+	assert_eq!(tree.height_range().1, 11.0);
+	let t = tree.raycast(ray_origin, Vector3::new(1.0, 0.0, 0.0)).unwrap();
+	assert!(t > 4.0 && t < 5.0);
+	check(&tree);
+
+	// Corner samples touch a single cell.
+	tree.set_height(0, 0, -5.0);
+	tree.set_height(columns - 1, rows - 1, 20.0);
+	assert_eq!(tree.height_range(), (-4.0, 21.0));
+	check(&tree);
+
+	let rebuilt = HeightfieldQuadtree::new(tree.clone().into_heightfield());
+	for i in 0..200 {
+		let (origin, direction) = rays(i);
+		assert_eq!(tree.raycast(origin, direction), rebuilt.raycast(origin, direction));
+	}
+}
+
+#[test]
+fn test_transform_pre_and_post_rotate_order() {
+	let t = Transform::new(
+		Vector3::new(1.0, 2.0, 3.0),
+		Quaternion::from_axis_angle(Vector3::new(1.0, 1.0, 0.0).normalized(), 40.0),
+		Vector3::new(2.0, 1.0, 0.5),
+	);
+	let q = Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 2.0).normalized(), 75.0);
+	let (local, world) = (t.pre_rotate(q), t.post_rotate(q));
+	let origin = t.translation();
+
+	for p in [Vector3::new(0.5, -1.0, 2.0), Vector3::zero(), Vector3::new(3.0, 0.0, -1.0)] {
+		let scaled = p * t.scale();
+		let expected = t.rotation().rotate_vector(q.rotate_vector(scaled)) + origin;
+		assert_vector3_near(local.transform_point(Point3::from_vector(p)).to_vector(), expected);
+
+		let expected = q.rotate_vector(t.transform_point(Point3::from_vector(p)).to_vector() - origin) + origin;
+		assert_vector3_near(world.transform_point(Point3::from_vector(p)).to_vector(), expected);
+	}
+	assert!(local.translation() == origin && world.translation() == origin);
+	assert!(local.rotation() == q.then(t.rotation()));
+	assert!(world.rotation() == t.rotation().then(q));
+}
+
+#[test]
+fn test_ellipsoid_raycast_hits_surface_and_aabb_is_tight() {
+	let rotation = Quaternion::from_axis_angle(Vector3::new(1.0, 2.0, -1.0).normalized(), 35.0);
+	let ellipsoid = Ellipsoid::new(Point3::new(1.0, -2.0, 0.5), Vector3::new(3.0, 1.5, 0.5), rotation);
+	let to_unit = ellipsoid.to_unit_sphere();
+	let aabb = ellipsoid.aabb();
+	let (min, max) = (aabb.min().to_vector(), aabb.max().to_vector());
+	let mut touched = [[false; 2]; 3];
+
+	for i in 0..200 {
+		let a = i as f64 * 0.731;
+		let direction = Vector3::new(a.cos(), (a * 1.7).sin(), (a * 0.3).cos() - 0.5);
+		let origin = Point3::from_vector(ellipsoid.center().to_vector() - direction * 10.0);
+		let t = ellipsoid.raycast(origin, direction).unwrap();
+		let hit = Point3::from_vector(origin.to_vector() + direction * t);
+		assert!(((hit.to_vector4() * to_unit).truncate().magnitude() - 1.0).abs() < 1e-9);
+		assert!(ellipsoid.contains(Point3::from_vector(origin.to_vector() + direction * (t + 1e-6))));
+		assert!(!ellipsoid.contains(Point3::from_vector(origin.to_vector() + direction * (t - 1e-6))));
+
+		// From inside, the ray leaves through the surface.
+		let exit = ellipsoid.raycast(ellipsoid.center(), direction).unwrap();
+		assert!(exit > 0.0 && (10.0 - exit - t).abs() < 1e-9);
+
+		for axis in 0..3 {
+			assert!(hit[axis] >= min[axis] - 1e-9 && hit[axis] <= max[axis] + 1e-9);
+		}
+	}
+
+	// Each face of the box touches the surface where its normal, taken to
+	// unit sphere space, is the normal of the sphere.
+	for axis in 0..3 {
+		for (side, sign) in [-1.0, 1.0].into_iter().enumerate() {
+			let mut normal = Vector3::zero();
+			normal[axis] = sign;
+			let local = rotation.conjugate().rotate_vector(normal) * ellipsoid.radii();
+			let point = ellipsoid.center().to_vector() + rotation.rotate_vector(local.normalized() * ellipsoid.radii());
+			let face = if sign < 0.0 { min[axis] } else { max[axis] };
+			touched[axis][side] = (point[axis] - face).abs() < 1e-9;
+		}
+	}
+	assert!(touched.iter().flatten().all(|t| *t));
+
+	assert!(ellipsoid.raycast(Point3::new(20.0, 20.0, 20.0), Vector3::new(1.0, 0.0, 0.0)).is_none());
+	assert!(ellipsoid.raycast(Point3::new(20.0, -2.0, 0.5), Vector3::new(1.0, 0.0, 0.0)).is_none());
+}
+
+#[test]
+fn test_ellipsoid_from_sphere() {
+	let sphere = Sphere::new(Point3::new(1.0, 2.0, 3.0), 2.0);
+	let ellipsoid = Ellipsoid::from_sphere(&sphere);
+	for point in [Point3::new(1.0, 2.0, 5.0), Point3::new(2.5, 3.0, 3.0), Point3::new(1.0, 4.1, 3.0)] {
+		assert_eq!(ellipsoid.contains(point), sphere.contains(point));
+	}
+	let aabb = ellipsoid.aabb();
+	assert!(aabb.min() == Point3::new(-1.0, 0.0, 1.0) && aabb.max() == Point3::new(3.0, 4.0, 5.0));
+}
+
+#[test]
+fn test_aabb3_transformed_matches_corners() {
+	let aabb = Aabb3::new(Point3::new(-1.0, 0.5, 2.0), Point3::new(3.0, 1.5, 2.25));
+	let rotation = Quaternion::from_axis_angle(Vector3::new(0.3, -1.0, 0.7).normalized(), 63.0);
+	let m = math3d::camera::scale(2.0, 0.5, 3.0) * rotation.rotation_matrix4() * math3d::camera::translation(4.0, -1.0, 0.5);
+
+	// Reference: bound all eight transformed corners.
+	let (min, max) = (aabb.min(), aabb.max());
+	let corners: Vec<Point3<f64>> = (0..8)
+		.map(|i: usize| {
+			let pick = |axis: usize| if i & (1 << axis) != 0 { max[axis] } else { min[axis] };
+			let corner = Point3::new(pick(0), pick(1), pick(2));
+			Point3::from_vector((corner.to_vector4() * m).truncate())
+		})
+		.collect();
+	let expected = Aabb3::from_points(&corners).unwrap();
+
+	let transformed = aabb.transformed(&m);
+	assert!(transformed.min().distance_to(expected.min()) < 1e-12);
+	assert!(transformed.max().distance_to(expected.max()) < 1e-12);
+}
@@ -0,0 +1,92 @@
+use math3d::geometry::{Obb, Sphere};
+use math3d::gizmo;
+use math3d::points::Point3;
+use math3d::quaternion::Quaternion;
+use math3d::vectors::Vector3;
+
+#[cfg(test)]
+
+fn length(segment: &gizmo::Segment<f64>) -> f64 {
+	segment.0.distance_to(segment.1)
+}
+
+#[test]
+fn test_gizmo_arrow() {
+	let from = Point3::<f64>::new(1.0, 2.0, 3.0);
+	let to = Point3::new(4.0, -2.0, 3.0);
+	let segments = gizmo::arrow(from, to, 0.5);
+
+	assert!(segments[0] == (from, to));
+	for head in &segments[1..] {
+		assert!(head.0 == to);
+		// Each head line goes back half a unit along the shaft and a quarter unit sideways.
+		let back = (to.to_vector() - from.to_vector()).normalized().dot(head.0.to_vector() - head.1.to_vector());
+		assert!((back - 0.5).abs() < 1e-12);
+		assert!((length(head) - 0.3125_f64.sqrt()).abs() < 1e-12);
+	}
+	assert_eq!(gizmo::arrow(from, from, 0.5).len(), 1);
+}
+
+#[test]
+fn test_gizmo_axes() {
+	let origin = Point3::new(0.0, 1.0, 0.0);
+	let rotation = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 90.0);
+	let segments = gizmo::axes(origin, rotation, 2.0);
+
+	assert_eq!(segments.len(), 15);
+	// The rotated x axis points along y.
+	assert!(segments[0].1.distance_to(Point3::new(0.0, 3.0, 0.0)) < 1e-12);
+	assert!(segments[5].1.distance_to(Point3::new(-2.0, 1.0, 0.0)) < 1e-12);
+	assert!(segments[10].1.distance_to(Point3::new(0.0, 1.0, 2.0)) < 1e-12);
+}
+
+#[test]
+fn test_gizmo_circle() {
+	let center = Point3::<f64>::new(1.0, -1.0, 2.0);
+	let normal = Vector3::new(1.0, 1.0, 1.0);
+	let segments = gizmo::circle(center, normal, 3.0, 24);
+
+	assert_eq!(segments.len(), 24);
+	assert!(segments[23].1 == segments[0].0);
+	for (i, segment) in segments.iter().enumerate() {
+		assert!(segment.0 == segments[(i + 23) % 24].1);
+		assert!((segment.0.distance_to(center) - 3.0).abs() < 1e-12);
+		assert!((segment.0.to_vector() - center.to_vector()).dot(normal).abs() < 1e-12);
+	}
+}
+
+#[test]
+fn test_gizmo_obb() {
+	let rotation = Quaternion::from_axis_angle(Vector3::new(1.0, 2.0, 3.0).normalized(), 30.0);
+	let axes = [
+		rotation.rotate_vector(Vector3::new(1.0, 0.0, 0.0)),
+		rotation.rotate_vector(Vector3::new(0.0, 1.0, 0.0)),
+		rotation.rotate_vector(Vector3::new(0.0, 0.0, 1.0)),
+	];
+	let obb = Obb::new(Point3::new(1.0, 2.0, 3.0), axes, Vector3::new(1.0, 2.0, 3.0));
+	let segments = gizmo::obb(&obb);
+
+	assert_eq!(segments.len(), 12);
+	let mut lengths: Vec<f64> = segments.iter().map(length).collect();
+	lengths.sort_by(|a, b| a.partial_cmp(b).unwrap());
+	for (i, expected) in [2.0, 4.0, 6.0].iter().enumerate() {
+		assert!(lengths[i * 4..i * 4 + 4].iter().all(|l| (l - expected).abs() < 1e-12));
+	}
+	assert!(segments.iter().all(|s| obb.contains(s.0, 1e-9) && obb.contains(s.1, 1e-9)));
+}
+
+#[test]
+fn test_gizmo_frustum_and_sphere() {
+	use math3d::camera::Camera;
+
+	let camera = Camera::new(Point3::new(0.0, 0.0, 5.0), Quaternion::identity(), 1.0, 1.5, 0.1, 10.0);
+	let corners = camera.frustum_corners();
+	let segments = gizmo::frustum(&camera);
+	assert_eq!(segments.len(), 12);
+	assert!(corners.iter().all(|c| segments.iter().filter(|s| s.0 == *c || s.1 == *c).count() == 3));
+
+	let sphere = Sphere::<f64>::new(Point3::new(1.0, 2.0, 3.0), 2.0);
+	let segments = gizmo::sphere(&sphere, 16);
+	assert_eq!(segments.len(), 48);
+	assert!(segments.iter().all(|s| (s.0.distance_to(sphere.center()) - 2.0).abs() < 1e-12));
+}
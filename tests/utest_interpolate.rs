@@ -0,0 +1,49 @@
+use math3d::interpolate::{damp, smoothing_from_half_life};
+use math3d::points::Point3;
+use math3d::quaternion::Quaternion;
+use math3d::vectors::Vector3;
+
+#[cfg(test)]
+
+#[test]
+fn test_damp_is_frame_rate_independent() {
+	let smoothing = smoothing_from_half_life(0.2);
+	let run = |frames: usize| {
+		let dt = 1.0 / frames as f64;
+		let (mut x, mut p) = (0.0, Point3::new(0.0, 0.0, 0.0));
+		for _ in 0..frames {
+			x = damp(x, 1.0, smoothing, dt);
+			p = damp(p, Point3::new(2.0, -4.0, 6.0), smoothing, dt);
+		}
+		(x, p)
+	};
+	let (x30, p30) = run(30);
+	let (x144, p144) = run(144);
+	// One second is five half-lives.
+	assert!((x30 - (1.0 - 1.0 / 32.0)).abs() < 1e-12);
+	assert!((x144 - x30).abs() < 1e-12);
+	assert!(p30.distance_to(p144) < 1e-12);
+	assert!(p30.distance_to(Point3::from_vector(Vector3::new(2.0, -4.0, 6.0) * (31.0 / 32.0))) < 1e-12);
+}
+
+#[test]
+fn test_damp_limits() {
+	let v = Vector3::new(1.0, 2.0, 3.0);
+	let target = Vector3::new(-1.0, 0.0, 5.0);
+	assert!(damp(v, target, 0.0, 1.0) == v);
+	assert!(damp(v, target, f64::INFINITY, 0.01) == target);
+	assert!(damp(v, target, 1000.0, 0.0) == v);
+}
+
+#[test]
+fn test_damp_quaternion_decays_angle() {
+	let axis = Vector3::new(0.0, 1.0, 0.0);
+	let target = Quaternion::from_axis_angle(axis, 120.0);
+	let mut q = Quaternion::<f64>::identity();
+	for _ in 0..60 {
+		q = damp(q, target, smoothing_from_half_life(0.5), 1.0 / 60.0);
+	}
+	// A quarter of the angle is left after two half-lives.
+	assert!(q.approx_eq(Quaternion::from_axis_angle(axis, 90.0), 1e-9));
+	assert!(q.is_normalized(1e-12));
+}
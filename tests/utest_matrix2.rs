@@ -0,0 +1,75 @@
+use math3d::matrices::{Matrix2, Matrix3};
+use math3d::vectors::{Vector2, Vector3};
+
+#[cfg(test)]
+
+#[test]
+fn test_matrix2_mul() {
+	let m1 = Matrix2::new(1.0, 2.0, 3.0, 4.0);
+	let m2 = Matrix2::new(5.0, 6.0, 7.0, 8.0);
+
+	assert!(m1 * m2 == Matrix2::new(19.0, 22.0, 43.0, 50.0));
+	assert!(m1 * Matrix2::identity() == m1);
+	assert!(2.0 * m1 == Matrix2::new(2.0, 4.0, 6.0, 8.0));
+
+	// Row vectors apply the left matrix first.
+	let v = Vector2::new(1.0, -2.0);
+	assert!(v * (m1 * m2) == (v * m1) * m2);
+	assert!(m1 * v == v * m1.transpose());
+}
+
+#[test]
+fn test_matrix2_rotation_matches_vector2_and_matrix3() {
+	let angle = 0.7_f64;
+	let r = Matrix2::from_angle(angle);
+	let r3 = Matrix3::from_rotation_z(angle.to_degrees());
+
+	for v in [Vector2::new(1.0, 0.0), Vector2::new(-2.0, 3.5), Vector2::new(0.25, -1.0)] {
+		let rotated = v * r;
+		assert!(rotated.approx_eq(v.rotate(angle), 1e-12));
+		let rotated3 = Vector3::new(v.x(), v.y(), 0.0) * r3;
+		assert!(rotated.approx_eq(Vector2::new(rotated3.x(), rotated3.y()), 1e-12));
+	}
+
+	assert!((r.determinant() - 1.0).abs() < 1e-12);
+	assert!(r.inverse().approx_eq(r.transpose(), 1e-12));
+	assert!((r * Matrix2::from_angle(-angle)).approx_eq(Matrix2::identity(), 1e-12));
+	assert!((r.trace() - 2.0 * angle.cos()).abs() < 1e-12);
+}
+
+#[test]
+fn test_matrix2_determinant_and_inverse() {
+	let m = Matrix2::new(3.0, -1.0, 2.0, 0.5);
+
+	assert_eq!(m.determinant(), 3.5);
+	assert!((m * m.inverse()).approx_eq(Matrix2::identity(), 1e-12));
+	assert!(m.try_inverse() == Some(m.inverse()));
+	assert!(Matrix2::new(1.0, 2.0, 2.0, 4.0).try_inverse().is_none());
+
+	let s = Matrix2::from_scale(Vector2::new(2.0, -4.0));
+	assert!(Vector2::new(1.0, 1.0) * s == Vector2::new(2.0, -4.0));
+	assert_eq!(s.determinant(), -8.0);
+	assert!(s.inverse() == Matrix2::from_scale(Vector2::new(0.5, -0.25)));
+}
+
+#[test]
+fn test_matrix2_symmetric_eigen_covariance() {
+	// Covariance of points spread along a 30 degree line.
+	let angle = 30.0_f64.to_radians();
+	let axes = Matrix2::from_angle(angle);
+	let covariance = axes.transpose() * Matrix2::from_scale(Vector2::new(9.0, 1.0)) * axes;
+
+	let (values, vectors) = covariance.symmetric_eigen();
+	assert!((values.x() - 9.0).abs() < 1e-12);
+	assert!((values.y() - 1.0).abs() < 1e-12);
+	let major = vectors.row(0);
+	assert!((major.x() * angle.sin() - major.y() * angle.cos()).abs() < 1e-12);
+	assert!((vectors * vectors.transpose()).approx_eq(Matrix2::identity(), 1e-12));
+
+	let mut m = Matrix2::identity();
+	m[(0, 1)] = 5.0;
+	assert_eq!(m[0][1], 5.0);
+	assert!(m.column(1) == Vector2::new(5.0, 1.0));
+	assert_eq!(Matrix2::from_array(m.to_array()), m);
+	assert_eq!(Matrix2::from_array_2d(m.to_array_2d()), m);
+}
@@ -1,4 +1,7 @@
-use math3d::matrices::Matrix3;
+use math3d::matrices::{Col, Matrix3, Row};
+use math3d::scalar::F32ConversionError;
+use math3d::quaternion::Quaternion;
+use math3d::vectors::Vector3;
 
 #[cfg(test)]
 
@@ -23,4 +26,434 @@ fn test_matrix3_mul() {
 	let m3 = m1 * m2;
 
 	assert!(m3 == e);
-}
\ No newline at end of file
+}
+#[test]
+fn test_matrix3_symmetric_eigen() {
+	let m = Matrix3::<f64>::from_array_2d([
+		[4.0, 1.0, 2.0],
+		[1.0, 3.0, 0.5],
+		[2.0, 0.5, 5.0],
+	]);
+	let (values, vectors) = m.symmetric_eigen();
+
+	assert!(values[0] >= values[1] && values[1] >= values[2]);
+	for i in 0..3 {
+		let v = vectors[i];
+		let mv = v * m;
+		assert!((mv - v * values[i]).magnitude() < 1e-9);
+		assert!((v.magnitude() - 1.0).abs() < 1e-9);
+	}
+}
+
+#[test]
+fn test_matrix3_pinv_singular() {
+	let m = Matrix3::<f64>::from_array_2d([
+		[1.0, 2.0, 3.0],
+		[4.0, 5.0, 6.0],
+		[7.0, 8.0, 9.0],
+	]);
+	let p = m.pinv();
+	let mpm = m * p * m;
+
+	for i in 0..3 {
+		for j in 0..3 {
+			assert!((mpm[i][j] - m[i][j]).abs() < 1e-10);
+		}
+	}
+}
+
+#[test]
+fn test_matrix3_at_and_set() {
+	use math3d::matrices::{Col, Row};
+
+	let mut m = Matrix3::from_array_2d([
+		[1.0, 2.0, 3.0],
+		[4.0, 5.0, 6.0],
+		[7.0, 8.0, 9.0],
+	]);
+
+	assert_eq!(m.at(Row(0), Col(2)), 3.0);
+	assert_eq!(m.at(Row(2), Col(0)), 7.0);
+
+	m.set(Row(1), Col(0), -4.0);
+
+	assert_eq!(m[1][0], -4.0);
+	assert_eq!(m.transpose().at(Row(0), Col(1)), -4.0);
+}
+
+#[test]
+fn test_matrix3_transform_normals_slice() {
+	use math3d::vectors::Vector3;
+
+	// Shear in the xy plane, applied to row vectors as `v * m`.
+	let m = Matrix3::<f64>::from_array_2d([
+		[2.0, 0.0, 0.0],
+		[1.0, 1.0, 0.0],
+		[0.0, 0.0, -3.0],
+	]);
+	let tangents = [Vector3::new(1.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 1.0)];
+	let normal = tangents[0].cross(tangents[1]).normalized();
+
+	let mut normals = vec![normal; 40000];
+	m.transform_normals_slice(&mut normals[..1]);
+	m.transform_normals_slice_par(&mut normals[1..]);
+
+	// The transformed tangents span the transformed surface; their cross product
+	// flips with the handedness of `m` while the inverse-transpose does not.
+	let sign = m.determinant().signum();
+	let transformed_normal = (tangents[0] * m).cross(tangents[1] * m).normalized() * sign;
+	for n in &normals {
+		assert!((n.magnitude() - 1.0).abs() < 1e-12);
+		assert!((*n - transformed_normal).magnitude() < 1e-12);
+		assert!((n.dot(tangents[0] * m)).abs() < 1e-12);
+	}
+
+	let expected = m.normal_matrix();
+	assert!(((normal * expected).normalized() - normals[0]).magnitude() < 1e-12);
+}
+
+#[test]
+fn test_matrix3_map() {
+	let m = Matrix3::from_array_2d([
+		[1.0, 2.0, 3.0],
+		[4.0, 5.0, 6.0],
+		[7.0, 8.0, 9.0],
+	]);
+
+	assert!(m.map(|a| a - 1.0) == Matrix3::from_array([0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]));
+	assert!(m.map(|a| a * 2.0) == m * 2.0);
+}
+
+#[test]
+fn test_matrix3_compound_assignment() {
+	let m = Matrix3::<f64>::from_array_2d([[2.0, 0.0, 1.0], [0.0, 1.0, 0.0], [1.0, 0.0, 3.0]]);
+	let mut a = m;
+	a *= m;
+	assert!(a == m * m);
+	a *= 2.0;
+	a /= 4.0;
+	assert!(a == (m * m) * 0.5);
+}
+
+#[test]
+fn test_matrix3_add_sub() {
+	let a = Matrix3::<f64>::from_array_2d([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+	let b = Matrix3::<f64>::identity();
+
+	assert!(a + b == Matrix3::from_array_2d([[2.0, 2.0, 3.0], [4.0, 6.0, 6.0], [7.0, 8.0, 10.0]]));
+	assert!(a - a == Matrix3::default());
+	assert!(a + a == a * 2.0);
+
+	let mut c = a;
+	c += b;
+	assert!(c == a + b);
+	c -= b;
+	assert!(c == a);
+}
+
+#[test]
+fn test_matrix3_div_is_mul_inverse() {
+	let a = Matrix3::<f64>::from_array_2d([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+	let b = Matrix3::<f64>::from_array_2d([[2.0, 0.0, 1.0], [0.0, 1.0, 0.0], [1.0, 0.0, 3.0]]);
+
+	assert!((a / b).approx_eq(a * b.inverse(), 1e-12));
+	assert!(((a / b) * b).approx_eq(a, 1e-12));
+	assert!((b / b).approx_eq(Matrix3::identity(), 1e-12));
+	assert!(a.checked_div(b) == Some(a / b));
+	assert!(b.checked_div(a).is_none());
+
+	let mut c = a;
+	c /= b;
+	assert!(c == a / b);
+}
+
+#[test]
+fn test_matrix3_scalar_on_the_left() {
+	let m = Matrix3::<f64>::from_array_2d([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+	assert!(3.0 * m == m * 3.0);
+}
+
+#[test]
+fn test_matrix3_inverse_and_det() {
+	let m = Matrix3::<f64>::from_array_2d([
+		[2.0, -1.0, 0.5],
+		[1.0, 3.0, -2.0],
+		[0.0, 4.0, 1.0],
+	]);
+	let (inverse, det) = m.inverse_and_det();
+	assert!((det - m.determinant()).abs() < 1e-12);
+
+	for product in [m * inverse, inverse * m] {
+		for i in 0..3 {
+			for j in 0..3 {
+				let expected = if i == j { 1.0 } else { 0.0 };
+				assert!((product[i][j] - expected).abs() < 1e-12);
+			}
+		}
+	}
+
+	let singular = Matrix3::<f64>::from_array_2d([
+		[1.0, 2.0, 3.0],
+		[4.0, 5.0, 6.0],
+		[7.0, 8.0, 9.0],
+	]);
+	assert_eq!(singular.inverse_and_det().1, 0.0);
+}
+
+#[test]
+fn test_matrix3_inverse_and_try_inverse() {
+	let m = Matrix3::<f64>::from_array_2d([
+		[0.0, 2.0, 1.0],
+		[3.0, -1.0, 2.0],
+		[1.0, 1.0, 4.0],
+	]);
+	let inverse = m.try_inverse().unwrap();
+	assert!(inverse == m.inverse());
+
+	let product = m * inverse;
+	for i in 0..3 {
+		for j in 0..3 {
+			let expected = if i == j { 1.0 } else { 0.0 };
+			assert!((product[i][j] - expected).abs() < 1e-12);
+		}
+	}
+
+	// A plain transpose over the determinant is not the inverse.
+	assert!((m.transpose() / m.determinant()) != inverse);
+
+	let singular = Matrix3::<f64>::from_array_2d([
+		[1.0, 2.0, 3.0],
+		[2.0, 4.0, 6.0],
+		[0.0, 1.0, 1.0],
+	]);
+	assert!(singular.try_inverse().is_none());
+}
+
+#[test]
+fn test_matrix3_iterators() {
+	let mut m = Matrix3::<f64>::from_array_2d([
+		[1.0, 2.0, 3.0],
+		[4.0, 5.0, 6.0],
+		[7.0, 8.0, 9.0],
+	]);
+	assert!(m.iter().copied().eq(m.to_array()));
+	assert_eq!(m.rows().count(), 3);
+	for (i, (row, col)) in m.rows().zip(m.cols()).enumerate() {
+		assert!(row == m[i]);
+		assert!(col == m.transpose()[i]);
+	}
+
+	for a in m.iter_mut() {
+		*a *= 2.0;
+	}
+	assert!(m.iter().copied().eq((1..=9).map(|i| 2.0 * i as f64)));
+}
+
+#[test]
+fn test_matrix3_try_to_array_f32() {
+	let mut m = Matrix3::<f64>::from_array_2d([
+		[0.5, -2.0, 3.25],
+		[4.0, f64::INFINITY, 6.0],
+		[7.0, 8.0, 1024.0],
+	]);
+	assert_eq!(m.try_to_array_f32(0.0), Ok(m.to_array_f32()));
+
+	m[2][0] = 0.1;
+	assert_eq!(m.try_to_array_f32(0.0), Err(F32ConversionError::PrecisionLoss { index: 6 }));
+	assert!(m.try_to_array_f32(1e-8).is_ok());
+
+	m[0][1] = -1e39;
+	assert_eq!(m.try_to_array_f32(f64::INFINITY), Err(F32ConversionError::OutOfRange { index: 1 }));
+	assert_eq!(m.try_to_array_f32(1e-8).unwrap_err().to_string(), "element 1 is out of f32 range");
+
+	let single = Matrix3::<f32>::identity() * 0.1;
+	assert_eq!(single.try_to_array_f32(0.0), Ok(single.to_array_f32()));
+}
+
+#[test]
+fn test_matrix3_default_zero_and_one() {
+	use num::{One, Zero};
+
+	let m = Matrix3::<f64>::from_array([2.0, -1.0, 0.5, 1.0, 3.0, -2.0, 0.0, 4.0, 1.0]);
+	assert!(Matrix3::<f64>::default().iter().all(|a| *a == 0.0));
+	assert!(Matrix3::<f64>::zero() == Matrix3::default());
+	assert!(Matrix3::zero() + m == m && (m - m).is_zero() && !m.is_zero());
+	assert!(Matrix3::one() * m == m && m * Matrix3::one() == m);
+}
+
+#[test]
+fn test_matrix3_table_and_diff_strings() {
+	let a = Matrix3::<f64>::from_array_2d([
+		[1.0, -2.5, 300.0],
+		[0.0, 1.0, 0.0],
+		[f64::NAN, 0.0, 1.0],
+	]);
+	assert_eq!(a.to_table_string(2), "[ 1.00  -2.50  300.00 ]\n[ 0.00   1.00    0.00 ]\n[  NaN   0.00    1.00 ]");
+
+	let mut b = a;
+	b[0][1] = -2.5 + 1e-12;
+	b[1][2] = 0.25;
+	let diff = a.diff_string(b, 1e-9);
+	let lines: Vec<&str> = diff.lines().collect();
+	assert_eq!(lines.len(), 4);
+	assert_eq!(lines[1], "[ 0.000000   1.000000   *0.000000 ]   [ 0.000000   1.000000   *0.250000 ]");
+	assert!(!lines[0].contains('*'));
+	assert!(lines[2].starts_with("[     *NaN"));
+	assert_eq!(lines[3], "2 of 9 elements differ by more than 0.000000001, the largest by NaN at row 2, column 0");
+
+	let mut c = b;
+	c[2][0] = 0.0;
+	assert!(a.diff_string(a, 0.0).contains("1 of 9 elements differ by more than 0, "));
+	assert!(Matrix3::<f64>::identity().diff_string(Matrix3::identity(), 0.0).ends_with("\n0 of 9 elements differ by more than 0"));
+	assert!(c.diff_string(b, 0.0).ends_with("the largest by NaN at row 2, column 0"));
+}
+
+#[test]
+fn test_matrix3_skew_symmetric_rodrigues() {
+	// Rodrigues' formula R = I + sin(a) K + (1 - cos(a)) K^2 with K the
+	// cross-product matrix of the unit axis, applied to column vectors.
+	let axis = Vector3::<f64>::new(1.0, -2.0, 2.0).normalized();
+	let angle = 40.0_f64.to_radians();
+	let k = Matrix3::skew_symmetric(axis);
+	let k2 = k * k;
+
+	let q = Quaternion::from_axis_angle(axis, 40.0);
+	for w in [Vector3::new(1.0, 0.0, 0.0), Vector3::new(0.3, 2.0, -1.5)] {
+		let rotated = w + k * w * angle.sin() + k2 * w * (1.0 - angle.cos());
+		assert!((rotated - q.rotate_vector(w)).magnitude() < 1e-12);
+		assert!((k * w - axis.cross(w)).magnitude() < 1e-15);
+		assert!((w * k - w.cross(axis)).magnitude() < 1e-15);
+	}
+}
+
+#[test]
+fn test_matrix3_column_and_row_vector_products() {
+	let r = Quaternion::<f64>::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 90.0).rotation_matrix();
+	let v = Vector3::new(1.0, 0.0, 0.0);
+
+	// Row vectors rotate by `v * r`; the column form needs the transpose.
+	assert!((v * r - Vector3::new(0.0, 1.0, 0.0)).magnitude() < 1e-12);
+	assert!((r.transpose() * v - v * r).magnitude() < 1e-12);
+	assert!((r * v - v * r.transpose()).magnitude() < 1e-12);
+}
+
+#[test]
+fn test_matrix3_rotation_constructors_match_quaternion() {
+	let axis = Vector3::<f64>::new(2.0, -1.0, 0.5).normalized();
+	for angle in [-135.0, -30.0, 0.0, 45.0, 170.0] {
+		let expected = Quaternion::from_axis_angle(axis, angle).rotation_matrix();
+		assert!(Matrix3::from_axis_angle(axis, angle).approx_eq(expected, 1e-12));
+
+		let x = Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), angle).rotation_matrix();
+		let y = Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), angle).rotation_matrix();
+		let z = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), angle).rotation_matrix();
+		assert!(Matrix3::from_rotation_x(angle).approx_eq(x, 1e-12));
+		assert!(Matrix3::from_rotation_y(angle).approx_eq(y, 1e-12));
+		assert!(Matrix3::from_rotation_z(angle).approx_eq(z, 1e-12));
+	}
+
+	for (x, y, z) in [(10.0, 20.0, 30.0), (-75.0, 89.0, 160.0), (0.0, -45.0, 0.0)] {
+		let expected = Quaternion::from_euler_angles(x, y, z).rotation_matrix();
+		assert!(Matrix3::from_euler_angles(x, y, z).approx_eq(expected, 1e-12));
+	}
+}
+
+#[test]
+fn test_matrix3_to_quaternion_round_trip() {
+	// Angles up to 180 degrees about each axis and a skew one cover every
+	// pivot of the conversion, including negative traces.
+	let axes = [Vector3::new(1.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0), Vector3::new(0.0, 0.0, 1.0), Vector3::<f64>::new(1.0, -2.0, 3.0).normalized()];
+	for axis in axes {
+		for angle in [0.0, 10.0, 90.0, 150.0, 179.9, 180.0] {
+			let m = Matrix3::from_axis_angle(axis, angle);
+			let q = m.to_quaternion();
+			assert!((q.norm() - 1.0).abs() < 1e-12);
+			assert!((q.dot(Quaternion::from_axis_angle(axis, angle)).abs() - 1.0).abs() < 1e-12);
+			assert!(q.rotation_matrix().approx_eq(m, 1e-12));
+		}
+	}
+}
+
+#[test]
+fn test_matrix3_trace_norm_adjugate_and_cofactors() {
+	let m = Matrix3::<f64>::new(2.0, -1.0, 0.5, 3.0, 4.0, -2.0, 1.0, 0.0, 5.0);
+
+	assert_eq!(m.trace(), 11.0);
+	assert_eq!(m.frobenius_norm(), (4.0 + 1.0 + 0.25 + 9.0 + 16.0 + 4.0 + 1.0 + 25.0_f64).sqrt());
+	assert!(m.adjugate().approx_eq(m.inverse() * m.determinant(), 1e-12));
+	assert!((m * m.adjugate()).approx_eq(Matrix3::identity() * m.determinant(), 1e-12));
+	for i in 0..3 {
+		for j in 0..3 {
+			assert_eq!(m.cofactor_at(Row(i), Col(j)), m.cofactor()[i][j]);
+		}
+	}
+
+	let rotation = Matrix3::<f64>::from_axis_angle(Vector3::new(0.0, 0.6, 0.8), 60.0);
+	assert!((rotation.trace() - 2.0).abs() < 1e-12);
+	assert!((rotation.frobenius_norm() - 3.0_f64.sqrt()).abs() < 1e-12);
+
+	// Defined for singular matrices too.
+	let singular = Matrix3::<f64>::new(1.0, 2.0, 3.0, 2.0, 4.0, 6.0, 0.0, 1.0, 1.0);
+	assert!((singular * singular.adjugate()).approx_eq(Matrix3::new(0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0), 1e-12));
+}
+
+#[test]
+fn test_matrix3_slice_views() {
+	let rotation = Matrix3::<f64>::from_rotation_z(90.0);
+	let mut data = [Matrix3::identity().to_array(), rotation.to_array()].concat();
+
+	let matrices = Matrix3::cast_slice(&data).unwrap();
+	assert_eq!(matrices.len(), 2);
+	assert!(matrices[0] == Matrix3::identity());
+	assert!(matrices[1] == rotation);
+	assert!(Matrix3::cast_slice(&data[..17]).is_none());
+	assert!(Matrix3::from_slice(&data[9..]) == Some(&rotation));
+	assert!(Matrix3::from_slice(&data).is_none());
+
+	*Matrix3::from_slice_mut(&mut data[..9]).unwrap() *= 2.0;
+	assert_eq!(data[4], 2.0);
+	for m in Matrix3::cast_slice_mut(&mut data).unwrap() {
+		*m = m.transpose();
+	}
+	assert!(Matrix3::from_slice(&data[9..]) == Some(&rotation.transpose()));
+}
+
+#[test]
+fn test_matrix3_row_column_accessors() {
+	let mut m = Matrix3::<f64>::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+
+	for i in 0..3 {
+		assert!(m.row(i) == m.rows().nth(i).unwrap());
+		assert!(m.column(i) == m.cols().nth(i).unwrap());
+		assert!(m.column(i) == m.transpose().row(i));
+		for j in 0..3 {
+			assert_eq!(m[(i, j)], m[i][j]);
+		}
+	}
+
+	m.set_row(0, Vector3::new(-1.0, -2.0, -3.0));
+	m.set_column(1, Vector3::new(10.0, 20.0, 30.0));
+	m[(2, 2)] = 0.5;
+	assert!(m == Matrix3::new(-1.0, 10.0, -3.0, 4.0, 20.0, 6.0, 7.0, 30.0, 0.5));
+}
+
+#[test]
+fn test_matrix3_column_major_export() {
+	let m = Matrix3::<f64>::from_euler_angles(10.0, -20.0, 30.0) * 2.0;
+	let cols = m.to_cols_array();
+
+	for j in 0..3 {
+		for i in 0..3 {
+			assert_eq!(cols[j * 3 + i], m[i][j]);
+			assert_eq!(m.to_cols_array_2d()[j][i], m[i][j]);
+		}
+	}
+	assert!(Matrix3::from_cols_array(cols) == m);
+	assert_eq!(m.transpose().to_cols_array(), m.to_array());
+
+	let std140 = m.to_std140();
+	for (j, column) in std140.iter().enumerate() {
+		assert_eq!(&column[..3], &m.to_cols_array_2d()[j]);
+		assert_eq!(column[3], 0.0);
+	}
+}
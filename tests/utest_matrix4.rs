@@ -0,0 +1,444 @@
+use math3d::matrices::{Col, Matrix4, Row};
+use math3d::points::Point3;
+use math3d::quaternion::Quaternion;
+use math3d::vectors::{Vector3, Vector4};
+
+#[cfg(test)]
+
+fn assert_matrix4_near(a: Matrix4<f64>, b: Matrix4<f64>, epsilon: f64) {
+	for i in 0..4 {
+		for j in 0..4 {
+			assert!((a[i][j] - b[i][j]).abs() < epsilon, "[{}][{}]: {} != {}", i, j, a[i][j], b[i][j]);
+		}
+	}
+}
+
+#[test]
+fn test_matrix4_pinv_invertible() {
+	let m = Matrix4::from_vectors(
+		Vector4::new(2.0, 1.0, 0.0, 0.0),
+		Vector4::new(1.0, 3.0, 1.0, 0.0),
+		Vector4::new(0.0, 1.0, 4.0, 1.0),
+		Vector4::new(0.0, 0.0, 1.0, 5.0),
+	);
+	assert_matrix4_near(m * m.pinv(), Matrix4::identity(), 1e-12);
+}
+
+#[test]
+fn test_matrix4_pinv_rank_deficient() {
+	let m = Matrix4::from_vectors(
+		Vector4::new(1.0, 2.0, 3.0, 4.0),
+		Vector4::new(2.0, 4.0, 6.0, 8.0),
+		Vector4::new(0.0, 1.0, 0.0, 1.0),
+		Vector4::new(1.0, 3.0, 3.0, 5.0),
+	);
+	let p = m.pinv();
+
+	assert_matrix4_near(m * p * m, m, 1e-10);
+	assert_matrix4_near(p * m * p, p, 1e-10);
+	assert_matrix4_near((m * p).transpose(), m * p, 1e-10);
+	assert_matrix4_near((p * m).transpose(), p * m, 1e-10);
+}
+
+#[test]
+fn test_matrix4_mul_slice() {
+	let parent = Matrix4::from_vectors(
+		Vector4::new(0.0, 1.0, 0.0, 0.0),
+		Vector4::new(-1.0, 0.0, 0.0, 0.0),
+		Vector4::new(0.0, 0.0, 2.0, 0.0),
+		Vector4::new(3.0, -1.0, 0.5, 1.0),
+	);
+	let locals: Vec<Matrix4<f64>> = (0..5000)
+		.map(|i| {
+			let f = i as f64;
+			Matrix4::from_vectors(
+				Vector4::new(1.0, f, 0.0, 0.0),
+				Vector4::new(0.0, 1.0, 0.0, 0.0),
+				Vector4::new(0.0, 0.0, 1.0, 0.0),
+				Vector4::new(f, -f, 2.0 * f, 1.0),
+			)
+		})
+		.collect();
+	let mut serial = vec![Matrix4::zero(); locals.len()];
+	let mut parallel = vec![Matrix4::zero(); locals.len()];
+
+	Matrix4::mul_slice(&parent, &locals, &mut serial);
+	Matrix4::mul_slice_par(&parent, &locals, &mut parallel);
+
+	for i in 0..locals.len() {
+		assert_matrix4_near(serial[i], locals[i] * parent, 1e-12);
+		assert_matrix4_near(parallel[i], serial[i], 1e-12);
+	}
+	let p = Vector4::new(1.0, 2.0, 3.0, 1.0);
+	assert!(p * serial[7] == (p * locals[7]) * parent);
+}
+
+#[test]
+fn test_matrix4_compound_assignment() {
+	let translation = |x: f64| {
+		Matrix4::from_vectors(
+			Vector4::new(1.0, 0.0, 0.0, 0.0),
+			Vector4::new(0.0, 1.0, 0.0, 0.0),
+			Vector4::new(0.0, 0.0, 1.0, 0.0),
+			Vector4::new(x, 2.0 * x, 3.0 * x, 1.0),
+		)
+	};
+	let mut a = translation(1.0);
+	a *= translation(1.0);
+	assert_matrix4_near(a, translation(2.0), 1e-12);
+	a *= 3.0;
+	a /= 6.0;
+	assert_matrix4_near(a, translation(2.0) * 0.5, 1e-12);
+	assert_eq!(a[3][0], 1.0);
+	assert_eq!(a[3][3], 0.5);
+}
+
+#[test]
+fn test_matrix4_add_sub() {
+	let a = Matrix4::<f64>::from_array([
+		1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0,
+	]);
+	let b = Matrix4::<f64>::identity();
+
+	let sum = a + b;
+	for i in 0..4 {
+		for j in 0..4 {
+			let expected = a[i][j] + if i == j { 1.0 } else { 0.0 };
+			assert_eq!(sum[i][j], expected);
+		}
+	}
+	assert!(a - a == Matrix4::default());
+	assert!(a + a == a * 2.0);
+
+	let mut c = a;
+	c += b;
+	assert!(c == sum);
+	c -= b;
+	assert!(c == a);
+
+	use num::Zero;
+	assert!(<Matrix4<f64> as Zero>::zero() == Matrix4::zero());
+	assert!((a - a).is_zero() && !a.is_zero());
+}
+
+#[test]
+fn test_matrix4_rotation_about_point() {
+	let q = Quaternion::from_axis_angle(Vector3::new(1.0, -2.0, 0.5).normalized(), 63.0);
+	let pivot = Point3::new(3.0, -1.0, 2.0);
+	let m = Matrix4::rotation_about_point(q, pivot);
+
+	let fixed = Vector4::new(3.0, -1.0, 2.0, 1.0) * m;
+	assert!((fixed - Vector4::new(3.0, -1.0, 2.0, 1.0)).magnitude() < 1e-12);
+
+	let p = Vector3::new(0.5, 4.0, -2.0);
+	let expected = q.rotate_vector(p - pivot.to_vector()) + pivot.to_vector();
+	let actual = Vector4::new(p[0], p[1], p[2], 1.0) * m;
+	assert!((actual - Vector4::new(expected[0], expected[1], expected[2], 1.0)).magnitude() < 1e-12);
+
+	let direction = Vector4::<f64>::new(1.0, 0.0, 0.0, 0.0) * m;
+	assert!(direction[3].abs() < 1e-12);
+}
+
+#[test]
+fn test_matrix4_inverse_and_det() {
+	let m = Matrix4::from_vectors(
+		Vector4::new(2.0, 1.0, 0.0, 0.5),
+		Vector4::new(1.0, 3.0, 1.0, 0.0),
+		Vector4::new(0.0, -1.0, 4.0, 1.0),
+		Vector4::new(3.0, 0.0, 1.0, 5.0),
+	);
+	let (inverse, det) = m.inverse_and_det();
+
+	assert_matrix4_near(m * inverse, Matrix4::identity(), 1e-12);
+	assert_matrix4_near(inverse * m, Matrix4::identity(), 1e-12);
+	assert_matrix4_near(inverse, m.pinv(), 1e-9);
+
+	// The determinant scales by the factor applied to one row.
+	let mut scaled = m;
+	scaled[2] *= 3.0;
+	assert!((scaled.inverse_and_det().1 - 3.0 * det).abs() < 1e-9);
+
+	let mut singular = m;
+	singular[3] = singular[0] + singular[1];
+	assert!(singular.inverse_and_det().1.abs() < 1e-12);
+}
+
+#[test]
+fn test_matrix4_determinant_inverse_and_transpose() {
+	let m = Matrix4::from_vectors(
+		Vector4::<f64>::new(1.0, 2.0, 0.0, -1.0),
+		Vector4::new(0.0, 1.0, 3.0, 2.0),
+		Vector4::new(4.0, 0.0, 1.0, 1.0),
+		Vector4::new(2.0, -2.0, 0.0, 3.0),
+	);
+	let det = m.determinant();
+	assert!((det - m.inverse_and_det().1).abs() < 1e-12);
+	assert!((m.transpose().determinant() - det).abs() < 1e-12);
+
+	let inverse = m.try_inverse().unwrap();
+	assert_matrix4_near(inverse, m.inverse(), 1e-15);
+	assert_matrix4_near(m * inverse, Matrix4::identity(), 1e-12);
+	assert_matrix4_near(m.transpose().inverse(), inverse.transpose(), 1e-12);
+
+	let mut singular = m;
+	singular[2] = singular[0] * 2.0;
+	assert_eq!(singular.determinant(), 0.0);
+	assert!(singular.try_inverse().is_none());
+}
+
+#[test]
+fn test_matrix4_approximate_equality() {
+	let m = Matrix4::<f64>::identity() * 0.1;
+	let mut n = m * 3.0 / 3.0;
+	n[3][3] += 1e-13;
+	assert!(m.approx_eq(n, 1e-12));
+	n[3][2] = 1e-9;
+	assert!(!m.approx_eq(n, 1e-12));
+	assert!(m.relative_eq(n, 1e-8, 0.0));
+}
+
+#[test]
+fn test_matrix4_iterators() {
+	let mut m = Matrix4::<f64>::from_array(std::array::from_fn(|i| i as f64));
+	assert!(m.iter().copied().eq((0..16).map(|i| i as f64)));
+	assert!(m.rows().eq(m.to_array_vectors()));
+	assert!(m.cols().eq(m.transpose().to_array_vectors()));
+	assert!(m.cols().nth(2).unwrap() == Vector4::new(2.0, 6.0, 10.0, 14.0));
+
+	m.iter_mut().for_each(|a| *a = -*a);
+	assert_eq!(m.iter().sum::<f64>(), -120.0);
+}
+
+#[test]
+fn test_matrix4_cast() {
+	let m = Matrix4::<f64>::from_array(std::array::from_fn(|i| i as f64 / 4.0));
+	let single: Matrix4<f32> = m.cast();
+	assert!(single.iter().copied().eq((0..16).map(|i| i as f32 / 4.0)));
+	assert!(single.cast::<f64>() == m);
+
+	let big = Matrix4::<f64>::identity() * 1e300;
+	assert!(big.cast::<f32>()[1][1].is_infinite());
+}
+
+#[test]
+fn test_matrix4_scalar_and_matrix_division() {
+	let q = Quaternion::from_axis_angle(Vector3::new(1.0, 1.0, 0.0).normalized(), 40.0);
+	let a = Matrix4::<f64>::from_array(std::array::from_fn(|i| (i * i % 7) as f64 - 2.0));
+	let b = q.rotation_matrix4() * math3d::camera::translation(1.0, -2.0, 0.5) * 2.0;
+
+	assert_matrix4_near((a / b) * b, a, 1e-12);
+	assert!(a.checked_div(b).unwrap() == a / b);
+	let mut c = a;
+	c /= b;
+	assert!(c == a / b);
+
+	assert!(a * 2.0 / 2.0 == a && 2.0 * a == a * 2.0);
+	assert!((a / 4.0)[1][2] == a[1][2] / 4.0);
+
+	let singular = Matrix4::from_array([1.0, 2.0, 3.0, 4.0, 2.0, 4.0, 6.0, 8.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0]);
+	assert!(a.checked_div(singular).is_none());
+	assert!(!(a / singular).is_finite());
+}
+
+#[test]
+fn test_matrix4_inverse_affine_and_rigid() {
+	let rotation = Quaternion::<f64>::from_axis_angle(Vector3::new(1.0, -2.0, 0.5).normalized(), 33.0);
+	let rigid = Matrix4::rotation_about_point(rotation, Point3::new(4.0, -1.0, 2.0));
+	assert_matrix4_near(rigid.inverse_rigid(), rigid.inverse(), 1e-12);
+	assert_matrix4_near(rigid.inverse_affine(), rigid.inverse(), 1e-12);
+
+	// Non-uniform scale and shear need the affine version.
+	let mut affine = rigid;
+	affine[0] *= 2.0;
+	affine[1][0] += 0.7;
+	assert_matrix4_near(affine.inverse_affine(), affine.inverse(), 1e-12);
+	assert_matrix4_near(affine * affine.inverse_affine(), Matrix4::identity(), 1e-12);
+}
+
+#[test]
+fn test_matrix4_column_and_row_vector_products() {
+	let m = Quaternion::<f64>::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 30.0).rotation_matrix4() * math3d::camera::translation(1.0, 2.0, 3.0);
+	let p = Vector4::new(1.0, -1.0, 0.5, 1.0);
+
+	assert!((m * p - p * m.transpose()).magnitude() < 1e-12);
+	assert!((m.transpose() * p - p * m).magnitude() < 1e-12);
+}
+
+#[test]
+fn test_matrix4_transform_positions_strided() {
+	let m = Quaternion::<f64>::from_axis_angle(Vector3::new(1.0, 1.0, 0.0).normalized(), 40.0).rotation_matrix4() * math3d::camera::translation(1.0, -2.0, 0.5);
+	let positions = [[0.0f32, 0.0, 0.0], [1.0, 2.0, 3.0], [-4.0, 0.5, 8.0]];
+
+	// Normal, position, then an unrelated attribute in a 32 byte vertex.
+	let mut bytes = Vec::new();
+	let mut floats = Vec::new();
+	for p in positions {
+		for c in [9.0f32, 9.0, 9.0].iter().chain(&p).chain(&[7.0, 7.0]) {
+			bytes.extend(c.to_ne_bytes());
+			floats.push(*c);
+		}
+	}
+	m.transform_positions_strided(&mut bytes, 32, 12, 3);
+	m.transform_positions_strided_f32(&mut floats, 8, 3, 3);
+
+	for (i, p) in positions.iter().enumerate() {
+		let expected = Vector4::new(p[0] as f64, p[1] as f64, p[2] as f64, 1.0) * m;
+		for j in 0..3 {
+			let start = i * 32 + 12 + 4 * j;
+			let from_bytes = f32::from_ne_bytes(bytes[start..start + 4].try_into().unwrap());
+			assert_eq!(from_bytes, expected[j] as f32);
+			assert_eq!(floats[i * 8 + 3 + j], expected[j] as f32);
+		}
+		assert_eq!(floats[i * 8..i * 8 + 3], [9.0; 3]);
+		assert_eq!(floats[i * 8 + 6..i * 8 + 8], [7.0; 2]);
+	}
+	let untouched: Vec<u8> = bytes.chunks(32).flat_map(|v| v[..12].iter().chain(&v[24..]).copied()).collect();
+	assert!(untouched.chunks(4).all(|c| [9.0, 7.0].contains(&f32::from_ne_bytes(c.try_into().unwrap()))));
+}
+
+#[test]
+#[should_panic(expected = "do not fit")]
+fn test_matrix4_transform_positions_strided_out_of_bounds() {
+	let mut data = [0.0f32; 10];
+	Matrix4::<f64>::identity().transform_positions_strided_f32(&mut data, 4, 2, 3);
+}
+
+#[test]
+fn test_matrix4_transform_point3_and_vector3() {
+	let m = Quaternion::<f64>::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 90.0).rotation_matrix4() * math3d::camera::translation(1.0, 2.0, 3.0);
+
+	let p = m.transform_point3(Point3::new(1.0, 0.0, 0.0));
+	assert!(p.distance_to(Point3::new(1.0, 3.0, 3.0)) < 1e-12);
+	let v = m.transform_vector3(Vector3::new(1.0, 0.0, 0.0));
+	assert!((v - Vector3::new(0.0, 1.0, 0.0)).magnitude() < 1e-12);
+
+	// A projective matrix divides by w: points at depth z scale by 1 / z.
+	let mut projective = Matrix4::<f64>::identity();
+	projective[2][3] = 1.0;
+	projective[3][3] = 0.0;
+	let p = projective.transform_point3(Point3::new(2.0, 4.0, 2.0));
+	assert!(p.distance_to(Point3::new(1.0, 2.0, 1.0)) < 1e-12);
+}
+
+#[test]
+fn test_matrix4_trs_constructors() {
+	let t = Vector3::<f64>::new(1.0, -2.0, 3.0);
+	let s = Vector3::new(2.0, 0.5, 3.0);
+	let axis = Vector3::new(1.0, 2.0, 2.0).normalized();
+	let r = Quaternion::from_axis_angle(axis, 35.0);
+
+	assert!(Matrix4::from_translation(t) == math3d::camera::translation(1.0, -2.0, 3.0));
+	assert!(Matrix4::from_scale(s) == math3d::camera::scale(2.0, 0.5, 3.0));
+	assert_matrix4_near(Matrix4::from_axis_angle(axis, 35.0), r.rotation_matrix4(), 1e-15);
+
+	let trs = Matrix4::from_trs(t, r, s);
+	assert_matrix4_near(trs, Matrix4::from_scale(s) * Matrix4::from_rotation(r) * Matrix4::from_translation(t), 1e-12);
+
+	let p = Point3::new(0.5, 1.0, -1.0);
+	let expected = r.rotate_vector(p.to_vector() * s) + t;
+	assert!(trs.transform_point3(p).distance_to(Point3::from_vector(expected)) < 1e-12);
+}
+
+#[test]
+fn test_matrix4_with_oblique_near_plane() {
+	let camera = math3d::camera::Camera::new(Point3::new(0.0, 0.0, 0.0), Quaternion::identity(), 1.2, 1.5, 0.1, 50.0);
+	let projection = camera.projection();
+
+	// Tilted plane in front of the camera, facing away from it.
+	let normal = Vector3::<f64>::new(0.3, -0.4, -1.0).normalized();
+	let on_plane = Vector3::new(0.2, 0.1, -4.0);
+	let plane = Vector4::new_from_vector3(normal, -normal.dot(on_plane));
+	let m = projection.with_oblique_near_plane(plane);
+
+	for offset in [Vector3::new(0.0, 0.0, 0.0), normal.cross(Vector3::new(1.0, 0.0, 0.0)) * 0.5] {
+		let p = Vector4::new_from_vector3(on_plane + offset, 1.0) * m;
+		assert!((p[2] + p[3]).abs() < 1e-9);
+	}
+	let behind = Vector4::new_from_vector3(on_plane - normal * 0.1, 1.0) * m;
+	let ahead = Vector4::new_from_vector3(on_plane + normal * 0.1, 1.0) * m;
+	assert!(behind[2] < -behind[3]);
+	assert!(ahead[2] > -ahead[3] && ahead[2] < ahead[3]);
+
+	// Only the depth column changes.
+	for i in 0..4 {
+		for j in [0, 1, 3] {
+			assert_eq!(m[i][j], projection[i][j]);
+		}
+	}
+}
+
+#[test]
+fn test_matrix4_trace_norm_adjugate_and_cofactors() {
+	let m = Matrix4::<f64>::from_array([
+		2.0, -1.0, 0.5, 0.0,
+		3.0, 4.0, -2.0, 1.0,
+		1.0, 0.0, 5.0, -3.0,
+		0.5, 2.0, -1.0, 3.0,
+	]);
+
+	assert_eq!(m.trace(), 14.0);
+	let squares: f64 = m.iter().map(|x| x * x).sum();
+	assert_eq!(m.frobenius_norm(), squares.sqrt());
+	assert!(m.adjugate().approx_eq(m.inverse() * m.determinant(), 1e-9));
+	assert!(m.cofactor().approx_eq(m.adjugate().transpose(), 0.0));
+	for i in 0..4 {
+		for j in 0..4 {
+			assert!((m.cofactor_at(Row(i), Col(j)) - m.cofactor()[i][j]).abs() < 1e-12);
+		}
+	}
+	// Laplace expansion along any row gives the determinant.
+	for i in 0..4 {
+		let det: f64 = (0..4).map(|j| m[i][j] * m.cofactor_at(Row(i), Col(j))).sum();
+		assert!((det - m.determinant()).abs() < 1e-9);
+	}
+
+	// Defined for singular matrices too.
+	let singular = Matrix4::from_trs(Vector3::new(1.0, 2.0, 3.0), Quaternion::identity(), Vector3::new(1.0, 0.0, 2.0));
+	assert!((singular * singular.adjugate()).approx_eq(Matrix4::zero(), 1e-12));
+}
+
+#[test]
+fn test_matrix4_row_column_accessors() {
+	let mut m = Matrix4::<f64>::from_array(std::array::from_fn(|i| i as f64));
+
+	for i in 0..4 {
+		assert!(m.row(i) == m.rows().nth(i).unwrap());
+		assert!(m.column(i) == m.cols().nth(i).unwrap());
+		for j in 0..4 {
+			assert_eq!(m[(i, j)], m[i][j]);
+			assert_eq!(m.column(j)[i], m.row(i)[j]);
+		}
+	}
+
+	m.set_row(1, Vector4::new(-1.0, -2.0, -3.0, -4.0));
+	m.set_column(3, Vector4::new(10.0, 20.0, 30.0, 40.0));
+	m[(0, 0)] = 0.5;
+	assert!(m.row(1) == Vector4::new(-1.0, -2.0, -3.0, 20.0));
+	assert!(m.column(3) == Vector4::new(10.0, 20.0, 30.0, 40.0));
+	assert_eq!(m[0][0], 0.5);
+
+	// Column vectors as in OpenGL: the translation is in the last column.
+	let mut gl = Matrix4::identity();
+	gl.set_column(3, Vector4::new(1.0, 2.0, 3.0, 1.0));
+	assert!(gl.transpose().approx_eq(Matrix4::from_translation(Vector3::new(1.0, 2.0, 3.0)), 0.0));
+}
+
+#[test]
+fn test_matrix4_column_major_export() {
+	let m = Matrix4::<f64>::from_trs(
+		Vector3::new(1.0, -2.0, 3.0),
+		Quaternion::from_axis_angle(Vector3::new(0.0, 0.6, 0.8), 40.0),
+		Vector3::new(2.0, 1.0, 0.5),
+	);
+	let cols = m.to_cols_array();
+
+	for j in 0..4 {
+		for i in 0..4 {
+			assert_eq!(cols[j * 4 + i], m[i][j]);
+			assert_eq!(m.to_cols_array_2d()[j][i], m[i][j]);
+		}
+	}
+	assert!(Matrix4::from_cols_array(cols).approx_eq(m, 0.0));
+	// Column-major storage of the transpose is the row-major storage.
+	assert_eq!(m.transpose().to_cols_array(), std::array::from_fn(|i| *m.index(i)));
+}
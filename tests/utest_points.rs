@@ -0,0 +1,64 @@
+use math3d::points::{center_of_mass, scale_about, scale_about_par, second_moment, translate_in_place, translate_in_place_par, weighted_centroid, Point3};
+use math3d::vectors::Vector3;
+
+#[cfg(test)]
+
+fn cloud(count: usize) -> Vec<Point3<f64>> {
+	(0..count).map(|i| Point3::new(1e6 + i as f64, (i % 11) as f64, -(i as f64) * 0.5)).collect()
+}
+
+#[test]
+fn test_translate_in_place_par_matches_serial() {
+	let mut p1 = cloud(50_000);
+	let mut p2 = p1.clone();
+	let offset = Vector3::new(-1e6, 2.5, 0.25);
+
+	translate_in_place(&mut p1, offset);
+	translate_in_place_par(&mut p2, offset);
+	assert!(p1 == p2);
+	assert!(p1[3] == Point3::new(3.0, 5.5, -1.25));
+
+	translate_in_place(&mut p1, -offset);
+	assert!(p1 == cloud(50_000));
+}
+
+#[test]
+fn test_scale_about_par_matches_serial() {
+	let mut p1 = cloud(50_000);
+	let mut p2 = p1.clone();
+	let pivot = Point3::new(1e6, 0.0, 0.0);
+
+	scale_about(&mut p1, pivot, 2.0);
+	scale_about_par(&mut p2, pivot, 2.0);
+	assert!(p1 == p2);
+	assert!(p1[0] == pivot);
+	assert!(p1[4] == Point3::new(1e6 + 8.0, 8.0, -4.0));
+
+	scale_about(&mut p1, pivot, 0.5);
+	assert!(p1 == cloud(50_000));
+}
+
+#[test]
+fn test_weighted_centroid_and_second_moment() {
+	let cloud = [Point3::new(1.0, 0.0, 0.0), Point3::new(0.0, 2.0, 0.0), Point3::new(0.0, 0.0, 3.0), Point3::new(1.0, 1.0, 1.0)];
+	let masses = [1.0_f64, 2.0, 3.0, 4.0];
+
+	let (center, total) = center_of_mass(&cloud, &masses).unwrap();
+	assert_eq!(total, 10.0);
+	assert!(center.distance_to(Point3::new(0.5, 0.8, 1.3)) < 1e-12);
+	assert!(weighted_centroid(&cloud, &[0.0; 4]).is_none());
+	assert!(weighted_centroid::<f64>(&[], &[]).is_none());
+
+	// Parallel axis theorem: the moment about the origin is the moment about
+	// the center of mass plus that of the total mass at the center.
+	let origin = Point3::new(0.0, 0.0, 0.0);
+	let about_origin = second_moment(&cloud, &masses, origin);
+	let about_center = second_moment(&cloud, &masses, center);
+	let c = center.to_vector();
+	for i in 0..3 {
+		for j in 0..3 {
+			assert!((about_origin[i][j] - about_center[i][j] - total * c[i] * c[j]).abs() < 1e-12);
+			assert_eq!(about_origin[i][j], about_origin[j][i]);
+		}
+	}
+}
@@ -0,0 +1,47 @@
+use math3d::pose::{DeadReckoner, Pose};
+use math3d::points::Point3;
+use math3d::quaternion::Quaternion;
+use math3d::vectors::Vector3;
+
+#[cfg(test)]
+
+fn assert_pose_near(a: Pose<f64>, b: Pose<f64>) {
+	assert!(a.position().distance_to(b.position()) < 1e-9, "{} != {}", a, b);
+	assert!((a.orientation().dot(b.orientation()).abs() - 1.0).abs() < 1e-9, "{} != {}", a, b);
+}
+
+#[test]
+fn test_pose_extrapolate() {
+	let pose = Pose::new(Point3::new(1.0, 0.0, 0.0), Quaternion::identity());
+	let angular = Vector3::new(0.0, 0.0, std::f64::consts::FRAC_PI_2);
+	let predicted = pose.extrapolate(Vector3::new(0.0, 2.0, 0.0), angular, 0.5);
+
+	let expected = Pose::new(
+		Point3::new(1.0, 1.0, 0.0),
+		Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 45.0),
+	);
+	assert_pose_near(predicted, expected);
+}
+
+#[test]
+fn test_dead_reckoner_is_continuous_and_converges() {
+	let start = Pose::new(Point3::new(0.0, 0.0, 0.0), Quaternion::identity());
+	let mut reckoner = DeadReckoner::new(start, Vector3::new(1.0, 0.0, 0.0), Vector3::zero(), 0.5);
+	reckoner.advance(1.0);
+
+	let before = reckoner.pose();
+	let received = Pose::new(Point3::new(1.0, 1.0, 0.0), Quaternion::identity());
+	let velocity = Vector3::new(0.0, 1.0, 0.0);
+	let angular = Vector3::new(0.0, 0.0, 1.0);
+	reckoner.receive(received, velocity, angular);
+
+	assert_pose_near(reckoner.pose(), before);
+
+	reckoner.advance(0.25);
+	let halfway = reckoner.pose();
+	assert!(halfway.position().distance_to(before.position()) > 0.0);
+
+	reckoner.advance(0.75);
+	assert_pose_near(reckoner.pose(), received.extrapolate(velocity, angular, 1.0));
+	assert!(reckoner.linear_velocity() == velocity);
+}
@@ -1,5 +1,5 @@
-use math3d::quaternion::Quaternion;
-use math3d::vectors::Vector3;
+use math3d::quaternion::{self, Quaternion};
+use math3d::vectors::{Vector3, Vector4};
 
 // Create unit tests for Quarternion
 #[cfg(test)]
@@ -32,6 +32,59 @@ fn test_quaternion_from_euler() {
 	assert!(q1 == expected);
 }
 
+fn euler_octants() -> Vec<(f64, f64, f64)> {
+	let mut angles = Vec::new();
+	for &x in &[-150.0, -20.0, 35.0, 170.0] {
+		for &y in &[-80.0, -10.0, 25.0, 85.0] {
+			for &z in &[-120.0, -45.0, 60.0, 135.0] {
+				angles.push((x, y, z));
+			}
+		}
+	}
+	angles
+}
+
+#[test]
+fn test_quaternion_from_euler_matches_axis_composition() {
+	let (x_axis, y_axis, z_axis) = (Vector3::new(1.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0), Vector3::new(0.0, 0.0, 1.0));
+	for (x, y, z) in euler_octants() {
+		let composed = Quaternion::from_axis_angle(x_axis, x) * Quaternion::from_axis_angle(y_axis, y) * Quaternion::from_axis_angle(z_axis, z);
+		let q = Quaternion::from_euler_angles(x, y, z);
+		assert_same_rotation(q, composed);
+		assert_same_rotation(q, Quaternion::from_euler_angles_rad(x.to_radians(), y.to_radians(), z.to_radians()));
+
+		// Intrinsic rotations: the z rotation acts first on the vector.
+		let v = Vector3::new(0.3, -1.2, 2.0);
+		let expected = composed.rotate_vector(v);
+		let m = q.rotation_matrix_col_major();
+		let by_matrix = Vector3::new(
+			m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2],
+			m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2],
+			m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2],
+		);
+		assert!((by_matrix - expected).magnitude() < 1e-9);
+	}
+}
+
+#[test]
+fn test_quaternion_euler_round_trip() {
+	for (x, y, z) in euler_octants() {
+		let q = Quaternion::from_euler_angles(x, y, z);
+		let (rx, ry, rz) = q.to_euler_angles();
+		assert!((rx - x).abs() < 1e-9 && (ry - y).abs() < 1e-9 && (rz - z).abs() < 1e-9, "{} {} {}", rx, ry, rz);
+		let (rx, ry, rz) = (-q).to_euler_angles();
+		assert!((rx - x).abs() < 1e-9 && (ry - y).abs() < 1e-9 && (rz - z).abs() < 1e-9);
+	}
+
+	// Gimbal lock keeps the rotation even though the angles are not unique.
+	for y in [90.0_f64, -90.0] {
+		let q = Quaternion::from_euler_angles(40.0, y, 25.0);
+		let (rx, ry, rz) = q.to_euler_angles();
+		assert!((ry - y).abs() < 1e-6 && rz == 0.0);
+		assert_same_rotation(Quaternion::from_euler_angles(rx, ry, rz), q);
+	}
+}
+
 #[test]
 fn test_quaternion_product() {
 	let q1 = Quaternion::new(1.0, [2.0, 3.0, 4.0]);
@@ -89,3 +142,377 @@ fn test_quaternion_exp() {
 fn test_quaternion_rotate_vector() {
 	todo!();
 }
+
+fn assert_same_rotation(a: Quaternion<f64>, b: Quaternion<f64>) {
+	assert!((a.dot(b).abs() - 1.0).abs() < 1e-9, "{} != {}", a, b);
+}
+
+#[test]
+fn test_quaternion_slerp_unclamped_extrapolates() {
+	let axis = Vector3::new(0.0, 0.0, 1.0);
+	let q1 = Quaternion::from_axis_angle(axis, 10.0);
+	let q2 = Quaternion::from_axis_angle(axis, 40.0);
+
+	assert_same_rotation(q1.slerp_unclamped(q2, 2.0), Quaternion::from_axis_angle(axis, 70.0));
+	assert_same_rotation(q1.slerp_unclamped(q2, -1.0), Quaternion::from_axis_angle(axis, -20.0));
+	assert_same_rotation(q1.slerp_unclamped(q2, 0.5), q1.slerp(q2, 0.5));
+	assert_same_rotation(q1.slerp(q2, 2.0), q2);
+}
+
+#[test]
+fn test_quaternion_lerp_unclamped_shortest_arc() {
+	let axis = Vector3::new(1.0, 0.0, 0.0);
+	let q1 = Quaternion::from_axis_angle(axis, 0.0);
+	let q2 = -Quaternion::from_axis_angle(axis, 20.0);
+
+	assert_same_rotation(q1.lerp_unclamped(q2, 0.5), Quaternion::from_axis_angle(axis, 10.0));
+	assert_same_rotation(q1.lerp(q2, -3.0), q1);
+	assert!((q1.lerp_unclamped(q2, 3.0).norm() - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_quaternion_rotation_matrix_conventions() {
+	let rotations = [
+		Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), 90.0),
+		Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), -35.0),
+		Quaternion::from_axis_angle(Vector3::new(1.0, -2.0, 0.5).normalized(), 123.0),
+	];
+	let vectors = [Vector3::new(1.0, 0.0, 0.0), Vector3::new(0.3, -1.2, 2.5)];
+
+	for q in &rotations {
+		let row_major = q.rotation_matrix_row_major();
+		let col_major = q.rotation_matrix_col_major();
+		assert!(q.rotation_matrix() == row_major);
+		assert!(col_major.transpose() == row_major);
+
+		for v in &vectors {
+			let expected = q.rotate_vector(*v);
+			let row = *v * row_major;
+			let col = Vector3::new(
+				col_major[0].dot(*v),
+				col_major[1].dot(*v),
+				col_major[2].dot(*v),
+			);
+			assert!((row - expected).magnitude() < 1e-12);
+			assert!((col - expected).magnitude() < 1e-12);
+		}
+	}
+}
+
+#[test]
+fn test_quaternion_rotation_vector_round_trip() {
+	let axis = Vector3::new(1.0, -2.0, 0.5).normalized();
+	for degrees in [0.0, 1e-6, 30.0, 179.0, 270.0] {
+		let q = Quaternion::from_axis_angle(axis, degrees);
+		let r = q.to_rotation_vector();
+		assert!(r.magnitude() <= std::f64::consts::PI + 1e-12);
+		assert_same_rotation(Quaternion::from_rotation_vector(r), q);
+		assert_same_rotation(Quaternion::from_rotation_vector((-q).to_rotation_vector()), q);
+	}
+}
+
+#[test]
+fn test_quaternion_from_rotation_matrix_round_trip() {
+	let axes = [
+		Vector3::new(1.0, 0.0, 0.0),
+		Vector3::new(0.0, 1.0, 0.0),
+		Vector3::new(0.0, 0.0, 1.0),
+		Vector3::new(1.0, -2.0, 0.5).normalized(),
+	];
+	for axis in &axes {
+		for degrees in [0.0, 45.0, 179.9, 180.0, 250.0] {
+			let q = Quaternion::from_axis_angle(*axis, degrees);
+			assert_same_rotation(Quaternion::from_rotation_matrix(q.rotation_matrix()), q);
+		}
+	}
+}
+
+#[test]
+fn test_quaternion_solve_alignment() {
+	let q = Quaternion::from_axis_angle(Vector3::new(0.3, -1.0, 2.0).normalized(), 217.0);
+	let a1 = Vector3::new(1.0, 2.0, 3.0);
+	let a2 = Vector3::new(-2.0, 0.5, 1.0);
+
+	let exact = Quaternion::solve_alignment((a1, a2), (q.rotate_vector(a1) * 2.0, q.rotate_vector(a2))).unwrap();
+	assert_same_rotation(exact, q);
+
+	// With a noisy second observation the first is still matched exactly.
+	let noisy = q.rotate_vector(a2) + Vector3::new(0.05, -0.02, 0.01);
+	let solved = Quaternion::solve_alignment((a1, a2), (q.rotate_vector(a1), noisy)).unwrap();
+	assert!((solved.rotate_vector(a1) - q.rotate_vector(a1)).magnitude() < 1e-12);
+	assert!((solved.rotate_vector(a2).normalized() - noisy.normalized()).magnitude() < 0.05);
+
+	assert!(Quaternion::solve_alignment((a1, a1 * 3.0), (a1, a2)).is_none());
+	assert!(Quaternion::solve_alignment((a1, a2), (Vector3::zero(), a2)).is_none());
+}
+
+#[test]
+fn test_quaternion_to_axis_angle() {
+	let axis = Vector3::new(2.0, -1.0, 0.5).normalized();
+	for degrees in [1e-4, 30.0, 179.0, 200.0] {
+		let q = Quaternion::from_axis_angle(axis, degrees);
+		let (a, angle) = q.to_axis_angle();
+		assert_same_rotation(Quaternion::from_axis_angle(a, angle), q);
+		assert!((0.0..=180.0).contains(&angle));
+	}
+
+	// Rounding can push w just above one; the angle must not become NaN.
+	let drifted = Quaternion::new(1.0 + 1e-15, [0.0, 0.0, 0.0]);
+	assert_eq!(drifted.to_axis_angle().1, 0.0);
+}
+
+#[test]
+fn test_quaternion_compound_assignment() {
+	let a = Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 30.0);
+	let b = Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), 45.0);
+	let mut q = a;
+	q *= b;
+	assert!(q == a * b);
+	q += a;
+	q -= a;
+	assert_same_rotation(q, a * b);
+	q *= 2.0;
+	q /= 2.0;
+	assert_same_rotation(q, a * b);
+}
+
+#[test]
+fn test_quaternion_scalar_on_the_left() {
+	let q = Quaternion::new(1.0, [2.0, 3.0, 4.0]);
+	assert!(2.0 * q == Quaternion::new(2.0, [4.0, 6.0, 8.0]));
+}
+
+#[test]
+fn test_quaternion_validity_queries() {
+	let q = Quaternion::<f64>::from_axis_angle(Vector3::new(1.0, 2.0, 2.0).normalized(), 75.0);
+	assert!(q.is_finite() && !q.is_nan() && q.is_normalized(1e-12));
+	assert!(!(q * 2.0).is_normalized(1e-6));
+
+	let nan = Quaternion::new(f64::NAN, [0.0, 0.0, 0.0]);
+	assert!(!nan.is_finite() && nan.is_nan() && !nan.is_normalized(1.0));
+	assert!(!Quaternion::new(0.0, [0.0, f64::INFINITY, 0.0]).is_finite());
+}
+
+#[test]
+fn test_quaternion_approximate_equality() {
+	let q = Quaternion::<f64>::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 30.0);
+	let r = q * q * q;
+	assert!(r.approx_eq(Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 90.0), 1e-12));
+	assert!(!r.approx_eq(-r, 1e-12));
+	assert!(r.relative_eq(r * (1.0 + 1e-15), 0.0, 1e-12));
+}
+
+#[test]
+fn test_quaternion_rotate_quaternion() {
+	let frame = Quaternion::<f64>::from_axis_angle(Vector3::new(1.0, -2.0, 0.5).normalized(), 65.0);
+	let local = Quaternion::from_axis_angle(Vector3::new(0.3, 0.4, -1.0).normalized(), 40.0);
+	let world = frame.rotate_quaternion(local);
+
+	let (axis, angle) = local.to_axis_angle();
+	let (world_axis, world_angle) = world.to_axis_angle();
+	assert!(world_axis.approx_eq(frame.rotate_vector(axis), 1e-12));
+	assert!((world_angle - angle).abs() < 1e-12);
+
+	let v = Vector3::new(2.0, 1.0, -3.0);
+	let expected = frame.rotate_vector(local.rotate_vector(v));
+	assert!(world.rotate_vector(frame.rotate_vector(v)).approx_eq(expected, 1e-12));
+	assert!(frame.rotate_quaternion(local).approx_eq((frame * 3.0).rotate_quaternion(local), 1e-12));
+}
+
+#[test]
+fn test_quaternion_conversions() {
+	let q = Quaternion::<f64>::new(1.0, [2.0, 3.0, 4.0]);
+	assert!(Quaternion::from([1.0, 2.0, 3.0, 4.0]) == q);
+	assert!(Quaternion::from((1.0, 2.0, 3.0, 4.0)) == q);
+	assert_eq!(<[f64; 4]>::from(q), [1.0, 2.0, 3.0, 4.0]);
+	assert_eq!(<(f64, f64, f64, f64)>::from(q), q.decompose());
+	assert!(Quaternion::try_from(&[1.0, 2.0, 3.0, 4.0][..]).unwrap() == q);
+	assert!(Quaternion::<f64>::try_from(&[1.0; 5][..]).is_err());
+}
+
+#[test]
+fn test_quaternion_then_applies_self_first() {
+	let a = Quaternion::<f64>::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 90.0);
+	let b = Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), 90.0);
+	let v = Vector3::new(0.0, 0.0, 1.0);
+
+	// a turns +z to +x, which b leaves alone; b first would turn +z to -y.
+	assert!(a.then(b).rotate_vector(v).approx_eq(Vector3::new(1.0, 0.0, 0.0), 1e-12));
+	assert!(b.then(a).rotate_vector(v).approx_eq(Vector3::new(0.0, -1.0, 0.0), 1e-12));
+	assert!(a.then(b) == b * a);
+	assert!(a.then(b).then(a).approx_eq(a * b * a, 1e-15));
+}
+
+#[test]
+fn test_quaternion_default_zero_and_one() {
+	use num::{One, Zero};
+
+	let q = Quaternion::<f64>::new(1.0, [2.0, 3.0, 4.0]);
+	assert!(Quaternion::<f64>::default() == Quaternion::identity());
+	assert!(<Quaternion<f64> as Zero>::zero() + q == q);
+	assert!(<Quaternion<f64> as One>::one() * q == q && q * Quaternion::one() == q);
+	assert!(Quaternion::<f64>::one() == Quaternion::identity());
+}
+
+#[test]
+fn test_quaternion_cast() {
+	let q = Quaternion::<f64>::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 90.0);
+	let single: Quaternion<f32> = q.cast();
+	let v = single.rotate_vector(Vector3::new(1.0f32, 0.0, 0.0));
+	assert!((v - Vector3::new(0.0, 1.0, 0.0)).magnitude() < 1e-6);
+	assert!(single.cast::<f64>().approx_eq(q, 1e-7));
+}
+
+#[test]
+fn test_quaternion_tuple_array_view_and_bits() {
+	use std::collections::BTreeSet;
+	use math3d::quaternion::QuaternionBits;
+
+	let q = Quaternion::from((1.0, Vector3::new(2.0, 3.0, 4.0)));
+	assert!(q == Quaternion::new(1.0, [2.0, 3.0, 4.0]));
+	assert_eq!(q.as_ref(), &[1.0, 2.0, 3.0, 4.0]);
+
+	let zero = Quaternion::<f64>::new(0.0, [0.0; 3]);
+	let negative_zero = Quaternion::<f64>::new(-0.0, [0.0; 3]);
+	let nan = Quaternion::<f64>::new(f64::NAN, [0.0; 3]);
+	assert!(zero == negative_zero && QuaternionBits(zero) != QuaternionBits(negative_zero));
+	assert!(nan != nan && QuaternionBits(nan) == QuaternionBits(nan));
+
+	let keys: BTreeSet<_> = [q, zero, negative_zero, nan, q, nan].into_iter().map(QuaternionBits).collect();
+	assert_eq!(keys.len(), 4);
+}
+
+#[test]
+fn test_quaternion_renormalize_fast_matches_versor() {
+	let q = Quaternion::<f64>::from_axis_angle(Vector3::new(1.0, 2.0, -0.5).normalized(), 70.0);
+	for drift in [1e-2, 1e-3, -1e-3, 1e-5] {
+		let drifted = q * (1.0 + drift);
+		let fast = drifted.renormalize_fast();
+		let full = drifted.versor();
+
+		let error = (fast.norm() - 1.0).abs();
+		assert!(error <= 1.5 * drift * drift * 1.01 + 1e-15);
+		assert!((fast - full).norm() <= error + 1e-15);
+	}
+
+	// Renormalizing every step keeps a steadily drifting integrator on the unit sphere.
+	let step = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 1.0) * (1.0 + 1e-4);
+	let mut orientation = Quaternion::<f64>::identity();
+	for _ in 0..10_000 {
+		orientation = (step * orientation).renormalize_fast();
+	}
+	assert!((orientation.norm() - 1.0).abs() < 1e-7);
+}
+
+#[test]
+fn test_quaternion_cumulative_compose() {
+	assert!(quaternion::cumulative_compose::<f64>(&[]).is_empty());
+	assert!(quaternion::cumulative_compose_par::<f64>(&[]).is_empty());
+
+	let rotations: Vec<Quaternion<f64>> = (0..40_000)
+		.map(|i| Quaternion::from_axis_angle(Vector3::new(1.0, (i % 7) as f64, -2.0).normalized(), (i % 13) as f64 * 0.1))
+		.collect();
+	let sequential = quaternion::cumulative_compose(&rotations);
+	let parallel = quaternion::cumulative_compose_par(&rotations);
+
+	assert!(sequential[0] == rotations[0]);
+	assert!(sequential[3] == rotations[0] * rotations[1] * rotations[2] * rotations[3]);
+	assert_eq!(parallel.len(), rotations.len());
+	for (s, p) in sequential.iter().zip(&parallel) {
+		assert!((*s - *p).norm() < 1e-9);
+	}
+}
+
+#[test]
+fn test_quaternion_unwrap_angles() {
+	let axis = Vector3::new(0.0, 2.0, 0.0);
+	let wobble = Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), 10.0);
+
+	// Three and a half turns backwards, with the sign of some samples flipped
+	// and a small rotation about another axis.
+	let expected: Vec<f64> = (0..=126).map(|i| -(i as f64) * 10.0).collect();
+	let samples: Vec<Quaternion<f64>> = expected
+		.iter()
+		.enumerate()
+		.map(|(i, angle)| {
+			let q = Quaternion::from_axis_angle(axis.normalized(), *angle) * wobble;
+			if i % 3 == 0 { q * -1.0 } else { q }
+		})
+		.collect();
+
+	let angles = quaternion::unwrap_angles(&samples, axis);
+	assert_eq!(angles.len(), expected.len());
+	for (angle, expected) in angles.iter().zip(&expected) {
+		assert!((angle - expected).abs() < 1e-9, "{} != {}", angle, expected);
+	}
+	assert!(quaternion::unwrap_angles::<f64>(&[], axis).is_empty());
+}
+
+#[test]
+fn test_quaternion_represents_same_rotation() {
+	let q = Quaternion::<f64>::from_axis_angle(Vector3::new(1.0, 2.0, 2.0) / 3.0, 70.0);
+
+	assert!(q.represents_same_rotation(q, 0.0));
+	assert!(q.represents_same_rotation(-q, 0.0));
+	assert!(q.represents_same_rotation(-q * (1.0 + 1e-10), 1e-9));
+	assert!(!q.represents_same_rotation(q.conjugate(), 1e-9));
+	assert!(!q.represents_same_rotation(Quaternion::from_axis_angle(Vector3::new(1.0, 2.0, 2.0) / 3.0, 70.1), 1e-6));
+
+	// Deduplicating rotations that differ only in sign.
+	let rotations = [q, -q, q.conjugate(), -q.conjugate(), Quaternion::identity()];
+	let mut unique: Vec<Quaternion<f64>> = Vec::new();
+	for r in rotations {
+		if !unique.iter().any(|u| u.represents_same_rotation(r, 1e-12)) {
+			unique.push(r);
+		}
+	}
+	assert_eq!(unique.len(), 3);
+}
+
+#[test]
+fn test_quaternion_from_accel_mag() {
+	let up = Vector3::<f64>::new(0.0, 1.0, 0.0);
+	let north = Vector3::new(0.0, 0.0, -1.0);
+	// Field 65 degrees below the horizon, as in central Europe.
+	let dip = 65.0_f64.to_radians();
+	let field = (north * dip.cos() - up * dip.sin()) * 48.0;
+
+	for truth in [
+		Quaternion::identity(),
+		Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 135.0),
+		Quaternion::from_euler_angles(20.0, -75.0, 10.0),
+		Quaternion::from_axis_angle(Vector3::new(1.0, -2.0, 0.5).normalized(), 160.0),
+	] {
+		let to_sensor = truth.conjugate();
+		let accel = to_sensor.rotate_vector(up) * 9.81;
+		let mag = to_sensor.rotate_vector(field);
+
+		let q = Quaternion::from_accel_mag(accel, mag).unwrap();
+		assert!(q.represents_same_rotation(truth, 1e-12));
+
+		// The dip does not change the heading.
+		let flat = Quaternion::from_accel_mag(accel, to_sensor.rotate_vector(north)).unwrap();
+		assert!(flat.represents_same_rotation(truth, 1e-12));
+	}
+
+	assert!(Quaternion::from_accel_mag(Vector3::zero(), Vector3::new(1.0, 0.0, 0.0)).is_none());
+	assert!(Quaternion::from_accel_mag(up, -up * 30.0).is_none());
+}
+
+#[test]
+fn test_quaternion_rotation_matrix4_embeds_rotation_matrix() {
+	let q = Quaternion::<f64>::from_axis_angle(Vector3::new(2.0, -1.0, 0.5).normalized(), 112.0);
+	let m = q.rotation_matrix4();
+	let r = q.rotation_matrix();
+	for i in 0..3 {
+		for j in 0..3 {
+			assert_eq!(m[i][j], r[i][j]);
+		}
+		assert_eq!(m[i][3], 0.0);
+		assert_eq!(m[3][i], 0.0);
+	}
+	assert_eq!(m[3][3], 1.0);
+	let v = Vector3::new(1.0, 2.0, 3.0);
+	let rotated = Vector4::new(v[0], v[1], v[2], 0.0) * m;
+	assert!((Vector3::new(rotated[0], rotated[1], rotated[2]) - q.rotate_vector(v)).magnitude() < 1e-12);
+}
@@ -0,0 +1,53 @@
+use math3d::quaternion::Quaternion;
+use math3d::transform::Transform;
+use math3d::vectors::Vector3;
+
+#[cfg(test)]
+
+#[test]
+fn test_quaternion_serde_human_readable() {
+	let q = Quaternion::new(1.0, [2.0, 3.0, 4.0]);
+	let json = serde_json::to_string(&q).unwrap();
+
+	assert_eq!(json, r#"{"w":1.0,"x":2.0,"y":3.0,"z":4.0}"#);
+	assert!(serde_json::from_str::<Quaternion<f64>>(&json).unwrap() == q);
+}
+
+#[test]
+fn test_quaternion_serde_binary() {
+	let q = Quaternion::new(1.0f32, [2.0, 3.0, 4.0]);
+	let bytes = serde_cbor::to_vec(&q).unwrap();
+	let value: serde_cbor::Value = serde_cbor::from_slice(&bytes).unwrap();
+
+	assert!(matches!(value, serde_cbor::Value::Array(ref items) if items.len() == 4));
+	assert!(serde_cbor::from_slice::<Quaternion<f32>>(&bytes).unwrap() == q);
+}
+
+#[test]
+fn test_transform_serde_human_readable() {
+	let t = Transform::new(
+		Vector3::new(1.0, 2.0, 3.0),
+		Quaternion::new(0.0, [1.0, 0.0, 0.0]),
+		Vector3::new(2.0, 2.0, 2.0),
+	);
+	let json = serde_json::to_value(t).unwrap();
+
+	assert_eq!(json["translation"]["y"], 2.0);
+	assert_eq!(json["rotation"]["x"], 1.0);
+	assert_eq!(json["scale"]["z"], 2.0);
+	assert!(serde_json::from_value::<Transform<f64>>(json).unwrap() == t);
+}
+
+#[test]
+fn test_transform_serde_binary() {
+	let t = Transform::new(
+		Vector3::new(1.0, 2.0, 3.0),
+		Quaternion::new(0.0, [1.0, 0.0, 0.0]),
+		Vector3::new(2.0, 2.0, 2.0),
+	);
+	let bytes = serde_cbor::to_vec(&t).unwrap();
+	let value: serde_cbor::Value = serde_cbor::from_slice(&bytes).unwrap();
+
+	assert!(matches!(value, serde_cbor::Value::Array(_)));
+	assert!(serde_cbor::from_slice::<Transform<f64>>(&bytes).unwrap() == t);
+}
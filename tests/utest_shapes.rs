@@ -0,0 +1,94 @@
+use math3d::shapes::{self, Mesh};
+use math3d::vectors::Vector3;
+
+#[cfg(test)]
+
+fn triangles(mesh: &Mesh<f64>) -> Vec<[Vector3<f64>; 3]> {
+	mesh.indices
+		.chunks(3)
+		.map(|t| [0, 1, 2].map(|k| mesh.positions[t[k] as usize].to_vector()))
+		.collect()
+}
+
+/// Volume enclosed by the mesh, positive when triangles wind
+/// counter-clockwise seen from outside.
+fn volume(mesh: &Mesh<f64>) -> f64 {
+	triangles(mesh).iter().map(|[a, b, c]| a.dot(b.cross(*c)) / 6.0).sum()
+}
+
+fn assert_well_formed(mesh: &Mesh<f64>) {
+	let count = mesh.positions.len();
+	assert!(mesh.normals.len() == count && mesh.uvs.len() == count);
+	assert_eq!(mesh.indices.len() % 3, 0);
+	assert!(mesh.indices.iter().all(|&i| (i as usize) < count));
+	assert!(mesh.normals.iter().all(|n| (n.magnitude() - 1.0).abs() < 1e-12));
+	assert!(mesh.uvs.iter().all(|uv| (0.0..=1.0).contains(&uv.x()) && (0.0..=1.0).contains(&uv.y())));
+
+	// Every triangle faces the same way as the normals of its vertices.
+	for t in mesh.indices.chunks(3) {
+		let [a, b, c] = [0, 1, 2].map(|k| mesh.positions[t[k] as usize].to_vector());
+		let face = (b - a).cross(c - a);
+		assert!(face.magnitude() > 0.0);
+		assert!(t.iter().all(|&i| face.dot(mesh.normals[i as usize]) > 0.0));
+	}
+}
+
+#[test]
+fn test_shapes_cube() {
+	let cube = shapes::cube(2.0);
+	assert_well_formed(&cube);
+	assert_eq!(cube.triangle_count(), 12);
+	assert!((volume(&cube) - 8.0).abs() < 1e-12);
+	assert!(cube.positions.iter().all(|p| (0..3).all(|k| p[k].abs() == 1.0)));
+}
+
+#[test]
+fn test_shapes_uv_sphere() {
+	let (segments, rings) = (48, 24);
+	let sphere = shapes::uv_sphere(2.0, segments, rings);
+	assert_well_formed(&sphere);
+	assert_eq!(sphere.positions.len(), (segments + 1) * (rings + 1));
+	assert_eq!(sphere.triangle_count(), 2 * segments * (rings - 1));
+	assert!(sphere.positions.iter().all(|p| (p.to_vector().magnitude() - 2.0).abs() < 1e-12));
+
+	let expected = 4.0 / 3.0 * std::f64::consts::PI * 8.0;
+	assert!(volume(&sphere) < expected && volume(&sphere) > 0.98 * expected);
+}
+
+#[test]
+fn test_shapes_icosphere() {
+	let sphere = shapes::icosphere(0.5, 3);
+	assert_well_formed(&sphere);
+	assert_eq!(sphere.triangle_count(), 20 * 64);
+	// Euler characteristic of a closed sphere with shared vertices.
+	assert_eq!(sphere.positions.len(), sphere.triangle_count() / 2 + 2);
+	assert!(sphere.positions.iter().all(|p| (p.to_vector().magnitude() - 0.5).abs() < 1e-12));
+
+	let expected = 4.0 / 3.0 * std::f64::consts::PI * 0.125;
+	assert!(volume(&sphere) < expected && volume(&sphere) > 0.98 * expected);
+}
+
+#[test]
+fn test_shapes_cylinder() {
+	let segments = 64;
+	let cylinder = shapes::cylinder(1.5, 4.0, segments);
+	assert_well_formed(&cylinder);
+	assert_eq!(cylinder.triangle_count(), 4 * segments);
+	assert!(cylinder.positions.iter().all(|p| p[1].abs() == 2.0));
+
+	// A regular polygon prism.
+	let polygon = 0.5 * segments as f64 * 1.5 * 1.5 * (2.0 * std::f64::consts::PI / segments as f64).sin();
+	assert!((volume(&cylinder) - polygon * 4.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_shapes_plane() {
+	let plane = shapes::plane(4.0, 2.0, 8, 3);
+	assert_well_formed(&plane);
+	assert_eq!(plane.positions.len(), 9 * 4);
+	assert_eq!(plane.triangle_count(), 2 * 8 * 3);
+
+	let area: f64 = triangles(&plane).iter().map(|[a, b, c]| (*b - *a).cross(*c - *a).magnitude() / 2.0).sum();
+	assert!((area - 8.0).abs() < 1e-12);
+	assert!(plane.positions.iter().all(|p| p[0].abs() <= 2.0 && p[1] == 0.0 && p[2].abs() <= 1.0));
+}
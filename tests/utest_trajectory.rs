@@ -0,0 +1,63 @@
+use math3d::points::Point3;
+use math3d::trajectory::{solve_launch_angle, Projectile};
+use math3d::vectors::Vector3;
+
+#[cfg(test)]
+
+fn assert_hits(origin: Point3<f64>, target: Point3<f64>, velocity: Vector3<f64>, gravity: Vector3<f64>) {
+	let projectile = Projectile::new(origin, velocity, gravity);
+	// Time from the horizontal distance, which covers hits on the way up too.
+	let offset = target.to_vector() - origin.to_vector();
+	let t = offset.reject_from(gravity).magnitude() / velocity.reject_from(gravity).magnitude();
+	assert!(projectile.position_at(t).distance_to(target) < 1e-9, "{:?} misses {:?}", projectile.position_at(t), target);
+}
+
+#[test]
+fn test_solve_launch_angle_low_and_high_hit_target() {
+	let gravity = Vector3::<f64>::new(0.0, -9.81, 0.0);
+	let origin = Point3::new(1.0, 2.0, 3.0);
+	for target in [Point3::new(31.0, 2.0, -7.0), Point3::new(-10.0, 12.0, 3.0), Point3::new(5.0, -40.0, 8.0)] {
+		let (low, high) = solve_launch_angle(origin, target, 30.0, gravity).unwrap();
+		assert!((low.magnitude() - 30.0).abs() < 1e-9 && (high.magnitude() - 30.0).abs() < 1e-9);
+		assert!(low.dot(-gravity) < high.dot(-gravity));
+		assert_hits(origin, target, low, gravity);
+		assert_hits(origin, target, high, gravity);
+	}
+}
+
+#[test]
+fn test_solve_launch_angle_edge_cases() {
+	let gravity = Vector3::new(0.0, 0.0, -10.0);
+	let origin = Point3::new(0.0, 0.0, 0.0);
+
+	// Out of range, coincident, and straight up beyond the reachable height.
+	assert!(solve_launch_angle(origin, Point3::new(100.0, 0.0, 0.0), 10.0, gravity).is_none());
+	assert!(solve_launch_angle(origin, origin, 10.0, gravity).is_none());
+	assert!(solve_launch_angle(origin, Point3::new(0.0, 0.0, 5.1), 10.0, gravity).is_none());
+
+	let (low, high) = solve_launch_angle(origin, Point3::new(0.0, 0.0, 4.0), 10.0, gravity).unwrap();
+	assert!(low == Vector3::new(0.0, 0.0, 10.0) && high == low);
+	let (low, high) = solve_launch_angle(origin, Point3::new(0.0, 0.0, -4.0), 10.0, gravity).unwrap();
+	assert!(low == Vector3::new(0.0, 0.0, -10.0) && high == Vector3::new(0.0, 0.0, 10.0));
+
+	let (low, high) = solve_launch_angle(origin, Point3::new(3.0, 4.0, 0.0), 10.0, Vector3::zero()).unwrap();
+	assert!(low == Vector3::new(6.0, 8.0, 0.0) && high == low);
+}
+
+#[test]
+fn test_projectile_apex_and_time_of_flight() {
+	let gravity = Vector3::new(0.0, -10.0, 0.0);
+	let projectile = Projectile::new(Point3::new(0.0, 1.0, 0.0), Vector3::new(2.0, 20.0, 0.0), gravity);
+
+	assert_eq!(projectile.time_to_apex(), 2.0);
+	assert!(projectile.apex() == Point3::new(4.0, 21.0, 0.0));
+	assert!(projectile.velocity_at(2.0) == Vector3::new(2.0, 0.0, 0.0));
+	assert_eq!(projectile.time_of_flight(0.0), Some(4.0));
+	assert_eq!(projectile.time_of_flight(20.0), Some(2.0));
+	assert_eq!(projectile.time_of_flight(20.5), None);
+
+	let falling = Projectile::new(Point3::new(0.0, 0.0, 0.0), Vector3::new(1.0, -5.0, 0.0), gravity);
+	assert_eq!(falling.time_to_apex(), 0.0);
+	assert_eq!(falling.time_of_flight(-10.0), Some(1.0));
+	assert_eq!(Projectile::new(Point3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 0.0, 0.0), Vector3::zero()).time_of_flight(0.0), None);
+}
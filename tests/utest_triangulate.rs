@@ -0,0 +1,109 @@
+use math3d::points::Point2;
+use math3d::predicates::{incircle, orient2d};
+use math3d::triangulate::delaunay;
+
+#[cfg(test)]
+
+fn pseudo_random_points(count: usize) -> Vec<Point2<f64>> {
+	let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+	let mut next = || {
+		state ^= state << 13;
+		state ^= state >> 7;
+		state ^= state << 17;
+		(state >> 11) as f64 / (1u64 << 53) as f64
+	};
+	(0..count).map(|_| Point2::new(next() * 100.0, next() * 100.0)).collect()
+}
+
+fn assert_delaunay(points: &[Point2<f64>], triangles: &[[u32; 3]]) {
+	for t in triangles {
+		let [a, b, c] = t.map(|i| points[i as usize]);
+		assert!(orient2d(a, b, c) > 0.0);
+		for p in points {
+			assert!(incircle(a, b, c, *p) <= 0.0);
+		}
+	}
+}
+
+fn total_area(points: &[Point2<f64>], triangles: &[[u32; 3]]) -> f64 {
+	triangles
+		.iter()
+		.map(|t| {
+			let [a, b, c] = t.map(|i| points[i as usize]);
+			orient2d(a, b, c) / 2.0
+		})
+		.sum()
+}
+
+#[test]
+fn test_orient2d_near_degenerate() {
+	let a = Point2::new(12.0, 12.0);
+	let b = Point2::new(24.0, 24.0);
+	let step = f64::EPSILON;
+	for i in 0..16 {
+		for j in 0..16 {
+			// The determinant is exactly 12 * (c.y - c.x).
+			let c = Point2::new(0.5 + i as f64 * step, 0.5 + j as f64 * step);
+			let orientation = orient2d(a, b, c);
+			match j.cmp(&i) {
+				std::cmp::Ordering::Greater => assert!(orientation > 0.0),
+				std::cmp::Ordering::Less => assert!(orientation < 0.0),
+				std::cmp::Ordering::Equal => assert_eq!(orientation, 0.0),
+			}
+		}
+	}
+}
+
+#[test]
+fn test_incircle_cocircular() {
+	let a = Point2::new(1e-3, 0.0);
+	let b = Point2::new(0.0, 1e-3);
+	let c = Point2::new(-1e-3, 0.0);
+
+	assert_eq!(incircle(a, b, c, Point2::new(0.0, -1e-3)), 0.0);
+	assert!(incircle(a, b, c, Point2::new(0.0, -1e-3 + 1e-18)) > 0.0);
+	assert!(incircle(a, b, c, Point2::new(0.0, -1e-3 - 1e-18)) < 0.0);
+}
+
+#[test]
+fn test_delaunay_random_points() {
+	let points = pseudo_random_points(500);
+	let triangles = delaunay(&points);
+
+	assert_delaunay(&points, &triangles);
+	// Euler: a triangulation of n points with h on the hull has 2n - 2 - h triangles.
+	assert!(triangles.len() <= 2 * points.len() - 5);
+	assert!(triangles.len() >= points.len());
+}
+
+#[test]
+fn test_delaunay_grid_and_duplicates() {
+	let mut points = Vec::new();
+	for y in 0..6 {
+		for x in 0..6 {
+			points.push(Point2::new(x as f64, y as f64));
+		}
+	}
+	points.push(Point2::new(2.0, 3.0));
+	let triangles = delaunay(&points);
+
+	assert_delaunay(&points, &triangles);
+	assert_eq!(triangles.len(), 50);
+	assert!((total_area(&points, &triangles) - 25.0).abs() < 1e-12);
+	assert!(triangles.iter().all(|t| !t.contains(&36)));
+}
+
+#[test]
+fn test_delaunay_degenerate_input() {
+	assert!(delaunay::<f64>(&[]).is_empty());
+	assert!(delaunay(&[Point2::new(0.0, 0.0), Point2::new(1.0, 1.0)]).is_empty());
+
+	let collinear: Vec<_> = (0..10).map(|i| Point2::new(i as f64, 2.0 * i as f64)).collect();
+	assert!(delaunay(&collinear).is_empty());
+
+	let mut points = collinear.clone();
+	points.push(Point2::new(0.0, 5.0));
+	let triangles = delaunay(&points);
+	assert_delaunay(&points, &triangles);
+	assert_eq!(triangles.len(), 9);
+}
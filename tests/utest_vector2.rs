@@ -0,0 +1,71 @@
+use math3d::vectors::Vector2;
+
+#[cfg(test)]
+
+#[test]
+fn test_vector2_arithmetic() {
+	let v1 = Vector2::new(3.0, 4.0);
+	let v2 = Vector2::new(1.0, -2.0);
+
+	assert!(v1 + v2 == Vector2::new(4.0, 2.0));
+	assert!(v1 - v2 == Vector2::new(2.0, 6.0));
+	assert!(v1 * 2.0 == Vector2::new(6.0, 8.0));
+	assert!(-v2 == Vector2::new(-1.0, 2.0));
+	assert_eq!(v1.dot(v2), -5.0);
+	assert_eq!(v1.magnitude(), 5.0);
+	assert_eq!(v1.magnitude_squared(), 25.0);
+	assert_eq!(v1.distance(v2), 40.0_f64.sqrt());
+	assert_eq!(v1.distance_squared(v2), 40.0);
+	assert_eq!(v1.element_sum(), 7.0);
+	assert_eq!(v1.element_product(), 12.0);
+	assert_eq!(v2.min_element(), -2.0);
+	assert_eq!(v2.max_element(), 1.0);
+	assert!(v1.normalized() == Vector2::new(0.6, 0.8));
+	assert!(v2.clamp(Vector2::zero(), Vector2::identity()) == Vector2::new(1.0, 0.0));
+}
+
+#[test]
+fn test_vector2_lerp() {
+	let v1 = Vector2::new(1.0, 1.0);
+	let v2 = Vector2::new(3.0, -1.0);
+
+	assert!(v1.lerp(v2, 0.25) == Vector2::new(1.5, 0.5));
+	assert!(v1.lerp(v2, -1.0) == v1);
+	assert!(v1.lerp_unclamped(v2, 2.0) == Vector2::new(5.0, -3.0));
+}
+
+#[test]
+fn test_vector2_try_normalize() {
+	assert!(Vector2::new(3.0, 4.0).try_normalize() == Some(Vector2::new(0.6, 0.8)));
+	assert!(Vector2::<f64>::zero().try_normalize().is_none());
+	assert!(Vector2::zero().normalize_or(Vector2::new(1.0, 0.0)) == Vector2::new(1.0, 0.0));
+}
+
+#[test]
+fn test_vector2_conversions() {
+	let v = Vector2::<f64>::new(1.0, 2.0);
+	assert!(Vector2::from([1.0, 2.0]) == v && Vector2::from((1.0, 2.0)) == v);
+	assert_eq!(<[f64; 2]>::from(v), [1.0, 2.0]);
+	assert_eq!(<(f64, f64)>::from(v), (1.0, 2.0));
+	assert!(Vector2::try_from(&[1.0, 2.0][..]).unwrap() == v);
+	assert!(Vector2::<f64>::try_from(&[][..]).is_err());
+}
+
+#[test]
+fn test_vector2_perp_and_rotation() {
+	let v = Vector2::<f64>::new(3.0, 1.0);
+	assert!(v.perp() == Vector2::new(-1.0, 3.0));
+	assert_eq!(v.perp().dot(v), 0.0);
+	assert_eq!(v.perp_dot(v * 2.0), 0.0);
+	assert_eq!(v.perp_dot(Vector2::new(1.0, 2.0)), -Vector2::new(1.0, 2.0).perp_dot(v));
+	assert!(v.perp_dot(v.perp()) > 0.0 && v.perp_dot(-v.perp()) < 0.0);
+
+	let quarter = std::f64::consts::FRAC_PI_2;
+	assert!(v.rotate(quarter).approx_eq(v.perp(), 1e-12));
+	assert!(v.rotate(-quarter).approx_eq(-v.perp(), 1e-12));
+	assert!((v.rotate(1.0).magnitude() - v.magnitude()).abs() < 1e-12);
+
+	assert!((v.rotate(0.5).angle() - v.angle() - 0.5).abs() < 1e-12);
+	assert_eq!(Vector2::new(0.0, 2.0).angle(), quarter);
+	assert_eq!(Vector2::new(-1.0, 0.0).angle(), std::f64::consts::PI);
+}
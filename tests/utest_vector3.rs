@@ -1,4 +1,5 @@
-use math3d::vectors::Vector3;
+use math3d::points::Point3;
+use math3d::vectors::{BVec3, Vector3};
 
 #[cfg(test)]
 
@@ -115,3 +116,467 @@ fn test_vector3_normalize() {
 	assert_eq!(v2[1], 0.5345224838248488);
 	assert_eq!(v2[2], 0.8017837257372732);
 }
+
+#[test]
+fn test_vector3_try_normalize() {
+	let v = Vector3::new(1.0, 2.0, 3.0);
+	assert!(v.try_normalize() == Some(v.normalized()));
+	assert!(Vector3::<f64>::zero().try_normalize().is_none());
+	assert!(Vector3::new(1e-200, 0.0, 0.0).try_normalize().is_none());
+	assert!(Vector3::new(f64::INFINITY, 0.0, 0.0).try_normalize().is_none());
+	assert!(Vector3::new(f64::NAN, 0.0, 0.0).try_normalize().is_none());
+
+	let fallback = Vector3::new(0.0, 0.0, 1.0);
+	assert!(Vector3::zero().normalize_or(fallback) == fallback);
+	assert!(v.normalize_or(fallback) == v.normalized());
+}
+
+#[test]
+fn test_vector3_map_zip_fold() {
+	let v1 = Vector3::new(1.0, -2.0, 3.0);
+	let v2 = Vector3::new(0.5, 4.0, -1.0);
+
+	assert!(v1.map(|c| c * c) == Vector3::new(1.0, 4.0, 9.0));
+	assert!(v1.zip_with(v2, f64::min) == Vector3::new(0.5, -2.0, -1.0));
+	assert_eq!(v1.fold(0.0, |acc, c| acc + c.abs()), 6.0);
+	assert_eq!(v1.zip_with(v2, |a, b| a * b).fold(0.0, |acc, c| acc + c), v1.dot(v2));
+}
+
+#[test]
+fn test_unit_vector3() {
+	use math3d::vectors::UnitVector3;
+
+	let u = UnitVector3::new(Vector3::<f64>::new(3.0, 0.0, -4.0)).unwrap();
+	assert!((u.to_vector().magnitude() - 1.0).abs() < 1e-15);
+	assert!(u.to_vector() == Vector3::new(0.6, 0.0, -0.8));
+	assert!((-u).to_vector() == Vector3::new(-0.6, 0.0, 0.8));
+	assert!(u * 5.0 == Vector3::new(3.0, 0.0, -4.0));
+	assert!(u[2] == -0.8);
+
+	assert!(UnitVector3::new(Vector3::<f64>::zero()).is_none());
+	assert!(UnitVector3::new(Vector3::new(f64::INFINITY, 0.0, 0.0)).is_none());
+	assert!(UnitVector3::<f64>::z_axis().to_vector() == Vector3::new(0.0, 0.0, 1.0));
+}
+
+#[test]
+fn test_vector3_component_wise() {
+	let v = Vector3::new(-1.5, 0.25, 2.5);
+	let lo = Vector3::new(-1.0, -1.0, -1.0);
+	let hi = Vector3::new(1.0, 1.0, 1.0);
+
+	assert!(v.min(lo) == Vector3::new(-1.5, -1.0, -1.0));
+	assert!(v.max(hi) == Vector3::new(1.0, 1.0, 2.5));
+	assert!(v.clamp(lo, hi) == Vector3::new(-1.0, 0.25, 1.0));
+	assert!(v.abs() == Vector3::new(1.5, 0.25, 2.5));
+	assert!(v.floor() == Vector3::new(-2.0, 0.0, 2.0));
+	assert!(v.ceil() == Vector3::new(-1.0, 1.0, 3.0));
+	assert!(v.round() == Vector3::new(-2.0, 0.0, 3.0));
+}
+
+#[test]
+fn test_vector3_lerp() {
+	let v1 = Vector3::new(1.0, 2.0, 3.0);
+	let v2 = Vector3::new(3.0, 2.0, -1.0);
+
+	assert!(v1.lerp(v2, 0.5) == Vector3::new(2.0, 2.0, 1.0));
+	assert!(v1.lerp(v2, 1.5) == v2);
+	assert!(v1.lerp_unclamped(v2, -1.0) == Vector3::new(-1.0, 2.0, 7.0));
+}
+
+#[test]
+fn test_vector3_slerp() {
+	let a = Vector3::<f64>::new(1.0, 2.0, -1.0).normalized();
+	let b = Vector3::new(-0.5, 1.0, 3.0).normalized();
+	let angle = a.dot(b).acos();
+
+	for &t in &[0.0, 0.2, 0.5, 0.9, 1.0] {
+		let v = a.slerp(b, t);
+		assert!((v.magnitude() - 1.0).abs() < 1e-12);
+		assert!((v.dot(a).min(1.0).acos() - angle * t).abs() < 1e-9);
+		assert!(v.dot(a.cross(b)).abs() < 1e-12);
+	}
+
+	// Opposite vectors rotate through a perpendicular direction.
+	let half = a.slerp(-a, 0.5);
+	assert!(half.dot(a).abs() < 1e-12);
+	assert!((half.magnitude() - 1.0).abs() < 1e-12);
+
+	// Nearly equal vectors stay unit length.
+	let c = (a + Vector3::new(1e-9, 0.0, 0.0)).normalized();
+	assert!((a.slerp(c, 0.5).magnitude() - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_vector3_angle_between_clamps_rounding() {
+	let v = Vector3::new(0.1, 0.7, 0.3).normalized();
+	assert_eq!(v.angle_between(v), 0.0);
+	assert_eq!(v.angle_between(-v), std::f64::consts::PI);
+	assert!(!v.slerp(v * (1.0 + 1e-15), 0.5)[0].is_nan());
+}
+
+#[test]
+fn test_vector3_angle_between_small_angles() {
+	let a = Vector3::<f64>::new(1.0, 0.0, 0.0);
+	let b = Vector3::new(1.0, 1e-9, 0.0);
+	assert!((a.angle_between(b) - 1e-9).abs() < 1e-20);
+	assert!((a.angle_between(-b) - (std::f64::consts::PI - 1e-9)).abs() < 1e-15);
+}
+
+#[test]
+fn test_vector3_signed_angle() {
+	let axis = Vector3::<f64>::new(1.0, 2.0, -1.0).normalized();
+	let a = Vector3::new(2.0, -1.0, 0.0);
+	for degrees in [-170.0_f64, -90.0, -10.0, 0.0, 35.0, 120.0] {
+		let b = math3d::quaternion::Quaternion::from_axis_angle(axis, degrees).rotate_vector(a);
+		assert!((a.signed_angle(b, axis).to_degrees() - degrees).abs() < 1e-9);
+		assert!((b.signed_angle(a, axis).to_degrees() + degrees).abs() < 1e-9);
+	}
+}
+
+#[test]
+fn test_vector3_project_and_reject() {
+	let v = Vector3::<f64>::new(2.0, -1.0, 4.0);
+	let onto = Vector3::new(1.0, 1.0, 1.0);
+	let projected = v.project_onto(onto);
+	let rejected = v.reject_from(onto);
+
+	assert!((projected + rejected - v).magnitude() < 1e-12);
+	assert!(projected.cross(onto).magnitude() < 1e-12);
+	assert!(rejected.dot(onto).abs() < 1e-12);
+	assert!((projected.project_onto(onto * 3.0) - projected).magnitude() < 1e-12);
+	assert!((v.project_onto_plane(onto * 5.0) - rejected).magnitude() < 1e-12);
+	assert!(v.project_onto(Vector3::zero()) == Vector3::zero());
+	assert!(v.reject_from(Vector3::zero()) == v);
+}
+
+#[test]
+fn test_vector3_unit_axes() {
+	let (x, y, z) = (Vector3::<f64>::unit_x(), Vector3::unit_y(), Vector3::unit_z());
+	assert!(x == Vector3::new(1.0, 0.0, 0.0) && y == Vector3::new(0.0, 1.0, 0.0) && z == Vector3::new(0.0, 0.0, 1.0));
+	assert!(x.cross(y) == z);
+	assert!(y.cross(z) == x);
+}
+
+#[test]
+fn test_vector3_compound_assignment() {
+	let mut v = Vector3::<f64>::new(1.0, 2.0, 3.0);
+	v += Vector3::new(1.0, 1.0, 1.0);
+	v -= 0.5;
+	v *= 2.0;
+	v /= Vector3::new(1.0, 2.0, 4.0);
+	assert!(v == Vector3::new(3.0, 2.5, 1.75));
+
+	let mut accumulated = Vector3::zero();
+	for _ in 0..4 {
+		accumulated += Vector3::new(0.25, 0.5, 1.0);
+	}
+	assert!(accumulated == Vector3::new(1.0, 2.0, 4.0));
+}
+
+#[test]
+fn test_vector3_accessors_by_value() {
+	let mut v = Vector3::<f64>::new(1.0, 2.0, 3.0);
+	let sum: f64 = v.x() + v.y() + v.z();
+	assert_eq!(sum, 6.0);
+	v.set_x(-1.0);
+	v.set_y(v.y() * 2.0);
+	v.set_z(0.5);
+	assert!(v == Vector3::new(-1.0, 4.0, 0.5));
+}
+
+#[test]
+fn test_vector3_mutable_accessors() {
+	let mut v = Vector3::<f64>::new(1.0, 2.0, 3.0);
+	*v.x_mut() = -1.0;
+	*v.y_mut() *= 2.0;
+	*v.z_mut() = 0.5;
+	assert!(v == Vector3::new(-1.0, 4.0, 0.5));
+
+	let mut p = Point3::<f64>::new(1.0, 2.0, 3.0);
+	*p.y_mut() += 1.0;
+	*p.z_mut() = p.x() + p.y();
+	*p.x_mut() = 0.0;
+	assert!(p == Point3::new(0.0, 3.0, 4.0));
+}
+
+#[test]
+fn test_vector3_scalar_on_the_left() {
+	let v = Vector3::new(1.0, -2.0, 0.5);
+	assert!(2.0 * v == v * 2.0);
+	assert!(0.5_f32 * Vector3::new(2.0_f32, 4.0, 6.0) == Vector3::new(1.0, 2.0, 3.0));
+}
+
+#[test]
+fn test_vector3_distance_and_squared_variants() {
+	let a = Vector3::<f64>::new(1.0, -2.0, 3.0);
+	let b = Vector3::new(-3.0, 2.0, 5.0);
+	assert_eq!(a.magnitude_squared(), 14.0);
+	assert_eq!(a.distance_squared(b), 36.0);
+	assert_eq!(a.distance(b), 6.0);
+	assert_eq!(a.distance(b), b.distance(a));
+	assert!((a.magnitude() * a.magnitude() - a.magnitude_squared()).abs() < 1e-12);
+}
+
+#[test]
+fn test_vector3_triple_products() {
+	let a = Vector3::<f64>::new(1.0, -2.0, 0.5);
+	let b = Vector3::new(3.0, 1.0, -1.0);
+	let c = Vector3::new(-2.0, 4.0, 2.0);
+	assert_eq!(a.scalar_triple(b, c), b.scalar_triple(c, a));
+	assert_eq!(a.scalar_triple(b, c), -a.scalar_triple(c, b));
+	assert_eq!(a.scalar_triple(a, c), 0.0);
+	assert!((a.vector_triple(b, c) - (b * a.dot(c) - c * a.dot(b))).magnitude() < 1e-12);
+}
+
+#[test]
+fn test_vector3_orthonormal_basis() {
+	let normals = [
+		Vector3::<f64>::unit_x(),
+		Vector3::unit_y(),
+		Vector3::unit_z(),
+		-Vector3::unit_z(),
+		Vector3::new(1e-9, -1e-9, -1.0).normalized(),
+		Vector3::new(0.3, -0.7, -0.2).normalized(),
+		Vector3::new(-1.0, 2.0, 3.0).normalized(),
+	];
+	for n in normals {
+		let (t, b) = n.orthonormal_basis();
+		assert!((t.magnitude() - 1.0).abs() < 1e-12 && (b.magnitude() - 1.0).abs() < 1e-12);
+		assert!(t.dot(n).abs() < 1e-12 && b.dot(n).abs() < 1e-12 && t.dot(b).abs() < 1e-12);
+		assert!((t.cross(b) - n).magnitude() < 1e-12);
+	}
+}
+
+#[test]
+fn test_vector3_iterators() {
+	let mut v = Vector3::<f64>::new(1.0, -2.0, 3.0);
+	assert_eq!(v.iter().copied().fold(f64::MIN, f64::max), 3.0);
+	for c in v.iter_mut() {
+		*c *= 2.0;
+	}
+	assert!(v == Vector3::new(2.0, -4.0, 6.0));
+	for c in &mut v {
+		*c += 1.0;
+	}
+	assert_eq!((&v).into_iter().count(), 3);
+	assert_eq!(v.into_iter().collect::<Vec<_>>(), vec![3.0, -3.0, 7.0]);
+
+	let slice = [0.5, 1.5, 2.5, 3.5];
+	let collected: Vector3<f64> = slice[1..].iter().copied().collect();
+	assert!(collected == Vector3::new(1.5, 2.5, 3.5));
+	let doubled: Vector3<f64> = v.into_iter().map(|c| c * 2.0).collect();
+	assert!(doubled == v * 2.0);
+
+	let points = [Vector3::new(1.0, 0.0, 0.0), Vector3::new(0.0, 2.0, 0.0), Vector3::new(2.0, 1.0, 3.0)];
+	assert!(points.iter().copied().sum::<Vector3<f64>>() == Vector3::new(3.0, 3.0, 3.0));
+}
+
+#[test]
+#[should_panic(expected = "three components")]
+fn test_vector3_from_iter_rejects_wrong_length() {
+	let _: Vector3<f64> = [1.0, 2.0].into_iter().collect();
+}
+
+#[test]
+fn test_vector3_horizontal_reductions() {
+	let v = Vector3::<f64>::new(-1.5, 4.0, 2.0);
+	assert_eq!(v.element_sum(), 4.5);
+	assert_eq!(v.element_product(), -12.0);
+	assert_eq!(v.min_element(), -1.5);
+	assert_eq!(v.max_element(), 4.0);
+	assert_eq!(v.element_sum(), v.fold(0.0, |acc, c| acc + c));
+	assert_eq!(Vector3::new(3.0, 3.0, 3.0).min_element(), 3.0);
+}
+
+#[test]
+fn test_vector3_combinators_for_color_operations() {
+	let linear = Vector3::<f64>::new(0.25, 1.0, 4.0);
+
+	let gamma = linear.map(|c| c.powf(1.0 / 2.2));
+	assert!((gamma.map(|c| c.powf(2.2)) - linear).magnitude() < 1e-12);
+
+	let tone_mapped = linear.map(|c| c / (1.0 + c));
+	assert!(tone_mapped == Vector3::new(0.2, 0.5, 0.8));
+
+	let quantized = tone_mapped.map(|c| (c * 255.0).round());
+	assert!(quantized == Vector3::new(51.0, 128.0, 204.0));
+
+	let weights = Vector3::new(0.2126, 0.7152, 0.0722);
+	let luminance = linear.zip_with(weights, |c, w| c * w).fold(0.0, |acc, c| acc + c);
+	assert!((luminance - linear.dot(weights)).abs() < 1e-12);
+}
+
+#[test]
+fn test_vector3_validity_queries() {
+	let v = Vector3::<f64>::new(1.0, -2.0, 0.5);
+	assert!(v.is_finite() && !v.is_nan() && !v.is_normalized(1e-6));
+	assert!(v.normalized().is_normalized(1e-12));
+
+	let infinite = Vector3::new(0.0, f64::INFINITY, 0.0);
+	assert!(!infinite.is_finite() && !infinite.is_nan() && !infinite.is_normalized(1e-6));
+
+	let nan = Vector3::new(f64::NAN, 1.0, 0.0);
+	assert!(!nan.is_finite() && nan.is_nan() && !nan.is_normalized(1.0));
+	assert!(Vector3::new(1.0 + 1e-9, 0.0, 0.0).is_normalized(1e-6));
+}
+
+#[test]
+fn test_vector3_approximate_equality() {
+	let a = Vector3::<f64>::new(0.1, 0.2, 0.3);
+	let b = a * 10.0 / 10.0 + Vector3::new(1e-13, -1e-13, 0.0);
+	assert!(a.approx_eq(b, 1e-12));
+	assert!(!a.approx_eq(b, 1e-14));
+	assert!(!a.approx_eq(Vector3::new(0.1, 0.2, f64::NAN), 1.0));
+
+	let large = Vector3::new(1e15, -3e15, 2e15);
+	let nudged = large + Vector3::new(0.5, 0.5, 0.5);
+	assert!(!large.approx_eq(nudged, 1e-6));
+	assert!(large.relative_eq(nudged, 1e-6, 4.0 * f64::EPSILON));
+	assert!(!large.relative_eq(large * 1.001, 1e-6, 4.0 * f64::EPSILON));
+
+	let infinite = Vector3::new(f64::INFINITY, 0.0, 0.0);
+	assert!(infinite.relative_eq(infinite, 0.0, 0.0));
+}
+
+#[test]
+fn test_vector3_comparison_masks() {
+	let a = Vector3::<f64>::new(1.0, 2.0, 3.0);
+	let b = Vector3::new(3.0, 2.0, 1.0);
+	assert_eq!(a.cmplt(b), BVec3::new(true, false, false));
+	assert_eq!(a.cmple(b), BVec3::new(true, true, false));
+	assert_eq!(a.cmpgt(b), BVec3::new(false, false, true));
+	assert_eq!(a.cmpge(b), BVec3::new(false, true, true));
+	assert_eq!(a.cmpeq(b), BVec3::new(false, true, false));
+	assert_eq!(!a.cmplt(b), a.cmpge(b));
+	assert_eq!(a.cmplt(b) | a.cmpeq(b), a.cmple(b));
+	assert!(!(a.cmplt(b) & a.cmpgt(b)).any());
+	assert!(a.cmplt(Vector3::new(f64::NAN, 3.0, 4.0)) == BVec3::new(false, true, true));
+
+	assert!(Vector3::select(a.cmplt(b), a, b) == a.min(b));
+	assert!(Vector3::select(BVec3::splat(true), a, b) == a);
+	assert!(Vector3::select(BVec3::default(), a, b) == b);
+}
+
+#[test]
+fn test_vector3_conversions() {
+	let v = Vector3::<f32>::new(1.0, 2.0, 3.0);
+	assert!(Vector3::from([1.0, 2.0, 3.0]) == v);
+	assert!(Vector3::from((1.0, 2.0, 3.0)) == v);
+	assert_eq!(<[f32; 3]>::from(v), [1.0, 2.0, 3.0]);
+	assert_eq!(<(f32, f32, f32)>::from(v), (1.0, 2.0, 3.0));
+
+	let vertices: Vec<[f32; 3]> = vec![v, -v].into_iter().map(Into::into).collect();
+	assert_eq!(vertices, vec![[1.0, 2.0, 3.0], [-1.0, -2.0, -3.0]]);
+
+	let buffer = [1.0, 2.0, 3.0, 4.0];
+	assert!(Vector3::try_from(&buffer[..3]).unwrap() == v);
+	assert!(Vector3::try_from(&buffer[..]).is_err());
+
+	let p: Point3<f32> = [1.0, 2.0, 3.0].into();
+	assert!(p.to_vector() == v);
+	assert_eq!(<[f32; 3]>::from(p), [1.0, 2.0, 3.0]);
+	assert!(Point3::try_from(&buffer[1..]).unwrap() == Point3::new(2.0, 3.0, 4.0));
+}
+
+#[test]
+fn test_vector3_default_zero_and_one() {
+	use num::{One, Zero};
+
+	let mut buffer = vec![Vector3::<f64>::new(1.0, 2.0, 3.0)];
+	buffer.resize_with(3, Default::default);
+	assert!(buffer[2] == Vector3::zero());
+	assert!(Point3::<f64>::default().to_vector() == Vector3::zero());
+
+	let v = Vector3::new(1.0, -2.0, 0.5);
+	assert!(<Vector3<f64> as Zero>::zero() + v == v);
+	assert!(<Vector3<f64> as One>::one() * v == v);
+	assert!(Zero::is_zero(&Vector3::<f64>::default()) && !Zero::is_zero(&v));
+}
+
+#[test]
+fn test_vector3_cast() {
+	let v = Vector3::<f64>::new(0.1, -2.5, 1e-3);
+	let single: Vector3<f32> = v.cast();
+	assert!(single == Vector3::new(0.1f32, -2.5, 1e-3));
+	assert!((single.cast::<f64>() - v).magnitude() < 1e-7);
+	assert!(Point3::<f64>::new(1.0, 2.0, 3.0).cast::<f32>() == Point3::new(1.0f32, 2.0, 3.0));
+}
+
+#[test]
+fn test_vector3_rotated_about_axes() {
+	let v = Vector3::<f64>::new(0.3, -1.2, 2.5);
+	let angle = 0.7;
+	let close = |a: Vector3<f64>, b: Vector3<f64>| (a - b).magnitude() < 1e-12;
+
+	assert!(close(v.rotated_x(angle), v.rotated_about(Vector3::new(1.0, 0.0, 0.0), angle)));
+	assert!(close(v.rotated_y(angle), v.rotated_about(Vector3::new(0.0, 1.0, 0.0), angle)));
+	assert!(close(v.rotated_z(angle), v.rotated_about(Vector3::new(0.0, 0.0, 1.0), angle)));
+	assert!(close(Vector3::new(0.0, 0.0, 1.0).rotated_y(std::f64::consts::FRAC_PI_2), Vector3::new(1.0, 0.0, 0.0)));
+
+	let axis = Vector3::new(1.0, 1.0, -0.5);
+	let rotated = v.rotated_about(axis, angle);
+	assert!((rotated.magnitude() - v.magnitude()).abs() < 1e-12);
+	assert!((rotated.dot(axis) - v.dot(axis)).abs() < 1e-12);
+	assert!((v.reject_from(axis).signed_angle(rotated.reject_from(axis), axis) - angle).abs() < 1e-12);
+	assert!(close(rotated.rotated_about(axis, -angle), v));
+}
+
+#[test]
+fn test_vector3_pack_r11g11b10f() {
+	// Every finite code of each channel survives an unpack and repack bit for bit.
+	for code in 0..0x7c0 {
+		let packed = code | code << 11 | (code >> 1) << 22;
+		assert_eq!(Vector3::<f64>::unpack_r11g11b10f(packed).pack_r11g11b10f(), packed);
+		assert_eq!(Vector3::<f32>::unpack_r11g11b10f(packed).pack_r11g11b10f(), packed);
+	}
+
+	// Largest finite values and smallest subnormals.
+	assert!(Vector3::<f64>::unpack_r11g11b10f(0x7bf | 0x7bf << 11 | 0x3df << 22) == Vector3::new(65024.0, 65024.0, 64512.0));
+	assert_eq!(Vector3::new(2.0_f64.powi(-20), 2.0_f64.powi(-20), 2.0_f64.powi(-19)).pack_r11g11b10f(), 1 | 1 << 11 | 1 << 22);
+
+	// Ties round to even, also into the next exponent and on to infinity.
+	assert_eq!(Vector3::new(1.0 + 1.0 / 128.0, 1.0 + 3.0 / 128.0, 0.0_f64).pack_r11g11b10f(), 0x3c0 | 0x3c2 << 11);
+	assert_eq!(Vector3::new(2.0 - 1.0 / 128.0, 65024.0 + 255.0, 65024.0_f64 + 256.0).pack_r11g11b10f(), 0x400 | 0x7bf << 11 | 0x3e0 << 22);
+
+	// Negative values clamp to zero, out of range and infinite values saturate and NaN stays NaN.
+	let packed = Vector3::new(-1.0, 1e9, f64::NAN).pack_r11g11b10f();
+	let unpacked = Vector3::<f64>::unpack_r11g11b10f(packed);
+	assert_eq!(unpacked.x(), 0.0);
+	assert_eq!(unpacked.y(), f64::INFINITY);
+	assert!(unpacked.z().is_nan());
+
+	// Round trip stays within half a unit in the last place of each channel.
+	for i in 0..1000 {
+		let v = Vector3::new(i as f64 * 0.37 + 1e-3, (i as f64 * 1.3).sin().abs() * 100.0 + 1e-3, i as f64 * 12.7 + 1e-3);
+		let r = Vector3::<f64>::unpack_r11g11b10f(v.pack_r11g11b10f());
+		assert!(((r.x() - v.x()) / v.x()).abs() <= 1.0 / 128.0);
+		assert!(((r.y() - v.y()) / v.y()).abs() <= 1.0 / 128.0);
+		assert!(((r.z() - v.z()) / v.z()).abs() <= 1.0 / 64.0);
+	}
+}
+
+#[test]
+fn test_vector3_slice_views() {
+	let mut data: Vec<f32> = (0..12).map(|i| i as f32).collect();
+
+	let vectors = Vector3::cast_slice(&data).unwrap();
+	assert_eq!(vectors.len(), 4);
+	assert!(vectors[2] == Vector3::new(6.0, 7.0, 8.0));
+	assert_eq!(Vector3::flatten_slice(vectors), &data[..]);
+	assert!(Vector3::cast_slice(&data[..11]).is_none());
+	assert_eq!(Vector3::<f32>::cast_slice(&[]).unwrap().len(), 0);
+
+	assert!(Vector3::from_slice(&data[3..6]) == Some(&Vector3::new(3.0, 4.0, 5.0)));
+	assert!(Vector3::from_slice(&data[3..9]).is_none());
+
+	// Views at any element offset are aligned.
+	assert!(Vector3::cast_slice(&data[1..10]).unwrap()[0] == Vector3::new(1.0, 2.0, 3.0));
+
+	for v in Vector3::cast_slice_mut(&mut data).unwrap() {
+		*v = v.normalized();
+	}
+	assert!((Vector3::from_slice(&data[9..]).unwrap().magnitude() - 1.0).abs() < 1e-6);
+
+	*Vector3::from_slice_mut(&mut data[..3]).unwrap() = Vector3::new(-1.0, -2.0, -3.0);
+	assert_eq!(&data[..3], &[-1.0, -2.0, -3.0]);
+}
@@ -0,0 +1,219 @@
+use math3d::vectors::{BVec4, Vector3, Vector4};
+
+#[cfg(test)]
+
+#[test]
+fn test_vector4_map_zip_fold() {
+	let v1 = Vector4::new(1.0, -2.0, 3.0, -4.0);
+	let v2 = Vector4::new(0.5, 4.0, -1.0, 2.0);
+
+	assert!(v1.map(|c| c * c) == Vector4::new(1.0, 4.0, 9.0, 16.0));
+	assert!(v1.zip_with(v2, f64::max) == Vector4::new(1.0, 4.0, 3.0, 2.0));
+	assert_eq!(v1.fold(0.0, |acc, c| acc + c), -2.0);
+}
+
+#[test]
+fn test_vector4_arithmetic() {
+	let v1 = Vector4::new(1.0, 2.0, 3.0, 4.0);
+	let v2 = Vector4::new(2.0, 2.0, 3.0, 4.0);
+
+	assert!(v1 + v2 == Vector4::new(3.0, 4.0, 6.0, 8.0));
+	assert!(v1 - 1.0 == Vector4::new(0.0, 1.0, 2.0, 3.0));
+	assert!(v1 * v2 == Vector4::new(2.0, 4.0, 9.0, 16.0));
+	assert!(v1 / 2.0 == Vector4::new(0.5, 1.0, 1.5, 2.0));
+	assert_eq!(v1.dot(v2), 31.0);
+	assert_eq!(v1.magnitude(), 30.0_f64.sqrt());
+}
+
+#[test]
+fn test_vector4_product_matrix_is_row_vector() {
+	use math3d::camera::translation;
+	use math3d::matrices::Matrix4;
+
+	let p = Vector4::new(1.0, 2.0, 3.0, 1.0);
+	let t = translation(4.0, 5.0, 6.0);
+
+	assert!(p * t == Vector4::new(5.0, 7.0, 9.0, 1.0));
+
+	let m = Matrix4::new(
+		1.0, 2.0, 3.0, 4.0,
+		5.0, 6.0, 7.0, 8.0,
+		9.0, 10.0, 11.0, 12.0,
+		13.0, 14.0, 15.0, 16.0,
+	);
+	assert!(p * m == m.transpose().product_vector(p));
+}
+
+#[test]
+fn test_vector4_component_wise() {
+	let color = Vector4::new(1.2, -0.1, 0.5, 1.0);
+
+	assert!(color.clamp(Vector4::zero(), Vector4::identity()) == Vector4::new(1.0, 0.0, 0.5, 1.0));
+	assert!(color.min(Vector4::zero()) == Vector4::new(0.0, -0.1, 0.0, 0.0));
+	assert!(color.max(Vector4::zero()) == Vector4::new(1.2, 0.0, 0.5, 1.0));
+	assert!(color.abs() == Vector4::new(1.2, 0.1, 0.5, 1.0));
+	assert!((color * 10.0).round() == Vector4::new(12.0, -1.0, 5.0, 10.0));
+	assert!(color.floor() == Vector4::new(1.0, -1.0, 0.0, 1.0));
+	assert!(color.ceil() == Vector4::new(2.0, -0.0, 1.0, 1.0));
+}
+
+#[test]
+fn test_vector4_lerp() {
+	let v1 = Vector4::new(0.0, 1.0, 2.0, 3.0);
+	let v2 = Vector4::new(4.0, 3.0, 2.0, 1.0);
+
+	assert!(v1.lerp(v2, 0.5) == Vector4::new(2.0, 2.0, 2.0, 2.0));
+	assert!(v1.lerp(v2, -0.5) == v1);
+	assert!(v1.lerp_unclamped(v2, 1.5) == Vector4::new(6.0, 4.0, 2.0, 0.0));
+}
+
+#[test]
+fn test_vector4_try_normalize() {
+	let v = Vector4::new(1.0, -1.0, 1.0, -1.0);
+	assert!(v.try_normalize() == Some(Vector4::new(0.5, -0.5, 0.5, -0.5)));
+	assert!(Vector4::<f64>::zero().try_normalize().is_none());
+	assert!(Vector4::zero().normalize_or(v) == v);
+}
+
+#[test]
+fn test_vector4_unit_axes() {
+	let axes = [Vector4::<f64>::unit_x(), Vector4::unit_y(), Vector4::unit_z(), Vector4::unit_w()];
+	for (i, axis) in axes.iter().enumerate() {
+		assert!((0..4).all(|k| axis[k] == if k == i { 1.0 } else { 0.0 }));
+	}
+}
+
+#[test]
+fn test_vector4_compound_assignment() {
+	let mut v = Vector4::<f64>::new(1.0, 2.0, 3.0, 4.0);
+	v += 1.0;
+	v -= Vector4::new(2.0, 2.0, 2.0, 2.0);
+	v *= Vector4::new(2.0, 2.0, 2.0, 0.5);
+	v /= 2.0;
+	assert!(v == Vector4::new(0.0, 1.0, 2.0, 0.75));
+}
+
+#[test]
+fn test_vector4_accessors() {
+	let mut v = Vector4::<f64>::new(1.0, 2.0, 3.0, 4.0);
+	assert_eq!([v.x(), v.y(), v.z(), v.w()], [1.0, 2.0, 3.0, 4.0]);
+	v.set_x(5.0);
+	v.set_y(6.0);
+	v.set_z(7.0);
+	v.set_w(v.w() / 4.0);
+	assert!(v == Vector4::new(5.0, 6.0, 7.0, 1.0));
+}
+
+#[test]
+fn test_vector4_mutable_accessors() {
+	let mut v = Vector4::<f64>::new(1.0, 2.0, 3.0, 4.0);
+	*v.x_mut() += 4.0;
+	*v.y_mut() = 6.0;
+	*v.z_mut() *= 3.0;
+	*v.w_mut() = -v.w();
+	assert!(v == Vector4::new(5.0, 6.0, 9.0, -4.0));
+}
+
+#[test]
+fn test_vector4_scalar_on_the_left() {
+	assert!(2.0 * Vector4::new(1.0, 2.0, 3.0, 4.0) == Vector4::new(2.0, 4.0, 6.0, 8.0));
+}
+
+#[test]
+fn test_vector4_iterators() {
+	let mut v = Vector4::<f64>::new(1.0, 2.0, 3.0, 4.0);
+	for c in &mut v {
+		*c -= 1.0;
+	}
+	assert!(v == Vector4::new(0.0, 1.0, 2.0, 3.0));
+	assert_eq!(v.iter().filter(|c| **c > 0.5).count(), 3);
+	let collected: Vector4<f64> = v.into_iter().rev().collect();
+	assert!(collected == Vector4::new(3.0, 2.0, 1.0, 0.0));
+	assert!([v, collected].into_iter().sum::<Vector4<f64>>() == Vector4::new(3.0, 3.0, 3.0, 3.0));
+}
+
+#[test]
+fn test_vector4_combinators() {
+	let color = Vector4::<f64>::new(0.5, 1.5, -0.5, 1.0);
+	let clamped = color.map(|c| c.clamp(0.0, 1.0));
+	assert!(clamped == Vector4::new(0.5, 1.0, 0.0, 1.0));
+	let blended = clamped.zip_with(Vector4::new(1.0, 0.0, 1.0, 1.0), |a, b| 0.5 * (a + b));
+	assert!(blended == Vector4::new(0.75, 0.5, 0.5, 1.0));
+	assert_eq!(blended.fold(f64::MIN, f64::max), 1.0);
+}
+
+#[test]
+fn test_vector4_comparison_masks() {
+	let a = Vector4::<f64>::new(1.0, 2.0, 3.0, 4.0);
+	let b = Vector4::new(4.0, 2.0, 1.0, 5.0);
+	assert_eq!(a.cmplt(b), BVec4::new(true, false, false, true));
+	assert_eq!(a.cmple(b), BVec4::new(true, true, false, true));
+	assert_eq!(a.cmpgt(b), BVec4::new(false, false, true, false));
+	assert_eq!(a.cmpge(b), BVec4::new(false, true, true, false));
+	assert_eq!(a.cmpeq(b), BVec4::new(false, true, false, false));
+	assert_eq!(!a.cmpgt(b), a.cmple(b));
+	assert!(a.cmpge(Vector4::zero()).all() && !a.cmpge(b).all());
+	assert!(!a.cmpeq(Vector4::new(f64::NAN, 0.0, 0.0, 0.0)).any());
+
+	assert!(Vector4::select(a.cmpgt(b), a, b) == a.max(b));
+	assert!(Vector4::select(BVec4::new(true, false, true, false), a, b) == Vector4::new(1.0, 2.0, 3.0, 5.0));
+}
+
+#[test]
+fn test_vector4_neg_and_normalization() {
+	let v = Vector4::<f64>::new(1.0, -2.0, 2.0, -4.0);
+	assert!(-v == Vector4::new(-1.0, 2.0, -2.0, 4.0));
+	assert!(-(-v) == v && v + -v == Vector4::zero());
+	assert_eq!(v.magnitude_squared(), 25.0);
+
+	let n = v.normalized();
+	assert!(n.approx_eq(Vector4::new(0.2, -0.4, 0.4, -0.8), 1e-15));
+	assert!(n.is_normalized(1e-12));
+	assert!(Vector4::<f64>::zero().normalized().is_nan());
+
+	let axis = Vector4::new(0.0, 1.0, 0.0, 1.0);
+	assert!(v.project_onto(axis) + v.reject_from(axis) == v);
+	assert!(v.reject_from(axis).dot(axis).abs() < 1e-12);
+	assert!(Vector4::from_array([1.0, -2.0, 2.0, -4.0]) == v);
+}
+
+#[test]
+fn test_vector4_homogeneous_coordinates() {
+	let p = Vector3::<f64>::new(1.5, -2.0, 0.25);
+	assert!(p.extend(1.0).truncate() == p);
+	assert!(p.extend(1.0).perspective_divide() == p);
+	assert!((p.extend(1.0) * 4.0).perspective_divide().approx_eq(p, 1e-15));
+	assert_eq!(p.extend(0.0).w(), 0.0);
+	assert!(!p.extend(0.0).perspective_divide().is_finite());
+}
+
+#[test]
+fn test_vector4_conversions() {
+	let v = Vector4::<f64>::new(1.0, 2.0, 3.0, 4.0);
+	assert!(Vector4::from([1.0, 2.0, 3.0, 4.0]) == v);
+	assert!(Vector4::from((1.0, 2.0, 3.0, 4.0)) == v);
+	assert_eq!(<[f64; 4]>::from(v), [1.0, 2.0, 3.0, 4.0]);
+	assert_eq!(<(f64, f64, f64, f64)>::from(v), (1.0, 2.0, 3.0, 4.0));
+	assert!(Vector4::try_from(&[1.0, 2.0, 3.0, 4.0][..]).unwrap() == v);
+	assert!(Vector4::<f64>::try_from(&[1.0, 2.0, 3.0][..]).is_err());
+}
+
+#[test]
+fn test_vector4_pack_rgb10a2() {
+	for code in 0..1024 {
+		let packed = code | (1023 - code) << 10 | code << 20 | (code & 3) << 30;
+		assert_eq!(Vector4::<f64>::unpack_rgb10a2(packed).pack_rgb10a2(), packed);
+		assert_eq!(Vector4::<f32>::unpack_rgb10a2(packed).pack_rgb10a2(), packed);
+	}
+
+	// Out of range components clamp and NaN packs as zero.
+	assert_eq!(Vector4::new(-0.5, 2.0, f64::NAN, 1.0).pack_rgb10a2(), 0x3ff << 10 | 3 << 30);
+
+	// A normal mapped from [-1, 1] to [0, 1] round trips within half a step.
+	let n = Vector3::new(0.3, -0.4, 0.5).normalized();
+	let v = Vector4::new_from_vector3(n * 0.5 + 0.5, 0.0);
+	let r = Vector4::<f64>::unpack_rgb10a2(v.pack_rgb10a2());
+	for i in 0..3 {
+		assert!((r[i] - v[i]).abs() <= 0.5 / 1023.0);
+	}
+}